@@ -1,16 +1,17 @@
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, ChildStdin, Command};
+use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
 use tokio::sync::{mpsc, oneshot, Mutex};
-use tokio::time::timeout;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
 
 use crate::backend::events::{AppServerEvent, EventSink};
 use crate::codex::args::parse_codex_args;
@@ -53,6 +54,10 @@ fn build_initialize_params(client_version: &str) -> Value {
 }
 
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(15);
+const RESTART_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const RESTART_MAX_BACKOFF: Duration = Duration::from_secs(30);
+const RESTART_MAX_ATTEMPTS: u32 = 6;
 
 pub(crate) struct WorkspaceSession {
     pub(crate) entry: WorkspaceEntry,
@@ -62,9 +67,32 @@ pub(crate) struct WorkspaceSession {
     pub(crate) next_id: AtomicU64,
     /// Callbacks for background threads - events for these threadIds are sent through the channel
     pub(crate) background_thread_callbacks: Mutex<HashMap<String, mpsc::UnboundedSender<Value>>>,
+    /// Threads loaded into the current app-server process; resumed after a restart.
+    pub(crate) active_threads: Mutex<HashSet<String>>,
+    /// Set when the session is shut down on purpose so the watchdog does not respawn it.
+    shutdown_requested: AtomicBool,
+    /// Cleared while the app-server process is down or could not be restarted.
+    available: AtomicBool,
+    /// Error returned to callers while the app-server process is down.
+    unavailable_reason: Mutex<Option<String>>,
 }
 
 impl WorkspaceSession {
+    pub(crate) fn new(entry: WorkspaceEntry, child: Child, stdin: ChildStdin) -> Self {
+        Self {
+            entry,
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            background_thread_callbacks: Mutex::new(HashMap::new()),
+            active_threads: Mutex::new(HashSet::new()),
+            shutdown_requested: AtomicBool::new(false),
+            available: AtomicBool::new(true),
+            unavailable_reason: Mutex::new(None),
+        }
+    }
+
     async fn write_message(&self, value: Value) -> Result<(), String> {
         let mut stdin = self.stdin.lock().await;
         let mut line = serde_json::to_string(&value).map_err(|e| e.to_string())?;
@@ -76,6 +104,16 @@ impl WorkspaceSession {
     }
 
     pub(crate) async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+        if let Some(reason) = self.unavailable_reason.lock().await.clone() {
+            return Err(reason);
+        }
+        self.send_request_unchecked(method, params).await
+    }
+
+    /// Sends a request even while the session is unavailable; used for the initialize
+    /// handshake of a respawned process before callers are let back in.
+    async fn send_request_unchecked(&self, method: &str, params: Value) -> Result<Value, String> {
+        self.track_outgoing_thread(method, &params).await;
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = oneshot::channel();
         self.pending.lock().await.insert(id, tx);
@@ -116,6 +154,74 @@ impl WorkspaceSession {
         self.write_message(json!({ "id": id, "result": result }))
             .await
     }
+
+    /// Kills the app-server process without triggering the restart watchdog.
+    pub(crate) async fn terminate(&self) {
+        self.shutdown_requested.store(true, Ordering::SeqCst);
+        let mut child = self.child.lock().await;
+        kill_child_process_tree(&mut child).await;
+    }
+
+    fn is_shutdown_requested(&self) -> bool {
+        self.shutdown_requested.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn is_available(&self) -> bool {
+        self.available.load(Ordering::SeqCst)
+    }
+
+    /// Marks the session unavailable and fails every in-flight request with `reason`.
+    async fn mark_unavailable(&self, reason: &str) {
+        self.available.store(false, Ordering::SeqCst);
+        *self.unavailable_reason.lock().await = Some(reason.to_string());
+        let pending = std::mem::take(&mut *self.pending.lock().await);
+        for (id, tx) in pending {
+            let _ = tx.send(json!({ "id": id, "error": { "message": reason } }));
+        }
+    }
+
+    async fn mark_available(&self) {
+        *self.unavailable_reason.lock().await = None;
+        self.available.store(true, Ordering::SeqCst);
+    }
+
+    async fn track_outgoing_thread(&self, method: &str, params: &Value) {
+        let Some(thread_id) = params.get("threadId").and_then(Value::as_str) else {
+            return;
+        };
+        if self
+            .background_thread_callbacks
+            .lock()
+            .await
+            .contains_key(thread_id)
+        {
+            // Background helper threads are one-shot and must not be resumed after a restart.
+            self.active_threads.lock().await.remove(thread_id);
+            return;
+        }
+        let mut active_threads = self.active_threads.lock().await;
+        match method {
+            "thread/resume" | "turn/start" => {
+                active_threads.insert(thread_id.to_string());
+            }
+            "thread/archive" => {
+                active_threads.remove(thread_id);
+            }
+            _ => {}
+        }
+    }
+
+    async fn track_incoming_thread(&self, method: &str, thread_id: Option<&str>) {
+        if !matches!(method, "thread/started" | "turn/started") {
+            return;
+        }
+        if let Some(thread_id) = thread_id {
+            self.active_threads
+                .lock()
+                .await
+                .insert(thread_id.to_string());
+        }
+    }
 }
 
 pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
@@ -304,6 +410,51 @@ pub(crate) async fn check_codex_installation(
     })
 }
 
+/// Everything needed to (re)launch the app-server process for a workspace.
+#[derive(Clone)]
+struct AppServerLaunch {
+    entry: WorkspaceEntry,
+    codex_bin: Option<String>,
+    codex_args: Option<String>,
+    codex_home: Option<PathBuf>,
+    client_version: String,
+}
+
+struct AppServerProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+    stderr: ChildStderr,
+}
+
+impl AppServerLaunch {
+    fn spawn(&self) -> Result<AppServerProcess, String> {
+        let mut command = build_codex_command_with_bin(
+            self.codex_bin.clone(),
+            self.codex_args.as_deref(),
+            vec!["app-server".to_string()],
+        )?;
+        command.current_dir(&self.entry.path);
+        if let Some(codex_home) = &self.codex_home {
+            command.env("CODEX_HOME", codex_home);
+        }
+        command.stdin(std::process::Stdio::piped());
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
+
+        let mut child = command.spawn().map_err(|e| e.to_string())?;
+        let stdin = child.stdin.take().ok_or("missing stdin")?;
+        let stdout = child.stdout.take().ok_or("missing stdout")?;
+        let stderr = child.stderr.take().ok_or("missing stderr")?;
+        Ok(AppServerProcess {
+            child,
+            stdin,
+            stdout,
+            stderr,
+        })
+    }
+}
+
 pub(crate) async fn spawn_workspace_session<E: EventSink>(
     entry: WorkspaceEntry,
     default_codex_bin: Option<String>,
@@ -319,36 +470,87 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         .or(default_codex_bin);
     let _ = check_codex_installation(codex_bin.clone()).await?;
 
-    let mut command = build_codex_command_with_bin(
+    let launch = AppServerLaunch {
+        entry: entry.clone(),
         codex_bin,
-        codex_args.as_deref(),
-        vec!["app-server".to_string()],
-    )?;
-    command.current_dir(&entry.path);
-    if let Some(codex_home) = codex_home {
-        command.env("CODEX_HOME", codex_home);
-    }
-    command.stdin(std::process::Stdio::piped());
-    command.stdout(std::process::Stdio::piped());
-    command.stderr(std::process::Stdio::piped());
+        codex_args,
+        codex_home,
+        client_version,
+    };
+    let process = launch.spawn()?;
+    let session = Arc::new(WorkspaceSession::new(
+        entry.clone(),
+        process.child,
+        process.stdin,
+    ));
+    spawn_stderr_reader(entry.id.clone(), process.stderr, event_sink.clone());
+    let stdout_reader =
+        spawn_stdout_reader(Arc::clone(&session), process.stdout, event_sink.clone());
+
+    if let Err(error) = initialize_session(&session, &launch.client_version).await {
+        session.terminate().await;
+        return Err(error);
+    }
 
-    let mut child = command.spawn().map_err(|e| e.to_string())?;
-    let stdin = child.stdin.take().ok_or("missing stdin")?;
-    let stdout = child.stdout.take().ok_or("missing stdout")?;
-    let stderr = child.stderr.take().ok_or("missing stderr")?;
+    tokio::spawn(run_session_watchdog(
+        Arc::clone(&session),
+        launch,
+        stdout_reader,
+        event_sink.clone(),
+    ));
 
-    let session = Arc::new(WorkspaceSession {
-        entry: entry.clone(),
-        child: Mutex::new(child),
-        stdin: Mutex::new(stdin),
-        pending: Mutex::new(HashMap::new()),
-        next_id: AtomicU64::new(1),
-        background_thread_callbacks: Mutex::new(HashMap::new()),
+    emit_lifecycle_event(
+        &event_sink,
+        &entry.id,
+        "codex/connected",
+        json!({ "workspaceId": entry.id.clone() }),
+    );
+
+    Ok(session)
+}
+
+async fn initialize_session(
+    session: &WorkspaceSession,
+    client_version: &str,
+) -> Result<(), String> {
+    let init_params = build_initialize_params(client_version);
+    let init_result = timeout(
+        INITIALIZE_TIMEOUT,
+        session.send_request_unchecked("initialize", init_params),
+    )
+    .await;
+    match init_result {
+        Ok(response) => {
+            response?;
+        }
+        Err(_) => {
+            return Err(
+                "Codex app-server did not respond to initialize. Check that `codex app-server` works in Terminal."
+                    .to_string(),
+            );
+        }
+    }
+    session.send_notification("initialized", None).await
+}
+
+fn emit_lifecycle_event<E: EventSink>(
+    event_sink: &E,
+    workspace_id: &str,
+    method: &str,
+    params: Value,
+) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({ "method": method, "params": params }),
     });
+}
 
-    let session_clone = Arc::clone(&session);
-    let workspace_id = entry.id.clone();
-    let event_sink_clone = event_sink.clone();
+fn spawn_stdout_reader<E: EventSink>(
+    session: Arc<WorkspaceSession>,
+    stdout: ChildStdout,
+    event_sink: E,
+) -> JoinHandle<()> {
+    let workspace_id = session.entry.id.clone();
     tokio::spawn(async move {
         let mut lines = BufReader::new(stdout).lines();
         while let Ok(Some(line)) = lines.next_line().await {
@@ -365,49 +567,30 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                             "params": { "error": err.to_string(), "raw": line },
                         }),
                     };
-                    event_sink_clone.emit_app_server_event(payload);
+                    event_sink.emit_app_server_event(payload);
                     continue;
                 }
             };
 
             let maybe_id = value.get("id").and_then(|id| id.as_u64());
-            let has_method = value.get("method").is_some();
+            let method = value
+                .get("method")
+                .and_then(Value::as_str)
+                .map(ToString::to_string);
             let has_result_or_error = value.get("result").is_some() || value.get("error").is_some();
 
             // Check if this event is for a background thread
             let thread_id = extract_thread_id(&value);
 
-            if let Some(id) = maybe_id {
-                if has_result_or_error {
-                    if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
-                        let _ = tx.send(value);
-                    }
-                } else if has_method {
-                    // Check for background thread callback
-                    let mut sent_to_background = false;
-                    if let Some(ref tid) = thread_id {
-                        let callbacks = session_clone.background_thread_callbacks.lock().await;
-                        if let Some(tx) = callbacks.get(tid) {
-                            let _ = tx.send(value.clone());
-                            sent_to_background = true;
-                        }
-                    }
-                    // Don't emit to frontend if this is a background thread event
-                    if !sent_to_background {
-                        let payload = AppServerEvent {
-                            workspace_id: workspace_id.clone(),
-                            message: value,
-                        };
-                        event_sink_clone.emit_app_server_event(payload);
-                    }
-                } else if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
+            if let (Some(id), true) = (maybe_id, has_result_or_error) {
+                if let Some(tx) = session.pending.lock().await.remove(&id) {
                     let _ = tx.send(value);
                 }
-            } else if has_method {
+            } else if let Some(method) = method {
                 // Check for background thread callback
                 let mut sent_to_background = false;
                 if let Some(ref tid) = thread_id {
-                    let callbacks = session_clone.background_thread_callbacks.lock().await;
+                    let callbacks = session.background_thread_callbacks.lock().await;
                     if let Some(tx) = callbacks.get(tid) {
                         let _ = tx.send(value.clone());
                         sent_to_background = true;
@@ -415,21 +598,30 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                 }
                 // Don't emit to frontend if this is a background thread event
                 if !sent_to_background {
+                    session
+                        .track_incoming_thread(&method, thread_id.as_deref())
+                        .await;
                     let payload = AppServerEvent {
                         workspace_id: workspace_id.clone(),
                         message: value,
                     };
-                    event_sink_clone.emit_app_server_event(payload);
+                    event_sink.emit_app_server_event(payload);
+                }
+            } else if let Some(id) = maybe_id {
+                if let Some(tx) = session.pending.lock().await.remove(&id) {
+                    let _ = tx.send(value);
                 }
             }
         }
 
-        // Ensure pending foreground requests cannot accumulate after process output ends.
-        session_clone.pending.lock().await.clear();
-    });
+        // Fail in-flight requests right away instead of letting them run into REQUEST_TIMEOUT.
+        session
+            .mark_unavailable("Codex app-server exited; the workspace session is unavailable")
+            .await;
+    })
+}
 
-    let workspace_id = entry.id.clone();
-    let event_sink_clone = event_sink.clone();
+fn spawn_stderr_reader<E: EventSink>(workspace_id: String, stderr: ChildStderr, event_sink: E) {
     tokio::spawn(async move {
         let mut lines = BufReader::new(stderr).lines();
         while let Ok(Some(line)) = lines.next_line().await {
@@ -443,46 +635,334 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                     "params": { "message": line },
                 }),
             };
-            event_sink_clone.emit_app_server_event(payload);
+            event_sink.emit_app_server_event(payload);
         }
     });
+}
 
-    let init_params = build_initialize_params(&client_version);
-    let init_result = timeout(
-        Duration::from_secs(15),
-        session.send_request("initialize", init_params),
-    )
-    .await;
-    let init_response = match init_result {
-        Ok(response) => response,
+fn restart_backoff(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    RESTART_INITIAL_BACKOFF
+        .saturating_mul(factor)
+        .min(RESTART_MAX_BACKOFF)
+}
+
+async fn describe_child_exit(session: &WorkspaceSession) -> (String, Option<i32>) {
+    let mut child = session.child.lock().await;
+    match timeout(Duration::from_secs(2), child.wait()).await {
+        Ok(Ok(status)) => (format!("Codex app-server exited ({status})"), status.code()),
+        Ok(Err(error)) => (format!("Codex app-server exited ({error})"), None),
         Err(_) => {
-            let mut child = session.child.lock().await;
+            // Output closed but the process is still alive; make sure it is gone before respawning.
             kill_child_process_tree(&mut child).await;
-            return Err(
-                "Codex app-server did not respond to initialize. Check that `codex app-server` works in Terminal."
-                    .to_string(),
-            );
+            (
+                "Codex app-server closed its output stream".to_string(),
+                None,
+            )
         }
-    };
-    init_response?;
-    session.send_notification("initialized", None).await?;
-
-    let payload = AppServerEvent {
-        workspace_id: entry.id.clone(),
-        message: json!({
-            "method": "codex/connected",
-            "params": { "workspaceId": entry.id.clone() }
+    }
+}
+
+/// Watches the app-server process and respawns it with exponential backoff when it exits.
+///
+/// Lifecycle events (`codex/disconnected`, `codex/reconnecting`, `codex/reconnected`) are
+/// emitted through the regular event sink so the supervisor loop and clients can react.
+async fn run_session_watchdog<E: EventSink>(
+    session: Arc<WorkspaceSession>,
+    launch: AppServerLaunch,
+    mut stdout_reader: JoinHandle<()>,
+    event_sink: E,
+) {
+    let workspace_id = session.entry.id.clone();
+    loop {
+        let _ = (&mut stdout_reader).await;
+        if session.is_shutdown_requested() {
+            return;
+        }
+
+        let (reason, exit_code) = describe_child_exit(&session).await;
+        session.mark_unavailable(&reason).await;
+        if session.is_shutdown_requested() {
+            return;
+        }
+        emit_lifecycle_event(
+            &event_sink,
+            &workspace_id,
+            "codex/disconnected",
+            json!({
+                "workspaceId": workspace_id,
+                "reason": reason,
+                "exitCode": exit_code,
+                "willRestart": true,
+            }),
+        );
+
+        match restart_session(&session, &launch, &event_sink).await {
+            Some(reader) => stdout_reader = reader,
+            None => return,
+        }
+    }
+}
+
+async fn restart_session<E: EventSink>(
+    session: &Arc<WorkspaceSession>,
+    launch: &AppServerLaunch,
+    event_sink: &E,
+) -> Option<JoinHandle<()>> {
+    let workspace_id = session.entry.id.clone();
+    let mut last_error = String::new();
+    for attempt in 1..=RESTART_MAX_ATTEMPTS {
+        let delay = restart_backoff(attempt);
+        emit_lifecycle_event(
+            event_sink,
+            &workspace_id,
+            "codex/reconnecting",
+            json!({
+                "workspaceId": workspace_id,
+                "attempt": attempt,
+                "maxAttempts": RESTART_MAX_ATTEMPTS,
+                "delayMs": delay.as_millis() as u64,
+            }),
+        );
+        sleep(delay).await;
+        if session.is_shutdown_requested() {
+            return None;
+        }
+
+        match respawn_session(session, launch, event_sink).await {
+            Ok(reader) => {
+                if session.is_shutdown_requested() {
+                    session.terminate().await;
+                    return None;
+                }
+                let (resumed, failed) = resume_active_threads(session).await;
+                emit_lifecycle_event(
+                    event_sink,
+                    &workspace_id,
+                    "codex/reconnected",
+                    json!({
+                        "workspaceId": workspace_id,
+                        "attempt": attempt,
+                        "resumedThreadIds": resumed,
+                        "failedThreadIds": failed,
+                    }),
+                );
+                emit_lifecycle_event(
+                    event_sink,
+                    &workspace_id,
+                    "codex/connected",
+                    json!({ "workspaceId": workspace_id }),
+                );
+                return Some(reader);
+            }
+            Err(error) => last_error = error,
+        }
+    }
+
+    let reason = format!(
+        "Codex app-server could not be restarted after {RESTART_MAX_ATTEMPTS} attempts: {last_error}"
+    );
+    session.mark_unavailable(&reason).await;
+    emit_lifecycle_event(
+        event_sink,
+        &workspace_id,
+        "codex/disconnected",
+        json!({
+            "workspaceId": workspace_id,
+            "reason": reason,
+            "exitCode": Value::Null,
+            "willRestart": false,
         }),
-    };
-    event_sink.emit_app_server_event(payload);
+    );
+    None
+}
 
-    Ok(session)
+async fn respawn_session<E: EventSink>(
+    session: &Arc<WorkspaceSession>,
+    launch: &AppServerLaunch,
+    event_sink: &E,
+) -> Result<JoinHandle<()>, String> {
+    let process = launch.spawn()?;
+    *session.child.lock().await = process.child;
+    *session.stdin.lock().await = process.stdin;
+    spawn_stderr_reader(session.entry.id.clone(), process.stderr, event_sink.clone());
+    let reader = spawn_stdout_reader(Arc::clone(session), process.stdout, event_sink.clone());
+
+    if let Err(error) = initialize_session(session, &launch.client_version).await {
+        {
+            let mut child = session.child.lock().await;
+            kill_child_process_tree(&mut child).await;
+        }
+        // Wait for the reader to finish so it cannot mark a later process unavailable.
+        let _ = reader.await;
+        return Err(error);
+    }
+    session.mark_available().await;
+    Ok(reader)
+}
+
+async fn resume_active_threads(session: &WorkspaceSession) -> (Vec<String>, Vec<String>) {
+    let mut thread_ids = session
+        .active_threads
+        .lock()
+        .await
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    thread_ids.sort();
+
+    let mut resumed = Vec::new();
+    let mut failed = Vec::new();
+    for thread_id in thread_ids {
+        let response = session
+            .send_request("thread/resume", json!({ "threadId": thread_id }))
+            .await;
+        match response {
+            Ok(value) if value.get("error").is_none() => resumed.push(thread_id),
+            _ => {
+                session.active_threads.lock().await.remove(&thread_id);
+                failed.push(thread_id);
+            }
+        }
+    }
+    (resumed, failed)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{build_initialize_params, extract_thread_id};
+    use super::{
+        build_initialize_params, extract_thread_id, restart_backoff, WorkspaceSession,
+        RESTART_MAX_BACKOFF,
+    };
+    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
     use serde_json::json;
+    use std::future::Future;
+    use std::process::Stdio;
+    use std::time::Duration;
+    use tokio::process::Command;
+    use tokio::sync::{mpsc, oneshot};
+
+    fn run_async<F>(future: F)
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime")
+            .block_on(future);
+    }
+
+    fn spawn_idle_session() -> WorkspaceSession {
+        #[cfg(target_os = "windows")]
+        let mut command = {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "more"]);
+            command
+        };
+        #[cfg(not(target_os = "windows"))]
+        let mut command = Command::new("cat");
+
+        command.stdin(Stdio::piped());
+        command.stdout(Stdio::null());
+        command.stderr(Stdio::null());
+
+        let mut child = command.spawn().expect("spawn test session process");
+        let stdin = child.stdin.take().expect("test session stdin");
+        WorkspaceSession::new(
+            WorkspaceEntry {
+                id: "ws-1".to_string(),
+                name: "Workspace".to_string(),
+                path: ".".to_string(),
+                codex_bin: None,
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: WorkspaceSettings::default(),
+            },
+            child,
+            stdin,
+        )
+    }
+
+    #[test]
+    fn restart_backoff_doubles_until_capped() {
+        assert_eq!(restart_backoff(1), Duration::from_secs(1));
+        assert_eq!(restart_backoff(2), Duration::from_secs(2));
+        assert_eq!(restart_backoff(4), Duration::from_secs(8));
+        assert_eq!(restart_backoff(10), RESTART_MAX_BACKOFF);
+        assert_eq!(restart_backoff(u32::MAX), RESTART_MAX_BACKOFF);
+    }
+
+    #[test]
+    fn mark_unavailable_fails_pending_and_new_requests() {
+        run_async(async {
+            let session = spawn_idle_session();
+            let (tx, rx) = oneshot::channel();
+            session.pending.lock().await.insert(7, tx);
+
+            session.mark_unavailable("app-server exited").await;
+
+            let response = rx.await.expect("pending request resolved");
+            assert_eq!(response["id"], json!(7));
+            assert_eq!(response["error"]["message"], json!("app-server exited"));
+            assert!(!session.is_available());
+            assert_eq!(
+                session.send_request("thread/list", json!({})).await,
+                Err("app-server exited".to_string())
+            );
+
+            session.mark_available().await;
+            assert!(session.is_available());
+            session.terminate().await;
+        });
+    }
+
+    #[test]
+    fn tracks_threads_to_resume_after_restart() {
+        run_async(async {
+            let session = spawn_idle_session();
+            session
+                .track_outgoing_thread("thread/resume", &json!({ "threadId": "thread-a" }))
+                .await;
+            session
+                .track_outgoing_thread("turn/start", &json!({ "threadId": "thread-b" }))
+                .await;
+            session
+                .track_incoming_thread("thread/started", Some("thread-c"))
+                .await;
+            session
+                .track_incoming_thread("item/completed", Some("thread-d"))
+                .await;
+            // A helper thread reported by thread/started before its callback was registered.
+            session
+                .track_incoming_thread("thread/started", Some("thread-e"))
+                .await;
+            let (tx, _rx) = mpsc::unbounded_channel();
+            session
+                .background_thread_callbacks
+                .lock()
+                .await
+                .insert("thread-e".to_string(), tx);
+            session
+                .track_outgoing_thread("turn/start", &json!({ "threadId": "thread-e" }))
+                .await;
+            session
+                .track_outgoing_thread("thread/archive", &json!({ "threadId": "thread-b" }))
+                .await;
+
+            let mut active = session
+                .active_threads
+                .lock()
+                .await
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            active.sort();
+            assert_eq!(active, vec!["thread-a".to_string(), "thread-c".to_string()]);
+            session.terminate().await;
+        });
+    }
 
     #[test]
     fn extract_thread_id_reads_camel_case() {
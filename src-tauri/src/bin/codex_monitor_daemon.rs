@@ -1,3 +1,5 @@
+#[path = "codex_monitor_daemon/audit.rs"]
+mod audit;
 #[allow(dead_code)]
 #[path = "../backend/mod.rs"]
 mod backend;
@@ -7,6 +9,8 @@ mod codex_args;
 mod codex_config;
 #[path = "../codex/home.rs"]
 mod codex_home;
+#[path = "codex_monitor_daemon/event_journal.rs"]
+mod event_journal;
 #[path = "../files/io.rs"]
 mod file_io;
 #[path = "../files/ops.rs"]
@@ -15,6 +19,8 @@ mod file_ops;
 mod file_policy;
 #[path = "../git_utils.rs"]
 mod git_utils;
+#[path = "codex_monitor_daemon/http.rs"]
+mod http;
 #[path = "codex_monitor_daemon/rpc.rs"]
 mod rpc;
 #[path = "codex_monitor_daemon/telegram.rs"]
@@ -25,6 +31,10 @@ mod rules;
 mod shared;
 #[path = "../storage.rs"]
 mod storage;
+#[path = "codex_monitor_daemon/tls.rs"]
+mod tls;
+#[path = "codex_monitor_daemon/tokens.rs"]
+mod tokens;
 #[path = "codex_monitor_daemon/transport.rs"]
 mod transport;
 #[allow(dead_code)]
@@ -32,6 +42,8 @@ mod transport;
 mod types;
 #[path = "../utils.rs"]
 mod utils;
+#[path = "codex_monitor_daemon/webhook.rs"]
+mod webhook;
 #[path = "../workspaces/macos.rs"]
 mod workspace_macos;
 #[path = "../workspaces/settings.rs"]
@@ -66,6 +78,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs::File;
 use std::io::Read;
 use std::net::SocketAddr;
@@ -74,26 +87,31 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use ignore::WalkBuilder;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::{broadcast, mpsc, Mutex, Semaphore};
 
 use backend::app_server::{spawn_workspace_session, WorkspaceSession};
 use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use event_journal::{EventJournal, EVENT_JOURNAL_CAPACITY};
 use shared::codex_core::CodexLoginCancelState;
 use shared::prompts_core::{self, CustomPromptEntry};
 use shared::supervisor_core::dispatch::SupervisorDispatchExecutor;
 use shared::supervisor_core::service as supervisor_service;
 use shared::supervisor_core::supervisor_loop::{self, SupervisorLoop, SupervisorLoopConfig};
+use shared::terminal_core::{self, TerminalSessionInfo, TerminalSessions};
 use shared::{
     agents_config_core, codex_aux_core, codex_core, files_core, git_core, git_ui_core,
     local_usage_core, settings_core, workspaces_core, worktree_core,
 };
 use storage::{read_settings, read_workspaces};
 use types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, LocalUsageSnapshot,
-    WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AppSettings, GitBlameHunk, GitCommitDiff, GitConflictContents, GitDiffSelection, GitFileDiff,
+    GitHubIssuesResponse, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestsResponse, GitLineRange, GitLogQuery, GitLogResponse, GitStashEntry,
+    LocalUsageSnapshot, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
 };
 use workspace_settings::apply_workspace_settings_update;
 
@@ -137,7 +155,7 @@ fn spawn_with_client(
 
 #[derive(Clone)]
 struct DaemonEventSink {
-    tx: broadcast::Sender<DaemonEvent>,
+    journal: Arc<EventJournal>,
     supervisor_loop: Arc<Mutex<SupervisorLoop>>,
     supervisor_state_path: PathBuf,
 }
@@ -145,9 +163,7 @@ struct DaemonEventSink {
 #[derive(Clone)]
 enum DaemonEvent {
     AppServer(AppServerEvent),
-    #[allow(dead_code)]
     TerminalOutput(TerminalOutput),
-    #[allow(dead_code)]
     TerminalExit(TerminalExit),
 }
 
@@ -168,20 +184,39 @@ impl EventSink for DaemonEventSink {
             drop(supervisor_loop);
             let _ = supervisor_service::write_supervisor_state(&supervisor_state_path, &snapshot);
         });
-        let _ = self.tx.send(DaemonEvent::AppServer(event));
+        self.journal.publish(DaemonEvent::AppServer(event));
     }
 
     fn emit_terminal_output(&self, event: TerminalOutput) {
-        let _ = self.tx.send(DaemonEvent::TerminalOutput(event));
+        self.journal.publish(DaemonEvent::TerminalOutput(event));
     }
 
     fn emit_terminal_exit(&self, event: TerminalExit) {
-        let _ = self.tx.send(DaemonEvent::TerminalExit(event));
+        self.journal.publish(DaemonEvent::TerminalExit(event));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DaemonListen {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+    Stdio,
+}
+
+impl fmt::Display for DaemonListen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DaemonListen::Tcp(addr) => write!(f, "{addr}"),
+            DaemonListen::Unix(path) => write!(f, "unix:{}", path.display()),
+            DaemonListen::Stdio => write!(f, "stdio"),
+        }
     }
 }
 
 struct DaemonConfig {
-    listen: SocketAddr,
+    listen: DaemonListen,
+    http_listen: Option<SocketAddr>,
+    tls: bool,
     token: Option<String>,
     data_dir: PathBuf,
 }
@@ -198,7 +233,10 @@ struct DaemonState {
     app_settings: Mutex<AppSettings>,
     event_sink: DaemonEventSink,
     codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
+    terminal_sessions: Arc<TerminalSessions>,
     daemon_binary_path: Option<String>,
+    tokens: Mutex<tokens::DaemonTokenStore>,
+    audit_log: Arc<std::sync::Mutex<audit::AuditLog>>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -222,6 +260,10 @@ impl DaemonState {
         let daemon_binary_path = std::env::current_exe()
             .ok()
             .and_then(|path| path.to_str().map(str::to_string));
+        let tokens = tokens::DaemonTokenStore::load(&config.data_dir).unwrap_or_else(|error| {
+            eprintln!("scoped tokens disabled: {error}");
+            tokens::DaemonTokenStore::unreadable(&config.data_dir, error)
+        });
         Self {
             data_dir: config.data_dir.clone(),
             workspaces: Mutex::new(workspaces),
@@ -234,7 +276,18 @@ impl DaemonState {
             app_settings: Mutex::new(app_settings),
             event_sink,
             codex_login_cancels: Mutex::new(HashMap::new()),
+            terminal_sessions: Arc::new(Mutex::new(HashMap::new())),
             daemon_binary_path,
+            tokens: Mutex::new(tokens),
+            audit_log: Arc::new(std::sync::Mutex::new(audit::AuditLog::new(
+                &config.data_dir,
+            ))),
+        }
+    }
+
+    async fn record_audit(&self, entry: audit::AuditEntry) {
+        if let Err(error) = audit::AuditLog::append_shared(&self.audit_log, entry).await {
+            eprintln!("failed to write audit log: {error}");
         }
     }
 
@@ -554,11 +607,8 @@ impl DaemonState {
     }
 
     async fn connect_workspace(&self, id: String, client_version: String) -> Result<(), String> {
-        {
-            let sessions = self.sessions.lock().await;
-            if sessions.contains_key(&id) {
-                return Ok(());
-            }
+        if workspaces_core::session_is_connected(&self.sessions, &id).await {
+            return Ok(());
         }
 
         let client_version = client_version.clone();
@@ -1014,12 +1064,18 @@ impl DaemonState {
                 .await;
         }
 
+        let hide_planner_thread = |workspace_id: &str, thread_id: &str| {
+            emit_background_thread_hide(&self.event_sink, workspace_id, thread_id);
+        };
         let response = supervisor_service::supervisor_chat_send_core(
-            &self.supervisor_loop,
-            &self.supervisor_dispatch_executor,
-            &self.sessions,
-            &self.workspaces,
-            &self.app_settings,
+            &supervisor_service::SupervisorChatContext {
+                supervisor_loop: &self.supervisor_loop,
+                dispatch_executor: &self.supervisor_dispatch_executor,
+                sessions: &self.sessions,
+                workspaces: &self.workspaces,
+                app_settings: &self.app_settings,
+                on_planner_thread: &hide_planner_thread,
+            },
             &command,
             supervisor_loop::now_timestamp_ms(),
         )
@@ -1140,8 +1196,9 @@ impl DaemonState {
         &self,
         workspace_id: String,
         limit: Option<usize>,
+        query: GitLogQuery,
     ) -> Result<GitLogResponse, String> {
-        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, limit).await
+        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, limit, query).await
     }
 
     async fn get_git_commit_diff(
@@ -1158,6 +1215,23 @@ impl DaemonState {
         .await
     }
 
+    async fn get_git_blame(
+        &self,
+        workspace_id: String,
+        path: String,
+        range: Option<GitLineRange>,
+        include_working_tree: bool,
+    ) -> Result<Vec<GitBlameHunk>, String> {
+        git_ui_core::get_git_blame_core(
+            &self.workspaces,
+            workspace_id,
+            path,
+            range,
+            include_working_tree,
+        )
+        .await
+    }
+
     async fn get_git_remote(&self, workspace_id: String) -> Result<Option<String>, String> {
         git_ui_core::get_git_remote_core(&self.workspaces, workspace_id).await
     }
@@ -1178,10 +1252,133 @@ impl DaemonState {
         git_ui_core::revert_git_file_core(&self.workspaces, workspace_id, path).await
     }
 
+    async fn stage_git_hunks(
+        &self,
+        workspace_id: String,
+        path: String,
+        selection: GitDiffSelection,
+    ) -> Result<(), String> {
+        git_ui_core::stage_git_hunks_core(&self.workspaces, workspace_id, path, selection).await
+    }
+
+    async fn unstage_git_hunks(
+        &self,
+        workspace_id: String,
+        path: String,
+        selection: GitDiffSelection,
+    ) -> Result<(), String> {
+        git_ui_core::unstage_git_hunks_core(&self.workspaces, workspace_id, path, selection).await
+    }
+
+    async fn revert_git_hunks(
+        &self,
+        workspace_id: String,
+        path: String,
+        selection: GitDiffSelection,
+    ) -> Result<(), String> {
+        git_ui_core::revert_git_hunks_core(&self.workspaces, workspace_id, path, selection).await
+    }
+
     async fn revert_git_all(&self, workspace_id: String) -> Result<(), String> {
         git_ui_core::revert_git_all_core(&self.workspaces, workspace_id).await
     }
 
+    async fn list_git_stashes(&self, workspace_id: String) -> Result<Vec<GitStashEntry>, String> {
+        git_ui_core::list_git_stashes_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn stash_push(
+        &self,
+        workspace_id: String,
+        message: Option<String>,
+        include_untracked: bool,
+        paths: Vec<String>,
+    ) -> Result<(), String> {
+        git_ui_core::stash_push_core(
+            &self.workspaces,
+            workspace_id,
+            message,
+            include_untracked,
+            paths,
+        )
+        .await
+    }
+
+    async fn stash_apply(
+        &self,
+        workspace_id: String,
+        index: usize,
+        sha: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::stash_apply_core(&self.workspaces, workspace_id, index, sha).await
+    }
+
+    async fn stash_pop(
+        &self,
+        workspace_id: String,
+        index: usize,
+        sha: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::stash_pop_core(&self.workspaces, workspace_id, index, sha).await
+    }
+
+    async fn stash_drop(
+        &self,
+        workspace_id: String,
+        index: usize,
+        sha: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::stash_drop_core(&self.workspaces, workspace_id, index, sha).await
+    }
+
+    async fn get_git_stash_diff(
+        &self,
+        workspace_id: String,
+        index: usize,
+    ) -> Result<Vec<GitCommitDiff>, String> {
+        git_ui_core::get_git_stash_diff_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            index,
+        )
+        .await
+    }
+
+    async fn get_git_conflict_contents(
+        &self,
+        workspace_id: String,
+        path: String,
+    ) -> Result<GitConflictContents, String> {
+        git_ui_core::get_git_conflict_contents_core(&self.workspaces, workspace_id, path).await
+    }
+
+    async fn mark_git_conflict_resolved(
+        &self,
+        workspace_id: String,
+        path: String,
+    ) -> Result<(), String> {
+        git_ui_core::mark_git_conflict_resolved_core(&self.workspaces, workspace_id, path).await
+    }
+
+    async fn resolve_git_conflict_with_side(
+        &self,
+        workspace_id: String,
+        path: String,
+        side: String,
+    ) -> Result<(), String> {
+        git_ui_core::resolve_git_conflict_with_side_core(&self.workspaces, workspace_id, path, side)
+            .await
+    }
+
+    async fn abort_git_operation(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::abort_git_operation_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn continue_git_operation(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::continue_git_operation_core(&self.workspaces, workspace_id).await
+    }
+
     async fn commit_git(&self, workspace_id: String, message: String) -> Result<(), String> {
         git_ui_core::commit_git_core(&self.workspaces, workspace_id, message).await
     }
@@ -1341,6 +1538,60 @@ impl DaemonState {
         .await
     }
 
+    async fn terminal_open(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+        cols: u16,
+        rows: u16,
+    ) -> Result<TerminalSessionInfo, String> {
+        terminal_core::terminal_open_core(
+            &self.terminal_sessions,
+            &self.workspaces,
+            workspace_id,
+            terminal_id,
+            cols,
+            rows,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
+    async fn terminal_write(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+        data: String,
+    ) -> Result<(), String> {
+        terminal_core::terminal_write_core(&self.terminal_sessions, workspace_id, terminal_id, data)
+            .await
+    }
+
+    async fn terminal_resize(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+        cols: u16,
+        rows: u16,
+    ) -> Result<(), String> {
+        terminal_core::terminal_resize_core(
+            &self.terminal_sessions,
+            workspace_id,
+            terminal_id,
+            cols,
+            rows,
+        )
+        .await
+    }
+
+    async fn terminal_close(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+    ) -> Result<(), String> {
+        terminal_core::terminal_close_core(&self.terminal_sessions, workspace_id, terminal_id).await
+    }
+
     async fn codex_doctor(
         &self,
         codex_bin: Option<String>,
@@ -1581,23 +1832,52 @@ fn default_data_dir() -> PathBuf {
 fn usage() -> String {
     format!(
         "\
-USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth]\n\n\
-OPTIONS:\n  --listen <addr>          Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>        Data dir holding workspaces.json/settings.json\n  --token <token>          Shared token required by TCP clients\n  --insecure-no-auth       Disable TCP auth (dev only)\n  -h, --help               Show this help\n"
+USAGE:\n  codex-monitor-daemon [--listen <addr> | --stdio] [--http-listen <addr>] [--tls] [--data-dir <path>] [--token <token> | --insecure-no-auth]\n\n\
+OPTIONS:\n  --listen <addr>          Bind address, or unix:<path> for a Unix socket (default: {DEFAULT_LISTEN_ADDR})\n  --tls                    Serve TCP and HTTP over TLS with a self-signed certificate kept in the data dir\n  --stdio                  Serve a single client over stdin/stdout (e.g. via ssh)\n  --http-listen <addr>     Also serve POST /rpc/<method> and GET /events (SSE) over HTTP\n  --data-dir <path>        Data dir holding workspaces.json/settings.json\n  --token <token>          Shared token required by TCP clients\n  --insecure-no-auth       Disable TCP auth (dev only)\n  -h, --help               Show this help\n\n\
+Unix socket and stdio clients are not asked for a token; the socket is created with 0600 permissions.\n\
+Additional scoped tokens (readOnly, supervisor, per-workspace) are read from <data-dir>/tokens.json.\n\
+Mutating RPC calls are recorded in <data-dir>/audit.jsonl.\n\
+Supervisor webhooks are read from <data-dir>/webhooks.json (or $SUPERVISOR_WEBHOOKS_CONFIG).\n"
     )
 }
 
+fn parse_listen(value: &str) -> Result<DaemonListen, String> {
+    let value = value.trim();
+    if let Some(path) = value.strip_prefix("unix:") {
+        let path = path.trim();
+        if path.is_empty() {
+            return Err("--listen unix: requires a socket path".to_string());
+        }
+        if !cfg!(unix) {
+            return Err("Unix socket listeners are not supported on this platform".to_string());
+        }
+        return Ok(DaemonListen::Unix(PathBuf::from(path)));
+    }
+    let addr = value.strip_prefix("tcp:").unwrap_or(value);
+    addr.parse::<SocketAddr>()
+        .map(DaemonListen::Tcp)
+        .map_err(|err| format!("invalid --listen address `{value}`: {err}"))
+}
+
 fn parse_args() -> Result<DaemonConfig, String> {
-    let mut listen = DEFAULT_LISTEN_ADDR
-        .parse::<SocketAddr>()
-        .map_err(|err| err.to_string())?;
-    let mut token = env::var("CODEX_MONITOR_DAEMON_TOKEN")
-        .ok()
+    let env_token = env::var("CODEX_MONITOR_DAEMON_TOKEN").ok();
+    parse_args_from(env::args().skip(1), env_token)
+}
+
+fn parse_args_from(
+    mut args: impl Iterator<Item = String>,
+    env_token: Option<String>,
+) -> Result<DaemonConfig, String> {
+    let mut listen: Option<DaemonListen> = None;
+    let mut http_listen: Option<SocketAddr> = None;
+    let mut stdio = false;
+    let mut tls = false;
+    let mut token = env_token
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty());
     let mut insecure_no_auth = false;
     let mut data_dir: Option<PathBuf> = None;
 
-    let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
         match arg.as_str() {
             "-h" | "--help" => {
@@ -1606,7 +1886,21 @@ fn parse_args() -> Result<DaemonConfig, String> {
             }
             "--listen" => {
                 let value = args.next().ok_or("--listen requires a value")?;
-                listen = value.parse::<SocketAddr>().map_err(|err| err.to_string())?;
+                listen = Some(parse_listen(&value)?);
+            }
+            "--http-listen" => {
+                let value = args.next().ok_or("--http-listen requires a value")?;
+                let addr = value
+                    .trim()
+                    .parse::<SocketAddr>()
+                    .map_err(|err| format!("invalid --http-listen address `{value}`: {err}"))?;
+                http_listen = Some(addr);
+            }
+            "--stdio" => {
+                stdio = true;
+            }
+            "--tls" => {
+                tls = true;
             }
             "--token" => {
                 let value = args.next().ok_or("--token requires a value")?;
@@ -1632,7 +1926,19 @@ fn parse_args() -> Result<DaemonConfig, String> {
         }
     }
 
-    if token.is_none() && !insecure_no_auth {
+    let listen = match (listen, stdio) {
+        (Some(_), true) => return Err("--stdio cannot be combined with --listen".to_string()),
+        (_, true) => DaemonListen::Stdio,
+        (Some(listen), false) => listen,
+        (None, false) => parse_listen(DEFAULT_LISTEN_ADDR)?,
+    };
+
+    let serves_tcp = matches!(listen, DaemonListen::Tcp(_)) || http_listen.is_some();
+    if tls && !serves_tcp {
+        return Err("--tls is only supported for TCP listeners".to_string());
+    }
+
+    if serves_tcp && token.is_none() && !insecure_no_auth {
         return Err(
             "Missing --token (or set CODEX_MONITOR_DAEMON_TOKEN). Use --insecure-no-auth for local dev only."
                 .to_string(),
@@ -1641,6 +1947,8 @@ fn parse_args() -> Result<DaemonConfig, String> {
 
     Ok(DaemonConfig {
         listen,
+        http_listen,
+        tls,
         token,
         data_dir: data_dir.unwrap_or_else(default_data_dir),
     })
@@ -1680,7 +1988,6 @@ mod tests {
     }
 
     fn test_state(data_dir: &std::path::Path) -> DaemonState {
-        let (tx, _rx) = broadcast::channel::<DaemonEvent>(32);
         let supervisor_state_path = supervisor_service::supervisor_state_path(data_dir);
         let supervisor_loop = Arc::new(Mutex::new(SupervisorLoop::new(
             SupervisorLoopConfig::default(),
@@ -1697,13 +2004,278 @@ mod tests {
             settings_path: data_dir.join("settings.json"),
             app_settings: Mutex::new(AppSettings::default()),
             event_sink: DaemonEventSink {
-                tx,
+                journal: Arc::new(EventJournal::new(32)),
                 supervisor_loop,
                 supervisor_state_path,
             },
             codex_login_cancels: Mutex::new(HashMap::new()),
+            terminal_sessions: Arc::new(Mutex::new(HashMap::new())),
             daemon_binary_path: Some("/tmp/codex-monitor-daemon".to_string()),
+            tokens: Mutex::new(tokens::DaemonTokenStore::empty(data_dir)),
+            audit_log: Arc::new(std::sync::Mutex::new(audit::AuditLog::new(data_dir))),
+        }
+    }
+
+    fn args(values: &[&str]) -> impl Iterator<Item = String> {
+        values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parse_listen_accepts_tcp_and_unix_addresses() {
+        assert_eq!(
+            parse_listen("127.0.0.1:4732").expect("tcp"),
+            DaemonListen::Tcp("127.0.0.1:4732".parse().expect("addr"))
+        );
+        assert_eq!(
+            parse_listen("tcp:0.0.0.0:9000").expect("tcp prefix"),
+            DaemonListen::Tcp("0.0.0.0:9000".parse().expect("addr"))
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            parse_listen("unix:/tmp/codex-monitor.sock").expect("unix"),
+            DaemonListen::Unix(PathBuf::from("/tmp/codex-monitor.sock"))
+        );
+        assert!(parse_listen("unix:").is_err());
+        assert!(parse_listen("not-an-address").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_listener_binds_only_inside_an_owner_only_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = make_temp_dir("unix-listener");
+        let mode = |path: &Path| {
+            std::fs::metadata(path)
+                .expect("metadata")
+                .permissions()
+                .mode()
+                & 0o777
+        };
+        run_async_test(async {
+            std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+            let error = bind_unix_listener(&dir.join("daemon.sock"))
+                .expect_err("shared directory is rejected");
+            assert!(error.contains("only by its owner"), "{error}");
+
+            let socket_dir = dir.join("private");
+            let socket = socket_dir.join("daemon.sock");
+            let _listener = bind_unix_listener(&socket).expect("bind");
+            assert_eq!(mode(&socket_dir), 0o700);
+            assert_eq!(mode(&socket), 0o600);
+        });
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_args_requires_token_only_for_tcp() {
+        let error = parse_args_from(args(&["--listen", "127.0.0.1:4732"]), None)
+            .err()
+            .expect("tcp without token fails");
+        assert!(error.contains("Missing --token"));
+
+        let config = parse_args_from(args(&["--stdio"]), None).expect("stdio config");
+        assert_eq!(config.listen, DaemonListen::Stdio);
+        assert!(config.token.is_none());
+
+        #[cfg(unix)]
+        {
+            let config = parse_args_from(args(&["--listen", "unix:/tmp/daemon.sock"]), None)
+                .expect("unix config");
+            assert_eq!(
+                config.listen,
+                DaemonListen::Unix(PathBuf::from("/tmp/daemon.sock"))
+            );
         }
+
+        assert!(parse_args_from(
+            args(&["--stdio", "--listen", "127.0.0.1:4732"]),
+            Some("token".to_string())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_args_accepts_tls_only_for_tcp() {
+        let config = parse_args_from(
+            args(&["--listen", "0.0.0.0:4732", "--tls"]),
+            Some("token".to_string()),
+        )
+        .expect("tls config");
+        assert!(config.tls);
+
+        let error = parse_args_from(args(&["--stdio", "--tls"]), None)
+            .err()
+            .expect("stdio with tls fails");
+        assert!(error.contains("--tls"));
+    }
+
+    #[test]
+    fn parse_args_http_listener_requires_token() {
+        let error = parse_args_from(args(&["--stdio", "--http-listen", "127.0.0.1:4733"]), None)
+            .err()
+            .expect("http without token fails");
+        assert!(error.contains("Missing --token"));
+
+        let config = parse_args_from(
+            args(&[
+                "--listen",
+                "unix:/tmp/daemon.sock",
+                "--http-listen",
+                "127.0.0.1:4733",
+                "--tls",
+            ]),
+            Some("token".to_string()),
+        )
+        .expect("http config");
+        assert_eq!(config.http_listen, Some("127.0.0.1:4733".parse().unwrap()));
+        assert!(config.tls);
+
+        assert!(parse_args_from(
+            args(&["--http-listen", "localhost"]),
+            Some("token".to_string())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn trusted_connection_skips_auth_handshake() {
+        run_async_test(async {
+            let tmp = make_temp_dir("trusted-connection");
+            let state = Arc::new(test_state(&tmp));
+            let config = Arc::new(DaemonConfig {
+                listen: DaemonListen::Stdio,
+                http_listen: None,
+                tls: false,
+                token: Some("secret".to_string()),
+                data_dir: tmp.clone(),
+            });
+
+            for (trusted, expected) in [
+                (true, json!({ "id": 1, "result": { "ok": true } })),
+                (
+                    false,
+                    json!({ "id": 1, "error": { "message": "unauthorized" } }),
+                ),
+            ] {
+                let events = Arc::new(EventJournal::new(8));
+                let (client, server) = tokio::io::duplex(4096);
+                let (server_reader, server_writer) = tokio::io::split(server);
+                let task = tokio::spawn(transport::serve_connection(
+                    server_reader,
+                    server_writer,
+                    "test".to_string(),
+                    trusted,
+                    Arc::clone(&config),
+                    Arc::clone(&state),
+                    events,
+                ));
+
+                let (client_reader, mut client_writer) = tokio::io::split(client);
+                client_writer
+                    .write_all(b"{\"id\":1,\"method\":\"ping\"}\n")
+                    .await
+                    .expect("write request");
+                let mut lines = BufReader::new(client_reader).lines();
+                let line = lines
+                    .next_line()
+                    .await
+                    .expect("read response")
+                    .expect("response line");
+                let response: Value = serde_json::from_str(&line).expect("response json");
+                assert_eq!(response, expected);
+
+                drop(client_writer);
+                drop(lines);
+                task.await.expect("connection task");
+            }
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    async fn next_json_line<R>(lines: &mut tokio::io::Lines<BufReader<R>>) -> Value
+    where
+        R: AsyncRead + Unpin,
+    {
+        let line = lines
+            .next_line()
+            .await
+            .expect("read line")
+            .expect("message line");
+        serde_json::from_str(&line).expect("message json")
+    }
+
+    #[test]
+    fn subscribe_replays_missed_events_before_live_ones() {
+        run_async_test(async {
+            let tmp = make_temp_dir("subscribe-replay");
+            let state = Arc::new(test_state(&tmp));
+            let config = Arc::new(DaemonConfig {
+                listen: DaemonListen::Stdio,
+                http_listen: None,
+                tls: false,
+                token: None,
+                data_dir: tmp.clone(),
+            });
+            let events = Arc::new(EventJournal::new(8));
+            for index in 0..3 {
+                events.publish(DaemonEvent::AppServer(AppServerEvent {
+                    workspace_id: "ws-1".to_string(),
+                    message: json!({ "method": "test", "params": { "index": index } }),
+                }));
+            }
+
+            let (client, server) = tokio::io::duplex(16 * 1024);
+            let (server_reader, server_writer) = tokio::io::split(server);
+            let task = tokio::spawn(transport::serve_connection(
+                server_reader,
+                server_writer,
+                "test".to_string(),
+                true,
+                Arc::clone(&config),
+                Arc::clone(&state),
+                Arc::clone(&events),
+            ));
+
+            let (client_reader, mut client_writer) = tokio::io::split(client);
+            let request = json!({
+                "id": 1,
+                "method": "subscribe",
+                "params": { "streamId": events.stream_id(), "sinceSeq": 1 },
+            });
+            client_writer
+                .write_all(format!("{request}\n").as_bytes())
+                .await
+                .expect("write request");
+            let mut lines = BufReader::new(client_reader).lines();
+
+            let response = next_json_line(&mut lines).await;
+            assert_eq!(response["result"]["latestSeq"], 3);
+            assert_eq!(response["result"]["gap"], false);
+            let resume = next_json_line(&mut lines).await;
+            assert_eq!(resume["method"], "event-resume");
+            assert_eq!(resume["params"]["fromSeq"], 1);
+            assert_eq!(next_json_line(&mut lines).await["seq"], 2);
+            assert_eq!(next_json_line(&mut lines).await["seq"], 3);
+
+            events.publish(DaemonEvent::AppServer(AppServerEvent {
+                workspace_id: "ws-1".to_string(),
+                message: json!({ "method": "test", "params": { "index": 3 } }),
+            }));
+            let live = next_json_line(&mut lines).await;
+            assert_eq!(live["method"], "app-server-event");
+            assert_eq!(live["seq"], 4);
+
+            drop(client_writer);
+            drop(lines);
+            task.await.expect("connection task");
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
     }
 
     async fn insert_workspace(state: &DaemonState, workspace_id: &str, workspace_path: &str) {
@@ -1735,6 +2307,7 @@ mod tests {
 
             let err = rpc::handle_rpc_request(
                 &state,
+                &tokens::DaemonPrincipal::owner(),
                 "add_clone",
                 json!({
                     "sourceWorkspaceId": "source",
@@ -1768,6 +2341,7 @@ mod tests {
 
             let result = rpc::handle_rpc_request(
                 &state,
+                &tokens::DaemonPrincipal::owner(),
                 "prompts_list",
                 json!({ "workspaceId": workspace_id }),
                 "daemon-test".to_string(),
@@ -1789,6 +2363,49 @@ mod tests {
         });
     }
 
+    #[test]
+    fn rpc_terminal_methods_require_known_workspace_and_session() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-terminal");
+            let state = test_state(&tmp);
+            let owner = tokens::DaemonPrincipal::owner();
+
+            let open_error = rpc::handle_rpc_request(
+                &state,
+                &owner,
+                "terminal_open",
+                json!({ "workspaceId": "missing", "terminalId": "term-1", "cols": 80, "rows": 24 }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("unknown workspace should fail");
+            assert_eq!(open_error, "Unknown workspace");
+
+            let write_error = rpc::handle_rpc_request(
+                &state,
+                &owner,
+                "terminal_write",
+                json!({ "workspaceId": "missing", "terminalId": "term-1", "data": "ls\n" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("missing session should fail");
+            assert_eq!(write_error, "Terminal session not found");
+
+            let missing_id = rpc::handle_rpc_request(
+                &state,
+                &owner,
+                "terminal_close",
+                json!({ "workspaceId": "missing" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("terminal id is required");
+            assert!(missing_id.contains("terminalId"));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
     #[test]
     fn rpc_local_usage_snapshot_returns_snapshot_shape() {
         run_async_test(async {
@@ -1797,6 +2414,7 @@ mod tests {
 
             let result = rpc::handle_rpc_request(
                 &state,
+                &tokens::DaemonPrincipal::owner(),
                 "local_usage_snapshot",
                 json!({ "days": 7 }),
                 "daemon-test".to_string(),
@@ -1818,6 +2436,7 @@ mod tests {
 
             let result = rpc::handle_rpc_request(
                 &state,
+                &tokens::DaemonPrincipal::owner(),
                 "daemon_info",
                 json!({}),
                 "daemon-test".to_string(),
@@ -1838,6 +2457,60 @@ mod tests {
         });
     }
 
+    #[test]
+    fn rpc_scoped_token_is_limited_to_its_scope() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-scoped-token");
+            let state = test_state(&tmp);
+            let owner = tokens::DaemonPrincipal::owner();
+
+            let created = rpc::handle_rpc_request(
+                &state,
+                &owner,
+                "daemon_token_create",
+                json!({ "name": "phone", "scope": "readOnly", "workspaceIds": ["ws-1"] }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("token create should succeed");
+            let secret = created
+                .get("token")
+                .and_then(Value::as_str)
+                .expect("token secret")
+                .to_string();
+
+            let principal = state
+                .tokens
+                .lock()
+                .await
+                .authenticate(&secret)
+                .expect("created token should authenticate");
+            let err = rpc::handle_rpc_request(
+                &state,
+                &principal,
+                "daemon_token_create",
+                json!({ "name": "other", "scope": "full" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("read-only token must not create tokens");
+            assert!(err.contains("not allowed"), "unexpected error: {err}");
+
+            let listed = rpc::handle_rpc_request(
+                &state,
+                &principal,
+                "list_workspaces",
+                json!({}),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("read-only token can list workspaces");
+            assert_eq!(listed, json!([]));
+
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
     #[test]
     fn load_supervisor_loop_restores_persisted_state() {
         run_async_test(async {
@@ -1890,12 +2563,12 @@ fn main() {
         .expect("failed to build tokio runtime");
 
     runtime.block_on(async move {
-        let (events_tx, _events_rx) = broadcast::channel::<DaemonEvent>(2048);
+        let journal = Arc::new(EventJournal::new(EVENT_JOURNAL_CAPACITY));
         let supervisor_loop = load_supervisor_loop(&config.data_dir);
         let supervisor_state_path = supervisor_service::supervisor_state_path(&config.data_dir);
         let supervisor_dispatch_executor = Arc::new(Mutex::new(SupervisorDispatchExecutor::new()));
         let event_sink = DaemonEventSink {
-            tx: events_tx.clone(),
+            journal: Arc::clone(&journal),
             supervisor_loop: Arc::clone(&supervisor_loop),
             supervisor_state_path: supervisor_state_path.clone(),
         };
@@ -1915,6 +2588,20 @@ fn main() {
                         &state.supervisor_loop,
                         &state.workspaces,
                         &state.sessions,
+                        &state.app_settings,
+                        supervisor_loop::now_timestamp_ms(),
+                    )
+                    .await;
+                    supervisor_service::run_job_policy_tick(
+                        &state.supervisor_loop,
+                        &state.sessions,
+                        supervisor_loop::now_timestamp_ms(),
+                    )
+                    .await;
+                    supervisor_service::run_schedule_tick(
+                        &state.supervisor_loop,
+                        &state.supervisor_dispatch_executor,
+                        &state.sessions,
                         supervisor_loop::now_timestamp_ms(),
                     )
                     .await;
@@ -1938,17 +2625,21 @@ fn main() {
             });
         } else {
             eprintln!(
-                "telegram bridge disabled: set SUPERVISOR_TELEGRAM_BOT_TOKEN and SUPERVISOR_TELEGRAM_ALLOWED_USER_ID"
+                "telegram bridge disabled: set SUPERVISOR_TELEGRAM_BOT_TOKEN and SUPERVISOR_TELEGRAM_ALLOWED_USER_ID or SUPERVISOR_TELEGRAM_ALLOWED_USERS"
             );
         }
 
-        let listener = match TcpListener::bind(config.listen).await {
-            Ok(listener) => listener,
-            Err(err) => {
-                eprintln!("failed to bind {}: {err}", config.listen);
-                std::process::exit(2);
+        match webhook::WebhookConfig::load(&state.data_dir) {
+            Ok(Some(webhook_config)) => {
+                let state = Arc::clone(&state);
+                tokio::spawn(async move {
+                    webhook::run(state, webhook_config).await;
+                });
             }
-        };
+            Ok(None) => {}
+            Err(error) => eprintln!("webhook notifier disabled: {error}"),
+        }
+
         eprintln!(
             "codex-monitor-daemon listening on {} (data dir: {})",
             config.listen,
@@ -1959,18 +2650,182 @@ fn main() {
                 .display()
         );
 
-        loop {
-            match listener.accept().await {
-                Ok((socket, _addr)) => {
-                    let config = Arc::clone(&config);
-                    let state = Arc::clone(&state);
-                    let events = events_tx.clone();
-                    tokio::spawn(async move {
-                        transport::handle_client(socket, config, state, events).await;
-                    });
+        let acceptor = if config.tls {
+            match tls::load_or_create(&config.data_dir) {
+                Ok(daemon_tls) => {
+                    eprintln!(
+                        "TLS enabled; certificate fingerprint (SHA-256): {}",
+                        daemon_tls.fingerprint
+                    );
+                    Some(daemon_tls.acceptor)
+                }
+                Err(err) => {
+                    eprintln!("failed to set up TLS: {err}");
+                    std::process::exit(2);
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some(addr) = config.http_listen {
+            let listener = match TcpListener::bind(addr).await {
+                Ok(listener) => listener,
+                Err(err) => {
+                    eprintln!("failed to bind HTTP listener {addr}: {err}");
+                    std::process::exit(2);
+                }
+            };
+            eprintln!("HTTP API listening on {addr}");
+            let config = Arc::clone(&config);
+            let state = Arc::clone(&state);
+            let journal = Arc::clone(&journal);
+            let acceptor = acceptor.clone();
+            let limiter = Arc::new(http::HttpRequestLimiter::default());
+            tokio::spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((socket, _addr)) => {
+                            tokio::spawn(http::handle_http_client(
+                                socket,
+                                acceptor.clone(),
+                                Arc::clone(&config),
+                                Arc::clone(&state),
+                                Arc::clone(&journal),
+                                Arc::clone(&limiter),
+                            ));
+                        }
+                        Err(_) => continue,
+                    }
+                }
+            });
+        }
+
+        match config.listen.clone() {
+            DaemonListen::Tcp(addr) => {
+                let listener = match TcpListener::bind(addr).await {
+                    Ok(listener) => listener,
+                    Err(err) => {
+                        eprintln!("failed to bind {addr}: {err}");
+                        std::process::exit(2);
+                    }
+                };
+                loop {
+                    match listener.accept().await {
+                        Ok((socket, _addr)) => {
+                            let config = Arc::clone(&config);
+                            let state = Arc::clone(&state);
+                            let events = Arc::clone(&journal);
+                            let acceptor = acceptor.clone();
+                            tokio::spawn(async move {
+                                match acceptor {
+                                    Some(acceptor) => {
+                                        transport::handle_tls_client(
+                                            socket, acceptor, config, state, events,
+                                        )
+                                        .await;
+                                    }
+                                    None => {
+                                        transport::handle_client(socket, config, state, events)
+                                            .await;
+                                    }
+                                }
+                            });
+                        }
+                        Err(_) => continue,
+                    }
+                }
+            }
+            #[cfg(unix)]
+            DaemonListen::Unix(path) => {
+                let listener = match bind_unix_listener(&path) {
+                    Ok(listener) => listener,
+                    Err(err) => {
+                        eprintln!("failed to bind unix:{}: {err}", path.display());
+                        std::process::exit(2);
+                    }
+                };
+                loop {
+                    match listener.accept().await {
+                        Ok((socket, _addr)) => {
+                            let config = Arc::clone(&config);
+                            let state = Arc::clone(&state);
+                            let events = Arc::clone(&journal);
+                            tokio::spawn(async move {
+                                transport::handle_unix_client(socket, config, state, events)
+                                    .await;
+                            });
+                        }
+                        Err(_) => continue,
+                    }
+                }
+            }
+            #[cfg(not(unix))]
+            DaemonListen::Unix(path) => {
+                eprintln!(
+                    "unix socket listeners are not supported on this platform: {}",
+                    path.display()
+                );
+                std::process::exit(2);
+            }
+            DaemonListen::Stdio => {
+                transport::serve_stdio(Arc::clone(&config), Arc::clone(&state), journal).await;
+                let sessions = state
+                    .sessions
+                    .lock()
+                    .await
+                    .drain()
+                    .map(|(_, session)| session)
+                    .collect::<Vec<_>>();
+                for session in sessions {
+                    session.terminate().await;
                 }
-                Err(_) => continue,
             }
         }
     });
 }
+
+/// Binds a Unix socket readable and writable only by the daemon user, replacing a stale socket.
+/// The socket lives in an owner-only directory, so other users cannot reach it before its
+/// permissions are tightened.
+#[cfg(unix)]
+fn bind_unix_listener(path: &Path) -> Result<UnixListener, String> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    ensure_private_dir(dir)?;
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err("path exists and is not a socket".to_string());
+        }
+        std::fs::remove_file(path).map_err(|err| err.to_string())?;
+    }
+    let listener = UnixListener::bind(path).map_err(|err| err.to_string())?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|err| err.to_string())?;
+    Ok(listener)
+}
+
+/// Creates `dir` with mode 0700 when missing, or checks that group and others cannot access it.
+#[cfg(unix)]
+fn ensure_private_dir(dir: &Path) -> Result<(), String> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    match std::fs::metadata(dir) {
+        Ok(metadata) if !metadata.is_dir() => Err(format!("{} is not a directory", dir.display())),
+        Ok(metadata) if metadata.permissions().mode() & 0o077 != 0 => Err(format!(
+            "socket directory {} must be accessible only by its owner (mode 0700)",
+            dir.display()
+        )),
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)
+            .map_err(|err| err.to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+use std::sync::Mutex as StdMutex;
+
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+
+use super::DaemonEvent;
+
+pub(super) const EVENT_JOURNAL_CAPACITY: usize = 4096;
+const EVENT_BROADCAST_CAPACITY: usize = 2048;
+
+#[derive(Clone)]
+pub(super) struct SequencedEvent {
+    pub(super) seq: u64,
+    pub(super) event: DaemonEvent,
+}
+
+/// Where a client wants the event stream to resume from.
+#[derive(Debug, Default, Clone)]
+pub(super) struct EventCursor {
+    pub(super) stream_id: Option<String>,
+    pub(super) since_seq: Option<u64>,
+}
+
+impl EventCursor {
+    pub(super) fn from_params(params: &Value) -> Self {
+        Self {
+            stream_id: params
+                .get("streamId")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            since_seq: params.get("sinceSeq").and_then(Value::as_u64),
+        }
+    }
+}
+
+/// Buffered events a subscriber missed, plus the gap if some are no longer buffered.
+pub(super) struct EventReplay {
+    /// `event-resume` notice telling the client which sequence the replay continues from.
+    pub(super) resume: Option<Value>,
+    pub(super) events: Vec<SequencedEvent>,
+    pub(super) gap: Option<Value>,
+    pub(super) latest_seq: u64,
+}
+
+struct JournalBuffer {
+    next_seq: u64,
+    events: VecDeque<SequencedEvent>,
+    /// Terminal output is buffered separately so a chatty terminal cannot evict
+    /// app-server events from the replay window.
+    terminal_output: VecDeque<SequencedEvent>,
+    /// Highest sequence number dropped from either buffer.
+    evicted_through: u64,
+}
+
+impl JournalBuffer {
+    fn push(&mut self, event: SequencedEvent, capacity: usize) {
+        let lane = match event.event {
+            DaemonEvent::TerminalOutput(_) => &mut self.terminal_output,
+            _ => &mut self.events,
+        };
+        if lane.len() >= capacity {
+            if let Some(evicted) = lane.pop_front() {
+                self.evicted_through = self.evicted_through.max(evicted.seq);
+            }
+        }
+        lane.push_back(event);
+    }
+
+    /// Events from both buffers after `since_seq`, in sequence order.
+    fn after(&self, since_seq: u64) -> Vec<SequencedEvent> {
+        let mut events = self
+            .events
+            .iter()
+            .chain(self.terminal_output.iter())
+            .filter(|event| event.seq > since_seq)
+            .cloned()
+            .collect::<Vec<_>>();
+        events.sort_by_key(|event| event.seq);
+        events
+    }
+}
+
+/// Assigns every daemon event a monotonically increasing sequence number and keeps the
+/// most recent ones so reconnecting clients can catch up. `stream_id` changes on every
+/// daemon start, which tells clients their sequence numbers no longer apply. Terminal
+/// output and all other events are each capped at `capacity`.
+pub(super) struct EventJournal {
+    stream_id: String,
+    capacity: usize,
+    sender: broadcast::Sender<SequencedEvent>,
+    buffer: StdMutex<JournalBuffer>,
+}
+
+impl EventJournal {
+    pub(super) fn new(capacity: usize) -> Self {
+        let (sender, _rx) = broadcast::channel(EVENT_BROADCAST_CAPACITY);
+        Self {
+            stream_id: uuid::Uuid::new_v4().to_string(),
+            capacity,
+            sender,
+            buffer: StdMutex::new(JournalBuffer {
+                next_seq: 1,
+                events: VecDeque::with_capacity(capacity),
+                terminal_output: VecDeque::new(),
+                evicted_through: 0,
+            }),
+        }
+    }
+
+    pub(super) fn stream_id(&self) -> &str {
+        &self.stream_id
+    }
+
+    pub(super) fn publish(&self, event: DaemonEvent) {
+        let mut buffer = self.buffer.lock().unwrap_or_else(|err| err.into_inner());
+        let sequenced = SequencedEvent {
+            seq: buffer.next_seq,
+            event,
+        };
+        buffer.next_seq += 1;
+        buffer.push(sequenced.clone(), self.capacity);
+        // Sent while holding the buffer lock so `subscribe` sees each event exactly once,
+        // either in the replay or on the receiver.
+        let _ = self.sender.send(sequenced);
+    }
+
+    /// Subscribes to live events and returns whatever the cursor missed. Without a
+    /// `since_seq` the subscription is live-only.
+    pub(super) fn subscribe(
+        &self,
+        cursor: &EventCursor,
+    ) -> (broadcast::Receiver<SequencedEvent>, EventReplay) {
+        let buffer = self.buffer.lock().unwrap_or_else(|err| err.into_inner());
+        let rx = self.sender.subscribe();
+        let replay = match cursor.since_seq {
+            None => EventReplay {
+                resume: None,
+                events: Vec::new(),
+                gap: None,
+                latest_seq: buffer.next_seq - 1,
+            },
+            Some(since_seq) => {
+                let same_stream = cursor
+                    .stream_id
+                    .as_deref()
+                    .is_none_or(|stream_id| stream_id == self.stream_id);
+                let (from_seq, mut replay) = if same_stream && since_seq < buffer.next_seq {
+                    (since_seq, self.replay_from(&buffer, since_seq))
+                } else {
+                    // The daemon restarted (or the client is ahead of it): everything still
+                    // buffered is new to the client, and anything before it is lost.
+                    let mut replay = self.replay_from(&buffer, 0);
+                    replay.gap = Some(self.gap(&buffer, since_seq, "restarted"));
+                    (0, replay)
+                };
+                replay.resume = Some(json!({
+                    "method": "event-resume",
+                    "params": {
+                        "streamId": self.stream_id,
+                        "fromSeq": from_seq,
+                        "latestSeq": replay.latest_seq,
+                    },
+                }));
+                replay
+            }
+        };
+        (rx, replay)
+    }
+
+    /// Events after `since_seq`, used when a subscriber lagged behind the live channel.
+    pub(super) fn replay_since(&self, since_seq: u64) -> EventReplay {
+        let buffer = self.buffer.lock().unwrap_or_else(|err| err.into_inner());
+        self.replay_from(&buffer, since_seq)
+    }
+
+    fn replay_from(&self, buffer: &JournalBuffer, since_seq: u64) -> EventReplay {
+        let gap =
+            (since_seq < buffer.evicted_through).then(|| self.gap(buffer, since_seq, "overflow"));
+        EventReplay {
+            resume: None,
+            events: buffer.after(since_seq),
+            gap,
+            latest_seq: buffer.next_seq - 1,
+        }
+    }
+
+    fn gap(&self, buffer: &JournalBuffer, since_seq: u64, reason: &str) -> Value {
+        json!({
+            "method": "event-gap",
+            "params": {
+                "streamId": self.stream_id,
+                "sinceSeq": since_seq,
+                // Every event from here on is still buffered.
+                "oldestSeq": buffer.evicted_through + 1,
+                "latestSeq": buffer.next_seq - 1,
+                "reason": reason,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::events::{AppServerEvent, TerminalOutput};
+
+    fn event(index: u64) -> DaemonEvent {
+        DaemonEvent::AppServer(AppServerEvent {
+            workspace_id: "ws-1".to_string(),
+            message: json!({ "method": "test", "params": { "index": index } }),
+        })
+    }
+
+    fn seqs(replay: &EventReplay) -> Vec<u64> {
+        replay.events.iter().map(|event| event.seq).collect()
+    }
+
+    fn cursor(stream_id: Option<&str>, since_seq: Option<u64>) -> EventCursor {
+        EventCursor {
+            stream_id: stream_id.map(str::to_string),
+            since_seq,
+        }
+    }
+
+    #[test]
+    fn replays_buffered_events_after_cursor() {
+        let journal = EventJournal::new(8);
+        for index in 0..5 {
+            journal.publish(event(index));
+        }
+
+        let (_rx, replay) = journal.subscribe(&cursor(Some(journal.stream_id()), Some(2)));
+        assert_eq!(seqs(&replay), vec![3, 4, 5]);
+        assert!(replay.gap.is_none());
+        assert_eq!(replay.latest_seq, 5);
+        let resume = replay.resume.expect("resume notice");
+        assert_eq!(resume["params"]["fromSeq"], 2);
+
+        let (_rx, live_only) = journal.subscribe(&EventCursor::default());
+        assert!(live_only.events.is_empty());
+        assert!(live_only.resume.is_none());
+        assert_eq!(live_only.latest_seq, 5);
+    }
+
+    #[test]
+    fn reports_gap_when_buffer_overflowed() {
+        let journal = EventJournal::new(3);
+        for index in 0..6 {
+            journal.publish(event(index));
+        }
+
+        let (_rx, replay) = journal.subscribe(&cursor(None, Some(1)));
+        assert_eq!(seqs(&replay), vec![4, 5, 6]);
+        let gap = replay.gap.expect("overflow gap");
+        assert_eq!(gap["method"], "event-gap");
+        assert_eq!(gap["params"]["oldestSeq"], 4);
+        assert_eq!(gap["params"]["reason"], "overflow");
+
+        assert!(journal.replay_since(3).gap.is_none());
+    }
+
+    #[test]
+    fn terminal_output_does_not_evict_app_server_events() {
+        let journal = EventJournal::new(3);
+        journal.publish(event(0));
+        for index in 0..5 {
+            journal.publish(DaemonEvent::TerminalOutput(TerminalOutput {
+                workspace_id: "ws-1".to_string(),
+                terminal_id: "term-1".to_string(),
+                data: format!("line {index}\n"),
+            }));
+        }
+        journal.publish(event(1));
+
+        let (_rx, replay) = journal.subscribe(&cursor(None, Some(0)));
+        assert_eq!(seqs(&replay), vec![1, 4, 5, 6, 7]);
+        assert_eq!(replay.gap.expect("terminal gap")["params"]["oldestSeq"], 4);
+        let (_rx, replay) = journal.subscribe(&cursor(None, Some(3)));
+        assert_eq!(seqs(&replay), vec![4, 5, 6, 7]);
+        assert!(replay.gap.is_none());
+    }
+
+    #[test]
+    fn foreign_stream_replays_everything_with_gap() {
+        let journal = EventJournal::new(8);
+        journal.publish(event(0));
+        journal.publish(event(1));
+
+        let (_rx, replay) = journal.subscribe(&cursor(Some("previous-daemon"), Some(40)));
+        assert_eq!(seqs(&replay), vec![1, 2]);
+        let gap = replay.gap.expect("restart gap");
+        assert_eq!(gap["params"]["reason"], "restarted");
+        assert_eq!(
+            replay.resume.expect("resume notice")["params"]["fromSeq"],
+            0
+        );
+        assert_eq!(gap["params"]["streamId"], journal.stream_id());
+    }
+
+    #[test]
+    fn subscriber_receives_events_published_after_subscribe() {
+        let journal = EventJournal::new(8);
+        journal.publish(event(0));
+        let (mut rx, replay) = journal.subscribe(&cursor(None, Some(0)));
+        journal.publish(event(1));
+
+        assert_eq!(seqs(&replay), vec![1]);
+        assert_eq!(rx.try_recv().expect("live event").seq, 2);
+    }
+}
@@ -0,0 +1,486 @@
+use super::event_journal::EventCursor;
+use super::rpc::{execute_rpc, forward_events, RpcCaller};
+use super::tokens::DaemonPrincipal;
+use super::transport::{authenticate_token, peer_label};
+use super::*;
+use tokio::io::{AsyncBufRead, AsyncReadExt};
+use tokio::time::timeout;
+use tokio_rustls::TlsAcceptor;
+
+const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const HTTP_TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const HTTP_MAX_HEAD_BYTES: usize = 16 * 1024;
+const HTTP_MAX_BODY_BYTES: usize = 8 * 1024 * 1024;
+const SSE_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// Each connection carries a single request, so `/rpc` calls share a limit per token
+/// instead of the per-connection limit used by the socket transports.
+const HTTP_MAX_IN_FLIGHT_RPC_PER_TOKEN: usize = MAX_IN_FLIGHT_RPC_PER_CONNECTION;
+
+/// In-flight `/rpc` limits, keyed by the authenticated token name.
+#[derive(Default)]
+pub(super) struct HttpRequestLimiter {
+    limiters: std::sync::Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl HttpRequestLimiter {
+    fn for_token(&self, token_name: &str) -> Arc<Semaphore> {
+        let mut limiters = self.limiters.lock().unwrap_or_else(|err| err.into_inner());
+        Arc::clone(
+            limiters
+                .entry(token_name.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(HTTP_MAX_IN_FLIGHT_RPC_PER_TOKEN))),
+        )
+    }
+}
+
+/// A parsed HTTP/1.1 request. Every connection serves a single request and is then
+/// closed, except for the event stream which stays open.
+#[derive(Debug)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    fn bearer_token(&self) -> Option<&str> {
+        let value = self.header("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+    }
+}
+
+#[derive(Debug)]
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+fn status_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+pub(super) async fn handle_http_client(
+    socket: TcpStream,
+    acceptor: Option<TlsAcceptor>,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: Arc<EventJournal>,
+    limiter: Arc<HttpRequestLimiter>,
+) {
+    let peer = format!("http:{}", peer_label(&socket));
+    match acceptor {
+        Some(acceptor) => {
+            let stream = match timeout(HTTP_TLS_HANDSHAKE_TIMEOUT, acceptor.accept(socket)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(err)) => {
+                    eprintln!("HTTP TLS handshake failed: {err}");
+                    return;
+                }
+                Err(_) => {
+                    eprintln!("HTTP TLS handshake timed out");
+                    return;
+                }
+            };
+            serve_http_connection(stream, peer, config, state, events, limiter).await;
+        }
+        None => serve_http_connection(socket, peer, config, state, events, limiter).await,
+    }
+}
+
+async fn serve_http_connection<S>(
+    stream: S,
+    peer: String,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: Arc<EventJournal>,
+    limiter: Arc<HttpRequestLimiter>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+    let request = match timeout(HTTP_REQUEST_TIMEOUT, read_request(&mut reader)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err(error)) => {
+            let _ = write_error(&mut writer, &error).await;
+            return;
+        }
+        Err(_) => {
+            let _ = write_error(&mut writer, &HttpError::new(408, "request timed out")).await;
+            return;
+        }
+    };
+
+    let result = route_request(&request, &peer, &config, &state, &limiter).await;
+    let outcome = match result {
+        Ok(HttpRoute::Rpc(value)) => {
+            write_json(&mut writer, 200, &json!({ "result": value })).await
+        }
+        Ok(HttpRoute::Events(caller)) => {
+            stream_events(&mut writer, &request, caller, events).await;
+            Ok(())
+        }
+        Err(error) => write_error(&mut writer, &error).await,
+    };
+    if outcome.is_ok() {
+        let _ = writer.shutdown().await;
+    }
+}
+
+enum HttpRoute {
+    Rpc(Value),
+    Events(Arc<RpcCaller>),
+}
+
+async fn route_request(
+    request: &HttpRequest,
+    peer: &str,
+    config: &DaemonConfig,
+    state: &DaemonState,
+    limiter: &HttpRequestLimiter,
+) -> Result<HttpRoute, HttpError> {
+    let rpc_method = request.path.strip_prefix("/rpc/");
+    let is_events = request.path == "/events";
+    if rpc_method.is_none() && !is_events {
+        return Err(HttpError::new(404, "not found"));
+    }
+    let expected_method = if is_events { "GET" } else { "POST" };
+    if request.method != expected_method {
+        return Err(HttpError::new(
+            405,
+            format!("{} expects {expected_method}", request.path),
+        ));
+    }
+
+    let principal = if config.token.is_none() {
+        DaemonPrincipal::owner()
+    } else {
+        let provided = request
+            .bearer_token()
+            .ok_or_else(|| HttpError::new(401, "missing bearer token"))?;
+        authenticate_token(config, state, provided)
+            .await
+            .ok_or_else(|| HttpError::new(401, "invalid token"))?
+    };
+    let caller = Arc::new(RpcCaller {
+        principal,
+        peer: peer.to_string(),
+    });
+
+    let Some(method) = rpc_method else {
+        return Ok(HttpRoute::Events(caller));
+    };
+    let params = if request.body.iter().all(u8::is_ascii_whitespace) {
+        Value::Null
+    } else {
+        serde_json::from_slice(&request.body)
+            .map_err(|err| HttpError::new(400, format!("invalid JSON body: {err}")))?
+    };
+    caller
+        .principal
+        .authorize(method, &params)
+        .map_err(|message| HttpError::new(403, message))?;
+    let _permit = limiter
+        .for_token(&caller.principal.name)
+        .acquire_owned()
+        .await
+        .map_err(|err| HttpError::new(500, err.to_string()))?;
+    execute_rpc(state, &caller, method, params)
+        .await
+        .map(HttpRoute::Rpc)
+        .map_err(|message| {
+            let status = if message.starts_with("unknown method") {
+                404
+            } else {
+                400
+            };
+            HttpError::new(status, message)
+        })
+}
+
+async fn read_request<R>(reader: &mut R) -> Result<HttpRequest, HttpError>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut head_bytes = 0;
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|err| HttpError::new(400, err.to_string()))?;
+        if read == 0 {
+            return Err(HttpError::new(400, "connection closed before request head"));
+        }
+        head_bytes += read;
+        if head_bytes > HTTP_MAX_HEAD_BYTES {
+            return Err(HttpError::new(413, "request head too large"));
+        }
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+
+    let mut lines = lines.into_iter();
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(HttpError::new(400, "malformed request line"));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let headers = lines
+        .filter_map(|line| {
+            line.split_once(':')
+                .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut request = HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query.to_string(),
+        headers,
+        body: Vec::new(),
+    };
+    if request
+        .header("transfer-encoding")
+        .is_some_and(|value| !value.eq_ignore_ascii_case("identity"))
+    {
+        return Err(HttpError::new(
+            411,
+            "chunked bodies are not supported; send Content-Length",
+        ));
+    }
+    let content_length = match request.header("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| HttpError::new(400, "invalid Content-Length"))?,
+        None => 0,
+    };
+    if content_length > HTTP_MAX_BODY_BYTES {
+        return Err(HttpError::new(413, "request body too large"));
+    }
+    request.body = vec![0; content_length];
+    reader
+        .read_exact(&mut request.body)
+        .await
+        .map_err(|err| HttpError::new(400, err.to_string()))?;
+    Ok(request)
+}
+
+async fn write_json<W>(writer: &mut W, status: u16, body: &Value) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let body = body.to_string();
+    let head = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status_reason(status),
+        body.len()
+    );
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(body.as_bytes()).await?;
+    writer.flush().await
+}
+
+async fn write_error<W>(writer: &mut W, error: &HttpError) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    write_json(
+        writer,
+        error.status,
+        &json!({ "error": { "message": error.message } }),
+    )
+    .await
+}
+
+/// Cursor for `GET /events`: `streamId`/`sinceSeq` query params, or the `Last-Event-ID`
+/// header an `EventSource` sends when it reconnects.
+fn events_cursor(request: &HttpRequest) -> EventCursor {
+    if let Some((stream_id, seq)) = request
+        .header("last-event-id")
+        .and_then(|value| value.rsplit_once(':'))
+    {
+        if let Ok(since_seq) = seq.parse::<u64>() {
+            return EventCursor {
+                stream_id: Some(stream_id.to_string()),
+                since_seq: Some(since_seq),
+            };
+        }
+    }
+    EventCursor {
+        stream_id: request.query_param("streamId").map(str::to_string),
+        since_seq: request
+            .query_param("sinceSeq")
+            .and_then(|value| value.parse::<u64>().ok()),
+    }
+}
+
+/// Formats one daemon notification as a Server-Sent Event. The event name is the
+/// notification method and the id is `<streamId>:<seq>`.
+fn format_sse_event(message: &str, stream_id: &str) -> String {
+    let parsed = serde_json::from_str::<Value>(message).unwrap_or(Value::Null);
+    let mut event = String::new();
+    if let Some(seq) = parsed.get("seq").and_then(Value::as_u64) {
+        event.push_str(&format!("id: {stream_id}:{seq}\n"));
+    }
+    if let Some(method) = parsed.get("method").and_then(Value::as_str) {
+        event.push_str(&format!("event: {method}\n"));
+    }
+    event.push_str(&format!("data: {message}\n\n"));
+    event
+}
+
+async fn stream_events<W>(
+    writer: &mut W,
+    request: &HttpRequest,
+    caller: Arc<RpcCaller>,
+    events: Arc<EventJournal>,
+) where
+    W: AsyncWrite + Unpin,
+{
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+    if writer.write_all(head.as_bytes()).await.is_err() || writer.flush().await.is_err() {
+        return;
+    }
+
+    let (rx, replay) = events.subscribe(&events_cursor(request));
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
+    let stream_id = events.stream_id().to_string();
+    let forward_task = tokio::spawn(forward_events(rx, replay, events, out_tx, caller));
+
+    loop {
+        let chunk = match timeout(SSE_KEEPALIVE_INTERVAL, out_rx.recv()).await {
+            Ok(Some(message)) => format_sse_event(&message, &stream_id),
+            Ok(None) => break,
+            Err(_) => ": keepalive\n\n".to_string(),
+        };
+        if writer.write_all(chunk.as_bytes()).await.is_err() || writer.flush().await.is_err() {
+            break;
+        }
+    }
+    forward_task.abort();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_async<F>(future: F)
+    where
+        F: std::future::Future<Output = ()>,
+    {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime")
+            .block_on(future);
+    }
+
+    #[test]
+    fn parses_request_head_body_and_bearer_token() {
+        run_async(async {
+            let raw = b"POST /rpc/get_git_status?x=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer  secret \r\nContent-Length: 22\r\n\r\n{\"workspaceId\":\"ws-1\"}\n\n";
+            let mut reader = BufReader::new(&raw[..]);
+            let request = read_request(&mut reader).await.expect("request");
+            assert_eq!(request.method, "POST");
+            assert_eq!(request.path, "/rpc/get_git_status");
+            assert_eq!(request.query_param("x"), Some("1"));
+            assert_eq!(request.bearer_token(), Some("secret"));
+            let params: Value = serde_json::from_slice(&request.body).expect("json body");
+            assert_eq!(params["workspaceId"], "ws-1");
+
+            let chunked = b"POST /rpc/ping HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+            let error = read_request(&mut BufReader::new(&chunked[..]))
+                .await
+                .expect_err("chunked bodies are rejected");
+            assert_eq!(error.status, 411);
+        });
+    }
+
+    #[test]
+    fn rpc_limiter_is_shared_per_token() {
+        let limiter = HttpRequestLimiter::default();
+        let phone = limiter.for_token("phone");
+        assert!(Arc::ptr_eq(&phone, &limiter.for_token("phone")));
+        assert!(!Arc::ptr_eq(&phone, &limiter.for_token("ci")));
+        assert_eq!(phone.available_permits(), HTTP_MAX_IN_FLIGHT_RPC_PER_TOKEN);
+    }
+
+    #[test]
+    fn formats_sse_events_and_resumes_from_last_event_id() {
+        let message = json!({
+            "method": "app-server-event",
+            "params": { "workspace_id": "ws-1" },
+            "seq": 7,
+        })
+        .to_string();
+        let event = format_sse_event(&message, "stream-1");
+        assert_eq!(
+            event,
+            format!("id: stream-1:7\nevent: app-server-event\ndata: {message}\n\n")
+        );
+
+        let request = HttpRequest {
+            method: "GET".to_string(),
+            path: "/events".to_string(),
+            query: "streamId=other&sinceSeq=2".to_string(),
+            headers: vec![("Last-Event-ID".to_string(), "stream-1:7".to_string())],
+            body: Vec::new(),
+        };
+        let cursor = events_cursor(&request);
+        assert_eq!(cursor.stream_id.as_deref(), Some("stream-1"));
+        assert_eq!(cursor.since_seq, Some(7));
+
+        let from_query = HttpRequest {
+            headers: Vec::new(),
+            ..request
+        };
+        let cursor = events_cursor(&from_query);
+        assert_eq!(cursor.stream_id.as_deref(), Some("other"));
+        assert_eq!(cursor.since_seq, Some(2));
+    }
+}
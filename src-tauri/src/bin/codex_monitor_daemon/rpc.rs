@@ -1,3 +1,7 @@
+use super::audit::{AuditLog, AuditQuery, PendingAuditEntry};
+use super::event_journal::{EventJournal, EventReplay, SequencedEvent};
+use super::tokens::DaemonPrincipal;
+use super::webhook::{WebhookDeliveryLog, WebhookDeliveryQuery};
 use super::*;
 
 #[path = "rpc/codex.rs"]
@@ -12,9 +16,17 @@ mod git;
 mod prompts;
 #[path = "rpc/supervisor.rs"]
 mod supervisor;
+#[path = "rpc/terminal.rs"]
+mod terminal;
 #[path = "rpc/workspace.rs"]
 mod workspace;
 
+/// The authenticated identity and peer address behind a connection.
+pub(super) struct RpcCaller {
+    pub(super) principal: DaemonPrincipal,
+    pub(super) peer: String,
+}
+
 pub(super) fn build_error_response(id: Option<u64>, message: &str) -> Option<String> {
     let id = id?;
     Some(
@@ -37,22 +49,18 @@ pub(super) fn build_result_response(id: Option<u64>, result: Value) -> Option<St
     )
 }
 
-fn build_event_notification(event: DaemonEvent) -> Option<String> {
-    let payload = match event {
-        DaemonEvent::AppServer(payload) => json!({
-            "method": "app-server-event",
-            "params": payload,
-        }),
-        DaemonEvent::TerminalOutput(payload) => json!({
-            "method": "terminal-output",
-            "params": payload,
-        }),
-        DaemonEvent::TerminalExit(payload) => json!({
-            "method": "terminal-exit",
-            "params": payload,
-        }),
+fn build_event_notification(sequenced: SequencedEvent) -> Option<String> {
+    let (method, params) = match sequenced.event {
+        DaemonEvent::AppServer(payload) => ("app-server-event", json!(payload)),
+        DaemonEvent::TerminalOutput(payload) => ("terminal-output", json!(payload)),
+        DaemonEvent::TerminalExit(payload) => ("terminal-exit", json!(payload)),
     };
-    serde_json::to_string(&payload).ok()
+    serde_json::to_string(&json!({
+        "method": method,
+        "params": params,
+        "seq": sequenced.seq,
+    }))
+    .ok()
 }
 
 pub(super) fn parse_auth_token(params: &Value) -> Option<String> {
@@ -135,25 +143,88 @@ pub(super) fn parse_optional_value(value: &Value, key: &str) -> Option<Value> {
 
 pub(super) async fn handle_rpc_request(
     state: &DaemonState,
+    principal: &DaemonPrincipal,
     method: &str,
     params: Value,
     client_version: String,
 ) -> Result<Value, String> {
-    dispatcher::dispatch_rpc_request(state, method, &params, &client_version).await
+    dispatcher::dispatch_rpc_request(state, principal, method, &params, &client_version).await
+}
+
+fn event_workspace_id(event: &DaemonEvent) -> &str {
+    match event {
+        DaemonEvent::AppServer(payload) => &payload.workspace_id,
+        DaemonEvent::TerminalOutput(payload) => &payload.workspace_id,
+        DaemonEvent::TerminalExit(payload) => &payload.workspace_id,
+    }
+}
+
+/// Sends replayed events, preceded by the resume and gap notices, to the client. Returns
+/// false once the connection is gone.
+fn send_replay(
+    out_tx_events: &mpsc::UnboundedSender<String>,
+    caller: &RpcCaller,
+    replay: EventReplay,
+) -> bool {
+    for notice in replay.resume.into_iter().chain(replay.gap) {
+        if out_tx_events.send(notice.to_string()).is_err() {
+            return false;
+        }
+    }
+    for sequenced in replay.events {
+        if !caller
+            .principal
+            .allows_workspace(event_workspace_id(&sequenced.event))
+        {
+            continue;
+        }
+        let Some(payload) = build_event_notification(sequenced) else {
+            continue;
+        };
+        if out_tx_events.send(payload).is_err() {
+            return false;
+        }
+    }
+    true
 }
 
 pub(super) async fn forward_events(
-    mut rx: broadcast::Receiver<DaemonEvent>,
+    mut rx: broadcast::Receiver<SequencedEvent>,
+    replay: EventReplay,
+    journal: Arc<EventJournal>,
     out_tx_events: mpsc::UnboundedSender<String>,
+    caller: Arc<RpcCaller>,
 ) {
+    let mut last_seq = replay.latest_seq;
+    if !send_replay(&out_tx_events, &caller, replay) {
+        return;
+    }
     loop {
-        let event = match rx.recv().await {
-            Ok(event) => event,
-            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+        let sequenced = match rx.recv().await {
+            Ok(sequenced) => sequenced,
+            Err(broadcast::error::RecvError::Lagged(_)) => {
+                // Catch up from the journal instead of silently dropping events.
+                let replay = journal.replay_since(last_seq);
+                last_seq = replay.latest_seq;
+                if !send_replay(&out_tx_events, &caller, replay) {
+                    break;
+                }
+                continue;
+            }
             Err(broadcast::error::RecvError::Closed) => break,
         };
+        if sequenced.seq <= last_seq {
+            continue;
+        }
+        last_seq = sequenced.seq;
+        if !caller
+            .principal
+            .allows_workspace(event_workspace_id(&sequenced.event))
+        {
+            continue;
+        }
 
-        let Some(payload) = build_event_notification(event) else {
+        let Some(payload) = build_event_notification(sequenced) else {
             continue;
         };
 
@@ -163,20 +234,42 @@ pub(super) async fn forward_events(
     }
 }
 
+/// Runs one RPC call for an authenticated caller and records it in the audit log.
+pub(super) async fn execute_rpc(
+    state: &DaemonState,
+    caller: &RpcCaller,
+    method: &str,
+    params: Value,
+) -> Result<Value, String> {
+    let pending_audit = PendingAuditEntry::begin(
+        &caller.peer,
+        &caller.principal,
+        method,
+        &params,
+        supervisor_loop::now_timestamp_ms(),
+    );
+    let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
+    let result = handle_rpc_request(state, &caller.principal, method, params, client_version).await;
+    if let Some(pending_audit) = pending_audit {
+        state.record_audit(pending_audit.finish(&result)).await;
+    }
+    result
+}
+
 pub(super) fn spawn_rpc_response_task(
     state: Arc<DaemonState>,
+    caller: Arc<RpcCaller>,
     out_tx: mpsc::UnboundedSender<String>,
     id: Option<u64>,
     method: String,
     params: Value,
-    client_version: String,
     request_limiter: Arc<Semaphore>,
 ) {
     tokio::spawn(async move {
         let Ok(_permit) = request_limiter.acquire_owned().await else {
             return;
         };
-        let result = handle_rpc_request(&state, &method, params, client_version).await;
+        let result = execute_rpc(&state, &caller, &method, params).await;
         let response = match result {
             Ok(result) => build_result_response(id, result),
             Err(message) => build_error_response(id, &message),
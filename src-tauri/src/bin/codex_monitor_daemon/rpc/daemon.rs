@@ -15,6 +15,60 @@ pub(super) async fn try_handle(
             });
             Some(Ok(json!({ "ok": true })))
         }
+        "daemon_tokens_list" => Some(Ok(state.tokens.lock().await.list())),
+        "daemon_token_create" => {
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let scope = match parse_string(params, "scope") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let workspace_ids = parse_optional_string_array(params, "workspaceIds");
+            Some(state.tokens.lock().await.create(
+                &name,
+                &scope,
+                workspace_ids,
+                supervisor_loop::now_timestamp_ms(),
+            ))
+        }
+        "daemon_token_revoke" => {
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .tokens
+                    .lock()
+                    .await
+                    .revoke(&name)
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "audit_log_query" => {
+            let query = match AuditQuery::from_params(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let entries = match AuditLog::query_shared(&state.audit_log, query).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(entries).map_err(|err| err.to_string()))
+        }
+        "webhook_deliveries" => {
+            let query = match WebhookDeliveryQuery::from_params(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let entries = match WebhookDeliveryLog::new(&state.data_dir).query(&query) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(entries).map_err(|err| err.to_string()))
+        }
         "menu_set_accelerators" => {
             let updates: Vec<Value> = match params {
                 Value::Object(map) => match map
@@ -2,10 +2,23 @@ use super::*;
 
 pub(super) async fn dispatch_rpc_request(
     state: &DaemonState,
+    principal: &DaemonPrincipal,
     method: &str,
     params: &Value,
     client_version: &str,
 ) -> Result<Value, String> {
+    principal.authorize(method, params)?;
+
+    if method == "list_workspaces" && principal.is_workspace_restricted() {
+        let workspaces = state
+            .list_workspaces()
+            .await
+            .into_iter()
+            .filter(|workspace| principal.allows_workspace(&workspace.id))
+            .collect::<Vec<_>>();
+        return serde_json::to_value(workspaces).map_err(|err| err.to_string());
+    }
+
     if let Some(result) = daemon::try_handle(state, method, params).await {
         return result;
     }
@@ -30,5 +43,9 @@ pub(super) async fn dispatch_rpc_request(
         return result;
     }
 
+    if let Some(result) = terminal::try_handle(state, method, params).await {
+        return result;
+    }
+
     Err(format!("unknown method: {method}"))
 }
@@ -1,5 +1,24 @@
 use super::*;
 
+fn parse_git_selection_request(
+    params: &Value,
+) -> Result<(String, String, GitDiffSelection), String> {
+    let workspace_id = parse_string(params, "workspaceId")?;
+    let path = parse_string(params, "path")?;
+    let selection = parse_optional_value(params, "selection")
+        .ok_or_else(|| "missing `selection`".to_string())?;
+    let selection = serde_json::from_value(selection).map_err(|err| err.to_string())?;
+    Ok((workspace_id, path, selection))
+}
+
+fn parse_stash_request(params: &Value) -> Result<(String, usize), String> {
+    let workspace_id = parse_string(params, "workspaceId")?;
+    let index = super::super::parse_optional_u64(params, "index")
+        .and_then(|value| usize::try_from(value).ok())
+        .ok_or("missing or invalid `index`")?;
+    Ok((workspace_id, index))
+}
+
 pub(super) async fn try_handle(
     state: &DaemonState,
     method: &str,
@@ -74,7 +93,15 @@ pub(super) async fn try_handle(
                 Err(err) => return Some(Err(err)),
             };
             let limit = parse_optional_u32(params, "limit").map(|value| value as usize);
-            let log = match state.get_git_log(workspace_id, limit).await {
+            let query = match parse_optional_value(params, "query")
+                .filter(|value| !value.is_null())
+                .map(serde_json::from_value)
+                .transpose()
+            {
+                Ok(value) => value.unwrap_or_default(),
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let log = match state.get_git_log(workspace_id, limit, query).await {
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
@@ -95,6 +122,34 @@ pub(super) async fn try_handle(
             };
             Some(serde_json::to_value(diff).map_err(|err| err.to_string()))
         }
+        "get_git_blame" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let range = match parse_optional_value(params, "range")
+                .filter(|value| !value.is_null())
+                .map(serde_json::from_value)
+                .transpose()
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
+            let include_working_tree =
+                parse_optional_bool(params, "includeWorkingTree").unwrap_or(false);
+            let blame = match state
+                .get_git_blame(workspace_id, path, range, include_working_tree)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(blame).map_err(|err| err.to_string()))
+        }
         "get_git_remote" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -166,6 +221,42 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "stage_git_hunks" => {
+            let (workspace_id, path, selection) = match parse_git_selection_request(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .stage_git_hunks(workspace_id, path, selection)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "unstage_git_hunks" => {
+            let (workspace_id, path, selection) = match parse_git_selection_request(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .unstage_git_hunks(workspace_id, path, selection)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "revert_git_hunks" => {
+            let (workspace_id, path, selection) = match parse_git_selection_request(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .revert_git_hunks(workspace_id, path, selection)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
         "revert_git_all" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -178,6 +269,158 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "list_git_stashes" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let stashes = match state.list_git_stashes(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(stashes).map_err(|err| err.to_string()))
+        }
+        "stash_push" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let message = parse_optional_string(params, "message");
+            let include_untracked =
+                parse_optional_bool(params, "includeUntracked").unwrap_or(false);
+            let paths = parse_optional_string_array(params, "paths").unwrap_or_default();
+            Some(
+                state
+                    .stash_push(workspace_id, message, include_untracked, paths)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "stash_apply" => {
+            let (workspace_id, index) = match parse_stash_request(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let sha = parse_optional_string(params, "sha");
+            Some(
+                state
+                    .stash_apply(workspace_id, index, sha)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "stash_pop" => {
+            let (workspace_id, index) = match parse_stash_request(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let sha = parse_optional_string(params, "sha");
+            Some(
+                state
+                    .stash_pop(workspace_id, index, sha)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "stash_drop" => {
+            let (workspace_id, index) = match parse_stash_request(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let sha = parse_optional_string(params, "sha");
+            Some(
+                state
+                    .stash_drop(workspace_id, index, sha)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "get_git_stash_diff" => {
+            let (workspace_id, index) = match parse_stash_request(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let diff = match state.get_git_stash_diff(workspace_id, index).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(diff).map_err(|err| err.to_string()))
+        }
+        "get_git_conflict_contents" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let contents = match state.get_git_conflict_contents(workspace_id, path).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(contents).map_err(|err| err.to_string()))
+        }
+        "mark_git_conflict_resolved" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .mark_git_conflict_resolved(workspace_id, path)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "resolve_git_conflict_with_side" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let side = match parse_string(params, "side") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .resolve_git_conflict_with_side(workspace_id, path, side)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "abort_git_operation" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .abort_git_operation(workspace_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "continue_git_operation" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .continue_git_operation(workspace_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
         "commit_git" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
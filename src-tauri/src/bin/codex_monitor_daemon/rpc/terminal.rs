@@ -0,0 +1,89 @@
+use super::*;
+
+const DEFAULT_TERMINAL_COLS: u16 = 80;
+const DEFAULT_TERMINAL_ROWS: u16 = 24;
+
+fn parse_terminal_size(params: &Value) -> (u16, u16) {
+    let dimension = |key: &str, default: u16| {
+        parse_optional_u32(params, key)
+            .map(|value| value.min(u16::MAX as u32) as u16)
+            .unwrap_or(default)
+    };
+    (
+        dimension("cols", DEFAULT_TERMINAL_COLS),
+        dimension("rows", DEFAULT_TERMINAL_ROWS),
+    )
+}
+
+fn parse_terminal_target(params: &Value) -> Result<(String, String), String> {
+    Ok((
+        parse_string(params, "workspaceId")?,
+        parse_string(params, "terminalId")?,
+    ))
+}
+
+pub(super) async fn try_handle(
+    state: &DaemonState,
+    method: &str,
+    params: &Value,
+) -> Option<Result<Value, String>> {
+    match method {
+        "terminal_open" => {
+            let (workspace_id, terminal_id) = match parse_terminal_target(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let (cols, rows) = parse_terminal_size(params);
+            let info = match state
+                .terminal_open(workspace_id, terminal_id, cols, rows)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(info).map_err(|err| err.to_string()))
+        }
+        "terminal_write" => {
+            let (workspace_id, terminal_id) = match parse_terminal_target(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let data = match parse_string(params, "data") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .terminal_write(workspace_id, terminal_id, data)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "terminal_resize" => {
+            let (workspace_id, terminal_id) = match parse_terminal_target(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let (cols, rows) = parse_terminal_size(params);
+            Some(
+                state
+                    .terminal_resize(workspace_id, terminal_id, cols, rows)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        "terminal_close" => {
+            let (workspace_id, terminal_id) = match parse_terminal_target(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .terminal_close(workspace_id, terminal_id)
+                    .await
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
+        _ => None,
+    }
+}
@@ -1,23 +1,57 @@
-use std::collections::HashSet;
+#[path = "telegram/store.rs"]
+mod store;
+
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
-use crate::shared::supervisor_core::{SupervisorSignal, SupervisorSignalKind};
+use crate::shared::supervisor_core::chat::{
+    parse_supervisor_chat_command, SupervisorChatCommand, SupervisorScheduleCommand,
+};
 use crate::shared::supervisor_core::service as supervisor_service;
+use crate::shared::supervisor_core::supervisor_loop;
+use crate::shared::supervisor_core::{
+    SupervisorPendingApproval, SupervisorSignal, SupervisorSignalKind,
+};
 
 use super::DaemonState;
+use store::{TelegramBridgeStore, TelegramSubscription};
 
 const DEFAULT_POLL_TIMEOUT_SECONDS: u64 = 30;
 const DEFAULT_SYNC_INTERVAL_SECONDS: u64 = 5;
+/// Telegram rejects inline buttons whose `callback_data` exceeds 64 bytes.
+const CALLBACK_DATA_MAX_BYTES: usize = 64;
+const APPROVAL_CALLBACK_PREFIX: &str = "ap";
+/// Approval params keys that may hold the command, mirroring `getApprovalCommandInfo` in
+/// the frontend.
+const APPROVAL_COMMAND_KEYS: &[&str] = &[
+    "argv",
+    "args",
+    "command",
+    "cmd",
+    "exec",
+    "shellCommand",
+    "script",
+    "proposedExecPolicyAmendment",
+    "proposed_exec_policy_amendment",
+];
+
+/// What an allowed Telegram user may do. Observers only get read-only commands and
+/// notifications; operators can also dispatch work and answer approvals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TelegramRole {
+    Operator,
+    Observer,
+}
 
 #[derive(Debug, Clone)]
 pub(crate) struct TelegramBridgeConfig {
     bot_token: String,
-    allowed_user_id: i64,
+    allowed_users: BTreeMap<i64, TelegramRole>,
     allowed_chat_id: Option<i64>,
     poll_timeout_seconds: u64,
     sync_interval_seconds: u64,
@@ -26,9 +60,18 @@ pub(crate) struct TelegramBridgeConfig {
 impl TelegramBridgeConfig {
     pub(crate) fn from_env() -> Option<Self> {
         let bot_token = std::env::var("SUPERVISOR_TELEGRAM_BOT_TOKEN").ok()?;
-        let allowed_user_id = std::env::var("SUPERVISOR_TELEGRAM_ALLOWED_USER_ID")
+        let mut allowed_users = std::env::var("SUPERVISOR_TELEGRAM_ALLOWED_USERS")
+            .map(|value| parse_allowed_users(&value))
+            .unwrap_or_default();
+        if let Some(admin_user_id) = std::env::var("SUPERVISOR_TELEGRAM_ALLOWED_USER_ID")
             .ok()
-            .and_then(|value| value.parse::<i64>().ok())?;
+            .and_then(|value| value.parse::<i64>().ok())
+        {
+            allowed_users.insert(admin_user_id, TelegramRole::Operator);
+        }
+        if allowed_users.is_empty() {
+            return None;
+        }
         let allowed_chat_id = std::env::var("SUPERVISOR_TELEGRAM_ALLOWED_CHAT_ID")
             .ok()
             .and_then(|value| value.parse::<i64>().ok());
@@ -45,7 +88,7 @@ impl TelegramBridgeConfig {
 
         Some(Self {
             bot_token,
-            allowed_user_id,
+            allowed_users,
             allowed_chat_id,
             poll_timeout_seconds,
             sync_interval_seconds,
@@ -55,6 +98,41 @@ impl TelegramBridgeConfig {
     fn api_base(&self) -> String {
         format!("https://api.telegram.org/bot{}", self.bot_token)
     }
+
+    fn operator_ids(&self) -> impl Iterator<Item = i64> + '_ {
+        self.allowed_users
+            .iter()
+            .filter(|(_, role)| **role == TelegramRole::Operator)
+            .map(|(user_id, _)| *user_id)
+    }
+}
+
+/// Parses `SUPERVISOR_TELEGRAM_ALLOWED_USERS`: comma-separated `user_id[:role]` entries,
+/// where role is `operator` (default) or `observer`.
+fn parse_allowed_users(value: &str) -> BTreeMap<i64, TelegramRole> {
+    let mut users = BTreeMap::new();
+    for entry in value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (user_id, role) = entry.split_once(':').unwrap_or((entry, "operator"));
+        let role = match role.trim().to_ascii_lowercase().as_str() {
+            "operator" => TelegramRole::Operator,
+            "observer" => TelegramRole::Observer,
+            other => {
+                eprintln!("telegram bridge: ignoring user `{entry}` with unknown role `{other}`");
+                continue;
+            }
+        };
+        match user_id.trim().parse::<i64>() {
+            Ok(user_id) => {
+                users.insert(user_id, role);
+            }
+            Err(_) => eprintln!("telegram bridge: ignoring invalid user id in `{entry}`"),
+        }
+    }
+    users
 }
 
 #[derive(Debug, Deserialize)]
@@ -69,16 +147,30 @@ struct TelegramUpdate {
     update_id: i64,
     #[serde(default)]
     message: Option<TelegramMessage>,
+    #[serde(default)]
+    callback_query: Option<TelegramCallbackQuery>,
 }
 
 #[derive(Debug, Deserialize)]
 struct TelegramMessage {
+    #[serde(default)]
+    message_id: i64,
     chat: TelegramChat,
     from: Option<TelegramUser>,
     #[serde(default)]
     text: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct TelegramCallbackQuery {
+    id: String,
+    from: TelegramUser,
+    #[serde(default)]
+    message: Option<TelegramMessage>,
+    #[serde(default)]
+    data: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct TelegramChat {
     id: i64,
@@ -93,6 +185,41 @@ struct TelegramUser {
 struct SendMessagePayload<'a> {
     chat_id: i64,
     text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_markup: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApprovalChoice {
+    Once,
+    Remember,
+    Decline,
+}
+
+impl ApprovalChoice {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Once => "o",
+            Self::Remember => "r",
+            Self::Decline => "d",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "o" => Some(Self::Once),
+            "r" => Some(Self::Remember),
+            "d" => Some(Self::Decline),
+            _ => None,
+        }
+    }
+
+    fn decision(self) -> &'static str {
+        match self {
+            Self::Once | Self::Remember => "accept",
+            Self::Decline => "decline",
+        }
+    }
 }
 
 pub(crate) async fn run(state: Arc<DaemonState>, config: TelegramBridgeConfig) {
@@ -109,32 +236,40 @@ pub(crate) async fn run(state: Arc<DaemonState>, config: TelegramBridgeConfig) {
     };
 
     eprintln!(
-        "telegram bridge enabled for user {}{}",
-        config.allowed_user_id,
+        "telegram bridge enabled for {} user(s){}",
+        config.allowed_users.len(),
         config
             .allowed_chat_id
             .map(|chat_id| format!(", chat {chat_id}"))
             .unwrap_or_default()
     );
 
-    let mut offset: Option<i64> = None;
-    let mut notified_signal_ids: HashSet<String> = HashSet::new();
+    let mut store = TelegramBridgeStore::load(&state.data_dir).unwrap_or_else(|error| {
+        eprintln!("telegram bridge state reset: {error}");
+        TelegramBridgeStore::empty(&state.data_dir)
+    });
 
     loop {
-        if let Err(error) = sync_notifications(&state, &config, &client, &mut notified_signal_ids).await {
+        if let Err(error) = sync_notifications(&state, &config, &client, &mut store).await {
             eprintln!("telegram notification sync error: {error}");
         }
 
-        match poll_updates(&config, &client, offset).await {
+        match poll_updates(&config, &client, store.offset()).await {
             Ok(updates) => {
+                let received = !updates.is_empty();
                 for update in updates {
-                    offset = Some(update.update_id + 1);
+                    store.set_offset(update.update_id + 1);
                     if let Err(error) =
-                        handle_update(&state, &config, &client, update).await
+                        handle_update(&state, &config, &client, &mut store, update).await
                     {
                         eprintln!("telegram update handling error: {error}");
                     }
                 }
+                if received {
+                    if let Err(error) = store.persist() {
+                        eprintln!("failed to persist telegram bridge state: {error}");
+                    }
+                }
             }
             Err(error) => {
                 eprintln!("telegram getUpdates failed: {error}");
@@ -151,7 +286,7 @@ async fn poll_updates(
 ) -> Result<Vec<TelegramUpdate>, String> {
     let mut payload = json!({
         "timeout": config.poll_timeout_seconds,
-        "allowed_updates": ["message"],
+        "allowed_updates": ["message", "callback_query"],
     });
     if let Some(offset) = offset {
         payload["offset"] = json!(offset);
@@ -182,8 +317,13 @@ async fn handle_update(
     state: &Arc<DaemonState>,
     config: &TelegramBridgeConfig,
     client: &Client,
+    store: &mut TelegramBridgeStore,
     update: TelegramUpdate,
 ) -> Result<(), String> {
+    if let Some(callback_query) = update.callback_query {
+        return handle_callback_query(state, config, client, callback_query).await;
+    }
+
     let message = match update.message {
         Some(message) => message,
         None => return Ok(()),
@@ -193,13 +333,7 @@ async fn handle_update(
         .from
         .ok_or_else(|| "message has no sender".to_string())?;
 
-    let allowed = from.id == config.allowed_user_id
-        && config
-            .allowed_chat_id
-            .map(|chat_id| chat_id == message.chat.id)
-            .unwrap_or(true);
-
-    if !allowed {
+    let Some(role) = user_role(config, from.id, Some(message.chat.id)) else {
         if config.allowed_chat_id.is_none() {
             let _ = send_message(
                 config,
@@ -210,7 +344,7 @@ async fn handle_update(
             .await;
         }
         return Ok(());
-    }
+    };
 
     let text = message
         .text
@@ -233,13 +367,28 @@ async fn handle_update(
             config,
             client,
             message.chat.id,
-            "Supervisor bot online ✅\nUse /help or plain language (EN/RU):\n- status\n- feed\n- dispatch task to workspace ...\n\nБот Supervisor в сети ✅\nМожно писать команды в свободной форме.",
+            "Supervisor bot online ✅\nUse /help or plain language (EN/RU):\n- status\n- feed\n- dispatch task to workspace ...\n\nNotifications: /subscriptions, /subscribe [kinds] [ws:<id>], /unsubscribe\n\nБот Supervisor в сети ✅\nМожно писать команды в свободной форме.",
         )
         .await?;
         return Ok(());
     }
 
-    let response = state.supervisor_chat_send(text).await?;
+    if let Some(reply) = handle_subscription_command(store, role, from.id, message.chat.id, &text) {
+        return send_message(config, client, message.chat.id, reply.as_str()).await;
+    }
+
+    if role == TelegramRole::Observer && !is_read_only_command(&text) {
+        return send_message(
+            config,
+            client,
+            message.chat.id,
+            "Read-only access: use /status, /job, /feed, /schedule list or /help. Доступ только для чтения.",
+        )
+        .await;
+    }
+
+    let client_version = format!("telegram-{}", env!("CARGO_PKG_VERSION"));
+    let response = state.supervisor_chat_send(text, client_version).await?;
     let rendered = extract_chat_response_text(&response)
         .unwrap_or_else(|| "Command accepted. Команда принята.".to_string());
     send_message(config, client, message.chat.id, rendered.as_str()).await
@@ -262,36 +411,385 @@ fn extract_chat_response_text(payload: &serde_json::Value) -> Option<String> {
         })
 }
 
+fn user_role(
+    config: &TelegramBridgeConfig,
+    user_id: i64,
+    chat_id: Option<i64>,
+) -> Option<TelegramRole> {
+    let chat_allowed = config
+        .allowed_chat_id
+        .is_none_or(|allowed_chat_id| chat_id == Some(allowed_chat_id));
+    chat_allowed
+        .then(|| config.allowed_users.get(&user_id).copied())
+        .flatten()
+}
+
+/// Observers may only run Supervisor chat commands that do not change anything.
+fn is_read_only_command(text: &str) -> bool {
+    text.starts_with('/')
+        && matches!(
+            parse_supervisor_chat_command(text),
+            Ok(SupervisorChatCommand::Help
+                | SupervisorChatCommand::Status { .. }
+                | SupervisorChatCommand::Job { .. }
+                | SupervisorChatCommand::Feed { .. }
+                | SupervisorChatCommand::Schedule(SupervisorScheduleCommand::List))
+        )
+}
+
+/// Handles the bridge's own notification commands; returns `None` for anything else.
+/// Observers may only change the subscription of their own private chat.
+fn handle_subscription_command(
+    store: &mut TelegramBridgeStore,
+    role: TelegramRole,
+    user_id: i64,
+    chat_id: i64,
+    text: &str,
+) -> Option<String> {
+    let (command, args) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let command = command
+        .split('@')
+        .next()
+        .unwrap_or(command)
+        .to_ascii_lowercase();
+    let reply = match command.as_str() {
+        "/subscriptions" => match store.subscriptions().get(&chat_id) {
+            Some(subscription) => format!("This chat is subscribed.\n{}", subscription.describe()),
+            None => "This chat has no subscription.".to_string(),
+        },
+        "/subscribe" | "/unsubscribe" if role != TelegramRole::Operator && chat_id != user_id => {
+            "Observers can only change subscriptions in their private chat.".to_string()
+        }
+        "/subscribe" => match TelegramSubscription::parse_args(args) {
+            Ok(subscription) => {
+                let description = subscription.describe();
+                store.subscribe(chat_id, subscription);
+                persist_reply(store, format!("Subscribed.\n{description}"))
+            }
+            Err(error) => format!("Error: {error}"),
+        },
+        "/unsubscribe" => {
+            if store.unsubscribe(chat_id) {
+                persist_reply(store, "Unsubscribed.".to_string())
+            } else {
+                "This chat has no subscription.".to_string()
+            }
+        }
+        _ => return None,
+    };
+    Some(reply)
+}
+
+fn persist_reply(store: &TelegramBridgeStore, reply: String) -> String {
+    match store.persist() {
+        Ok(()) => reply,
+        Err(error) => format!("{reply}\nWarning: not saved ({error})."),
+    }
+}
+
+/// Chats a signal is pushed to: every subscribed chat whose filter matches, plus the
+/// locked chat when it has no explicit filter. Unrouted signals go to the operators'
+/// private chats.
+fn signal_recipients(
+    config: &TelegramBridgeConfig,
+    store: &TelegramBridgeStore,
+    signal: &SupervisorSignal,
+) -> Vec<i64> {
+    let mut recipients = store
+        .subscriptions()
+        .iter()
+        .filter(|(_, subscription)| subscription.matches(signal))
+        .map(|(chat_id, _)| *chat_id)
+        .collect::<Vec<_>>();
+    if let Some(chat_id) = config.allowed_chat_id {
+        if !store.subscriptions().contains_key(&chat_id) {
+            recipients.push(chat_id);
+        }
+    }
+    if recipients.is_empty() {
+        recipients.extend(config.operator_ids());
+    }
+    recipients
+}
+
+async fn handle_callback_query(
+    state: &Arc<DaemonState>,
+    config: &TelegramBridgeConfig,
+    client: &Client,
+    query: TelegramCallbackQuery,
+) -> Result<(), String> {
+    let chat_id = query.message.as_ref().map(|message| message.chat.id);
+    match user_role(config, query.from.id, chat_id) {
+        Some(TelegramRole::Operator) => {}
+        Some(TelegramRole::Observer) => {
+            return answer_callback_query(
+                config,
+                client,
+                &query.id,
+                "Observers cannot answer approvals.",
+            )
+            .await;
+        }
+        None => {
+            return answer_callback_query(
+                config,
+                client,
+                &query.id,
+                "Access denied. Доступ запрещен.",
+            )
+            .await;
+        }
+    }
+
+    let Some((choice, request_key)) = query.data.as_deref().and_then(parse_approval_callback)
+    else {
+        return answer_callback_query(config, client, &query.id, "Unknown action.").await;
+    };
+
+    match resolve_approval(state, choice, request_key).await {
+        Ok(outcome) => {
+            if let Some(message) = query.message {
+                let text = format!(
+                    "{}\n\n{outcome}",
+                    message.text.as_deref().unwrap_or("Supervisor approval")
+                );
+                edit_message_text(config, client, message.chat.id, message.message_id, &text)
+                    .await?;
+            }
+            answer_callback_query(config, client, &query.id, &outcome).await
+        }
+        // The buttons stay in place so the approval can be retried.
+        Err(error) => {
+            answer_callback_query(config, client, &query.id, &format!("Failed: {error}")).await
+        }
+    }
+}
+
+/// Answers the approval behind `request_key` and resolves it in Supervisor state. Returns
+/// the outcome line shown under the original notification.
+async fn resolve_approval(
+    state: &Arc<DaemonState>,
+    choice: ApprovalChoice,
+    request_key: &str,
+) -> Result<String, String> {
+    let approval = supervisor_service::supervisor_snapshot_core(&state.supervisor_loop)
+        .await
+        .pending_approvals
+        .remove(request_key)
+        .filter(|approval| approval.resolved_at_ms.is_none());
+    let Some(approval) = approval else {
+        return Ok("ℹ️ Already resolved / Уже обработано".to_string());
+    };
+
+    let mut rule_error = None;
+    if choice == ApprovalChoice::Remember {
+        let saved = match approval_command_tokens(&approval.params) {
+            Some(command) => state
+                .remember_approval_rule(approval.workspace_id.clone(), command)
+                .await
+                .map(|_| ()),
+            None => Err("approval has no command to remember".to_string()),
+        };
+        rule_error = saved.err();
+    }
+
+    state
+        .respond_to_server_request(
+            approval.workspace_id.clone(),
+            approval.original_request_id(),
+            json!({ "decision": choice.decision() }),
+        )
+        .await?;
+
+    state
+        .supervisor_loop
+        .lock()
+        .await
+        .resolve_pending_approval(request_key, supervisor_loop::now_timestamp_ms());
+    supervisor_service::persist_supervisor_snapshot(
+        &state.supervisor_loop,
+        &state.supervisor_state_path,
+    )
+    .await?;
+
+    Ok(match (choice, rule_error) {
+        (ApprovalChoice::Once, _) => "✅ Approved once / Одобрено один раз".to_string(),
+        (ApprovalChoice::Remember, None) => {
+            "✅ Approved and remembered / Одобрено и запомнено".to_string()
+        }
+        (ApprovalChoice::Remember, Some(error)) => {
+            format!("✅ Approved once; rule not saved: {error}")
+        }
+        (ApprovalChoice::Decline, _) => "❌ Declined / Отклонено".to_string(),
+    })
+}
+
+fn approval_callback_data(choice: ApprovalChoice, request_key: &str) -> Option<String> {
+    let data = format!("{APPROVAL_CALLBACK_PREFIX}:{}:{request_key}", choice.code());
+    (data.len() <= CALLBACK_DATA_MAX_BYTES).then_some(data)
+}
+
+fn parse_approval_callback(data: &str) -> Option<(ApprovalChoice, &str)> {
+    let mut parts = data.splitn(3, ':');
+    if parts.next()? != APPROVAL_CALLBACK_PREFIX {
+        return None;
+    }
+    let choice = ApprovalChoice::from_code(parts.next()?)?;
+    let request_key = parts.next().filter(|value| !value.is_empty())?;
+    Some((choice, request_key))
+}
+
+/// Inline keyboard for a pending approval, or `None` when the request key does not fit
+/// into Telegram callback data. "Remember" is only offered when a command is known.
+fn approval_keyboard(approval: &SupervisorPendingApproval) -> Option<Value> {
+    let mut rows = vec![json!([{
+        "text": "✅ Approve once",
+        "callback_data": approval_callback_data(ApprovalChoice::Once, &approval.request_key)?,
+    }])];
+    if approval_command_tokens(&approval.params).is_some() {
+        rows.push(json!([{
+            "text": "📌 Approve & remember",
+            "callback_data": approval_callback_data(ApprovalChoice::Remember, &approval.request_key)?,
+        }]));
+    }
+    rows.push(json!([{
+        "text": "❌ Decline",
+        "callback_data": approval_callback_data(ApprovalChoice::Decline, &approval.request_key)?,
+    }]));
+    Some(json!({ "inline_keyboard": rows }))
+}
+
+fn approval_command_tokens(params: &Value) -> Option<Vec<String>> {
+    let tokens = match params {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().map(|token| token.trim().to_string()))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .filter(|token| !token.is_empty())
+            .collect(),
+        Value::String(command) => split_command_line(command),
+        Value::Object(map) => {
+            return APPROVAL_COMMAND_KEYS
+                .iter()
+                .filter_map(|key| map.get(*key))
+                .chain(map.iter().filter_map(|(key, value)| {
+                    let key = key.to_lowercase();
+                    (key.contains("execpolicy") || key.contains("exec_policy")).then_some(value)
+                }))
+                .find_map(approval_command_tokens);
+        }
+        _ => return None,
+    };
+    (!tokens.is_empty()).then_some(tokens)
+}
+
+fn split_command_line(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for ch in input.chars() {
+        if escaped {
+            current.push(ch);
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if let Some(open) = quote {
+            if ch == open {
+                quote = None;
+            } else {
+                current.push(ch);
+            }
+        } else if ch == '"' || ch == '\'' {
+            quote = Some(ch);
+        } else if ch.is_whitespace() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(ch);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
 async fn sync_notifications(
     state: &Arc<DaemonState>,
     config: &TelegramBridgeConfig,
     client: &Client,
-    notified_signal_ids: &mut HashSet<String>,
+    store: &mut TelegramBridgeStore,
 ) -> Result<(), String> {
     let snapshot = supervisor_service::supervisor_snapshot_core(&state.supervisor_loop).await;
-    let Some(chat_id) = config.allowed_chat_id else {
-        return Ok(());
-    };
+    let live_signal_ids = snapshot
+        .signals
+        .iter()
+        .map(|signal| signal.id.as_str())
+        .collect::<HashSet<_>>();
+    let mut changed = store.retain_notified(&live_signal_ids);
 
     let mut pending = snapshot
         .signals
         .iter()
         .filter(|signal| signal.acknowledged_at_ms.is_none())
-        .filter(|signal| !notified_signal_ids.contains(&signal.id))
+        .filter(|signal| !store.is_notified(&signal.id))
         .collect::<Vec<_>>();
     pending.sort_by_key(|signal| signal.created_at_ms);
 
+    let mut result = Ok(());
     for signal in pending {
-        let text = format_signal_message(signal);
-        send_message(config, client, chat_id, text.as_str()).await?;
-        notified_signal_ids.insert(signal.id.clone());
+        let approval = (signal.kind == SupervisorSignalKind::NeedsApproval)
+            .then(|| signal.context.get("requestKey").and_then(Value::as_str))
+            .flatten()
+            .and_then(|request_key| snapshot.pending_approvals.get(request_key))
+            .filter(|approval| approval.resolved_at_ms.is_none());
+        let text = format_signal_message(signal, approval);
+        let reply_markup = approval.and_then(approval_keyboard);
+
+        // A signal counts as delivered once any recipient got it, so a single broken
+        // chat does not cause repeats everywhere else.
+        let mut delivered = false;
+        for chat_id in signal_recipients(config, store, signal) {
+            let sent = call_api(
+                config,
+                client,
+                "sendMessage",
+                &SendMessagePayload {
+                    chat_id,
+                    text: text.as_str(),
+                    reply_markup: reply_markup.clone(),
+                },
+            )
+            .await;
+            match sent {
+                Ok(()) => delivered = true,
+                Err(error) => result = Err(format!("chat {chat_id}: {error}")),
+            }
+        }
+        if delivered {
+            store.mark_notified(&signal.id);
+            changed = true;
+        }
+    }
+
+    if changed {
+        if let Err(error) = store.persist() {
+            eprintln!("failed to persist telegram bridge state: {error}");
+        }
     }
 
     tokio::time::sleep(Duration::from_secs(config.sync_interval_seconds)).await;
-    Ok(())
+    result
 }
 
-fn format_signal_message(signal: &SupervisorSignal) -> String {
+fn format_signal_message(
+    signal: &SupervisorSignal,
+    approval: Option<&SupervisorPendingApproval>,
+) -> String {
     let kind = match signal.kind {
         SupervisorSignalKind::NeedsApproval => "Needs approval / Требуется подтверждение",
         SupervisorSignalKind::Failed => "Failed / Ошибка",
@@ -299,9 +797,13 @@ fn format_signal_message(signal: &SupervisorSignal) -> String {
         SupervisorSignalKind::Stalled => "Stalled / Застопорилось",
         SupervisorSignalKind::Disconnected => "Disconnected / Отключено",
     };
+    let command = approval
+        .and_then(|approval| approval_command_tokens(&approval.params))
+        .map(|tokens| format!("\nCommand: {}", tokens.join(" ")))
+        .unwrap_or_default();
 
     format!(
-        "🔔 Supervisor signal\nType: {kind}\nMessage: {}\nWorkspace: {}\nThread: {}\n\nСигнал Supervisor: {kind}",
+        "🔔 Supervisor signal\nType: {kind}\nMessage: {}\nWorkspace: {}\nThread: {}{command}\n\nСигнал Supervisor: {kind}",
         signal.message,
         signal.workspace_id.as_deref().unwrap_or("-"),
         signal.thread_id.as_deref().unwrap_or("-")
@@ -313,10 +815,61 @@ async fn send_message(
     client: &Client,
     chat_id: i64,
     text: &str,
+) -> Result<(), String> {
+    call_api(
+        config,
+        client,
+        "sendMessage",
+        &SendMessagePayload {
+            chat_id,
+            text,
+            reply_markup: None,
+        },
+    )
+    .await
+}
+
+/// Replaces the message text; omitting `reply_markup` also removes the inline keyboard.
+async fn edit_message_text(
+    config: &TelegramBridgeConfig,
+    client: &Client,
+    chat_id: i64,
+    message_id: i64,
+    text: &str,
+) -> Result<(), String> {
+    call_api(
+        config,
+        client,
+        "editMessageText",
+        &json!({ "chat_id": chat_id, "message_id": message_id, "text": text }),
+    )
+    .await
+}
+
+async fn answer_callback_query(
+    config: &TelegramBridgeConfig,
+    client: &Client,
+    callback_query_id: &str,
+    text: &str,
+) -> Result<(), String> {
+    call_api(
+        config,
+        client,
+        "answerCallbackQuery",
+        &json!({ "callback_query_id": callback_query_id, "text": text }),
+    )
+    .await
+}
+
+async fn call_api<T: Serialize + ?Sized>(
+    config: &TelegramBridgeConfig,
+    client: &Client,
+    method: &str,
+    payload: &T,
 ) -> Result<(), String> {
     let response = client
-        .post(format!("{}/sendMessage", config.api_base()))
-        .json(&SendMessagePayload { chat_id, text })
+        .post(format!("{}/{method}", config.api_base()))
+        .json(payload)
         .send()
         .await
         .map_err(|error| error.to_string())?;
@@ -346,4 +899,235 @@ mod tests {
         });
         assert_eq!(extract_chat_response_text(&payload).as_deref(), Some("ok"));
     }
+
+    fn approval(request_key: &str, params: Value) -> SupervisorPendingApproval {
+        SupervisorPendingApproval {
+            request_key: request_key.to_string(),
+            workspace_id: "ws-1".to_string(),
+            thread_id: Some("thread-1".to_string()),
+            turn_id: None,
+            item_id: None,
+            request_id: "42".to_string(),
+            request_id_value: json!(42),
+            method: "item/commandExecution/requestApproval".to_string(),
+            params,
+            created_at_ms: 1,
+            resolved_at_ms: None,
+        }
+    }
+
+    #[test]
+    fn approval_keyboard_round_trips_callback_data() {
+        let keyboard = approval_keyboard(&approval(
+            "ws-1:42",
+            json!({ "command": "git push 'origin main'" }),
+        ))
+        .expect("keyboard");
+        let rows = keyboard["inline_keyboard"].as_array().expect("rows");
+        assert_eq!(rows.len(), 3);
+        let remember = rows[1][0]["callback_data"].as_str().expect("data");
+        assert_eq!(
+            parse_approval_callback(remember),
+            Some((ApprovalChoice::Remember, "ws-1:42"))
+        );
+        assert_eq!(parse_approval_callback("ap:x:ws-1:42"), None);
+        assert_eq!(parse_approval_callback("other:o:ws-1:42"), None);
+
+        let without_command = approval_keyboard(&approval("ws-1:42", json!({}))).expect("keyboard");
+        assert_eq!(
+            without_command["inline_keyboard"].as_array().map(Vec::len),
+            Some(2)
+        );
+
+        let long_key = format!("{}:42", "w".repeat(64));
+        assert!(approval_keyboard(&approval(&long_key, json!({}))).is_none());
+    }
+
+    #[test]
+    fn extracts_approval_command_tokens() {
+        assert_eq!(
+            approval_command_tokens(&json!({ "command": "npm run \"test all\" --watch" })),
+            Some(vec![
+                "npm".to_string(),
+                "run".to_string(),
+                "test all".to_string(),
+                "--watch".to_string(),
+            ])
+        );
+        assert_eq!(
+            approval_command_tokens(&json!({ "proposedExecPolicyAmendment": [" cargo ", "test"] })),
+            Some(vec!["cargo".to_string(), "test".to_string()])
+        );
+        assert_eq!(
+            approval_command_tokens(&json!({ "reason": "network" })),
+            None
+        );
+        let numeric = approval("ws-1:42", Value::Null);
+        assert_eq!(numeric.original_request_id(), json!(42));
+        let legacy = SupervisorPendingApproval {
+            request_id: "42".to_string(),
+            request_id_value: Value::Null,
+            ..numeric
+        };
+        assert_eq!(legacy.original_request_id(), json!("42"));
+    }
+
+    fn config(users: &str, allowed_chat_id: Option<i64>) -> TelegramBridgeConfig {
+        TelegramBridgeConfig {
+            bot_token: "token".to_string(),
+            allowed_users: parse_allowed_users(users),
+            allowed_chat_id,
+            poll_timeout_seconds: DEFAULT_POLL_TIMEOUT_SECONDS,
+            sync_interval_seconds: DEFAULT_SYNC_INTERVAL_SECONDS,
+        }
+    }
+
+    #[test]
+    fn parses_allowed_users_with_roles() {
+        let users = parse_allowed_users(" 7, 8:observer,9:OPERATOR, nope, 10:admin ,");
+        assert_eq!(
+            users.into_iter().collect::<Vec<_>>(),
+            vec![
+                (7, TelegramRole::Operator),
+                (8, TelegramRole::Observer),
+                (9, TelegramRole::Operator),
+            ]
+        );
+    }
+
+    #[test]
+    fn callback_authorization_checks_user_and_chat() {
+        let mut config = config("7,8:observer", None);
+        assert_eq!(user_role(&config, 7, None), Some(TelegramRole::Operator));
+        assert_eq!(
+            user_role(&config, 8, Some(100)),
+            Some(TelegramRole::Observer)
+        );
+        assert_eq!(user_role(&config, 9, Some(100)), None);
+
+        config.allowed_chat_id = Some(100);
+        assert_eq!(
+            user_role(&config, 7, Some(100)),
+            Some(TelegramRole::Operator)
+        );
+        assert_eq!(user_role(&config, 7, Some(101)), None);
+        assert_eq!(user_role(&config, 7, None), None);
+    }
+
+    #[test]
+    fn observers_are_limited_to_read_only_commands() {
+        assert!(is_read_only_command("/status"));
+        assert!(is_read_only_command("/feed"));
+        assert!(is_read_only_command("/job chat-dispatch-1"));
+        assert!(is_read_only_command("/help"));
+        assert!(!is_read_only_command("/dispatch --ws ws-1 run tests"));
+        assert!(!is_read_only_command("/cancel job-1"));
+        assert!(is_read_only_command("/schedule list"));
+        assert!(!is_read_only_command("/schedule pause sched-1"));
+        assert!(!is_read_only_command("status"));
+
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-telegram-{}", uuid::Uuid::new_v4()));
+        let mut store = TelegramBridgeStore::empty(&dir);
+        let denied = handle_subscription_command(
+            &mut store,
+            TelegramRole::Observer,
+            8,
+            -100,
+            "/subscribe failed",
+        );
+        assert_eq!(
+            denied.as_deref(),
+            Some("Observers can only change subscriptions in their private chat.")
+        );
+        assert!(store.subscriptions().is_empty());
+        assert!(
+            handle_subscription_command(&mut store, TelegramRole::Observer, 8, 8, "/status")
+                .is_none()
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn routes_signals_to_matching_subscriptions() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-telegram-{}", uuid::Uuid::new_v4()));
+        let mut store = TelegramBridgeStore::empty(&dir);
+        let signal = SupervisorSignal {
+            id: "signal-1".to_string(),
+            kind: SupervisorSignalKind::Failed,
+            workspace_id: Some("ws-1".to_string()),
+            thread_id: None,
+            job_id: None,
+            message: "boom".to_string(),
+            created_at_ms: 1,
+            acknowledged_at_ms: None,
+            context: Value::Null,
+        };
+
+        let operators_only = config("7,8:observer,9", None);
+        assert_eq!(
+            signal_recipients(&operators_only, &store, &signal),
+            vec![7, 9]
+        );
+
+        let locked = config("7", Some(-100));
+        assert_eq!(signal_recipients(&locked, &store, &signal), vec![-100]);
+
+        let reply = handle_subscription_command(
+            &mut store,
+            TelegramRole::Operator,
+            7,
+            -100,
+            "/subscribe@supervisor_bot completed",
+        )
+        .expect("reply");
+        assert!(reply.starts_with("Subscribed."), "{reply}");
+        handle_subscription_command(
+            &mut store,
+            TelegramRole::Operator,
+            7,
+            -200,
+            "/subscribe ws:ws-1",
+        )
+        .expect("reply");
+        assert_eq!(signal_recipients(&locked, &store, &signal), vec![-200]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn observers_subscribe_their_private_chat() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-telegram-{}", uuid::Uuid::new_v4()));
+        let mut store = TelegramBridgeStore::empty(&dir);
+        let signal = SupervisorSignal {
+            id: "signal-1".to_string(),
+            kind: SupervisorSignalKind::Completed,
+            workspace_id: Some("ws-1".to_string()),
+            thread_id: None,
+            job_id: None,
+            message: "done".to_string(),
+            created_at_ms: 1,
+            acknowledged_at_ms: None,
+            context: Value::Null,
+        };
+
+        let observers_only = config("8:observer", None);
+        assert!(signal_recipients(&observers_only, &store, &signal).is_empty());
+
+        let reply =
+            handle_subscription_command(&mut store, TelegramRole::Observer, 8, 8, "/subscribe")
+                .expect("reply");
+        assert!(reply.starts_with("Subscribed."), "{reply}");
+        assert_eq!(signal_recipients(&observers_only, &store, &signal), vec![8]);
+
+        let reply =
+            handle_subscription_command(&mut store, TelegramRole::Observer, 8, 8, "/unsubscribe")
+                .expect("reply");
+        assert!(reply.starts_with("Unsubscribed."), "{reply}");
+        assert!(signal_recipients(&observers_only, &store, &signal).is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,269 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::supervisor_core::{SupervisorSignal, SupervisorSignalKind};
+
+pub(super) const TELEGRAM_STATE_FILE: &str = "telegram-bridge.json";
+
+const SIGNAL_KIND_NAMES: &[(&str, SupervisorSignalKind)] = &[
+    ("needs_approval", SupervisorSignalKind::NeedsApproval),
+    ("failed", SupervisorSignalKind::Failed),
+    ("completed", SupervisorSignalKind::Completed),
+    ("stalled", SupervisorSignalKind::Stalled),
+    ("disconnected", SupervisorSignalKind::Disconnected),
+];
+
+/// Which signals a chat receives. Empty lists match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TelegramSubscription {
+    #[serde(default)]
+    pub(super) kinds: Vec<SupervisorSignalKind>,
+    #[serde(default)]
+    pub(super) workspace_ids: Vec<String>,
+}
+
+impl TelegramSubscription {
+    /// Parses `/subscribe` arguments: signal kind names plus `ws:<workspace_id>` filters.
+    pub(super) fn parse_args(args: &str) -> Result<Self, String> {
+        let mut subscription = Self::default();
+        for token in args.split(|ch: char| ch.is_whitespace() || ch == ',') {
+            let token = token.trim();
+            if token.is_empty() || token.eq_ignore_ascii_case("all") {
+                continue;
+            }
+            if let Some(workspace_id) = token.strip_prefix("ws:") {
+                if !workspace_id.is_empty()
+                    && !subscription
+                        .workspace_ids
+                        .iter()
+                        .any(|id| id == workspace_id)
+                {
+                    subscription.workspace_ids.push(workspace_id.to_string());
+                }
+                continue;
+            }
+            let kind = SIGNAL_KIND_NAMES
+                .iter()
+                .find(|(name, _)| token.eq_ignore_ascii_case(name))
+                .map(|(_, kind)| kind.clone())
+                .ok_or_else(|| {
+                    format!(
+                        "unknown signal kind `{token}` (expected {} or ws:<workspace_id>)",
+                        SIGNAL_KIND_NAMES
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })?;
+            if !subscription.kinds.contains(&kind) {
+                subscription.kinds.push(kind);
+            }
+        }
+        Ok(subscription)
+    }
+
+    pub(super) fn matches(&self, signal: &SupervisorSignal) -> bool {
+        let kind_matches = self.kinds.is_empty() || self.kinds.contains(&signal.kind);
+        let workspace_matches = self.workspace_ids.is_empty()
+            || signal
+                .workspace_id
+                .as_ref()
+                .is_some_and(|workspace_id| self.workspace_ids.contains(workspace_id));
+        kind_matches && workspace_matches
+    }
+
+    pub(super) fn describe(&self) -> String {
+        let kinds = if self.kinds.is_empty() {
+            "all".to_string()
+        } else {
+            self.kinds
+                .iter()
+                .filter_map(|kind| {
+                    SIGNAL_KIND_NAMES
+                        .iter()
+                        .find(|(_, candidate)| candidate == kind)
+                        .map(|(name, _)| *name)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let workspaces = if self.workspace_ids.is_empty() {
+            "all".to_string()
+        } else {
+            self.workspace_ids.join(", ")
+        };
+        format!("Signals: {kinds}\nWorkspaces: {workspaces}")
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TelegramBridgeFile {
+    #[serde(default)]
+    offset: Option<i64>,
+    #[serde(default)]
+    notified_signal_ids: BTreeSet<String>,
+    #[serde(default)]
+    subscriptions: BTreeMap<i64, TelegramSubscription>,
+}
+
+/// Bridge state that must survive daemon restarts: the `getUpdates` offset, which
+/// signals were already pushed, and per-chat subscriptions.
+pub(super) struct TelegramBridgeStore {
+    path: PathBuf,
+    state: TelegramBridgeFile,
+}
+
+impl TelegramBridgeStore {
+    pub(super) fn empty(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(TELEGRAM_STATE_FILE),
+            state: TelegramBridgeFile::default(),
+        }
+    }
+
+    pub(super) fn load(data_dir: &Path) -> Result<Self, String> {
+        let path = data_dir.join(TELEGRAM_STATE_FILE);
+        if !path.exists() {
+            return Ok(Self::empty(data_dir));
+        }
+        let raw = fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let state = serde_json::from_str(&raw)
+            .map_err(|err| format!("invalid {}: {err}", path.display()))?;
+        Ok(Self { path, state })
+    }
+
+    pub(super) fn offset(&self) -> Option<i64> {
+        self.state.offset
+    }
+
+    pub(super) fn set_offset(&mut self, offset: i64) {
+        self.state.offset = Some(offset);
+    }
+
+    pub(super) fn is_notified(&self, signal_id: &str) -> bool {
+        self.state.notified_signal_ids.contains(signal_id)
+    }
+
+    pub(super) fn mark_notified(&mut self, signal_id: &str) {
+        self.state.notified_signal_ids.insert(signal_id.to_string());
+    }
+
+    /// Forgets signals Supervisor no longer tracks so the set does not grow forever.
+    /// Returns whether anything was removed.
+    pub(super) fn retain_notified(&mut self, live_signal_ids: &HashSet<&str>) -> bool {
+        let before = self.state.notified_signal_ids.len();
+        self.state
+            .notified_signal_ids
+            .retain(|signal_id| live_signal_ids.contains(signal_id.as_str()));
+        self.state.notified_signal_ids.len() != before
+    }
+
+    pub(super) fn subscriptions(&self) -> &BTreeMap<i64, TelegramSubscription> {
+        &self.state.subscriptions
+    }
+
+    pub(super) fn subscribe(&mut self, chat_id: i64, subscription: TelegramSubscription) {
+        self.state.subscriptions.insert(chat_id, subscription);
+    }
+
+    pub(super) fn unsubscribe(&mut self, chat_id: i64) -> bool {
+        self.state.subscriptions.remove(&chat_id).is_some()
+    }
+
+    pub(super) fn persist(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(&self.state).map_err(|err| err.to_string())?;
+        fs::write(&self.path, data).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn signal(kind: SupervisorSignalKind, workspace_id: Option<&str>) -> SupervisorSignal {
+        SupervisorSignal {
+            id: "signal-1".to_string(),
+            kind,
+            workspace_id: workspace_id.map(str::to_string),
+            thread_id: None,
+            job_id: None,
+            message: String::new(),
+            created_at_ms: 1,
+            acknowledged_at_ms: None,
+            context: Value::Null,
+        }
+    }
+
+    #[test]
+    fn subscription_filters_by_kind_and_workspace() {
+        let subscription =
+            TelegramSubscription::parse_args("needs_approval, FAILED ws:ws-1").expect("parse");
+        assert_eq!(
+            subscription.kinds,
+            vec![
+                SupervisorSignalKind::NeedsApproval,
+                SupervisorSignalKind::Failed
+            ]
+        );
+        assert!(subscription.matches(&signal(SupervisorSignalKind::Failed, Some("ws-1"))));
+        assert!(!subscription.matches(&signal(SupervisorSignalKind::Failed, Some("ws-2"))));
+        assert!(!subscription.matches(&signal(SupervisorSignalKind::Completed, Some("ws-1"))));
+        assert!(!subscription.matches(&signal(SupervisorSignalKind::Failed, None)));
+
+        let everything = TelegramSubscription::parse_args("all").expect("parse");
+        assert!(everything.matches(&signal(SupervisorSignalKind::Stalled, None)));
+        assert_eq!(everything.describe(), "Signals: all\nWorkspaces: all");
+
+        assert!(TelegramSubscription::parse_args("urgent").is_err());
+    }
+
+    #[test]
+    fn store_round_trips_and_prunes_notified_signals() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-telegram-{}-{}",
+            std::process::id(),
+            uuid::Uuid::new_v4().simple()
+        ));
+        let mut store = TelegramBridgeStore::load(&dir).expect("empty store");
+        assert_eq!(store.offset(), None);
+
+        store.set_offset(42);
+        store.mark_notified("signal-1");
+        store.mark_notified("signal-2");
+        store.subscribe(
+            -100,
+            TelegramSubscription::parse_args("stalled").expect("parse"),
+        );
+        store.persist().expect("persist");
+
+        let mut restored = TelegramBridgeStore::load(&dir).expect("restored store");
+        assert_eq!(restored.offset(), Some(42));
+        assert!(restored.is_notified("signal-1"));
+        assert_eq!(
+            restored
+                .subscriptions()
+                .get(&-100)
+                .map(|sub| sub.kinds.clone()),
+            Some(vec![SupervisorSignalKind::Stalled])
+        );
+
+        assert!(restored.retain_notified(&HashSet::from(["signal-2"])));
+        assert!(!restored.is_notified("signal-1"));
+        assert!(restored.is_notified("signal-2"));
+        assert!(restored.unsubscribe(-100));
+        assert!(!restored.unsubscribe(-100));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+use crate::shared::tls_core::certificate_fingerprint;
+
+const TLS_DIR: &str = "tls";
+const CERT_FILE: &str = "daemon-cert.der";
+const KEY_FILE: &str = "daemon-key.der";
+const CERT_SUBJECT_NAMES: [&str; 2] = ["codex-monitor-daemon", "localhost"];
+
+pub(super) struct DaemonTls {
+    pub(super) acceptor: TlsAcceptor,
+    pub(super) fingerprint: String,
+}
+
+/// Loads the daemon certificate from `<data-dir>/tls`, generating a self-signed one on first use.
+pub(super) fn load_or_create(data_dir: &Path) -> Result<DaemonTls, String> {
+    let (cert_der, key_der) = load_or_create_identity(&data_dir.join(TLS_DIR))?;
+    let fingerprint = certificate_fingerprint(&cert_der);
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| err.to_string())?
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![CertificateDer::from(cert_der)],
+            PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_der)),
+        )
+        .map_err(|err| format!("invalid daemon TLS certificate: {err}"))?;
+    Ok(DaemonTls {
+        acceptor: TlsAcceptor::from(Arc::new(config)),
+        fingerprint,
+    })
+}
+
+fn load_or_create_identity(dir: &Path) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let cert_path = dir.join(CERT_FILE);
+    let key_path = dir.join(KEY_FILE);
+    if cert_path.is_file() && key_path.is_file() {
+        let cert = fs::read(&cert_path).map_err(|err| read_error(&cert_path, err))?;
+        let key = fs::read(&key_path).map_err(|err| read_error(&key_path, err))?;
+        return Ok((cert, key));
+    }
+
+    let subject_names = CERT_SUBJECT_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    let generated = rcgen::generate_simple_self_signed(subject_names)
+        .map_err(|err| format!("failed to generate daemon TLS certificate: {err}"))?;
+    let cert = generated.cert.der().to_vec();
+    let key = generated.key_pair.serialize_der();
+
+    fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    write_private_file(&key_path, &key)?;
+    fs::write(&cert_path, &cert).map_err(|err| err.to_string())?;
+    Ok((cert, key))
+}
+
+fn write_private_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path).map_err(|err| err.to_string())?;
+    file.write_all(bytes).map_err(|err| err.to_string())
+}
+
+fn read_error(path: &Path, err: std::io::Error) -> String {
+    format!("failed to read {}: {err}", path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_or_create, CERT_FILE, KEY_FILE, TLS_DIR};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn generates_certificate_once_and_reuses_it() {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let data_dir = std::env::temp_dir().join(format!(
+            "codex-monitor-daemon-tls-{}-{unique}",
+            std::process::id()
+        ));
+
+        let first = load_or_create(&data_dir).expect("create tls identity");
+        let second = load_or_create(&data_dir).expect("reload tls identity");
+        assert_eq!(first.fingerprint, second.fingerprint);
+        assert_eq!(first.fingerprint.split(':').count(), 32);
+        assert!(data_dir.join(TLS_DIR).join(CERT_FILE).is_file());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(data_dir.join(TLS_DIR).join(KEY_FILE))
+                .expect("key metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
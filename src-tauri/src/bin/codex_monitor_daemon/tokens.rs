@@ -0,0 +1,552 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub(super) const TOKENS_FILE: &str = "tokens.json";
+const OWNER_NAME: &str = "owner";
+
+/// Methods any authenticated client may call.
+const BASIC_METHODS: &[&str] = &["ping", "daemon_info"];
+
+/// Methods that only read state; allowed for `readOnly` tokens.
+const READ_METHODS: &[&str] = &[
+    "list_workspaces",
+    "worktree_setup_status",
+    "list_workspace_files",
+    "read_workspace_file",
+    "file_read",
+    "local_usage_snapshot",
+    "get_git_status",
+    "list_git_roots",
+    "get_git_diffs",
+    "get_git_log",
+    "get_git_commit_diff",
+    "get_git_blame",
+    "get_git_remote",
+    "list_git_branches",
+    "list_git_stashes",
+    "get_git_stash_diff",
+    "get_git_conflict_contents",
+    "get_github_issues",
+    "get_github_pull_requests",
+    "get_github_pull_request_diff",
+    "get_github_pull_request_comments",
+    "list_threads",
+    "resume_thread",
+    "thread_live_subscribe",
+    "thread_live_unsubscribe",
+    "list_mcp_server_status",
+    "model_list",
+    "experimental_feature_list",
+    "collaboration_mode_list",
+    "get_agents_settings",
+    "read_agent_config_toml",
+    "get_codex_config_path",
+    "get_config_model",
+    "account_rate_limits",
+    "account_read",
+    "skills_list",
+    "apps_list",
+    "prompts_list",
+    "supervisor_snapshot",
+    "supervisor_feed",
+    "supervisor_chat_history",
+];
+
+/// Methods that manage the daemon itself; owner or unrestricted `full` tokens only.
+const ADMIN_METHODS: &[&str] = &[
+    "daemon_shutdown",
+    "daemon_tokens_list",
+    "daemon_token_create",
+    "daemon_token_revoke",
+    "audit_log_query",
+    "webhook_deliveries",
+    "get_app_settings",
+    "update_app_settings",
+];
+
+/// Workspace management methods that take the target workspace as `id`.
+const WORKSPACE_ID_METHODS: &[&str] = &[
+    "connect_workspace",
+    "remove_workspace",
+    "remove_worktree",
+    "rename_worktree",
+    "rename_worktree_upstream",
+    "update_workspace_settings",
+    "update_workspace_codex_bin",
+];
+
+/// Params naming the workspace a request acts on.
+const WORKSPACE_PARAM_KEYS: &[&str] = &["workspaceId", "parentId", "sourceWorkspaceId"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) enum DaemonTokenScope {
+    Full,
+    ReadOnly,
+    Supervisor,
+}
+
+impl DaemonTokenScope {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "full" => Ok(Self::Full),
+            "readOnly" => Ok(Self::ReadOnly),
+            "supervisor" => Ok(Self::Supervisor),
+            _ => Err(format!(
+                "invalid token scope `{value}` (expected full, readOnly or supervisor)"
+            )),
+        }
+    }
+}
+
+/// A named token from `tokens.json`. Tokens created over RPC only store `tokenSha256`;
+/// hand-written entries may use a plain `token` instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DaemonTokenEntry {
+    pub(super) name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token_sha256: Option<String>,
+    pub(super) scope: DaemonTokenScope,
+    /// Workspaces this token may act on; `None` allows all workspaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) workspace_ids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) created_at_ms: Option<i64>,
+}
+
+impl DaemonTokenEntry {
+    fn matches(&self, token: &str) -> bool {
+        if let Some(expected) = self.token.as_deref() {
+            return digest_eq(expected, token);
+        }
+        self.token_sha256
+            .as_deref()
+            .is_some_and(|expected| expected.eq_ignore_ascii_case(&token_sha256(token)))
+    }
+
+    fn summary(&self) -> Value {
+        serde_json::json!({
+            "name": self.name,
+            "scope": self.scope,
+            "workspaceIds": self.workspace_ids,
+            "createdAtMs": self.created_at_ms,
+        })
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DaemonTokensFile {
+    #[serde(default)]
+    tokens: Vec<DaemonTokenEntry>,
+}
+
+pub(super) struct DaemonTokenStore {
+    path: PathBuf,
+    entries: Vec<DaemonTokenEntry>,
+    /// Set when the tokens file exists but could not be loaded; the store then refuses
+    /// changes so the unreadable file is never overwritten.
+    load_error: Option<String>,
+}
+
+impl DaemonTokenStore {
+    pub(super) fn empty(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(TOKENS_FILE),
+            entries: Vec::new(),
+            load_error: None,
+        }
+    }
+
+    /// A store without tokens that rejects create/revoke, used when loading failed.
+    pub(super) fn unreadable(data_dir: &Path, error: String) -> Self {
+        Self {
+            load_error: Some(error),
+            ..Self::empty(data_dir)
+        }
+    }
+
+    pub(super) fn load(data_dir: &Path) -> Result<Self, String> {
+        let path = data_dir.join(TOKENS_FILE);
+        if !path.exists() {
+            return Ok(Self::empty(data_dir));
+        }
+        let raw = fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let file: DaemonTokensFile = serde_json::from_str(&raw)
+            .map_err(|err| format!("invalid {}: {err}", path.display()))?;
+        Ok(Self {
+            path,
+            entries: file.tokens,
+            load_error: None,
+        })
+    }
+
+    pub(super) fn authenticate(&self, token: &str) -> Option<DaemonPrincipal> {
+        if token.is_empty() {
+            return None;
+        }
+        self.entries
+            .iter()
+            .find(|entry| entry.matches(token))
+            .map(DaemonPrincipal::from_entry)
+    }
+
+    pub(super) fn list(&self) -> Value {
+        Value::Array(self.entries.iter().map(DaemonTokenEntry::summary).collect())
+    }
+
+    /// Creates a token and returns its secret; only the hash is persisted.
+    pub(super) fn create(
+        &mut self,
+        name: &str,
+        scope: &str,
+        workspace_ids: Option<Vec<String>>,
+        created_at_ms: i64,
+    ) -> Result<Value, String> {
+        self.ensure_writable()?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("token name is required".to_string());
+        }
+        if name == OWNER_NAME || self.entries.iter().any(|entry| entry.name == name) {
+            return Err(format!("token `{name}` already exists"));
+        }
+        let scope = DaemonTokenScope::parse(scope)?;
+        let secret = generate_token()?;
+        let entry = DaemonTokenEntry {
+            name: name.to_string(),
+            token: None,
+            token_sha256: Some(token_sha256(&secret)),
+            scope,
+            workspace_ids,
+            created_at_ms: Some(created_at_ms),
+        };
+        let summary = entry.summary();
+        self.entries.push(entry);
+        if let Err(error) = self.persist() {
+            self.entries.pop();
+            return Err(error);
+        }
+        Ok(serde_json::json!({ "token": secret, "entry": summary }))
+    }
+
+    pub(super) fn revoke(&mut self, name: &str) -> Result<(), String> {
+        self.ensure_writable()?;
+        let Some(index) = self.entries.iter().position(|entry| entry.name == name) else {
+            return Err(format!("token `{name}` not found"));
+        };
+        let removed = self.entries.remove(index);
+        if let Err(error) = self.persist() {
+            self.entries.insert(index, removed);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    fn ensure_writable(&self) -> Result<(), String> {
+        match &self.load_error {
+            Some(error) => Err(format!(
+                "scoped tokens are read-only until the tokens file is fixed: {error}"
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let file = DaemonTokensFile {
+            tokens: self.entries.clone(),
+        };
+        let data = serde_json::to_string_pretty(&file).map_err(|err| err.to_string())?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut handle = options.open(&self.path).map_err(|err| err.to_string())?;
+        handle
+            .write_all(data.as_bytes())
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// The identity a connection authenticated as, used to authorize each request.
+#[derive(Debug, Clone)]
+pub(super) struct DaemonPrincipal {
+    pub(super) name: String,
+    scope: DaemonTokenScope,
+    workspace_ids: Option<HashSet<String>>,
+}
+
+impl DaemonPrincipal {
+    /// The `--token` holder, or any client on a trusted transport.
+    pub(super) fn owner() -> Self {
+        Self {
+            name: OWNER_NAME.to_string(),
+            scope: DaemonTokenScope::Full,
+            workspace_ids: None,
+        }
+    }
+
+    fn from_entry(entry: &DaemonTokenEntry) -> Self {
+        Self {
+            name: entry.name.clone(),
+            scope: entry.scope,
+            workspace_ids: entry
+                .workspace_ids
+                .as_ref()
+                .map(|ids| ids.iter().cloned().collect()),
+        }
+    }
+
+    pub(super) fn is_workspace_restricted(&self) -> bool {
+        self.workspace_ids.is_some()
+    }
+
+    pub(super) fn allows_workspace(&self, workspace_id: &str) -> bool {
+        self.workspace_ids
+            .as_ref()
+            .is_none_or(|ids| ids.contains(workspace_id))
+    }
+
+    pub(super) fn authorize(&self, method: &str, params: &Value) -> Result<(), String> {
+        if BASIC_METHODS.contains(&method) {
+            return Ok(());
+        }
+        let allowed_by_scope = match self.scope {
+            DaemonTokenScope::Full => true,
+            DaemonTokenScope::ReadOnly => READ_METHODS.contains(&method),
+            DaemonTokenScope::Supervisor => method.starts_with("supervisor_"),
+        };
+        if !allowed_by_scope {
+            return Err(format!(
+                "token `{}` is not allowed to call `{method}`",
+                self.name
+            ));
+        }
+        if ADMIN_METHODS.contains(&method) && self.is_workspace_restricted() {
+            return Err(format!("`{method}` requires an unrestricted token"));
+        }
+        if !self.is_workspace_restricted() {
+            return Ok(());
+        }
+
+        let targets = target_workspace_ids(method, params);
+        if targets.is_empty() {
+            // Global methods would expose or change other workspaces; list_workspaces is
+            // filtered by the dispatcher instead.
+            if method == "list_workspaces" {
+                return Ok(());
+            }
+            return Err(format!(
+                "token `{}` is restricted to specific workspaces and cannot call `{method}`",
+                self.name
+            ));
+        }
+        match targets.iter().find(|id| !self.allows_workspace(id)) {
+            Some(denied) => Err(format!(
+                "token `{}` is not allowed to access workspace `{denied}`",
+                self.name
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Whether `method` only reads state, i.e. is callable with a `readOnly` token.
+pub(super) fn is_read_only_method(method: &str) -> bool {
+    BASIC_METHODS.contains(&method) || READ_METHODS.contains(&method)
+}
+
+pub(super) fn target_workspace_ids(method: &str, params: &Value) -> Vec<String> {
+    let Value::Object(map) = params else {
+        return Vec::new();
+    };
+    let mut keys = WORKSPACE_PARAM_KEYS.to_vec();
+    if WORKSPACE_ID_METHODS.contains(&method) {
+        keys.push("id");
+    }
+    keys.into_iter()
+        .filter_map(|key| map.get(key).and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+fn token_sha256(token: &str) -> String {
+    digest(&SHA256, token.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Compares tokens through their digests so the comparison time does not depend on
+/// where the first mismatching byte is.
+fn digest_eq(expected: &str, provided: &str) -> bool {
+    digest(&SHA256, expected.as_bytes()).as_ref() == digest(&SHA256, provided.as_bytes()).as_ref()
+}
+
+pub(super) fn token_matches(expected: &str, provided: &str) -> bool {
+    !provided.is_empty() && digest_eq(expected, provided)
+}
+
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "failed to generate token".to_string())?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn principal(scope: DaemonTokenScope, workspace_ids: Option<&[&str]>) -> DaemonPrincipal {
+        DaemonPrincipal {
+            name: "test".to_string(),
+            scope,
+            workspace_ids: workspace_ids.map(|ids| ids.iter().map(|id| id.to_string()).collect()),
+        }
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-{prefix}-{}-{}",
+            std::process::id(),
+            generate_token().expect("token")
+        ));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn read_only_scope_rejects_mutating_methods() {
+        let principal = principal(DaemonTokenScope::ReadOnly, None);
+        let params = json!({ "workspaceId": "ws-1" });
+        assert!(principal.authorize("get_git_diffs", &params).is_ok());
+        assert!(principal.authorize("list_workspaces", &json!({})).is_ok());
+        assert!(principal.authorize("push_git", &params).is_err());
+        assert!(principal.authorize("file_write", &params).is_err());
+        assert!(principal
+            .authorize("remove_workspace", &json!({ "id": "ws-1" }))
+            .is_err());
+        assert!(principal
+            .authorize("supervisor_dispatch", &json!({}))
+            .is_err());
+    }
+
+    #[test]
+    fn supervisor_scope_only_allows_supervisor_methods() {
+        let principal = principal(DaemonTokenScope::Supervisor, None);
+        assert!(principal.authorize("ping", &Value::Null).is_ok());
+        assert!(principal
+            .authorize("supervisor_chat_send", &json!({}))
+            .is_ok());
+        assert!(principal.authorize("list_workspaces", &json!({})).is_err());
+        assert!(principal
+            .authorize("send_user_message", &json!({ "workspaceId": "ws-1" }))
+            .is_err());
+    }
+
+    #[test]
+    fn workspace_allowlist_limits_targets_and_global_methods() {
+        let principal = principal(DaemonTokenScope::Full, Some(&["ws-1"]));
+        assert!(principal
+            .authorize("send_user_message", &json!({ "workspaceId": "ws-1" }))
+            .is_ok());
+        assert!(principal
+            .authorize("send_user_message", &json!({ "workspaceId": "ws-2" }))
+            .is_err());
+        assert!(principal
+            .authorize("connect_workspace", &json!({ "id": "ws-2" }))
+            .is_err());
+        assert!(principal
+            .authorize("add_clone", &json!({ "sourceWorkspaceId": "ws-2" }))
+            .is_err());
+        assert!(principal.authorize("list_workspaces", &json!({})).is_ok());
+        assert!(principal
+            .authorize("add_workspace", &json!({ "path": "/tmp" }))
+            .is_err());
+        assert!(principal
+            .authorize("daemon_token_create", &json!({}))
+            .is_err());
+    }
+
+    #[test]
+    fn created_tokens_persist_hashed_and_authenticate() {
+        let dir = temp_dir("tokens");
+        let mut store = DaemonTokenStore::load(&dir).expect("empty store");
+        let created = store
+            .create("phone", "readOnly", Some(vec!["ws-1".to_string()]), 42)
+            .expect("create token");
+        let secret = created["token"].as_str().expect("secret").to_string();
+        assert_eq!(created["entry"]["scope"], json!("readOnly"));
+        assert!(store.create("phone", "full", None, 43).is_err());
+        assert!(store.create("other", "admin", None, 43).is_err());
+
+        let raw = fs::read_to_string(dir.join(TOKENS_FILE)).expect("tokens file");
+        assert!(!raw.contains(&secret));
+
+        let reloaded = DaemonTokenStore::load(&dir).expect("reload store");
+        let principal = reloaded.authenticate(&secret).expect("authenticated");
+        assert_eq!(principal.name, "phone");
+        assert!(principal.is_workspace_restricted());
+        assert!(reloaded.authenticate("wrong").is_none());
+
+        let mut reloaded = reloaded;
+        reloaded.revoke("phone").expect("revoke");
+        assert!(reloaded.authenticate(&secret).is_none());
+        assert!(reloaded.revoke("phone").is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unreadable_tokens_file_is_never_overwritten() {
+        let dir = temp_dir("tokens-invalid");
+        fs::write(dir.join(TOKENS_FILE), "{ not json").expect("write tokens");
+        let error = DaemonTokenStore::load(&dir).err().expect("load fails");
+        let mut store = DaemonTokenStore::unreadable(&dir, error);
+        assert!(store.create("phone", "readOnly", None, 42).is_err());
+        assert!(store.revoke("phone").is_err());
+        assert_eq!(
+            fs::read_to_string(dir.join(TOKENS_FILE)).expect("tokens file"),
+            "{ not json"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn hand_written_plain_tokens_are_accepted() {
+        let dir = temp_dir("tokens-plain");
+        fs::write(
+            dir.join(TOKENS_FILE),
+            r#"{ "tokens": [ { "name": "ci", "token": "s3cret", "scope": "supervisor" } ] }"#,
+        )
+        .expect("write tokens");
+        let store = DaemonTokenStore::load(&dir).expect("load store");
+        let principal = store.authenticate("s3cret").expect("authenticated");
+        assert_eq!(principal.name, "ci");
+        assert!(principal.authorize("supervisor_feed", &json!({})).is_ok());
+        assert!(principal.authorize("list_threads", &json!({})).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
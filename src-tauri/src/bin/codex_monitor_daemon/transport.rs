@@ -1,16 +1,117 @@
+use super::event_journal::EventCursor;
 use super::rpc::{
     build_error_response, build_result_response, forward_events, parse_auth_token,
-    spawn_rpc_response_task,
+    spawn_rpc_response_task, RpcCaller,
 };
+use super::tokens::{token_matches, DaemonPrincipal};
 use super::*;
+use tokio::time::timeout;
+use tokio_rustls::TlsAcceptor;
+
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub(super) async fn handle_client(
     socket: TcpStream,
     config: Arc<DaemonConfig>,
     state: Arc<DaemonState>,
-    events: broadcast::Sender<DaemonEvent>,
+    events: Arc<EventJournal>,
+) {
+    let peer = peer_label(&socket);
+    let (reader, writer) = socket.into_split();
+    let authenticated = config.token.is_none();
+    serve_connection(reader, writer, peer, authenticated, config, state, events).await;
+}
+
+pub(super) async fn handle_tls_client(
+    socket: TcpStream,
+    acceptor: TlsAcceptor,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: Arc<EventJournal>,
+) {
+    let peer = peer_label(&socket);
+    let stream = match timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(socket)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(err)) => {
+            eprintln!("TLS handshake failed: {err}");
+            return;
+        }
+        Err(_) => {
+            eprintln!("TLS handshake timed out");
+            return;
+        }
+    };
+    let (reader, writer) = tokio::io::split(stream);
+    let authenticated = config.token.is_none();
+    serve_connection(reader, writer, peer, authenticated, config, state, events).await;
+}
+
+/// Unix socket clients are trusted: access is restricted by the socket file permissions.
+#[cfg(unix)]
+pub(super) async fn handle_unix_client(
+    socket: UnixStream,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: Arc<EventJournal>,
+) {
+    let (reader, writer) = socket.into_split();
+    let peer = config.listen.to_string();
+    serve_connection(reader, writer, peer, true, config, state, events).await;
+}
+
+/// Serves a single trusted client over stdin/stdout until stdin closes.
+pub(super) async fn serve_stdio(
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: Arc<EventJournal>,
 ) {
-    let (reader, mut writer) = socket.into_split();
+    serve_connection(
+        tokio::io::stdin(),
+        tokio::io::stdout(),
+        "stdio".to_string(),
+        true,
+        config,
+        state,
+        events,
+    )
+    .await;
+}
+
+/// Resolves a client token to the shared owner token or a scoped token from `tokens.json`.
+pub(super) async fn authenticate_token(
+    config: &DaemonConfig,
+    state: &DaemonState,
+    provided: &str,
+) -> Option<DaemonPrincipal> {
+    if config
+        .token
+        .as_deref()
+        .is_some_and(|expected| token_matches(expected, provided))
+    {
+        return Some(DaemonPrincipal::owner());
+    }
+    state.tokens.lock().await.authenticate(provided)
+}
+
+pub(super) fn peer_label(socket: &TcpStream) -> String {
+    socket
+        .peer_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|_| "tcp:unknown".to_string())
+}
+
+pub(super) async fn serve_connection<R, W>(
+    reader: R,
+    mut writer: W,
+    peer: String,
+    authenticated: bool,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: Arc<EventJournal>,
+) where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
     let mut lines = BufReader::new(reader).lines();
 
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
@@ -22,18 +123,31 @@ pub(super) async fn handle_client(
             if writer.write_all(b"\n").await.is_err() {
                 break;
             }
+            if writer.flush().await.is_err() {
+                break;
+            }
         }
     });
 
-    let mut authenticated = config.token.is_none();
     let mut events_task: Option<tokio::task::JoinHandle<()>> = None;
     let request_limiter = Arc::new(Semaphore::new(MAX_IN_FLIGHT_RPC_PER_CONNECTION));
-    let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
 
-    if authenticated {
-        let rx = events.subscribe();
+    let mut caller = authenticated.then(|| {
+        Arc::new(RpcCaller {
+            principal: DaemonPrincipal::owner(),
+            peer: peer.clone(),
+        })
+    });
+    if let Some(caller) = caller.as_ref() {
+        let (rx, replay) = events.subscribe(&EventCursor::default());
         let out_tx_events = out_tx.clone();
-        events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
+        events_task = Some(tokio::spawn(forward_events(
+            rx,
+            replay,
+            Arc::clone(&events),
+            out_tx_events,
+            Arc::clone(caller),
+        )));
     }
 
     while let Ok(Some(line)) = lines.next_line().await {
@@ -55,7 +169,7 @@ pub(super) async fn handle_client(
             .to_string();
         let params = message.get("params").cloned().unwrap_or(Value::Null);
 
-        if !authenticated {
+        let Some(current_caller) = caller.as_ref() else {
             if method != "auth" {
                 if let Some(response) = build_error_response(id, "unauthorized") {
                     let _ = out_tx.send(response);
@@ -63,34 +177,74 @@ pub(super) async fn handle_client(
                 continue;
             }
 
-            let expected = config.token.clone().unwrap_or_default();
             let provided = parse_auth_token(&params).unwrap_or_default();
-            if expected != provided {
+            let Some(resolved) = authenticate_token(&config, &state, &provided).await else {
                 if let Some(response) = build_error_response(id, "invalid token") {
                     let _ = out_tx.send(response);
                 }
                 continue;
-            }
+            };
 
-            authenticated = true;
-            if let Some(response) = build_result_response(id, json!({ "ok": true })) {
+            let resolved = Arc::new(RpcCaller {
+                principal: resolved,
+                peer: peer.clone(),
+            });
+            if let Some(response) = build_result_response(
+                id,
+                json!({ "ok": true, "tokenName": resolved.principal.name }),
+            ) {
                 let _ = out_tx.send(response);
             }
 
-            let rx = events.subscribe();
+            let (rx, replay) = events.subscribe(&EventCursor::default());
             let out_tx_events = out_tx.clone();
-            events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
+            events_task = Some(tokio::spawn(forward_events(
+                rx,
+                replay,
+                Arc::clone(&events),
+                out_tx_events,
+                Arc::clone(&resolved),
+            )));
+            caller = Some(resolved);
 
+            continue;
+        };
+
+        if method == "subscribe" {
+            // Restart event forwarding from the client's cursor; the previous forwarder is
+            // stopped first so its last writes cannot interleave with the response or the
+            // replay that follows it.
+            if let Some(task) = events_task.take() {
+                task.abort();
+                let _ = task.await;
+            }
+            let (rx, replay) = events.subscribe(&EventCursor::from_params(&params));
+            let result = json!({
+                "ok": true,
+                "streamId": events.stream_id(),
+                "latestSeq": replay.latest_seq,
+                "gap": replay.gap.is_some(),
+            });
+            if let Some(response) = build_result_response(id, result) {
+                let _ = out_tx.send(response);
+            }
+            events_task = Some(tokio::spawn(forward_events(
+                rx,
+                replay,
+                Arc::clone(&events),
+                out_tx.clone(),
+                Arc::clone(current_caller),
+            )));
             continue;
         }
 
         spawn_rpc_response_task(
             Arc::clone(&state),
+            Arc::clone(current_caller),
             out_tx.clone(),
             id,
             method,
             params,
-            client_version.clone(),
             Arc::clone(&request_limiter),
         );
     }
@@ -0,0 +1,742 @@
+#[path = "webhook/deliveries.rs"]
+mod deliveries;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, StatusCode};
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, Mutex};
+
+use crate::shared::supervisor_core::service as supervisor_service;
+use crate::shared::supervisor_core::supervisor_loop;
+use crate::shared::supervisor_core::{
+    SupervisorJobState, SupervisorJobStatus, SupervisorSignal, SupervisorSignalKind,
+    SupervisorState,
+};
+
+use super::DaemonState;
+use deliveries::{WebhookDeliveryEntry, WebhookDeliveryOutcome};
+pub(crate) use deliveries::{WebhookDeliveryLog, WebhookDeliveryQuery};
+
+pub(crate) const WEBHOOK_CONFIG_FILE: &str = "webhooks.json";
+const DEFAULT_SYNC_INTERVAL_SECONDS: u64 = 5;
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+const DEFAULT_CONTENT_TYPE: &str = "application/json";
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+const EVENT_HEADER: &str = "X-CodexMonitor-Event";
+const DELIVERY_HEADER: &str = "X-CodexMonitor-Delivery";
+const SIGNATURE_HEADER: &str = "X-CodexMonitor-Signature";
+const ERROR_BODY_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum WebhookEventKind {
+    Signal,
+    Job,
+}
+
+impl WebhookEventKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Signal => "signal",
+            Self::Job => "job",
+        }
+    }
+}
+
+/// One configured receiver. Empty filter lists match everything.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookEndpoint {
+    id: String,
+    url: String,
+    #[serde(default)]
+    secret: Option<String>,
+    /// Reads the signing secret from this environment variable instead of the file.
+    #[serde(default)]
+    secret_env: Option<String>,
+    #[serde(default)]
+    events: Vec<WebhookEventKind>,
+    #[serde(default)]
+    signal_kinds: Vec<SupervisorSignalKind>,
+    #[serde(default)]
+    workspace_ids: Vec<String>,
+    /// Request body with `{{path.to.field}}` placeholders; the raw event JSON when unset.
+    #[serde(default)]
+    body_template: Option<String>,
+    #[serde(default)]
+    content_type: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    max_attempts: Option<u32>,
+    #[serde(default)]
+    timeout_seconds: Option<u64>,
+}
+
+impl WebhookEndpoint {
+    fn matches(&self, event: &WebhookEvent) -> bool {
+        let event_matches = self.events.is_empty() || self.events.contains(&event.kind);
+        let kind_matches = self.signal_kinds.is_empty()
+            || event
+                .signal_kind
+                .as_ref()
+                .is_none_or(|kind| self.signal_kinds.contains(kind));
+        let workspace_matches = self.workspace_ids.is_empty()
+            || event
+                .workspace_id
+                .as_ref()
+                .is_some_and(|workspace_id| self.workspace_ids.contains(workspace_id));
+        event_matches && kind_matches && workspace_matches
+    }
+
+    fn content_type(&self) -> &str {
+        self.content_type.as_deref().unwrap_or(DEFAULT_CONTENT_TYPE)
+    }
+
+    fn render_body(&self, payload: &Value) -> String {
+        match &self.body_template {
+            Some(template) => {
+                let json_escape = self.content_type().contains("json");
+                render_template(template, payload, json_escape)
+            }
+            None => payload.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookConfig {
+    #[serde(default)]
+    sync_interval_seconds: Option<u64>,
+    #[serde(default)]
+    endpoints: Vec<WebhookEndpoint>,
+}
+
+impl WebhookConfig {
+    /// Reads `SUPERVISOR_WEBHOOKS_CONFIG`, or `webhooks.json` in the data dir. Returns
+    /// `Ok(None)` when no endpoints are configured.
+    pub(crate) fn load(data_dir: &Path) -> Result<Option<Self>, String> {
+        let explicit_path = std::env::var("SUPERVISOR_WEBHOOKS_CONFIG")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(PathBuf::from);
+        let path = explicit_path
+            .clone()
+            .unwrap_or_else(|| data_dir.join(WEBHOOK_CONFIG_FILE));
+        if explicit_path.is_none() && !path.exists() {
+            return Ok(None);
+        }
+        let raw = std::fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let config =
+            Self::parse(&raw).map_err(|err| format!("invalid {}: {err}", path.display()))?;
+        Ok((!config.endpoints.is_empty()).then_some(config))
+    }
+
+    fn parse(raw: &str) -> Result<Self, String> {
+        let mut config: Self = serde_json::from_str(raw).map_err(|err| err.to_string())?;
+        let mut ids = HashSet::new();
+        for endpoint in &mut config.endpoints {
+            if endpoint.id.trim().is_empty() {
+                return Err("endpoint id must not be empty".to_string());
+            }
+            if !ids.insert(endpoint.id.clone()) {
+                return Err(format!("duplicate endpoint id `{}`", endpoint.id));
+            }
+            if !endpoint.url.starts_with("http://") && !endpoint.url.starts_with("https://") {
+                return Err(format!("endpoint `{}` needs an http(s) url", endpoint.id));
+            }
+            if let Some(name) = endpoint.secret_env.as_deref() {
+                let secret = std::env::var(name).map_err(|_| {
+                    format!(
+                        "endpoint `{}`: environment variable {name} is not set",
+                        endpoint.id
+                    )
+                })?;
+                endpoint.secret = Some(secret);
+            }
+        }
+        Ok(config)
+    }
+
+    fn sync_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.sync_interval_seconds
+                .filter(|value| *value > 0)
+                .unwrap_or(DEFAULT_SYNC_INTERVAL_SECONDS),
+        )
+    }
+}
+
+/// A signal or job status change, ready to be posted.
+#[derive(Debug, Clone)]
+struct WebhookEvent {
+    id: String,
+    kind: WebhookEventKind,
+    workspace_id: Option<String>,
+    signal_kind: Option<SupervisorSignalKind>,
+    payload: Value,
+}
+
+impl WebhookEvent {
+    fn signal(signal: &SupervisorSignal, now_ms: i64) -> Self {
+        let id = uuid::Uuid::new_v4().to_string();
+        let kind_name = serde_json::to_value(&signal.kind)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let text = format!(
+            "[{kind_name}] {}: {}",
+            signal.workspace_id.as_deref().unwrap_or("supervisor"),
+            signal.message
+        );
+        Self {
+            payload: json!({
+                "id": id,
+                "event": WebhookEventKind::Signal.as_str(),
+                "timestamp_ms": now_ms,
+                "workspace_id": signal.workspace_id,
+                "text": text,
+                "signal": signal,
+            }),
+            id,
+            kind: WebhookEventKind::Signal,
+            workspace_id: signal.workspace_id.clone(),
+            signal_kind: Some(signal.kind.clone()),
+        }
+    }
+
+    fn job(
+        job: &SupervisorJobState,
+        previous_status: Option<&SupervisorJobStatus>,
+        now_ms: i64,
+    ) -> Self {
+        let id = uuid::Uuid::new_v4().to_string();
+        let status_name = |status: &SupervisorJobStatus| {
+            serde_json::to_value(status)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default()
+        };
+        let status = status_name(&job.status);
+        let previous = previous_status.map(status_name);
+        let mut text = format!(
+            "Job {} in {}: {} → {status}",
+            job.description,
+            job.workspace_id,
+            previous.as_deref().unwrap_or("new")
+        );
+        if let Some(error) = job.error.as_deref() {
+            text.push_str(&format!(" ({error})"));
+        }
+        Self {
+            payload: json!({
+                "id": id,
+                "event": WebhookEventKind::Job.as_str(),
+                "timestamp_ms": now_ms,
+                "workspace_id": job.workspace_id,
+                "text": text,
+                "job": {
+                    "id": job.id,
+                    "workspace_id": job.workspace_id,
+                    "thread_id": job.thread_id,
+                    "description": job.description,
+                    "status": status,
+                    "previous_status": previous,
+                    "error": job.error,
+                    "requested_at_ms": job.requested_at_ms,
+                    "started_at_ms": job.started_at_ms,
+                    "completed_at_ms": job.completed_at_ms,
+                },
+            }),
+            id,
+            kind: WebhookEventKind::Job,
+            workspace_id: Some(job.workspace_id.clone()),
+            signal_kind: None,
+        }
+    }
+}
+
+/// Turns successive Supervisor snapshots into new signals and job status changes. The
+/// first snapshot only primes the watcher, so a restart does not replay old state.
+#[derive(Debug, Default)]
+struct WebhookWatcher {
+    primed: bool,
+    seen_signal_ids: HashSet<String>,
+    job_statuses: HashMap<String, SupervisorJobStatus>,
+}
+
+impl WebhookWatcher {
+    fn collect(&mut self, snapshot: &SupervisorState, now_ms: i64) -> Vec<WebhookEvent> {
+        let mut events = Vec::new();
+
+        let mut signals = snapshot.signals.iter().collect::<Vec<_>>();
+        signals.sort_by_key(|signal| signal.created_at_ms);
+        for signal in signals {
+            if self.seen_signal_ids.insert(signal.id.clone())
+                && self.primed
+                && signal.acknowledged_at_ms.is_none()
+            {
+                events.push(WebhookEvent::signal(signal, now_ms));
+            }
+        }
+        self.seen_signal_ids
+            .retain(|id| snapshot.signals.iter().any(|signal| &signal.id == id));
+
+        for job in snapshot.jobs.values() {
+            let previous = self.job_statuses.insert(job.id.clone(), job.status.clone());
+            if self.primed && previous.as_ref() != Some(&job.status) {
+                events.push(WebhookEvent::job(job, previous.as_ref(), now_ms));
+            }
+        }
+        self.job_statuses
+            .retain(|job_id, _| snapshot.jobs.contains_key(job_id));
+
+        self.primed = true;
+        events
+    }
+}
+
+pub(crate) async fn run(state: Arc<DaemonState>, config: WebhookConfig) {
+    let client = match Client::builder().build() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("webhook notifier disabled: failed to build HTTP client: {error}");
+            return;
+        }
+    };
+    eprintln!(
+        "webhook notifier enabled for {} endpoint(s)",
+        config.endpoints.len()
+    );
+
+    let log = Arc::new(Mutex::new(WebhookDeliveryLog::new(&state.data_dir)));
+    // One queue per endpoint keeps deliveries ordered and lets a slow or retrying
+    // endpoint fall behind without holding up the others.
+    let queues = config
+        .endpoints
+        .iter()
+        .map(|endpoint| {
+            let (sender, receiver) = mpsc::unbounded_channel::<Arc<WebhookEvent>>();
+            let endpoint = Arc::new(endpoint.clone());
+            tokio::spawn(run_endpoint_queue(
+                client.clone(),
+                Arc::clone(&endpoint),
+                Arc::clone(&log),
+                receiver,
+            ));
+            (endpoint, sender)
+        })
+        .collect::<Vec<_>>();
+
+    let mut watcher = WebhookWatcher::default();
+    loop {
+        let snapshot = supervisor_service::supervisor_snapshot_core(&state.supervisor_loop).await;
+        for event in watcher.collect(&snapshot, supervisor_loop::now_timestamp_ms()) {
+            let event = Arc::new(event);
+            for (endpoint, sender) in &queues {
+                if endpoint.matches(&event) {
+                    let _ = sender.send(Arc::clone(&event));
+                }
+            }
+        }
+        tokio::time::sleep(config.sync_interval()).await;
+    }
+}
+
+async fn run_endpoint_queue(
+    client: Client,
+    endpoint: Arc<WebhookEndpoint>,
+    log: Arc<Mutex<WebhookDeliveryLog>>,
+    mut receiver: mpsc::UnboundedReceiver<Arc<WebhookEvent>>,
+) {
+    while let Some(event) = receiver.recv().await {
+        let entry = deliver(&client, &endpoint, &event, RETRY_BASE_DELAY).await;
+        if entry.outcome == WebhookDeliveryOutcome::Failed {
+            eprintln!(
+                "webhook `{}` delivery {} failed after {} attempt(s): {}",
+                entry.endpoint_id,
+                entry.delivery_id,
+                entry.attempts,
+                entry.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        if let Err(error) = log.lock().await.append(&entry) {
+            eprintln!("failed to write webhook delivery log: {error}");
+        }
+    }
+}
+
+/// Posts one event, retrying network errors, 429 and 5xx responses with exponential
+/// backoff. Other 4xx responses are treated as permanent failures.
+async fn deliver(
+    client: &Client,
+    endpoint: &WebhookEndpoint,
+    event: &WebhookEvent,
+    retry_base_delay: Duration,
+) -> WebhookDeliveryEntry {
+    let started = Instant::now();
+    let delivery_id = format!("{}:{}", event.id, endpoint.id);
+    let body = endpoint.render_body(&event.payload);
+    let signature = endpoint
+        .secret
+        .as_deref()
+        .map(|secret| sign_body(secret, &body));
+    let max_attempts = endpoint.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1);
+    let timeout = Duration::from_secs(
+        endpoint
+            .timeout_seconds
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_TIMEOUT_SECONDS),
+    );
+
+    let mut attempts = 0;
+    let mut http_status = None;
+    let mut error = None;
+    let mut delivered = false;
+    while attempts < max_attempts {
+        if attempts > 0 {
+            let delay = retry_base_delay
+                .saturating_mul(1 << (attempts - 1).min(16))
+                .min(RETRY_MAX_DELAY);
+            tokio::time::sleep(delay).await;
+        }
+        attempts += 1;
+
+        let mut request = client
+            .post(&endpoint.url)
+            .timeout(timeout)
+            .header(CONTENT_TYPE, endpoint.content_type())
+            .header(EVENT_HEADER, event.kind.as_str())
+            .header(DELIVERY_HEADER, delivery_id.as_str());
+        for (name, value) in &endpoint.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(signature) = signature.as_deref() {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        match request.body(body.clone()).send().await {
+            Ok(response) => {
+                let status = response.status();
+                http_status = Some(status.as_u16());
+                if status.is_success() {
+                    delivered = true;
+                    error = None;
+                    break;
+                }
+                let response_body = response.text().await.unwrap_or_default();
+                error = Some(format!(
+                    "status {status}: {}",
+                    response_body
+                        .chars()
+                        .take(ERROR_BODY_LIMIT)
+                        .collect::<String>()
+                ));
+                if !is_retryable_status(status) {
+                    break;
+                }
+            }
+            Err(send_error) => {
+                http_status = None;
+                error = Some(send_error.to_string());
+            }
+        }
+    }
+
+    WebhookDeliveryEntry {
+        timestamp_ms: supervisor_loop::now_timestamp_ms(),
+        delivery_id,
+        endpoint_id: endpoint.id.clone(),
+        event: event.kind,
+        workspace_id: event.workspace_id.clone(),
+        outcome: if delivered {
+            WebhookDeliveryOutcome::Delivered
+        } else {
+            WebhookDeliveryOutcome::Failed
+        },
+        attempts,
+        http_status,
+        error,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// `sha256=<hex HMAC-SHA256 of the request body>`, in the style of GitHub webhooks.
+fn sign_body(secret: &str, body: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, body.as_bytes());
+    let hex = tag
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256={hex}")
+}
+
+/// Replaces `{{path}}` placeholders with values from the event payload. Strings are
+/// JSON-escaped for JSON bodies so templates can put them inside quotes; missing values
+/// render as empty.
+fn render_template(template: &str, payload: &Value, json_escape: bool) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        rendered.push_str(&template_value(payload, after[..end].trim(), json_escape));
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+fn template_value(payload: &Value, path: &str, json_escape: bool) -> String {
+    let value = path.split('.').try_fold(payload, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => value.get(key),
+    });
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) if json_escape => {
+            let quoted = Value::String(text.clone()).to_string();
+            quoted[1..quoted.len() - 1].to_string()
+        }
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn signal(id: &str, kind: SupervisorSignalKind, workspace_id: &str) -> SupervisorSignal {
+        SupervisorSignal {
+            id: id.to_string(),
+            kind,
+            workspace_id: Some(workspace_id.to_string()),
+            thread_id: None,
+            job_id: None,
+            message: "Tests \"failed\"".to_string(),
+            created_at_ms: 1,
+            acknowledged_at_ms: None,
+            context: Value::Null,
+        }
+    }
+
+    fn endpoint(raw: Value) -> WebhookEndpoint {
+        serde_json::from_value(raw).expect("endpoint")
+    }
+
+    #[test]
+    fn renders_templates_with_json_escaping() {
+        let event = WebhookEvent::signal(&signal("s-1", SupervisorSignalKind::Failed, "ws-1"), 5);
+        let template = r#"{"text":"{{ text }}","kind":"{{signal.kind}}","at":{{timestamp_ms}},"missing":"{{signal.nope}}"}"#;
+
+        let json_body = render_template(template, &event.payload, true);
+        let parsed: Value = serde_json::from_str(&json_body).expect("valid json");
+        assert_eq!(parsed["text"], "[failed] ws-1: Tests \"failed\"");
+        assert_eq!(parsed["kind"], "failed");
+        assert_eq!(parsed["at"], 5);
+        assert_eq!(parsed["missing"], "");
+
+        assert_eq!(
+            render_template("{{text}} {{unterminated", &event.payload, false),
+            "[failed] ws-1: Tests \"failed\" {{unterminated"
+        );
+    }
+
+    #[test]
+    fn endpoint_filters_by_event_kind_and_workspace() {
+        let failures = endpoint(json!({
+            "id": "alerts",
+            "url": "https://example.invalid/hook",
+            "signalKinds": ["failed", "stalled"],
+            "workspaceIds": ["ws-1"],
+        }));
+        let failed = WebhookEvent::signal(&signal("s-1", SupervisorSignalKind::Failed, "ws-1"), 1);
+        let completed =
+            WebhookEvent::signal(&signal("s-2", SupervisorSignalKind::Completed, "ws-1"), 1);
+        let other_workspace =
+            WebhookEvent::signal(&signal("s-3", SupervisorSignalKind::Failed, "ws-2"), 1);
+        let job = WebhookEvent::job(
+            &SupervisorJobState {
+                id: "job-1".to_string(),
+                workspace_id: "ws-1".to_string(),
+                ..Default::default()
+            },
+            None,
+            1,
+        );
+        assert!(failures.matches(&failed));
+        assert!(!failures.matches(&completed));
+        assert!(!failures.matches(&other_workspace));
+        assert!(failures.matches(&job));
+
+        let signals_only = endpoint(json!({
+            "id": "signals",
+            "url": "http://127.0.0.1:9/hook",
+            "events": ["signal"],
+        }));
+        assert!(signals_only.matches(&completed));
+        assert!(!signals_only.matches(&job));
+    }
+
+    #[test]
+    fn watcher_reports_new_signals_and_job_transitions_after_priming() {
+        let mut snapshot = SupervisorState::default();
+        snapshot
+            .signals
+            .push(signal("old", SupervisorSignalKind::Failed, "ws-1"));
+        snapshot.jobs.insert(
+            "job-1".to_string(),
+            SupervisorJobState {
+                id: "job-1".to_string(),
+                workspace_id: "ws-1".to_string(),
+                description: "run tests".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let mut watcher = WebhookWatcher::default();
+        assert!(watcher.collect(&snapshot, 1).is_empty());
+        assert!(watcher.collect(&snapshot, 2).is_empty());
+
+        snapshot
+            .signals
+            .push(signal("new", SupervisorSignalKind::Stalled, "ws-1"));
+        if let Some(job) = snapshot.jobs.get_mut("job-1") {
+            job.status = SupervisorJobStatus::Failed;
+            job.error = Some("exit 1".to_string());
+        }
+        let events = watcher.collect(&snapshot, 3);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].payload["signal"]["id"], "new");
+        assert_eq!(events[1].payload["job"]["previous_status"], "queued");
+        assert_eq!(events[1].payload["job"]["status"], "failed");
+        assert_eq!(
+            events[1].payload["text"],
+            "Job run tests in ws-1: queued → failed (exit 1)"
+        );
+        assert!(watcher.collect(&snapshot, 4).is_empty());
+    }
+
+    #[test]
+    fn signs_bodies_with_hmac_sha256() {
+        // Test vector 2 from RFC 4231.
+        assert_eq!(
+            sign_body("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    fn read_request(stream: &mut std::net::TcpStream) -> (Vec<String>, String) {
+        let mut reader = BufReader::new(stream);
+        let mut headers = Vec::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("read header");
+            let line = line.trim_end().to_string();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = value.trim().parse().expect("content length");
+            }
+            headers.push(line);
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).expect("read body");
+        (headers, String::from_utf8(body).expect("utf8 body"))
+    }
+
+    #[test]
+    fn delivers_with_signature_and_retries_server_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}/hook", listener.local_addr().expect("addr"));
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut stream, _) = listener.accept().expect("accept");
+                requests.push(read_request(&mut stream));
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .expect("respond");
+            }
+            requests
+        });
+
+        let endpoint = endpoint(json!({
+            "id": "local",
+            "url": url,
+            "secret": "s3cret",
+            "bodyTemplate": "{\"text\":\"{{text}}\"}",
+            "headers": { "X-Team": "core" },
+        }));
+        let event = WebhookEvent::signal(&signal("s-1", SupervisorSignalKind::Failed, "ws-1"), 1);
+        let entry = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime")
+            .block_on(deliver(
+                &Client::new(),
+                &endpoint,
+                &event,
+                Duration::from_millis(10),
+            ));
+
+        assert_eq!(entry.outcome, WebhookDeliveryOutcome::Delivered);
+        assert_eq!(entry.attempts, 2);
+        assert_eq!(entry.http_status, Some(200));
+
+        let requests = server.join().expect("server thread");
+        let (headers, body) = &requests[1];
+        assert_eq!(body, "{\"text\":\"[failed] ws-1: Tests \\\"failed\\\"\"}");
+        let has_header = |expected: &str| {
+            headers
+                .iter()
+                .any(|line| line.eq_ignore_ascii_case(expected))
+        };
+        assert!(has_header(&format!(
+            "x-codexmonitor-signature: {}",
+            sign_body("s3cret", body)
+        )));
+        assert!(has_header("x-codexmonitor-event: signal"));
+        assert!(has_header("x-team: core"));
+    }
+
+    #[test]
+    fn rejects_invalid_endpoint_configs() {
+        assert!(WebhookConfig::parse(r#"{"endpoints":[{"id":"a","url":"ftp://x"}]}"#).is_err());
+        assert!(WebhookConfig::parse(
+            r#"{"endpoints":[{"id":"a","url":"http://x"},{"id":"a","url":"http://y"}]}"#
+        )
+        .is_err());
+        let config = WebhookConfig::parse(r#"{"endpoints":[{"id":"a","url":"http://x"}]}"#)
+            .expect("valid config");
+        assert_eq!(config.sync_interval(), Duration::from_secs(5));
+    }
+}
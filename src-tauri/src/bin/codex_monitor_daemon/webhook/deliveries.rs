@@ -0,0 +1,199 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::WebhookEventKind;
+
+pub(crate) const WEBHOOK_DELIVERY_LOG_FILE: &str = "webhook-deliveries.jsonl";
+const DELIVERY_LOG_MAX_BYTES: u64 = 2 * 1024 * 1024;
+const DELIVERY_QUERY_DEFAULT_LIMIT: usize = 100;
+const DELIVERY_QUERY_MAX_LIMIT: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WebhookDeliveryOutcome {
+    Delivered,
+    Failed,
+}
+
+/// One delivery of one event to one endpoint, after all retries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookDeliveryEntry {
+    pub(crate) timestamp_ms: i64,
+    pub(crate) delivery_id: String,
+    pub(crate) endpoint_id: String,
+    pub(crate) event: WebhookEventKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) outcome: WebhookDeliveryOutcome,
+    pub(crate) attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) http_status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+    pub(crate) duration_ms: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct WebhookDeliveryQuery {
+    endpoint_id: Option<String>,
+    outcome: Option<WebhookDeliveryOutcome>,
+    limit: Option<usize>,
+}
+
+impl WebhookDeliveryQuery {
+    pub(crate) fn from_params(params: &Value) -> Result<Self, String> {
+        match params {
+            Value::Null => Ok(Self::default()),
+            value => serde_json::from_value(value.clone()).map_err(|err| err.to_string()),
+        }
+    }
+
+    fn matches(&self, entry: &WebhookDeliveryEntry) -> bool {
+        self.endpoint_id
+            .as_ref()
+            .is_none_or(|endpoint_id| &entry.endpoint_id == endpoint_id)
+            && self.outcome.is_none_or(|outcome| entry.outcome == outcome)
+    }
+}
+
+/// Append-only delivery log; the previous file is kept as `webhook-deliveries.jsonl.1`
+/// once the current one exceeds the size cap.
+pub(crate) struct WebhookDeliveryLog {
+    path: PathBuf,
+    max_bytes: u64,
+}
+
+impl WebhookDeliveryLog {
+    pub(crate) fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(WEBHOOK_DELIVERY_LOG_FILE),
+            max_bytes: DELIVERY_LOG_MAX_BYTES,
+        }
+    }
+
+    fn rotated_path(&self) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(".1");
+        PathBuf::from(name)
+    }
+
+    pub(crate) fn append(&self, entry: &WebhookDeliveryEntry) -> Result<(), String> {
+        let mut line = serde_json::to_string(entry).map_err(|err| err.to_string())?;
+        line.push('\n');
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let current_len = fs::metadata(&self.path).map(|meta| meta.len()).unwrap_or(0);
+        if current_len > 0 && current_len + line.len() as u64 > self.max_bytes {
+            fs::rename(&self.path, self.rotated_path()).map_err(|err| err.to_string())?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .map_err(|err| err.to_string())?;
+        file.write_all(line.as_bytes())
+            .map_err(|err| err.to_string())
+    }
+
+    /// Returns matching entries, newest first, across the current and rotated files.
+    pub(crate) fn query(
+        &self,
+        query: &WebhookDeliveryQuery,
+    ) -> Result<Vec<WebhookDeliveryEntry>, String> {
+        let limit = query
+            .limit
+            .unwrap_or(DELIVERY_QUERY_DEFAULT_LIMIT)
+            .clamp(1, DELIVERY_QUERY_MAX_LIMIT);
+        let mut results = Vec::new();
+        for path in [self.path.clone(), self.rotated_path()] {
+            let file = match fs::File::open(&path) {
+                Ok(file) => file,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(format!("failed to read {}: {err}", path.display())),
+            };
+            let entries = BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| serde_json::from_str::<WebhookDeliveryEntry>(&line).ok())
+                .collect::<Vec<_>>();
+            for entry in entries.into_iter().rev() {
+                if query.matches(&entry) {
+                    results.push(entry);
+                    if results.len() >= limit {
+                        return Ok(results);
+                    }
+                }
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(
+        timestamp_ms: i64,
+        endpoint_id: &str,
+        outcome: WebhookDeliveryOutcome,
+    ) -> WebhookDeliveryEntry {
+        WebhookDeliveryEntry {
+            timestamp_ms,
+            delivery_id: format!("event-{timestamp_ms}:{endpoint_id}"),
+            endpoint_id: endpoint_id.to_string(),
+            event: WebhookEventKind::Signal,
+            workspace_id: None,
+            outcome,
+            attempts: 1,
+            http_status: Some(200),
+            error: None,
+            duration_ms: 1,
+        }
+    }
+
+    #[test]
+    fn log_rotates_and_filters_newest_first() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-webhooks-{}-{}",
+            std::process::id(),
+            uuid::Uuid::new_v4()
+        ));
+        let mut log = WebhookDeliveryLog::new(&dir);
+        log.max_bytes = 500;
+
+        for index in 0..8 {
+            let (endpoint_id, outcome) = if index % 2 == 0 {
+                ("slack", WebhookDeliveryOutcome::Delivered)
+            } else {
+                ("ntfy", WebhookDeliveryOutcome::Failed)
+            };
+            log.append(&entry(index, endpoint_id, outcome))
+                .expect("append");
+        }
+        assert!(log.rotated_path().exists());
+
+        let failed = log
+            .query(
+                &WebhookDeliveryQuery::from_params(&json!({ "outcome": "failed", "limit": 2 }))
+                    .expect("query params"),
+            )
+            .expect("query");
+        let timestamps = failed
+            .iter()
+            .map(|entry| entry.timestamp_ms)
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![7, 5]);
+        assert!(failed.iter().all(|entry| entry.endpoint_id == "ntfy"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
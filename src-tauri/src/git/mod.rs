@@ -6,8 +6,9 @@ use crate::remote_backend;
 use crate::shared::git_ui_core;
 use crate::state::AppState;
 use crate::types::{
-    GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse,
+    GitBlameHunk, GitCommitDiff, GitConflictContents, GitDiffSelection, GitFileDiff,
+    GitHubIssuesResponse, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestsResponse, GitLineRange, GitLogQuery, GitLogResponse, GitStashEntry,
 };
 
 async fn call_remote_if_enabled(
@@ -189,6 +190,57 @@ pub(crate) async fn revert_git_file(
     git_ui_core::revert_git_file_core(&state.workspaces, workspace_id, path).await
 }
 
+#[tauri::command]
+pub(crate) async fn stage_git_hunks(
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "stage_git_hunks",
+        json!({ "workspaceId": &workspace_id, "path": &path, "selection": &selection })
+    );
+    git_ui_core::stage_git_hunks_core(&state.workspaces, workspace_id, path, selection).await
+}
+
+#[tauri::command]
+pub(crate) async fn unstage_git_hunks(
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "unstage_git_hunks",
+        json!({ "workspaceId": &workspace_id, "path": &path, "selection": &selection })
+    );
+    git_ui_core::unstage_git_hunks_core(&state.workspaces, workspace_id, path, selection).await
+}
+
+#[tauri::command]
+pub(crate) async fn revert_git_hunks(
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "revert_git_hunks",
+        json!({ "workspaceId": &workspace_id, "path": &path, "selection": &selection })
+    );
+    git_ui_core::revert_git_hunks_core(&state.workspaces, workspace_id, path, selection).await
+}
+
 #[tauri::command]
 pub(crate) async fn revert_git_all(
     workspace_id: String,
@@ -204,6 +256,209 @@ pub(crate) async fn revert_git_all(
     git_ui_core::revert_git_all_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn list_git_stashes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitStashEntry>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "list_git_stashes",
+        json!({ "workspaceId": &workspace_id }),
+        Vec<GitStashEntry>
+    );
+    git_ui_core::list_git_stashes_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn stash_push(
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: Option<bool>,
+    paths: Option<Vec<String>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let include_untracked = include_untracked.unwrap_or(false);
+    let paths = paths.unwrap_or_default();
+    try_remote_unit!(
+        state,
+        app,
+        "stash_push",
+        json!({
+            "workspaceId": &workspace_id,
+            "message": &message,
+            "includeUntracked": include_untracked,
+            "paths": &paths,
+        })
+    );
+    git_ui_core::stash_push_core(
+        &state.workspaces,
+        workspace_id,
+        message,
+        include_untracked,
+        paths,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn stash_apply(
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "stash_apply",
+        json!({ "workspaceId": &workspace_id, "index": index, "sha": &sha })
+    );
+    git_ui_core::stash_apply_core(&state.workspaces, workspace_id, index, sha).await
+}
+
+#[tauri::command]
+pub(crate) async fn stash_pop(
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "stash_pop",
+        json!({ "workspaceId": &workspace_id, "index": index, "sha": &sha })
+    );
+    git_ui_core::stash_pop_core(&state.workspaces, workspace_id, index, sha).await
+}
+
+#[tauri::command]
+pub(crate) async fn stash_drop(
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "stash_drop",
+        json!({ "workspaceId": &workspace_id, "index": index, "sha": &sha })
+    );
+    git_ui_core::stash_drop_core(&state.workspaces, workspace_id, index, sha).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_stash_diff(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitCommitDiff>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_stash_diff",
+        json!({ "workspaceId": &workspace_id, "index": index }),
+        Vec<GitCommitDiff>
+    );
+    git_ui_core::get_git_stash_diff_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        index,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_conflict_contents(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitConflictContents, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_conflict_contents",
+        json!({ "workspaceId": &workspace_id, "path": &path }),
+        GitConflictContents
+    );
+    git_ui_core::get_git_conflict_contents_core(&state.workspaces, workspace_id, path).await
+}
+
+#[tauri::command]
+pub(crate) async fn mark_git_conflict_resolved(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "mark_git_conflict_resolved",
+        json!({ "workspaceId": &workspace_id, "path": &path })
+    );
+    git_ui_core::mark_git_conflict_resolved_core(&state.workspaces, workspace_id, path).await
+}
+
+#[tauri::command]
+pub(crate) async fn resolve_git_conflict_with_side(
+    workspace_id: String,
+    path: String,
+    side: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "resolve_git_conflict_with_side",
+        json!({ "workspaceId": &workspace_id, "path": &path, "side": &side })
+    );
+    git_ui_core::resolve_git_conflict_with_side_core(&state.workspaces, workspace_id, path, side)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn abort_git_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "abort_git_operation",
+        json!({ "workspaceId": &workspace_id })
+    );
+    git_ui_core::abort_git_operation_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn continue_git_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "continue_git_operation",
+        json!({ "workspaceId": &workspace_id })
+    );
+    git_ui_core::continue_git_operation_core(&state.workspaces, workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn commit_git(
     workspace_id: String,
@@ -330,6 +585,7 @@ pub(crate) async fn get_git_diffs(
 pub(crate) async fn get_git_log(
     workspace_id: String,
     limit: Option<usize>,
+    query: Option<GitLogQuery>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<GitLogResponse, String> {
@@ -337,10 +593,16 @@ pub(crate) async fn get_git_log(
         state,
         app,
         "get_git_log",
-        json!({ "workspaceId": &workspace_id, "limit": limit }),
+        json!({ "workspaceId": &workspace_id, "limit": limit, "query": &query }),
         GitLogResponse
     );
-    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit).await
+    git_ui_core::get_git_log_core(
+        &state.workspaces,
+        workspace_id,
+        limit,
+        query.unwrap_or_default(),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -361,6 +623,38 @@ pub(crate) async fn get_git_commit_diff(
         .await
 }
 
+#[tauri::command]
+pub(crate) async fn get_git_blame(
+    workspace_id: String,
+    path: String,
+    range: Option<GitLineRange>,
+    include_working_tree: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitBlameHunk>, String> {
+    let include_working_tree = include_working_tree.unwrap_or(false);
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_blame",
+        json!({
+            "workspaceId": &workspace_id,
+            "path": &path,
+            "range": &range,
+            "includeWorkingTree": include_working_tree,
+        }),
+        Vec<GitBlameHunk>
+    );
+    git_ui_core::get_git_blame_core(
+        &state.workspaces,
+        workspace_id,
+        path,
+        range,
+        include_working_tree,
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn get_git_remote(
     workspace_id: String,
@@ -124,6 +124,13 @@ pub fn run() {
                                 &state.supervisor_loop,
                                 &state.workspaces,
                                 &state.sessions,
+                                &state.app_settings,
+                                shared::supervisor_core::supervisor_loop::now_timestamp_ms(),
+                            )
+                            .await;
+                            shared::supervisor_core::service::run_job_policy_tick(
+                                &state.supervisor_loop,
+                                &state.sessions,
                                 shared::supervisor_core::supervisor_loop::now_timestamp_ms(),
                             )
                             .await;
@@ -245,12 +252,27 @@ pub fn run() {
             git::get_git_diffs,
             git::get_git_log,
             git::get_git_commit_diff,
+            git::get_git_blame,
             git::get_git_remote,
             git::stage_git_file,
             git::stage_git_all,
             git::unstage_git_file,
             git::revert_git_file,
+            git::stage_git_hunks,
+            git::unstage_git_hunks,
+            git::revert_git_hunks,
             git::revert_git_all,
+            git::list_git_stashes,
+            git::stash_push,
+            git::stash_apply,
+            git::stash_pop,
+            git::stash_drop,
+            git::get_git_stash_diff,
+            git::get_git_conflict_contents,
+            git::mark_git_conflict_resolved,
+            git::resolve_git_conflict_with_side,
+            git::abort_git_operation,
+            git::continue_git_operation,
             git::commit_git,
             git::push_git,
             git::pull_git,
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Tauri event carrying [`RemoteConnectionEvent`] updates to the frontend.
+pub(crate) const REMOTE_CONNECTION_EVENT: &str = "remote-backend-connection";
+
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum RemoteConnectionState {
+    Connected,
+    Reconnecting,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemoteConnectionEvent {
+    pub(crate) state: RemoteConnectionState,
+    /// Reconnect attempt number; 0 for the initial connection.
+    pub(crate) attempt: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) retry_in_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+impl RemoteConnectionEvent {
+    pub(crate) fn connected(attempt: u32) -> Self {
+        Self {
+            state: RemoteConnectionState::Connected,
+            attempt,
+            retry_in_ms: None,
+            error: None,
+        }
+    }
+
+    pub(crate) fn reconnecting(attempt: u32, retry_in: Duration, error: Option<String>) -> Self {
+        Self {
+            state: RemoteConnectionState::Reconnecting,
+            attempt,
+            retry_in_ms: Some(retry_in.as_millis() as u64),
+            error,
+        }
+    }
+}
+
+/// Exponential backoff with jitter: the capped delay for `attempt` is scaled into
+/// `[50%, 100%]` by `jitter` (0.0..=1.0) so clients do not reconnect in lockstep after
+/// a daemon restart.
+pub(crate) fn reconnect_delay(attempt: u32, jitter: f64) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    let capped = RECONNECT_BASE_DELAY
+        .saturating_mul(1 << exponent)
+        .min(RECONNECT_MAX_DELAY);
+    capped.mul_f64(0.5 + 0.5 * jitter.clamp(0.0, 1.0))
+}
+
+pub(crate) fn reconnect_jitter() -> f64 {
+    (uuid::Uuid::new_v4().as_u128() as u64) as f64 / u64::MAX as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reconnect_delay, RemoteConnectionEvent};
+    use std::time::Duration;
+
+    #[test]
+    fn reconnect_delay_doubles_with_jitter_and_caps() {
+        assert_eq!(reconnect_delay(1, 1.0), Duration::from_millis(500));
+        assert_eq!(reconnect_delay(1, 0.0), Duration::from_millis(250));
+        assert_eq!(reconnect_delay(3, 1.0), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(20, 1.0), Duration::from_secs(30));
+        assert_eq!(reconnect_delay(u32::MAX, 0.0), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn connection_event_serializes_for_frontend() {
+        let event = RemoteConnectionEvent::reconnecting(
+            2,
+            Duration::from_millis(750),
+            Some("connection refused".to_string()),
+        );
+        assert_eq!(
+            serde_json::to_value(&event).expect("serialize"),
+            serde_json::json!({
+                "state": "reconnecting",
+                "attempt": 2,
+                "retryInMs": 750,
+                "error": "connection refused",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(RemoteConnectionEvent::connected(0)).expect("serialize"),
+            serde_json::json!({ "state": "connected", "attempt": 0 })
+        );
+    }
+}
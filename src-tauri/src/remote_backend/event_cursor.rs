@@ -0,0 +1,147 @@
+use std::sync::Mutex;
+
+use serde_json::{json, Value};
+
+#[derive(Debug, Default)]
+struct EventPosition {
+    stream_id: Option<String>,
+    last_seq: Option<u64>,
+    /// Set while a reconnect waits for the daemon's `event-resume` notice; live events
+    /// arriving before it are dropped because the replay that follows includes them.
+    resuming: bool,
+}
+
+/// Position in the daemon's sequenced event stream. It outlives individual connections
+/// so a reconnect can `subscribe` from the last delivered event instead of losing
+/// everything emitted while the client was away.
+#[derive(Debug, Default)]
+pub(crate) struct RemoteEventCursor {
+    position: Mutex<EventPosition>,
+}
+
+impl RemoteEventCursor {
+    fn position(&self) -> std::sync::MutexGuard<'_, EventPosition> {
+        self.position.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Records a sequenced event. Returns false for events that should not be delivered:
+    /// duplicates from an overlapping replay, or live events received while resuming.
+    pub(crate) fn observe(&self, seq: u64) -> bool {
+        let mut position = self.position();
+        if position.resuming || position.last_seq.is_some_and(|last_seq| seq <= last_seq) {
+            return false;
+        }
+        position.last_seq = Some(seq);
+        true
+    }
+
+    /// Returns the `subscribe` params for a new connection. With a known position the
+    /// cursor holds back events until the daemon confirms where its replay starts.
+    pub(crate) fn begin_resume(&self) -> Value {
+        let mut position = self.position();
+        match (position.stream_id.clone(), position.last_seq) {
+            (Some(stream_id), Some(last_seq)) => {
+                position.resuming = true;
+                json!({ "streamId": stream_id, "sinceSeq": last_seq })
+            }
+            _ => {
+                position.resuming = false;
+                json!({})
+            }
+        }
+    }
+
+    /// Gives up on resuming, e.g. when the daemon does not support `subscribe`.
+    pub(crate) fn cancel_resume(&self) {
+        self.position().resuming = false;
+    }
+
+    /// Applies the daemon's `event-resume` notice, sent right before replayed events.
+    pub(crate) fn apply_resume(&self, params: &Value) {
+        let mut position = self.position();
+        if let Some(stream_id) = params.get("streamId").and_then(Value::as_str) {
+            position.stream_id = Some(stream_id.to_string());
+        }
+        position.last_seq = params.get("fromSeq").and_then(Value::as_u64);
+        position.resuming = false;
+    }
+
+    /// Adopts the stream id from a live-only `subscribe` response.
+    pub(crate) fn apply_subscribed(&self, result: &Value) {
+        let mut position = self.position();
+        if position.stream_id.is_some() {
+            return;
+        }
+        if let Some(stream_id) = result.get("streamId").and_then(Value::as_str) {
+            position.stream_id = Some(stream_id.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RemoteEventCursor;
+    use serde_json::json;
+
+    #[test]
+    fn first_connection_is_live_only_and_adopts_stream() {
+        let cursor = RemoteEventCursor::default();
+        assert_eq!(cursor.begin_resume(), json!({}));
+
+        assert!(cursor.observe(7));
+        cursor.apply_subscribed(&json!({ "streamId": "stream-a", "latestSeq": 7 }));
+        assert_eq!(
+            cursor.begin_resume(),
+            json!({ "streamId": "stream-a", "sinceSeq": 7 })
+        );
+    }
+
+    #[test]
+    fn replayed_duplicates_are_dropped() {
+        let cursor = RemoteEventCursor::default();
+        assert!(cursor.observe(1));
+        assert!(cursor.observe(2));
+        assert!(!cursor.observe(2));
+        assert!(!cursor.observe(1));
+        assert!(cursor.observe(3));
+    }
+
+    #[test]
+    fn resume_holds_live_events_until_the_replay_starts() {
+        let cursor = RemoteEventCursor::default();
+        assert!(cursor.observe(10));
+        cursor.apply_subscribed(&json!({ "streamId": "stream-a" }));
+
+        cursor.begin_resume();
+        assert!(!cursor.observe(50));
+        cursor.apply_resume(&json!({ "streamId": "stream-a", "fromSeq": 10 }));
+        assert!(cursor.observe(11));
+        assert!(cursor.observe(50));
+        assert!(!cursor.observe(50));
+    }
+
+    #[test]
+    fn resume_after_daemon_restart_starts_over() {
+        let cursor = RemoteEventCursor::default();
+        assert!(cursor.observe(40));
+        cursor.apply_subscribed(&json!({ "streamId": "stream-a" }));
+
+        cursor.begin_resume();
+        cursor.apply_resume(&json!({ "streamId": "stream-b", "fromSeq": 0 }));
+        assert!(cursor.observe(1));
+        assert_eq!(
+            cursor.begin_resume(),
+            json!({ "streamId": "stream-b", "sinceSeq": 1 })
+        );
+    }
+
+    #[test]
+    fn cancel_resume_delivers_events_again() {
+        let cursor = RemoteEventCursor::default();
+        assert!(cursor.observe(3));
+        cursor.apply_subscribed(&json!({ "streamId": "stream-a" }));
+        cursor.begin_resume();
+        cursor.cancel_resume();
+        assert!(cursor.observe(4));
+    }
+}
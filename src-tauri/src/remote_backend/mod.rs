@@ -1,25 +1,46 @@
+mod connection;
+mod event_cursor;
 mod protocol;
+mod stdio_transport;
 mod tcp_transport;
+mod tls;
 mod transport;
+mod unix_transport;
 
 use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex, Weak};
 use std::time::Duration;
 
-use tauri::AppHandle;
-use tokio::sync::Mutex;
-use tokio::time::timeout;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{watch, Notify};
+use tokio::time::{timeout, timeout_at, Instant};
 
 use crate::state::AppState;
 use crate::types::BackendMode;
 
+use self::connection::{
+    reconnect_delay, reconnect_jitter, RemoteConnectionEvent, REMOTE_CONNECTION_EVENT,
+};
+pub(crate) use self::event_cursor::RemoteEventCursor;
 use self::protocol::{build_request_line, DEFAULT_REMOTE_HOST, DISCONNECTED_MESSAGE};
+use self::stdio_transport::StdioTransport;
 use self::tcp_transport::TcpTransport;
-use self::transport::{PendingMap, RemoteTransport, RemoteTransportConfig, RemoteTransportKind};
+use self::transport::{
+    RemoteTransport, RemoteTransportConfig, RemoteTransportKind, TransportConnection,
+};
+use self::unix_transport::UnixTransport;
 
 const REMOTE_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
 const REMOTE_SEND_TIMEOUT: Duration = Duration::from_secs(15);
+/// How long retry-safe requests wait for a dropped connection to come back.
+const REMOTE_RECONNECT_WAIT: Duration = Duration::from_secs(30);
+/// Remote host prefixes selecting a non-TCP transport, e.g. `unix:/run/codex-monitor.sock`
+/// or `stdio:ssh host codex-monitor-daemon --stdio`.
+const UNIX_HOST_PREFIX: &str = "unix:";
+const STDIO_HOST_PREFIX: &str = "stdio:";
 
 pub(crate) fn normalize_path_for_remote(path: String) -> String {
     let trimmed = path.trim();
@@ -61,31 +82,156 @@ pub(crate) struct RemoteBackend {
 }
 
 struct RemoteBackendInner {
-    out_tx: tokio::sync::mpsc::Sender<String>,
-    pending: Arc<Mutex<PendingMap>>,
+    app: AppHandle,
+    config: RemoteTransportConfig,
+    events: Arc<RemoteEventCursor>,
+    connection: StdMutex<Option<Arc<TransportConnection>>>,
+    state: watch::Sender<RemoteConnectionEvent>,
     next_id: AtomicU64,
-    connected: Arc<std::sync::atomic::AtomicBool>,
+    /// Threads attached with `thread_live_subscribe`, re-attached after a reconnect.
+    live_threads: StdMutex<HashSet<(String, String)>>,
 }
 
 impl RemoteBackend {
+    async fn connect(
+        app: AppHandle,
+        config: RemoteTransportConfig,
+        events: Arc<RemoteEventCursor>,
+    ) -> Result<Self, String> {
+        let (state, _) = watch::channel(RemoteConnectionEvent::connected(0));
+        let inner = Arc::new(RemoteBackendInner {
+            app,
+            config,
+            events,
+            connection: StdMutex::new(None),
+            state,
+            next_id: AtomicU64::new(1),
+            live_threads: StdMutex::new(HashSet::new()),
+        });
+        let connection = inner.open_connection().await?;
+        let closed = Arc::clone(&connection.closed);
+        inner.install(connection, RemoteConnectionEvent::connected(0));
+        tokio::spawn(supervise_connection(Arc::downgrade(&inner), closed));
+        Ok(Self { inner })
+    }
+
+    /// Sends a request over the current connection. While the backend is reconnecting,
+    /// retry-safe methods wait for the new connection; everything else fails fast since
+    /// the daemon may or may not have applied it.
     pub(crate) async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
-        if !self.inner.connected.load(Ordering::SeqCst) {
+        let retry_safe = can_retry_after_disconnect(method);
+        let deadline = Instant::now() + REMOTE_RECONNECT_WAIT;
+        loop {
+            let result = match self.inner.current_connection() {
+                Some(connection) => {
+                    self.inner
+                        .request(&connection, method, params.clone())
+                        .await
+                }
+                None => Err(DISCONNECTED_MESSAGE.to_string()),
+            };
+            match result {
+                Err(err) if err == DISCONNECTED_MESSAGE && retry_safe => {
+                    self.inner.wait_until_connected(deadline).await?;
+                }
+                result => {
+                    if result.is_ok() {
+                        self.inner.track_live_thread(method, &params);
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+impl RemoteBackendInner {
+    /// Connects, authenticates and re-establishes the event subscription and live
+    /// threads, without exposing the connection to callers until all of that succeeded.
+    async fn open_connection(&self) -> Result<Arc<TransportConnection>, String> {
+        let transport: Box<dyn RemoteTransport> = match self.config.kind() {
+            RemoteTransportKind::Tcp => Box::new(TcpTransport),
+            RemoteTransportKind::Unix => Box::new(UnixTransport),
+            RemoteTransportKind::Stdio => Box::new(StdioTransport),
+        };
+        let subscribe_params = self.events.begin_resume();
+        let connection = match transport
+            .connect(
+                self.app.clone(),
+                self.config.clone(),
+                Arc::clone(&self.events),
+            )
+            .await
+        {
+            Ok(connection) => Arc::new(connection),
+            Err(err) => {
+                self.events.cancel_resume();
+                return Err(err);
+            }
+        };
+
+        if let Some(token) = self.config.auth_token() {
+            if let Err(err) = self
+                .request(&connection, "auth", json!({ "token": token }))
+                .await
+            {
+                self.events.cancel_resume();
+                return Err(err);
+            }
+        }
+
+        match self
+            .request(&connection, "subscribe", subscribe_params)
+            .await
+        {
+            Ok(result) => self.events.apply_subscribed(&result),
+            Err(err) => {
+                self.events.cancel_resume();
+                // A command transport (e.g. ssh) that exits during setup never connected.
+                if !connection.connected.load(Ordering::SeqCst) {
+                    return Err(connection.describe_error(err).await);
+                }
+                // Daemons without event replay reject `subscribe`; keep their live-only stream.
+            }
+        }
+
+        let live_threads: Vec<(String, String)> = self.live_threads().iter().cloned().collect();
+        for (workspace_id, thread_id) in live_threads {
+            let params = json!({ "workspaceId": workspace_id, "threadId": thread_id });
+            if let Err(err) = self
+                .request(&connection, "thread_live_subscribe", params)
+                .await
+            {
+                eprintln!("remote backend failed to re-attach thread {thread_id}: {err}");
+            }
+        }
+
+        Ok(connection)
+    }
+
+    async fn request(
+        &self,
+        connection: &TransportConnection,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, String> {
+        if !connection.connected.load(Ordering::SeqCst) {
             return Err(DISCONNECTED_MESSAGE.to_string());
         }
 
-        let id = self.inner.next_id.fetch_add(1, Ordering::SeqCst);
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = tokio::sync::oneshot::channel();
-        self.inner.pending.lock().await.insert(id, tx);
+        connection.pending.lock().await.insert(id, tx);
 
         let message = build_request_line(id, method, params)?;
-        match timeout(REMOTE_SEND_TIMEOUT, self.inner.out_tx.send(message)).await {
+        match timeout(REMOTE_SEND_TIMEOUT, connection.out_tx.send(message)).await {
             Ok(Ok(())) => {}
             Ok(Err(_)) => {
-                self.inner.pending.lock().await.remove(&id);
+                connection.pending.lock().await.remove(&id);
                 return Err(DISCONNECTED_MESSAGE.to_string());
             }
             Err(_) => {
-                self.inner.pending.lock().await.remove(&id);
+                connection.pending.lock().await.remove(&id);
                 return Err(format!(
                     "remote backend request dispatch timed out after {} seconds",
                     REMOTE_SEND_TIMEOUT.as_secs()
@@ -97,7 +243,7 @@ impl RemoteBackend {
             Ok(Ok(result)) => result,
             Ok(Err(_)) => Err(DISCONNECTED_MESSAGE.to_string()),
             Err(_) => {
-                self.inner.pending.lock().await.remove(&id);
+                connection.pending.lock().await.remove(&id);
                 Err(format!(
                     "remote backend request timed out after {} seconds",
                     REMOTE_REQUEST_TIMEOUT.as_secs()
@@ -105,6 +251,105 @@ impl RemoteBackend {
             }
         }
     }
+
+    fn current_connection(&self) -> Option<Arc<TransportConnection>> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .as_ref()
+            .filter(|connection| connection.connected.load(Ordering::SeqCst))
+            .cloned()
+    }
+
+    fn install(&self, connection: Arc<TransportConnection>, event: RemoteConnectionEvent) {
+        *self
+            .connection
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = Some(connection);
+        self.set_state(event);
+    }
+
+    fn set_state(&self, event: RemoteConnectionEvent) {
+        let _ = self.app.emit(REMOTE_CONNECTION_EVENT, &event);
+        self.state.send_replace(event);
+    }
+
+    async fn wait_until_connected(&self, deadline: Instant) -> Result<(), String> {
+        let mut state = self.state.subscribe();
+        let reconnected = timeout_at(
+            deadline,
+            state.wait_for(|_| self.current_connection().is_some()),
+        )
+        .await
+        .is_ok_and(|result| result.is_ok());
+        if reconnected {
+            Ok(())
+        } else {
+            Err(DISCONNECTED_MESSAGE.to_string())
+        }
+    }
+
+    fn live_threads(&self) -> std::sync::MutexGuard<'_, HashSet<(String, String)>> {
+        self.live_threads
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn track_live_thread(&self, method: &str, params: &Value) {
+        let subscribe = match method {
+            "thread_live_subscribe" => true,
+            "thread_live_unsubscribe" => false,
+            _ => return,
+        };
+        let field = |key: &str| params.get(key).and_then(Value::as_str).map(str::to_string);
+        let (Some(workspace_id), Some(thread_id)) = (field("workspaceId"), field("threadId"))
+        else {
+            return;
+        };
+        let mut live_threads = self.live_threads();
+        if subscribe {
+            live_threads.insert((workspace_id, thread_id));
+        } else {
+            live_threads.remove(&(workspace_id, thread_id));
+        }
+    }
+}
+
+/// Waits for the current connection to drop and reconnects with jittered backoff until
+/// it succeeds or the backend is dropped (e.g. after a settings change).
+async fn supervise_connection(inner: Weak<RemoteBackendInner>, mut closed: Arc<Notify>) {
+    loop {
+        closed.notified().await;
+        let mut last_error = None;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let delay = reconnect_delay(attempt, reconnect_jitter());
+            {
+                let Some(inner) = inner.upgrade() else {
+                    return;
+                };
+                inner.set_state(RemoteConnectionEvent::reconnecting(
+                    attempt,
+                    delay,
+                    last_error.take(),
+                ));
+            }
+            tokio::time::sleep(delay).await;
+
+            let Some(inner) = inner.upgrade() else {
+                return;
+            };
+            match inner.open_connection().await {
+                Ok(connection) => {
+                    closed = Arc::clone(&connection.closed);
+                    inner.install(connection, RemoteConnectionEvent::connected(attempt));
+                    break;
+                }
+                Err(err) => last_error = Some(err),
+            }
+        }
+    }
 }
 
 pub(crate) async fn is_remote_mode(state: &AppState) -> bool {
@@ -118,28 +363,8 @@ pub(crate) async fn call_remote(
     method: &str,
     params: Value,
 ) -> Result<Value, String> {
-    let client = ensure_remote_backend(state, app.clone()).await?;
-    match client.call(method, params.clone()).await {
-        Ok(value) => Ok(value),
-        Err(err) if err == DISCONNECTED_MESSAGE => {
-            *state.remote_backend.lock().await = None;
-            if !can_retry_after_disconnect(method) {
-                return Err(err);
-            }
-            let retry_client = ensure_remote_backend(state, app).await?;
-            match retry_client.call(method, params).await {
-                Ok(value) => Ok(value),
-                Err(retry_err) => {
-                    *state.remote_backend.lock().await = None;
-                    Err(retry_err)
-                }
-            }
-        }
-        Err(err) => {
-            *state.remote_backend.lock().await = None;
-            Err(err)
-        }
-    }
+    let client = ensure_remote_backend(state, app).await?;
+    client.call(method, params).await
 }
 
 fn can_retry_after_disconnect(method: &str) -> bool {
@@ -154,10 +379,13 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "file_read"
             | "get_agents_settings"
             | "get_config_model"
+            | "get_git_blame"
             | "get_git_commit_diff"
+            | "get_git_conflict_contents"
             | "get_git_diffs"
             | "get_git_log"
             | "get_git_remote"
+            | "get_git_stash_diff"
             | "get_git_status"
             | "get_github_issues"
             | "get_github_pull_request_comments"
@@ -166,6 +394,7 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "is_workspace_path_dir"
             | "list_git_branches"
             | "list_git_roots"
+            | "list_git_stashes"
             | "list_mcp_server_status"
             | "list_threads"
             | "local_usage_snapshot"
@@ -194,31 +423,12 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
         let settings = state.app_settings.lock().await;
         resolve_transport_config(&settings)?
     };
-    let transport_kind = transport_config.kind();
-    let auth_token = transport_config.auth_token().map(|value| value.to_string());
-
-    let transport: Box<dyn RemoteTransport> = match transport_config.kind() {
-        RemoteTransportKind::Tcp => Box::new(TcpTransport),
-    };
-    let connection = transport.connect(app, transport_config).await?;
-
-    let client = RemoteBackend {
-        inner: Arc::new(RemoteBackendInner {
-            out_tx: connection.out_tx,
-            pending: connection.pending,
-            next_id: AtomicU64::new(1),
-            connected: connection.connected,
-        }),
-    };
-
-    if matches!(transport_kind, RemoteTransportKind::Tcp) {
-        if let Some(token) = auth_token {
-            client
-                .call("auth", json!({ "token": token }))
-                .await
-                .map(|_| ())?;
-        }
-    }
+    let client = RemoteBackend::connect(
+        app,
+        transport_config,
+        Arc::clone(&state.remote_event_cursor),
+    )
+    .await?;
 
     {
         let mut guard = state.remote_backend.lock().await;
@@ -231,14 +441,45 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
 fn resolve_transport_config(
     settings: &crate::types::AppSettings,
 ) -> Result<RemoteTransportConfig, String> {
-    let host = if settings.remote_backend_host.trim().is_empty() {
+    let raw_host = settings.remote_backend_host.trim();
+    if let Some(path) = raw_host.strip_prefix(UNIX_HOST_PREFIX) {
+        let path = path.trim();
+        if path.is_empty() {
+            return Err("Remote backend host `unix:` requires a socket path".to_string());
+        }
+        return Ok(RemoteTransportConfig::Unix {
+            path: PathBuf::from(path),
+        });
+    }
+    if let Some(command) = raw_host.strip_prefix(STDIO_HOST_PREFIX) {
+        let mut parts = shell_words::split(command)
+            .map_err(|err| format!("Invalid remote backend stdio command: {err}"))?
+            .into_iter();
+        let program = parts
+            .next()
+            .ok_or("Remote backend host `stdio:` requires a command")?;
+        return Ok(RemoteTransportConfig::Stdio {
+            program,
+            args: parts.collect(),
+        });
+    }
+
+    let host = if raw_host.is_empty() {
         DEFAULT_REMOTE_HOST.to_string()
     } else {
         settings.remote_backend_host.clone()
     };
+    let tls_fingerprint = settings
+        .active_remote_tls_fingerprint()
+        .map(|value| {
+            tls::normalize_fingerprint(value)
+                .ok_or_else(|| format!("Invalid remote backend TLS fingerprint: {value}"))
+        })
+        .transpose()?;
     Ok(RemoteTransportConfig::Tcp {
         host,
         auth_token: settings.remote_backend_token.clone(),
+        tls_fingerprint,
     })
 }
 
@@ -246,7 +487,7 @@ fn resolve_transport_config(
 mod tests {
     use super::{can_retry_after_disconnect, resolve_transport_config};
     use crate::remote_backend::transport::RemoteTransportConfig;
-    use crate::types::AppSettings;
+    use crate::types::{AppSettings, RemoteBackendProvider, RemoteBackendTarget};
 
     #[test]
     fn resolve_tcp_transport_uses_remote_host() {
@@ -260,6 +501,70 @@ mod tests {
         assert_eq!(host, "tcp.example:4732");
     }
 
+    #[test]
+    fn resolve_tcp_transport_pins_active_target_fingerprint() {
+        let mut settings = AppSettings::default();
+        settings.remote_backends = vec![RemoteBackendTarget {
+            id: "remote-1".to_string(),
+            name: "Remote".to_string(),
+            provider: RemoteBackendProvider::Tcp,
+            host: "tcp.example:4732".to_string(),
+            token: None,
+            tls_fingerprint: Some(format!("sha256:{}", "ab".repeat(32))),
+            last_connected_at_ms: None,
+        }];
+        settings.active_remote_backend_id = Some("remote-1".to_string());
+
+        let config = resolve_transport_config(&settings).expect("transport config");
+        let RemoteTransportConfig::Tcp {
+            tls_fingerprint, ..
+        } = config
+        else {
+            panic!("expected tcp transport config");
+        };
+        assert_eq!(tls_fingerprint, Some(vec!["AB"; 32].join(":")));
+
+        settings.remote_backends[0].tls_fingerprint = Some("not-a-fingerprint".to_string());
+        assert!(resolve_transport_config(&settings).is_err());
+    }
+
+    #[test]
+    fn resolve_unix_transport_from_host_prefix() {
+        let mut settings = AppSettings::default();
+        settings.remote_backend_host = "unix:/tmp/codex-monitor.sock".to_string();
+        settings.remote_backend_token = Some("ignored".to_string());
+
+        let config = resolve_transport_config(&settings).expect("transport config");
+        assert_eq!(config.auth_token(), None);
+        let RemoteTransportConfig::Unix { path } = config else {
+            panic!("expected unix transport config");
+        };
+        assert_eq!(path, std::path::PathBuf::from("/tmp/codex-monitor.sock"));
+    }
+
+    #[test]
+    fn resolve_stdio_transport_splits_command() {
+        let mut settings = AppSettings::default();
+        settings.remote_backend_host =
+            "stdio:ssh dev-box 'codex-monitor-daemon' --stdio".to_string();
+
+        let config = resolve_transport_config(&settings).expect("transport config");
+        let RemoteTransportConfig::Stdio { program, args } = config else {
+            panic!("expected stdio transport config");
+        };
+        assert_eq!(program, "ssh");
+        assert_eq!(args, vec!["dev-box", "codex-monitor-daemon", "--stdio"]);
+    }
+
+    #[test]
+    fn resolve_transport_rejects_empty_prefixed_hosts() {
+        let mut settings = AppSettings::default();
+        settings.remote_backend_host = "unix:".to_string();
+        assert!(resolve_transport_config(&settings).is_err());
+        settings.remote_backend_host = "stdio:  ".to_string();
+        assert!(resolve_transport_config(&settings).is_err());
+    }
+
     #[test]
     fn retries_only_retry_safe_methods_after_disconnect() {
         assert!(can_retry_after_disconnect("resume_thread"));
@@ -11,6 +11,8 @@ pub(crate) enum IncomingMessage {
     Notification {
         method: String,
         params: Value,
+        /// Daemon event sequence number, absent on daemons without event replay.
+        seq: Option<u64>,
     },
 }
 
@@ -57,5 +59,6 @@ pub(crate) fn parse_incoming_line(line: &str) -> Option<IncomingMessage> {
     Some(IncomingMessage::Notification {
         method: method.to_string(),
         params,
+        seq: message.get("seq").and_then(|value| value.as_u64()),
     })
 }
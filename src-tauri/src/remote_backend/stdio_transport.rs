@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use tauri::AppHandle;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::ChildStderr;
+use tokio::sync::watch;
+use tokio::time::timeout;
+
+use crate::shared::process_core::tokio_command;
+
+use super::event_cursor::RemoteEventCursor;
+use super::transport::{
+    spawn_transport_io, RemoteTransport, RemoteTransportConfig, TransportFuture,
+};
+
+const STDERR_TAIL_LINES: usize = 20;
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub(crate) struct StdioTransport;
+
+/// Last lines the remote backend command wrote to stderr, such as ssh authentication or
+/// host key errors, so a failed connect can say why.
+pub(crate) struct StderrTail {
+    lines: StdMutex<VecDeque<String>>,
+    finished: watch::Sender<bool>,
+}
+
+impl StderrTail {
+    fn spawn(stderr: ChildStderr) -> Arc<Self> {
+        let tail = Arc::new(Self {
+            lines: StdMutex::new(VecDeque::new()),
+            finished: watch::channel(false).0,
+        });
+        let collector = Arc::clone(&tail);
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let mut tail = collector.lines();
+                if tail.len() == STDERR_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line.to_string());
+            }
+            collector.finished.send_replace(true);
+        });
+        tail
+    }
+
+    fn lines(&self) -> std::sync::MutexGuard<'_, VecDeque<String>> {
+        self.lines.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Waits briefly for the command to close stderr, then returns what it printed.
+    pub(crate) async fn text(&self) -> String {
+        let mut finished = self.finished.subscribe();
+        let _ = timeout(STDERR_DRAIN_TIMEOUT, finished.wait_for(|done| *done)).await;
+        self.lines().iter().cloned().collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl RemoteTransport for StdioTransport {
+    fn connect(
+        &self,
+        app: AppHandle,
+        config: RemoteTransportConfig,
+        events: Arc<RemoteEventCursor>,
+    ) -> TransportFuture {
+        Box::pin(async move {
+            let RemoteTransportConfig::Stdio { program, args } = config else {
+                return Err("Stdio transport requires a command".to_string());
+            };
+
+            let mut command = tokio_command(&program);
+            command.args(&args);
+            command.stdin(Stdio::piped());
+            command.stdout(Stdio::piped());
+            command.stderr(Stdio::piped());
+            command.kill_on_drop(true);
+
+            let mut child = command.spawn().map_err(|err| {
+                format!("Failed to start remote backend command {program}: {err}")
+            })?;
+            let stdin = child
+                .stdin
+                .take()
+                .ok_or("remote backend command is missing stdin")?;
+            let stdout = child
+                .stdout
+                .take()
+                .ok_or("remote backend command is missing stdout")?;
+            let stderr = child
+                .stderr
+                .take()
+                .ok_or("remote backend command is missing stderr")?;
+
+            // The reader sees EOF once the command exits; closing stdin (when the
+            // backend is dropped) asks a `--stdio` daemon to shut down.
+            tokio::spawn(async move {
+                let _ = child.wait().await;
+            });
+
+            let mut connection = spawn_transport_io(app, stdout, stdin, events);
+            connection.stderr = Some(StderrTail::spawn(stderr));
+            Ok(connection)
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::StderrTail;
+    use std::process::Stdio;
+    use tokio::process::Command;
+
+    #[test]
+    fn stderr_tail_keeps_what_the_command_printed() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let mut child = Command::new("sh")
+                .args(["-c", "echo 'Permission denied (publickey).' >&2; exit 255"])
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("spawn");
+            let tail = StderrTail::spawn(child.stderr.take().expect("stderr"));
+            let _ = child.wait().await;
+            assert_eq!(tail.text().await, "Permission denied (publickey).");
+        });
+    }
+}
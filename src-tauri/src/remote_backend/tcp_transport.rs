@@ -1,22 +1,55 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use tauri::AppHandle;
 use tokio::net::TcpStream;
+use tokio::time::timeout;
 
+use super::event_cursor::RemoteEventCursor;
+use super::tls::{pinned_connector, server_name_for_host};
 use super::transport::{
     spawn_transport_io, RemoteTransport, RemoteTransportConfig, TransportFuture,
 };
 
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub(crate) struct TcpTransport;
 
 impl RemoteTransport for TcpTransport {
-    fn connect(&self, app: AppHandle, config: RemoteTransportConfig) -> TransportFuture {
+    fn connect(
+        &self,
+        app: AppHandle,
+        config: RemoteTransportConfig,
+        events: Arc<RemoteEventCursor>,
+    ) -> TransportFuture {
         Box::pin(async move {
-            let RemoteTransportConfig::Tcp { host, .. } = config;
+            let RemoteTransportConfig::Tcp {
+                host,
+                tls_fingerprint,
+                ..
+            } = config
+            else {
+                return Err("TCP transport requires a TCP transport config".to_string());
+            };
 
             let stream = TcpStream::connect(host.clone())
                 .await
                 .map_err(|err| format!("Failed to connect to remote backend at {host}: {err}"))?;
-            let (reader, writer) = stream.into_split();
-            Ok(spawn_transport_io(app, reader, writer))
+
+            let Some(fingerprint) = tls_fingerprint else {
+                let (reader, writer) = stream.into_split();
+                return Ok(spawn_transport_io(app, reader, writer, events));
+            };
+            let connector = pinned_connector(&fingerprint)?;
+            let tls_stream = timeout(
+                TLS_HANDSHAKE_TIMEOUT,
+                connector.connect(server_name_for_host(&host), stream),
+            )
+            .await
+            .map_err(|_| format!("TLS handshake with remote backend at {host} timed out"))?
+            .map_err(|err| format!("TLS handshake with remote backend at {host} failed: {err}"))?;
+            let (reader, writer) = tokio::io::split(tls_stream);
+            Ok(spawn_transport_io(app, reader, writer, events))
         })
     }
 }
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, Error as TlsError, SignatureScheme};
+use tokio_rustls::TlsConnector;
+
+use crate::shared::tls_core::certificate_fingerprint;
+
+const FALLBACK_SERVER_NAME: &str = "codex-monitor-daemon";
+
+/// Accepts exactly one server certificate, identified by its SHA-256 fingerprint.
+///
+/// The daemon uses a self-signed certificate, so chain and hostname checks are replaced by the
+/// pin; handshake signatures are still verified against the pinned certificate's key.
+#[derive(Debug)]
+struct PinnedCertificateVerifier {
+    fingerprint: String,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertificateVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        let actual = certificate_fingerprint(end_entity.as_ref());
+        if actual == self.fingerprint {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(TlsError::General(format!(
+                "daemon certificate fingerprint {actual} does not match pinned {}",
+                self.fingerprint
+            )))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+/// Normalizes a user-supplied fingerprint (`sha256:` prefix, any case, with or without colons).
+pub(crate) fn normalize_fingerprint(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let without_prefix = trimmed
+        .get(..7)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("sha256:"))
+        .map(|_| &trimmed[7..])
+        .unwrap_or(trimmed);
+    let hex = without_prefix
+        .chars()
+        .filter(|ch| *ch != ':' && !ch.is_whitespace())
+        .collect::<String>();
+    if hex.len() != 64 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_uppercase();
+    Some(
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).into_owned())
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
+
+pub(crate) fn pinned_connector(fingerprint: &str) -> Result<TlsConnector, String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let verifier = Arc::new(PinnedCertificateVerifier {
+        fingerprint: fingerprint.to_string(),
+        provider: Arc::clone(&provider),
+    });
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| err.to_string())?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier)
+        .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// SNI name for `host:port`; the pin does not depend on it, so unparsable hosts fall back.
+pub(crate) fn server_name_for_host(host: &str) -> ServerName<'static> {
+    let hostname = host
+        .rsplit_once(':')
+        .map(|(name, _port)| name)
+        .unwrap_or(host)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    ServerName::try_from(hostname.to_string())
+        .or_else(|_| ServerName::try_from(FALLBACK_SERVER_NAME))
+        .expect("fallback server name is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_fingerprint, pinned_connector, server_name_for_host};
+    use crate::shared::tls_core::certificate_fingerprint;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName};
+    use rustls::ServerConfig;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsAcceptor;
+
+    const FINGERPRINT: &str =
+        "BA:78:16:BF:8F:01:CF:EA:41:41:40:DE:5D:AE:22:23:B0:03:61:A3:96:17:7A:9C:B4:10:FF:61:F2:00:15:AD";
+
+    fn test_acceptor() -> (TlsAcceptor, String) {
+        let generated = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+            .expect("generate certificate");
+        let cert = generated.cert.der().to_vec();
+        let fingerprint = certificate_fingerprint(&cert);
+        let config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .expect("protocol versions")
+                .with_no_client_auth()
+                .with_single_cert(
+                    vec![CertificateDer::from(cert)],
+                    PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+                        generated.key_pair.serialize_der(),
+                    )),
+                )
+                .expect("server config");
+        (TlsAcceptor::from(Arc::new(config)), fingerprint)
+    }
+
+    async fn handshake(acceptor: TlsAcceptor, pinned: &str) -> Result<Vec<u8>, String> {
+        let (client, server) = tokio::io::duplex(16 * 1024);
+        let server_task = tokio::spawn(async move {
+            if let Ok(mut stream) = acceptor.accept(server).await {
+                let _ = stream.write_all(b"pong").await;
+                let _ = stream.flush().await;
+            }
+        });
+        let connector = pinned_connector(pinned)?;
+        let mut stream = connector
+            .connect(server_name_for_host("127.0.0.1:4732"), client)
+            .await
+            .map_err(|err| err.to_string())?;
+        let mut buffer = vec![0u8; 4];
+        stream
+            .read_exact(&mut buffer)
+            .await
+            .map_err(|err| err.to_string())?;
+        let _ = server_task.await;
+        Ok(buffer)
+    }
+
+    fn run_async<F: std::future::Future<Output = ()>>(future: F) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime")
+            .block_on(future);
+    }
+
+    #[test]
+    fn normalize_fingerprint_accepts_common_formats() {
+        let bare = FINGERPRINT.replace(':', "").to_ascii_lowercase();
+        assert_eq!(normalize_fingerprint(&bare).as_deref(), Some(FINGERPRINT));
+        assert_eq!(
+            normalize_fingerprint(&format!("SHA256:{FINGERPRINT}")).as_deref(),
+            Some(FINGERPRINT)
+        );
+        assert_eq!(normalize_fingerprint("AB:CD"), None);
+        assert_eq!(normalize_fingerprint(&"zz".repeat(32)), None);
+    }
+
+    #[test]
+    fn server_name_uses_host_or_falls_back() {
+        assert_eq!(
+            server_name_for_host("daemon.local:4732"),
+            ServerName::try_from("daemon.local").expect("name")
+        );
+        assert!(matches!(
+            server_name_for_host("[::1]:4732"),
+            ServerName::IpAddress(_)
+        ));
+        assert_eq!(
+            server_name_for_host("bad host:1"),
+            ServerName::try_from("codex-monitor-daemon").expect("name")
+        );
+    }
+
+    #[test]
+    fn pinned_connector_accepts_only_matching_certificate() {
+        run_async(async {
+            let (acceptor, fingerprint) = test_acceptor();
+            let payload = handshake(acceptor, &fingerprint)
+                .await
+                .expect("pinned handshake");
+            assert_eq!(payload, b"pong");
+
+            let (acceptor, _fingerprint) = test_acceptor();
+            let error = handshake(acceptor, FINGERPRINT)
+                .await
+                .expect_err("mismatched pin is rejected");
+            assert!(error.contains("does not match pinned"));
+        });
+    }
+}
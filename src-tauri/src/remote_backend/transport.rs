@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::future::Future;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -7,9 +8,11 @@ use std::sync::Arc;
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
-use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::sync::{mpsc, oneshot, Mutex, Notify};
 
+use super::event_cursor::RemoteEventCursor;
 use super::protocol::{parse_incoming_line, IncomingMessage, DISCONNECTED_MESSAGE};
+use super::stdio_transport::StderrTail;
 
 pub(crate) type PendingMap = HashMap<u64, oneshot::Sender<Result<Value, String>>>;
 const OUTBOUND_QUEUE_CAPACITY: usize = 512;
@@ -19,24 +22,36 @@ pub(crate) enum RemoteTransportConfig {
     Tcp {
         host: String,
         auth_token: Option<String>,
+        /// Pinned daemon certificate fingerprint; enables TLS when set.
+        tls_fingerprint: Option<String>,
     },
+    /// Local daemon socket; access is controlled by the socket file permissions.
+    Unix { path: PathBuf },
+    /// Daemon spawned as a child process speaking the protocol over stdin/stdout,
+    /// e.g. `ssh host codex-monitor-daemon --stdio`.
+    Stdio { program: String, args: Vec<String> },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) enum RemoteTransportKind {
     Tcp,
+    Unix,
+    Stdio,
 }
 
 impl RemoteTransportConfig {
     pub(crate) fn kind(&self) -> RemoteTransportKind {
         match self {
             RemoteTransportConfig::Tcp { .. } => RemoteTransportKind::Tcp,
+            RemoteTransportConfig::Unix { .. } => RemoteTransportKind::Unix,
+            RemoteTransportConfig::Stdio { .. } => RemoteTransportKind::Stdio,
         }
     }
 
     pub(crate) fn auth_token(&self) -> Option<&str> {
         match self {
             RemoteTransportConfig::Tcp { auth_token, .. } => auth_token.as_deref(),
+            RemoteTransportConfig::Unix { .. } | RemoteTransportConfig::Stdio { .. } => None,
         }
     }
 }
@@ -45,19 +60,44 @@ pub(crate) struct TransportConnection {
     pub(crate) out_tx: mpsc::Sender<String>,
     pub(crate) pending: Arc<Mutex<PendingMap>>,
     pub(crate) connected: Arc<AtomicBool>,
+    /// Notified once the connection is lost, so the backend can reconnect.
+    pub(crate) closed: Arc<Notify>,
+    /// Stderr of a spawned backend command; reported when the connection fails.
+    pub(crate) stderr: Option<Arc<StderrTail>>,
+}
+
+impl TransportConnection {
+    /// Appends what a spawned backend command printed to stderr to a connect error.
+    pub(crate) async fn describe_error(&self, err: String) -> String {
+        let Some(stderr) = &self.stderr else {
+            return err;
+        };
+        let output = stderr.text().await;
+        if output.is_empty() {
+            err
+        } else {
+            format!("{err}: {output}")
+        }
+    }
 }
 
 pub(crate) type TransportFuture =
     Pin<Box<dyn Future<Output = Result<TransportConnection, String>> + Send>>;
 
 pub(crate) trait RemoteTransport: Send + Sync {
-    fn connect(&self, app: AppHandle, config: RemoteTransportConfig) -> TransportFuture;
+    fn connect(
+        &self,
+        app: AppHandle,
+        config: RemoteTransportConfig,
+        events: Arc<RemoteEventCursor>,
+    ) -> TransportFuture;
 }
 
 pub(crate) fn spawn_transport_io<R, W>(
     app: AppHandle,
     reader: R,
     mut writer: W,
+    events: Arc<RemoteEventCursor>,
 ) -> TransportConnection
 where
     R: AsyncRead + Unpin + Send + 'static,
@@ -71,26 +111,44 @@ where
     let connected = Arc::new(AtomicBool::new(true));
     let connected_for_writer = Arc::clone(&connected);
     let connected_for_reader = Arc::clone(&connected);
+    let closed = Arc::new(Notify::new());
+    let closed_for_writer = Arc::clone(&closed);
+    let closed_for_reader = Arc::clone(&closed);
 
     tokio::spawn(async move {
         while let Some(message) = out_rx.recv().await {
             if writer.write_all(message.as_bytes()).await.is_err()
                 || writer.write_all(b"\n").await.is_err()
             {
-                mark_disconnected(&pending_for_writer, &connected_for_writer).await;
+                mark_disconnected(
+                    &pending_for_writer,
+                    &connected_for_writer,
+                    &closed_for_writer,
+                )
+                .await;
                 break;
             }
         }
     });
 
     tokio::spawn(async move {
-        read_loop(app, reader, pending_for_reader, connected_for_reader).await;
+        read_loop(
+            app,
+            reader,
+            pending_for_reader,
+            connected_for_reader,
+            closed_for_reader,
+            events,
+        )
+        .await;
     });
 
     TransportConnection {
         out_tx,
         pending,
         connected,
+        closed,
+        stderr: None,
     }
 }
 
@@ -99,6 +157,8 @@ async fn read_loop<R>(
     reader: R,
     pending: Arc<Mutex<PendingMap>>,
     connected: Arc<AtomicBool>,
+    closed: Arc<Notify>,
+    events: Arc<RemoteEventCursor>,
 ) where
     R: AsyncRead + Unpin + Send + 'static,
 {
@@ -109,15 +169,16 @@ async fn read_loop<R>(
         if trimmed.is_empty() {
             continue;
         }
-        dispatch_incoming_line(&app, &pending, trimmed).await;
+        dispatch_incoming_line(&app, &pending, &events, trimmed).await;
     }
 
-    mark_disconnected(&pending, &connected).await;
+    mark_disconnected(&pending, &connected, &closed).await;
 }
 
 pub(crate) async fn dispatch_incoming_line(
     app: &AppHandle,
     pending: &Arc<Mutex<PendingMap>>,
+    events: &RemoteEventCursor,
     line: &str,
 ) {
     let Some(message) = parse_incoming_line(line) else {
@@ -131,7 +192,12 @@ pub(crate) async fn dispatch_incoming_line(
                 let _ = sender.send(payload);
             }
         }
-        IncomingMessage::Notification { method, params } => match method.as_str() {
+        IncomingMessage::Notification { seq: Some(seq), .. } if !events.observe(seq) => {}
+        IncomingMessage::Notification { method, params, .. } => match method.as_str() {
+            "event-resume" => events.apply_resume(&params),
+            "event-gap" => {
+                eprintln!("remote backend missed daemon events: {params}");
+            }
             "app-server-event" => {
                 let _ = app.emit("app-server-event", params);
             }
@@ -149,8 +215,11 @@ pub(crate) async fn dispatch_incoming_line(
 pub(crate) async fn mark_disconnected(
     pending: &Arc<Mutex<PendingMap>>,
     connected: &Arc<AtomicBool>,
+    closed: &Notify,
 ) {
-    connected.store(false, Ordering::SeqCst);
+    if connected.swap(false, Ordering::SeqCst) {
+        closed.notify_one();
+    }
     let mut pending = pending.lock().await;
     for (_, sender) in pending.drain() {
         let _ = sender.send(Err(DISCONNECTED_MESSAGE.to_string()));
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use tauri::AppHandle;
+
+use super::event_cursor::RemoteEventCursor;
+use super::transport::{RemoteTransport, RemoteTransportConfig, TransportFuture};
+
+pub(crate) struct UnixTransport;
+
+impl RemoteTransport for UnixTransport {
+    fn connect(
+        &self,
+        app: AppHandle,
+        config: RemoteTransportConfig,
+        events: Arc<RemoteEventCursor>,
+    ) -> TransportFuture {
+        Box::pin(async move {
+            let RemoteTransportConfig::Unix { path } = config else {
+                return Err("Unix transport requires a unix socket path".to_string());
+            };
+
+            #[cfg(unix)]
+            {
+                let stream = tokio::net::UnixStream::connect(&path)
+                    .await
+                    .map_err(|err| {
+                        format!(
+                            "Failed to connect to remote backend socket {}: {err}",
+                            path.display()
+                        )
+                    })?;
+                let (reader, writer) = stream.into_split();
+                Ok(super::transport::spawn_transport_io(
+                    app, reader, writer, events,
+                ))
+            }
+
+            #[cfg(not(unix))]
+            {
+                let _ = (app, events);
+                Err(format!(
+                    "Unix socket transport is not supported on this platform ({})",
+                    path.display()
+                ))
+            }
+        })
+    }
+}
@@ -54,6 +54,7 @@ fn should_reset_remote_backend(previous: &AppSettings, updated: &AppSettings) ->
         || previous.remote_backend_provider != updated.remote_backend_provider
         || previous.remote_backend_host != updated.remote_backend_host
         || previous.remote_backend_token != updated.remote_backend_token
+        || previous.active_remote_tls_fingerprint() != updated.active_remote_tls_fingerprint()
 }
 
 async fn ensure_remote_runtime_for_settings(settings: &AppSettings, state: State<'_, AppState>) {
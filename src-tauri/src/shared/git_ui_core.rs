@@ -5,12 +5,18 @@ use serde_json::Value;
 use tokio::sync::Mutex;
 
 use crate::types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, WorkspaceEntry,
+    AppSettings, GitBlameHunk, GitCommitDiff, GitConflictContents, GitDiffSelection, GitFileDiff,
+    GitHubIssuesResponse, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestsResponse, GitLineRange, GitLogQuery, GitLogResponse, GitStashEntry,
+    WorkspaceEntry,
 };
 
+#[path = "git_ui_core/blame.rs"]
+mod blame;
 #[path = "git_ui_core/commands.rs"]
 mod commands;
+#[path = "git_ui_core/conflicts.rs"]
+mod conflicts;
 #[path = "git_ui_core/context.rs"]
 mod context;
 #[path = "git_ui_core/diff.rs"]
@@ -19,6 +25,10 @@ mod diff;
 mod github;
 #[path = "git_ui_core/log.rs"]
 mod log;
+#[path = "git_ui_core/patch.rs"]
+mod patch;
+#[path = "git_ui_core/stash.rs"]
+mod stash;
 
 #[cfg(test)]
 #[path = "git_ui_core/tests.rs"]
@@ -81,8 +91,9 @@ pub(crate) async fn get_git_log_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     limit: Option<usize>,
+    query: GitLogQuery,
 ) -> Result<GitLogResponse, String> {
-    log::get_git_log_inner(workspaces, workspace_id, limit).await
+    log::get_git_log_inner(workspaces, workspace_id, limit, query).await
 }
 
 pub(crate) async fn get_git_commit_diff_core(
@@ -94,6 +105,16 @@ pub(crate) async fn get_git_commit_diff_core(
     diff::get_git_commit_diff_inner(workspaces, app_settings, workspace_id, sha).await
 }
 
+pub(crate) async fn get_git_blame_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    range: Option<GitLineRange>,
+    include_working_tree: bool,
+) -> Result<Vec<GitBlameHunk>, String> {
+    blame::get_git_blame_inner(workspaces, workspace_id, path, range, include_working_tree).await
+}
+
 pub(crate) async fn get_git_remote_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -132,6 +153,33 @@ pub(crate) async fn revert_git_file_core(
     commands::revert_git_file_inner(workspaces, workspace_id, path).await
 }
 
+pub(crate) async fn stage_git_hunks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+) -> Result<(), String> {
+    commands::stage_git_hunks_inner(workspaces, workspace_id, path, selection).await
+}
+
+pub(crate) async fn unstage_git_hunks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+) -> Result<(), String> {
+    commands::unstage_git_hunks_inner(workspaces, workspace_id, path, selection).await
+}
+
+pub(crate) async fn revert_git_hunks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+) -> Result<(), String> {
+    commands::revert_git_hunks_inner(workspaces, workspace_id, path, selection).await
+}
+
 pub(crate) async fn revert_git_all_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -139,6 +187,98 @@ pub(crate) async fn revert_git_all_core(
     commands::revert_git_all_inner(workspaces, workspace_id).await
 }
 
+pub(crate) async fn list_git_stashes_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitStashEntry>, String> {
+    stash::list_git_stashes_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn stash_push_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: bool,
+    paths: Vec<String>,
+) -> Result<(), String> {
+    stash::stash_push_inner(workspaces, workspace_id, message, include_untracked, paths).await
+}
+
+pub(crate) async fn stash_apply_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+) -> Result<(), String> {
+    stash::stash_apply_inner(workspaces, workspace_id, index, sha).await
+}
+
+pub(crate) async fn stash_pop_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+) -> Result<(), String> {
+    stash::stash_pop_inner(workspaces, workspace_id, index, sha).await
+}
+
+pub(crate) async fn stash_drop_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+) -> Result<(), String> {
+    stash::stash_drop_inner(workspaces, workspace_id, index, sha).await
+}
+
+pub(crate) async fn get_git_stash_diff_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    index: usize,
+) -> Result<Vec<GitCommitDiff>, String> {
+    stash::get_git_stash_diff_inner(workspaces, app_settings, workspace_id, index).await
+}
+
+pub(crate) async fn get_git_conflict_contents_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+) -> Result<GitConflictContents, String> {
+    conflicts::get_git_conflict_contents_inner(workspaces, workspace_id, path).await
+}
+
+pub(crate) async fn mark_git_conflict_resolved_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+) -> Result<(), String> {
+    conflicts::mark_git_conflict_resolved_inner(workspaces, workspace_id, path).await
+}
+
+pub(crate) async fn resolve_git_conflict_with_side_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    side: String,
+) -> Result<(), String> {
+    conflicts::resolve_git_conflict_with_side_inner(workspaces, workspace_id, path, side).await
+}
+
+pub(crate) async fn abort_git_operation_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    conflicts::abort_git_operation_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn continue_git_operation_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    conflicts::continue_git_operation_inner(workspaces, workspace_id).await
+}
+
 pub(crate) async fn commit_git_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
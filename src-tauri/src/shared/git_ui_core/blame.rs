@@ -0,0 +1,104 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use git2::{BlameOptions, Commit, Oid, Repository};
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::types::{GitBlameHunk, GitLineRange, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+use super::context::workspace_entry_for_id;
+
+/// Blames `path` at HEAD, or the working-tree file when `include_working_tree` is set, and
+/// clips the hunks to `range`.
+pub(super) fn blame_path(
+    repo: &Repository,
+    path: &str,
+    range: Option<GitLineRange>,
+    include_working_tree: bool,
+) -> Result<Vec<GitBlameHunk>, String> {
+    if range.is_some_and(|range| range.start == 0 || range.end < range.start) {
+        return Err("Blame range must be 1-based with start <= end.".to_string());
+    }
+    let mut options = BlameOptions::new();
+    // Working-tree line numbers can differ from HEAD's, so the range is applied afterwards.
+    if let Some(range) = range.filter(|_| !include_working_tree) {
+        options
+            .min_line(range.start as usize)
+            .max_line(range.end as usize);
+    }
+    let blame = repo
+        .blame_file(Path::new(path), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let blame = if include_working_tree {
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| "Cannot blame the working tree of a bare repository.".to_string())?;
+        let contents = fs::read(workdir.join(path)).map_err(|e| e.to_string())?;
+        blame.blame_buffer(&contents).map_err(|e| e.to_string())?
+    } else {
+        blame
+    };
+
+    let (first, last) = range
+        .map(|range| (range.start as usize, range.end as usize))
+        .unwrap_or((1, usize::MAX));
+    let mut commits: HashMap<Oid, Commit<'_>> = HashMap::new();
+    let mut hunks = Vec::new();
+    for hunk in blame.iter() {
+        let start = hunk.final_start_line();
+        let end = start + hunk.lines_in_hunk().saturating_sub(1);
+        let (start, end) = (start.max(first), end.min(last));
+        if hunk.lines_in_hunk() == 0 || start > end {
+            continue;
+        }
+        let oid = hunk.final_commit_id();
+        // Uncommitted hunks carry no signature, so details always come from the commit.
+        let (sha, author, timestamp, summary) = if oid.is_zero() {
+            (None, String::new(), 0, String::new())
+        } else {
+            let commit = match commits.entry(oid) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(repo.find_commit(oid).map_err(|e| e.to_string())?)
+                }
+            };
+            (
+                Some(oid.to_string()),
+                commit.author().name().unwrap_or("").to_string(),
+                commit.time().seconds(),
+                commit.summary().unwrap_or("").to_string(),
+            )
+        };
+        hunks.push(GitBlameHunk {
+            start_line: start,
+            line_count: end - start + 1,
+            sha,
+            author,
+            timestamp,
+            summary,
+        });
+    }
+    Ok(hunks)
+}
+
+pub(super) async fn get_git_blame_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    range: Option<GitLineRange>,
+    include_working_tree: bool,
+) -> Result<Vec<GitBlameHunk>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path);
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        blame_path(&repo, &path, range, include_working_tree)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
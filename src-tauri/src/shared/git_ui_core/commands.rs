@@ -10,12 +10,13 @@ use crate::git_utils::{
     checkout_branch, list_git_roots as scan_git_roots, parse_github_repo, resolve_git_root,
 };
 use crate::shared::process_core::tokio_command;
-use crate::types::{BranchInfo, WorkspaceEntry};
+use crate::types::{BranchInfo, GitDiffSelection, WorkspaceEntry};
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
 use super::context::workspace_entry_for_id;
+use super::patch::{apply_partial_selection, PartialAction};
 
-async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String> {
+pub(super) async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String> {
     let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
     let output = tokio_command(git_bin)
         .args(args)
@@ -454,6 +455,72 @@ pub(super) async fn revert_git_file_inner(
     Ok(())
 }
 
+async fn apply_git_selection(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+    action: PartialAction,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path);
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        apply_partial_selection(&repo, &path, &selection, action)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+pub(super) async fn stage_git_hunks_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+) -> Result<(), String> {
+    apply_git_selection(
+        workspaces,
+        workspace_id,
+        path,
+        selection,
+        PartialAction::Stage,
+    )
+    .await
+}
+
+pub(super) async fn unstage_git_hunks_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+) -> Result<(), String> {
+    apply_git_selection(
+        workspaces,
+        workspace_id,
+        path,
+        selection,
+        PartialAction::Unstage,
+    )
+    .await
+}
+
+pub(super) async fn revert_git_hunks_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitDiffSelection,
+) -> Result<(), String> {
+    apply_git_selection(
+        workspaces,
+        workspace_id,
+        path,
+        selection,
+        PartialAction::Revert,
+    )
+    .await
+}
+
 pub(super) async fn revert_git_all_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
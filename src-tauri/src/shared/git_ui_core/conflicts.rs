@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{IndexConflict, IndexEntry, Repository, RepositoryState};
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::types::{GitConflictContents, GitConflictFile, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+use super::commands::run_git_command;
+use super::context::workspace_entry_for_id;
+use super::diff::{bytes_look_binary, MAX_TEXT_DIFF_BYTES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum GitOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+}
+
+impl GitOperation {
+    pub(super) fn from_repo(repo: &Repository) -> Option<Self> {
+        match repo.state() {
+            RepositoryState::Merge => Some(Self::Merge),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge
+            | RepositoryState::ApplyMailboxOrRebase => Some(Self::Rebase),
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                Some(Self::CherryPick)
+            }
+            RepositoryState::Revert | RepositoryState::RevertSequence => Some(Self::Revert),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+            Self::CherryPick => "cherry-pick",
+            Self::Revert => "revert",
+        }
+    }
+}
+
+fn conflict_path(conflict: &IndexConflict) -> Option<String> {
+    [&conflict.our, &conflict.their, &conflict.ancestor]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+}
+
+/// Lists conflicted paths from the index with the stages each has. Contents are read per file
+/// by [`read_conflict_contents`], so status polls stay cheap.
+pub(super) fn collect_conflicts(repo: &Repository) -> Result<Vec<GitConflictFile>, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    if !index.has_conflicts() {
+        return Ok(Vec::new());
+    }
+    let mut conflicts = Vec::new();
+    for conflict in index.conflicts().map_err(|e| e.to_string())? {
+        let conflict = conflict.map_err(|e| e.to_string())?;
+        let Some(path) = conflict_path(&conflict) else {
+            continue;
+        };
+        conflicts.push(GitConflictFile {
+            path: normalize_git_path(&path),
+            has_base: conflict.ancestor.is_some(),
+            has_ours: conflict.our.is_some(),
+            has_theirs: conflict.their.is_some(),
+        });
+    }
+    Ok(conflicts)
+}
+
+fn find_conflict(repo: &Repository, path: &str) -> Result<IndexConflict, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let conflict = index
+        .conflicts()
+        .map_err(|e| e.to_string())?
+        .filter_map(Result::ok)
+        .find(|conflict| {
+            conflict_path(conflict).is_some_and(|entry| Path::new(&entry) == Path::new(path))
+        })
+        .ok_or_else(|| format!("{path} is not conflicted."))?;
+    Ok(conflict)
+}
+
+/// Reads the base/ours/theirs stage contents of one conflicted path.
+pub(super) fn read_conflict_contents(
+    repo: &Repository,
+    path: &str,
+) -> Result<GitConflictContents, String> {
+    let conflict = find_conflict(repo, path)?;
+    let sides = [&conflict.ancestor, &conflict.our, &conflict.their].map(|entry| {
+        entry.as_ref().and_then(|entry| {
+            repo.find_blob(entry.id)
+                .ok()
+                .map(|blob| blob.content().to_vec())
+        })
+    });
+    let is_binary = sides
+        .iter()
+        .flatten()
+        .any(|content| content.len() > MAX_TEXT_DIFF_BYTES || bytes_look_binary(content));
+    let [base, ours, theirs] = sides.map(|content| {
+        content
+            .filter(|_| !is_binary)
+            .map(|content| String::from_utf8_lossy(&content).to_string())
+    });
+    Ok(GitConflictContents {
+        path: path.to_string(),
+        base,
+        ours,
+        theirs,
+        is_binary,
+    })
+}
+
+pub(super) async fn get_git_conflict_contents_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+) -> Result<GitConflictContents, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path);
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    read_conflict_contents(&repo, &path)
+}
+
+fn conflict_side<'a>(
+    repo: &Repository,
+    path: &str,
+    side: &'a str,
+) -> Result<(&'a str, Option<IndexEntry>), String> {
+    let conflict = find_conflict(repo, path)?;
+    match side {
+        "ours" => Ok(("--ours", conflict.our)),
+        "theirs" => Ok(("--theirs", conflict.their)),
+        _ => Err(format!(
+            "Unknown conflict side `{side}`; expected ours or theirs."
+        )),
+    }
+}
+
+pub(super) async fn mark_git_conflict_resolved_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path);
+    run_git_command(&repo_root, &["add", "-A", "--", &path]).await
+}
+
+/// Resolves a conflict by taking one side's version. `ours`/`theirs` follow git's index
+/// stages, so during a rebase `ours` is the branch being rebased onto.
+pub(super) async fn resolve_git_conflict_with_side_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    side: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(&path);
+    let (flag, side_entry) = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        conflict_side(&repo, &path, side.trim())?
+    };
+    if side_entry.is_none() {
+        // The chosen side deleted the file.
+        return run_git_command(&repo_root, &["rm", "--quiet", "--", &path]).await;
+    }
+    run_git_command(&repo_root, &["checkout", flag, "--", &path]).await?;
+    run_git_command(&repo_root, &["add", "--", &path]).await
+}
+
+fn operation_for_root(repo_root: &Path) -> Result<GitOperation, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    GitOperation::from_repo(&repo)
+        .ok_or_else(|| "No merge, rebase, cherry-pick or revert is in progress.".to_string())
+}
+
+pub(super) async fn abort_git_operation_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let operation = operation_for_root(&repo_root)?;
+    run_git_command(&repo_root, &[operation.as_str(), "--abort"]).await
+}
+
+pub(super) async fn continue_git_operation_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let operation = operation_for_root(&repo_root)?;
+    {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let index = repo.index().map_err(|e| e.to_string())?;
+        if index.has_conflicts() {
+            return Err("Resolve all conflicts before continuing.".to_string());
+        }
+    }
+    // Keep git's prepared message instead of opening an editor.
+    match operation {
+        GitOperation::Merge => run_git_command(&repo_root, &["commit", "--no-edit"]).await,
+        _ => {
+            run_git_command(
+                &repo_root,
+                &["-c", "core.editor=true", operation.as_str(), "--continue"],
+            )
+            .await
+        }
+    }
+}
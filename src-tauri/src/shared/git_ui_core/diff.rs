@@ -16,11 +16,12 @@ use crate::shared::process_core::std_command;
 use crate::types::{AppSettings, GitCommitDiff, GitFileDiff, GitFileStatus, WorkspaceEntry};
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
+use super::conflicts::{collect_conflicts, GitOperation};
 use super::context::workspace_entry_for_id;
 
 const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
 const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
-const MAX_TEXT_DIFF_BYTES: usize = 2 * 1024 * 1024;
+pub(super) const MAX_TEXT_DIFF_BYTES: usize = 2 * 1024 * 1024;
 
 fn encode_image_base64(data: &[u8]) -> Option<String> {
     if data.len() > MAX_IMAGE_BYTES {
@@ -45,7 +46,7 @@ fn read_image_base64(path: &Path) -> Option<String> {
     encode_image_base64(&data)
 }
 
-fn bytes_look_binary(bytes: &[u8]) -> bool {
+pub(super) fn bytes_look_binary(bytes: &[u8]) -> bool {
     bytes.iter().take(8192).any(|byte| *byte == 0)
 }
 
@@ -365,6 +366,9 @@ pub(super) async fn get_git_status_inner(
 
     let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
     let index = repo.index().ok();
+    let operation = GitOperation::from_repo(&repo).map(GitOperation::as_str);
+    // A damaged index must not take the rest of the status down with it.
+    let conflicted_files = collect_conflicts(&repo).unwrap_or_default();
 
     let mut files = Vec::new();
     let mut staged_files = Vec::new();
@@ -387,6 +391,9 @@ pub(super) async fn get_git_status_inner(
             }
         }
         let status = entry.status();
+        if status.contains(Status::CONFLICTED) {
+            continue;
+        }
         let normalized_path = normalize_git_path(path);
         let include_index = status.intersects(
             Status::INDEX_NEW
@@ -459,6 +466,8 @@ pub(super) async fn get_git_status_inner(
         "unstagedFiles": unstaged_files,
         "totalAdditions": total_additions,
         "totalDeletions": total_deletions,
+        "operation": operation,
+        "conflictedFiles": conflicted_files,
     }))
 }
 
@@ -635,10 +644,26 @@ pub(super) async fn get_git_commit_diff_inner(
     let commit_tree = commit.tree().map_err(|e| e.to_string())?;
     let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
 
+    collect_tree_diffs(
+        &repo,
+        parent_tree.as_ref(),
+        &commit_tree,
+        ignore_whitespace_changes,
+    )
+}
+
+/// Builds per-file diffs between two trees in the `GitCommitDiff` shape; a missing old tree
+/// treats every file as added.
+pub(super) fn collect_tree_diffs(
+    repo: &Repository,
+    parent_tree: Option<&git2::Tree<'_>>,
+    commit_tree: &git2::Tree<'_>,
+    ignore_whitespace_changes: bool,
+) -> Result<Vec<GitCommitDiff>, String> {
     let mut options = DiffOptions::new();
     options.ignore_whitespace_change(ignore_whitespace_changes);
     let diff = repo
-        .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), Some(&mut options))
+        .diff_tree_to_tree(parent_tree, Some(commit_tree), Some(&mut options))
         .map_err(|e| e.to_string())?;
 
     let mut results = Vec::new();
@@ -661,7 +686,6 @@ pub(super) async fn get_git_commit_diff_inner(
 
         let old_lines = if !is_added {
             parent_tree
-                .as_ref()
                 .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
                 .and_then(|entry| repo.find_blob(entry.id()).ok())
                 .and_then(blob_to_lines)
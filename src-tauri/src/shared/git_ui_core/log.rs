@@ -1,42 +1,257 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use git2::{BranchType, Repository, Sort};
+use git2::{BranchType, Commit, Oid, Repository, Sort};
 use tokio::sync::Mutex;
 
 use crate::git_utils::{commit_to_entry, resolve_git_root};
-use crate::types::{GitLogResponse, WorkspaceEntry};
+use crate::types::{GitLogQuery, GitLogResponse, WorkspaceEntry};
+use crate::utils::normalize_git_path;
 
 use super::context::workspace_entry_for_id;
 
+/// Once a page is full, the walk stops after scanning this many commits, so counting `total`
+/// and looking ahead for another page stay bounded on filtered queries.
+const LOG_SCAN_CAP: usize = 10_000;
+
+/// Position in a log walk: the commits still queued after the last entry of the previous page,
+/// and the total counted on the first page.
+struct LogCursor {
+    frontier: Vec<Oid>,
+    total: usize,
+    total_capped: bool,
+}
+
+impl LogCursor {
+    fn parse(value: &str) -> Result<Self, String> {
+        let invalid = || "Invalid git log cursor.".to_string();
+        let parts: Vec<&str> = value.split(':').collect();
+        let [frontier, total, capped] = parts.as_slice() else {
+            return Err(invalid());
+        };
+        let frontier = frontier
+            .split(',')
+            .map(|oid| Oid::from_str(oid).map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            frontier,
+            total: total.parse().map_err(|_| invalid())?,
+            total_capped: *capped == "1",
+        })
+    }
+
+    fn encode(&self) -> String {
+        let frontier = self
+            .frontier
+            .iter()
+            .map(Oid::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{frontier}:{}:{}", self.total, u8::from(self.total_capped))
+    }
+}
+
+struct LogFilter {
+    path: Option<PathBuf>,
+    author: Option<String>,
+    message: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    first_parent: bool,
+}
+
+impl LogFilter {
+    fn from_query(query: &GitLogQuery) -> Self {
+        let needle = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_lowercase)
+        };
+        Self {
+            path: query
+                .path
+                .as_deref()
+                .map(normalize_git_path)
+                .map(|path| path.trim_matches('/').to_string())
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from),
+            author: needle(&query.author),
+            message: needle(&query.message),
+            since: query.since,
+            until: query.until,
+            first_parent: query.first_parent,
+        }
+    }
+
+    fn matches(&self, commit: &Commit<'_>) -> Result<bool, String> {
+        let time = commit.time().seconds();
+        if self.since.is_some_and(|since| time < since)
+            || self.until.is_some_and(|until| time > until)
+        {
+            return Ok(false);
+        }
+        if let Some(author) = &self.author {
+            let signature = commit.author();
+            if ![signature.name(), signature.email()]
+                .into_iter()
+                .flatten()
+                .any(|value| value.to_lowercase().contains(author))
+            {
+                return Ok(false);
+            }
+        }
+        if let Some(message) = &self.message {
+            if !commit
+                .message()
+                .unwrap_or("")
+                .to_lowercase()
+                .contains(message)
+            {
+                return Ok(false);
+            }
+        }
+        match &self.path {
+            Some(path) => touches_path(commit, path, self.first_parent),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Like `git log -- <path>`, a commit is skipped when the path is identical in one of its
+/// parents.
+fn touches_path(commit: &Commit<'_>, path: &Path, first_parent: bool) -> Result<bool, String> {
+    let entry_id = |commit: &Commit<'_>| -> Result<Option<Oid>, String> {
+        let tree = commit.tree().map_err(|e| e.to_string())?;
+        Ok(tree.get_path(path).ok().map(|entry| entry.id()))
+    };
+    let own = entry_id(commit)?;
+    let parent_count = if first_parent {
+        commit.parent_count().min(1)
+    } else {
+        commit.parent_count()
+    };
+    if parent_count == 0 {
+        return Ok(own.is_some());
+    }
+    for index in 0..parent_count {
+        let parent = commit.parent(index).map_err(|e| e.to_string())?;
+        if entry_id(&parent)? == own {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn resolve_log_tip(repo: &Repository, reference: Option<&str>) -> Result<Oid, String> {
+    match reference.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(reference) => repo
+            .revparse_single(reference)
+            .and_then(|object| object.peel_to_commit())
+            .map(|commit| commit.id())
+            .map_err(|_| format!("Unknown ref `{reference}`.")),
+        None => repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map(|commit| commit.id())
+            .map_err(|e| e.to_string()),
+    }
+}
+
 pub(super) async fn get_git_log_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     limit: Option<usize>,
+    query: GitLogQuery,
 ) -> Result<GitLogResponse, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
     let max_items = limit.unwrap_or(40);
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
-
-    let mut total = 0usize;
-    for oid_result in revwalk {
-        oid_result.map_err(|e| e.to_string())?;
-        total += 1;
-    }
+    let filter = LogFilter::from_query(&query);
+    let first_page = query.cursor.is_none();
+    let LogCursor {
+        frontier,
+        mut total,
+        mut total_capped,
+    } = match query.cursor.as_deref() {
+        Some(cursor) => LogCursor::parse(cursor)?,
+        None => LogCursor {
+            frontier: vec![resolve_log_tip(&repo, query.reference.as_deref())?],
+            total: 0,
+            total_capped: false,
+        },
+    };
 
     let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
+    for oid in &frontier {
+        revwalk.push(*oid).map_err(|e| e.to_string())?;
+    }
     revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
+    if filter.first_parent {
+        revwalk.simplify_first_parent().map_err(|e| e.to_string())?;
+    }
 
+    // The first page keeps walking past the page to count `total`; later pages only look
+    // one match ahead to learn whether another page exists. `pending` mirrors the walk's
+    // queue so the next page resumes from the commits queued after the page's last entry.
+    let mut pending: BTreeSet<Oid> = frontier.into_iter().collect();
+    let mut seen = HashSet::new();
     let mut entries = Vec::new();
-    for oid_result in revwalk.take(max_items) {
+    let mut page_frontier = None;
+    let mut scanned = 0usize;
+    let mut has_more = false;
+    for oid_result in revwalk {
         let oid = oid_result.map_err(|e| e.to_string())?;
+        scanned += 1;
         let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
-        entries.push(commit_to_entry(commit));
+        pending.remove(&oid);
+        seen.insert(oid);
+        let parent_count = if filter.first_parent {
+            commit.parent_count().min(1)
+        } else {
+            commit.parent_count()
+        };
+        pending.extend(
+            commit
+                .parent_ids()
+                .take(parent_count)
+                .filter(|parent| !seen.contains(parent)),
+        );
+
+        if filter.matches(&commit)? {
+            if entries.len() < max_items {
+                entries.push(commit_to_entry(commit));
+                if entries.len() == max_items {
+                    page_frontier = Some(pending.iter().copied().collect::<Vec<_>>());
+                }
+            } else {
+                has_more = true;
+                if !first_page {
+                    break;
+                }
+            }
+            if first_page {
+                total += 1;
+            }
+        }
+        if page_frontier.is_some() && scanned >= LOG_SCAN_CAP {
+            if !pending.is_empty() {
+                has_more = true;
+                total_capped = total_capped || first_page;
+            }
+            break;
+        }
     }
+    let next_cursor = page_frontier.filter(|_| has_more).map(|frontier| {
+        LogCursor {
+            frontier,
+            total,
+            total_capped,
+        }
+        .encode()
+    });
 
     let mut ahead = 0usize;
     let mut behind = 0usize;
@@ -97,6 +312,8 @@ pub(super) async fn get_git_log_inner(
         ahead_entries,
         behind_entries,
         upstream,
+        next_cursor,
+        total_capped,
     })
 }
 
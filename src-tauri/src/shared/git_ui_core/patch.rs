@@ -0,0 +1,387 @@
+use std::path::Path;
+
+use git2::{ApplyLocation, Diff, DiffOptions, Patch, Repository};
+
+use crate::types::{GitDiffHunkRange, GitDiffSelection, GitLineRange};
+
+const DEFAULT_CONTEXT_LINES: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PartialAction {
+    /// Copy selected workdir changes into the index.
+    Stage,
+    /// Take selected staged changes back out of the index.
+    Unstage,
+    /// Discard selected unstaged changes from the workdir.
+    Revert,
+}
+
+impl PartialAction {
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Stage => "stage",
+            Self::Unstage => "unstage",
+            Self::Revert => "revert",
+        }
+    }
+}
+
+/// Applies the selected hunks/lines of one file through libgit2.
+///
+/// The selection refers to the HEAD-to-workdir diff shown in the UI. Stage works on the
+/// index-to-workdir diff. Unstage and revert work on the reversed HEAD-to-index and
+/// index-to-workdir diffs. HEAD lines are translated to index lines for stage/revert, and
+/// workdir lines to index lines for unstage, so earlier staged changes do not shift the
+/// selection.
+pub(super) fn apply_partial_selection(
+    repo: &Repository,
+    path: &str,
+    selection: &GitDiffSelection,
+    action: PartialAction,
+) -> Result<(), String> {
+    if selection.hunks.is_empty()
+        && selection.removed_lines.is_empty()
+        && selection.added_lines.is_empty()
+    {
+        return Err("Select at least one hunk or line.".to_string());
+    }
+
+    let selection = expand_hunks(selection)?;
+    let (old_line_count, new_line_count) = (
+        head_line_count(repo, path)?,
+        workdir_line_count(repo, path)?,
+    );
+    validate_ranges(&selection.removed_lines, path, old_line_count)?;
+    validate_ranges(&selection.added_lines, path, new_line_count)?;
+    let selection = match action {
+        PartialAction::Stage | PartialAction::Revert => {
+            let staged = hunk_ranges(&path_diff(repo, path, true, 0, false)?)?;
+            GitDiffSelection {
+                removed_lines: translate_ranges(&selection.removed_lines, |line| {
+                    map_unchanged_line(&staged, line, false)
+                }),
+                ..selection
+            }
+        }
+        PartialAction::Unstage => {
+            let unstaged = hunk_ranges(&path_diff(repo, path, false, 0, false)?)?;
+            GitDiffSelection {
+                added_lines: translate_ranges(&selection.added_lines, |line| {
+                    map_unchanged_line(&unstaged, line, true)
+                }),
+                ..selection
+            }
+        }
+    };
+    let selection = match action {
+        PartialAction::Stage => selection,
+        PartialAction::Unstage | PartialAction::Revert => swap_sides(&selection),
+    };
+
+    let diff = path_diff(
+        repo,
+        path,
+        action == PartialAction::Unstage,
+        DEFAULT_CONTEXT_LINES,
+        action != PartialAction::Stage,
+    )?;
+    let mut patch_text = None;
+    for index in 0..diff.deltas().len() {
+        let Some(mut patch) = Patch::from_diff(&diff, index).map_err(|e| e.to_string())? else {
+            continue;
+        };
+        if patch_path_matches(&patch, path) {
+            patch_text = filter_patch(&mut patch, &selection, action)?;
+            break;
+        }
+    }
+    let Some(patch_text) = patch_text else {
+        return Err(format!(
+            "No changes to {} match the selection in {path}.",
+            action.verb()
+        ));
+    };
+
+    let filtered = Diff::from_buffer(&patch_text).map_err(|e| e.to_string())?;
+    let location = match action {
+        PartialAction::Stage | PartialAction::Unstage => ApplyLocation::Index,
+        PartialAction::Revert => ApplyLocation::WorkDir,
+    };
+    repo.apply(&filtered, location, None).map_err(|e| {
+        format!(
+            "Failed to {} selected changes: {}",
+            action.verb(),
+            e.message()
+        )
+    })
+}
+
+/// HEAD-to-index (`staged`) or index-to-workdir diff of one file.
+fn path_diff<'repo>(
+    repo: &'repo Repository,
+    path: &str,
+    staged: bool,
+    context_lines: u32,
+    reverse: bool,
+) -> Result<Diff<'repo>, String> {
+    let mut options = DiffOptions::new();
+    options
+        .pathspec(path)
+        .disable_pathspec_match(true)
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true)
+        .context_lines(context_lines)
+        .reverse(reverse);
+    if staged {
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut options))
+    } else {
+        repo.diff_index_to_workdir(None, Some(&mut options))
+    }
+    .map_err(|e| e.to_string())
+}
+
+fn hunk_ranges(diff: &Diff<'_>) -> Result<Vec<GitDiffHunkRange>, String> {
+    let mut ranges = Vec::new();
+    for index in 0..diff.deltas().len() {
+        let Some(patch) = Patch::from_diff(diff, index).map_err(|e| e.to_string())? else {
+            continue;
+        };
+        for hunk_index in 0..patch.num_hunks() {
+            let (hunk, _) = patch.hunk(hunk_index).map_err(|e| e.to_string())?;
+            ranges.push(GitDiffHunkRange {
+                old_start: hunk.old_start(),
+                old_lines: hunk.old_lines(),
+                new_start: hunk.new_start(),
+                new_lines: hunk.new_lines(),
+            });
+        }
+    }
+    Ok(ranges)
+}
+
+/// Maps a line that `hunks` (taken without context) leave unchanged to the other side of the
+/// diff: old to new, or new to old with `from_new`. Changed lines have no counterpart.
+fn map_unchanged_line(hunks: &[GitDiffHunkRange], line: u32, from_new: bool) -> Option<u32> {
+    let mut shift = 0i64;
+    for hunk in hunks {
+        let (start, lines, other_lines) = if from_new {
+            (hunk.new_start, hunk.new_lines, hunk.old_lines)
+        } else {
+            (hunk.old_start, hunk.old_lines, hunk.new_lines)
+        };
+        // A hunk with no lines on this side is an insertion after `start`.
+        let last = if lines == 0 { start } else { start + lines - 1 };
+        if lines > 0 && start <= line && line <= last {
+            return None;
+        }
+        if last >= line {
+            break;
+        }
+        shift += i64::from(other_lines) - i64::from(lines);
+    }
+    u32::try_from(i64::from(line) + shift).ok()
+}
+
+fn translate_ranges(
+    ranges: &[GitLineRange],
+    map_line: impl Fn(u32) -> Option<u32>,
+) -> Vec<GitLineRange> {
+    let mut translated: Vec<GitLineRange> = Vec::new();
+    for line in ranges.iter().flat_map(|range| range.start..=range.end) {
+        let Some(line) = map_line(line) else {
+            continue;
+        };
+        match translated.last_mut() {
+            Some(range) if range.end + 1 == line => range.end = line,
+            _ => translated.push(GitLineRange {
+                start: line,
+                end: line,
+            }),
+        }
+    }
+    translated
+}
+
+/// Turns selected hunks into the line ranges they cover, so every line can be translated.
+fn expand_hunks(selection: &GitDiffSelection) -> Result<GitDiffSelection, String> {
+    let mut removed_lines = selection.removed_lines.clone();
+    let mut added_lines = selection.added_lines.clone();
+    for hunk in &selection.hunks {
+        if hunk.old_lines > 0 {
+            removed_lines.push(hunk_line_range(hunk.old_start, hunk.old_lines)?);
+        }
+        if hunk.new_lines > 0 {
+            added_lines.push(hunk_line_range(hunk.new_start, hunk.new_lines)?);
+        }
+    }
+    Ok(GitDiffSelection {
+        hunks: Vec::new(),
+        removed_lines,
+        added_lines,
+    })
+}
+
+fn hunk_line_range(start: u32, lines: u32) -> Result<GitLineRange, String> {
+    let end = start
+        .checked_add(lines - 1)
+        .ok_or_else(|| format!("Invalid hunk in selection: {lines} lines from line {start}."))?;
+    Ok(GitLineRange { start, end })
+}
+
+/// Rejects ranges that are empty, reversed, 0-based or run past the `line_count` lines of that
+/// side of the diff.
+fn validate_ranges(ranges: &[GitLineRange], path: &str, line_count: u32) -> Result<(), String> {
+    for range in ranges {
+        if range.start == 0 || range.start > range.end {
+            return Err(format!(
+                "Invalid line range {}-{} in selection.",
+                range.start, range.end
+            ));
+        }
+        if range.end > line_count {
+            return Err(format!(
+                "Line {} is past the end of {path} ({line_count} lines).",
+                range.end
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn head_line_count(repo: &Repository, path: &str) -> Result<u32, String> {
+    let Some(tree) = repo.head().ok().and_then(|head| head.peel_to_tree().ok()) else {
+        return Ok(0);
+    };
+    let Ok(entry) = tree.get_path(Path::new(path)) else {
+        return Ok(0);
+    };
+    let blob = repo.find_blob(entry.id()).map_err(|e| e.to_string())?;
+    Ok(line_count(blob.content()))
+}
+
+fn workdir_line_count(repo: &Repository, path: &str) -> Result<u32, String> {
+    let root = repo
+        .workdir()
+        .ok_or_else(|| "Repository has no working directory.".to_string())?;
+    match std::fs::read(root.join(path)) {
+        Ok(content) => Ok(line_count(&content)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn line_count(content: &[u8]) -> u32 {
+    let newlines = content.iter().filter(|byte| **byte == b'\n').count();
+    let unterminated = usize::from(content.last().is_some_and(|byte| *byte != b'\n'));
+    u32::try_from(newlines + unterminated).unwrap_or(u32::MAX)
+}
+
+fn patch_path_matches(patch: &Patch<'_>, path: &str) -> bool {
+    let delta = patch.delta();
+    [delta.new_file().path(), delta.old_file().path()]
+        .into_iter()
+        .flatten()
+        .any(|candidate| candidate == Path::new(path))
+}
+
+fn swap_sides(selection: &GitDiffSelection) -> GitDiffSelection {
+    GitDiffSelection {
+        hunks: Vec::new(),
+        removed_lines: selection.added_lines.clone(),
+        added_lines: selection.removed_lines.clone(),
+    }
+}
+
+fn in_ranges(ranges: &[GitLineRange], line: u32) -> bool {
+    ranges
+        .iter()
+        .any(|range| range.start <= line && line <= range.end)
+}
+
+/// Rewrites `patch` so only the selected lines (hunks already expanded) remain: unselected removals become context
+/// and unselected additions are dropped. Returns `None` when nothing is selected.
+fn filter_patch(
+    patch: &mut Patch<'_>,
+    selection: &GitDiffSelection,
+    action: PartialAction,
+) -> Result<Option<Vec<u8>>, String> {
+    let buf = patch.to_buf().map_err(|e| e.to_string())?;
+    let full: &[u8] = &buf;
+    let header_end = full
+        .windows(3)
+        .position(|window| window == b"\n@@")
+        .map(|position| position + 1)
+        .ok_or_else(|| "Binary files cannot be split into hunks.".to_string())?;
+    let deletes_file = patch.delta().status() == git2::Delta::Deleted;
+
+    let mut output = full[..header_end].to_vec();
+    let mut emitted = false;
+    let mut offset = 0i64;
+    for hunk_index in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(hunk_index).map_err(|e| e.to_string())?;
+        let mut body = Vec::new();
+        let mut new_lines = 0u32;
+        let mut changed = false;
+        for line_index in 0..line_count {
+            let line = patch
+                .line_in_hunk(hunk_index, line_index)
+                .map_err(|e| e.to_string())?;
+            let origin = match line.origin() {
+                ' ' => ' ',
+                '-' if line
+                    .old_lineno()
+                    .is_some_and(|number| in_ranges(&selection.removed_lines, number)) =>
+                {
+                    changed = true;
+                    '-'
+                }
+                '-' => ' ',
+                '+' if line
+                    .new_lineno()
+                    .is_some_and(|number| in_ranges(&selection.added_lines, number)) =>
+                {
+                    changed = true;
+                    '+'
+                }
+                // Unselected additions and end-of-file newline markers.
+                _ => continue,
+            };
+            if origin != '-' {
+                new_lines += 1;
+            }
+            body.push(origin as u8);
+            body.extend_from_slice(line.content());
+            if !line.content().ends_with(b"\n") {
+                body.extend_from_slice(b"\n\\ No newline at end of file\n");
+            }
+        }
+        if !changed {
+            continue;
+        }
+        if deletes_file && new_lines > 0 {
+            return Err(format!(
+                "Select the whole file to {} its deletion.",
+                action.verb()
+            ));
+        }
+
+        let old_start = i64::from(hunk.old_start());
+        let old_lines = hunk.old_lines();
+        let new_start = if new_lines == 0 {
+            (old_start + offset - 1).max(0)
+        } else if old_lines == 0 {
+            old_start + offset + 1
+        } else {
+            old_start + offset
+        };
+        output.extend_from_slice(
+            format!("@@ -{old_start},{old_lines} +{new_start},{new_lines} @@\n").as_bytes(),
+        );
+        output.extend_from_slice(&body);
+        offset += i64::from(new_lines) - i64::from(old_lines);
+        emitted = true;
+    }
+    Ok(emitted.then_some(output))
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{Oid, Repository};
+use tokio::sync::Mutex;
+
+use crate::git_utils::resolve_git_root;
+use crate::types::{AppSettings, GitCommitDiff, GitStashEntry, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+use super::commands::run_git_command;
+use super::context::workspace_entry_for_id;
+use super::diff::collect_tree_diffs;
+
+fn stash_ref(index: usize) -> String {
+    format!("stash@{{{index}}}")
+}
+
+fn read_stashes(repo_root: &Path) -> Result<Vec<GitStashEntry>, String> {
+    let mut repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let mut stashes = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        stashes.push((index, message.to_string(), *oid));
+        true
+    })
+    .map_err(|e| e.to_string())?;
+
+    stashes
+        .into_iter()
+        .map(|(index, message, oid)| {
+            let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+            Ok(GitStashEntry {
+                index,
+                sha: oid.to_string(),
+                message,
+                timestamp: commit.time().seconds(),
+            })
+        })
+        .collect()
+}
+
+fn find_stash_oid(repo_root: &Path, index: usize) -> Result<Oid, String> {
+    read_stashes(repo_root)?
+        .into_iter()
+        .find(|entry| entry.index == index)
+        .ok_or_else(|| format!("{} does not exist.", stash_ref(index)))
+        .and_then(|entry| Oid::from_str(&entry.sha).map_err(|e| e.to_string()))
+}
+
+/// Resolves `stash@{index}`, first checking that it still points at `sha` when one is given, so a
+/// stash list that changed since the caller read it cannot make git touch a different stash.
+fn checked_stash_ref(repo_root: &Path, index: usize, sha: Option<&str>) -> Result<String, String> {
+    if let Some(sha) = sha {
+        let oid = find_stash_oid(repo_root, index)?;
+        if !oid.to_string().eq_ignore_ascii_case(sha) {
+            return Err(format!(
+                "{} changed since the stash list was loaded; refresh and try again.",
+                stash_ref(index)
+            ));
+        }
+    }
+    Ok(stash_ref(index))
+}
+
+pub(super) async fn list_git_stashes_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitStashEntry>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    read_stashes(&repo_root)
+}
+
+pub(super) async fn stash_push_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: bool,
+    paths: Vec<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let before = read_stashes(&repo_root)?.len();
+
+    let message = message.map(|value| value.trim().to_string());
+    let paths: Vec<String> = paths.iter().map(|path| normalize_git_path(path)).collect();
+    let mut args = vec!["stash", "push"];
+    if include_untracked {
+        args.push("--include-untracked");
+    }
+    if let Some(message) = message.as_deref().filter(|value| !value.is_empty()) {
+        args.extend(["-m", message]);
+    }
+    if !paths.is_empty() {
+        args.push("--");
+        args.extend(paths.iter().map(String::as_str));
+    }
+    run_git_command(&repo_root, &args).await?;
+
+    // `git stash push` exits successfully when there is nothing to save.
+    if read_stashes(&repo_root)?.len() == before {
+        return Err("No local changes to stash.".to_string());
+    }
+    Ok(())
+}
+
+pub(super) async fn stash_apply_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let stash = checked_stash_ref(&repo_root, index, sha.as_deref())?;
+    run_git_command(&repo_root, &["stash", "apply", &stash]).await
+}
+
+pub(super) async fn stash_pop_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let stash = checked_stash_ref(&repo_root, index, sha.as_deref())?;
+    run_git_command(&repo_root, &["stash", "pop", &stash]).await
+}
+
+pub(super) async fn stash_drop_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    index: usize,
+    sha: Option<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let stash = checked_stash_ref(&repo_root, index, sha.as_deref())?;
+    run_git_command(&repo_root, &["stash", "drop", &stash]).await
+}
+
+pub(super) async fn get_git_stash_diff_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    index: usize,
+) -> Result<Vec<GitCommitDiff>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+
+    let ignore_whitespace_changes = {
+        let settings = app_settings.lock().await;
+        settings.git_diff_ignore_whitespace_changes
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let oid = find_stash_oid(&repo_root, index)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let stash = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    let stash_tree = stash.tree().map_err(|e| e.to_string())?;
+    let base_tree = stash.parent(0).ok().and_then(|parent| parent.tree().ok());
+
+    let mut results = collect_tree_diffs(
+        &repo,
+        base_tree.as_ref(),
+        &stash_tree,
+        ignore_whitespace_changes,
+    )?;
+    // Stashes made with --include-untracked keep untracked files in a third parent commit.
+    if let Some(untracked_tree) = stash.parent(2).ok().and_then(|parent| parent.tree().ok()) {
+        results.extend(collect_tree_diffs(
+            &repo,
+            None,
+            &untracked_tree,
+            ignore_whitespace_changes,
+        )?);
+    }
+    Ok(results)
+}
@@ -7,10 +7,18 @@ use serde_json::Value;
 use tokio::runtime::Runtime;
 use tokio::sync::Mutex;
 
-use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+use crate::types::{
+    AppSettings, GitDiffHunkRange, GitDiffSelection, GitLineRange, GitLogQuery, WorkspaceEntry,
+    WorkspaceKind, WorkspaceSettings,
+};
 
+use super::blame::blame_path;
 use super::commands;
+use super::conflicts;
 use super::diff;
+use super::log;
+use super::patch::{apply_partial_selection, PartialAction};
+use super::stash;
 
 fn create_temp_repo() -> (PathBuf, Repository) {
     let root = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
@@ -437,3 +445,701 @@ fn collect_ignored_paths_with_git_handles_large_ignored_output() {
 
     assert_eq!(ignored_paths.len(), total);
 }
+
+fn numbered_lines(changed: &[usize]) -> String {
+    (1..=20)
+        .map(|line| {
+            if changed.contains(&line) {
+                format!("changed {line}\n")
+            } else {
+                format!("line {line}\n")
+            }
+        })
+        .collect()
+}
+
+fn commit_numbered_file(root: &Path, repo: &Repository) {
+    fs::write(root.join("lines.txt"), numbered_lines(&[])).expect("write base file");
+    let mut index = repo.index().expect("index");
+    index.add_path(Path::new("lines.txt")).expect("add path");
+    index.write().expect("write index");
+    let tree_id = index.write_tree().expect("write tree");
+    let tree = repo.find_tree(tree_id).expect("find tree");
+    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+    repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+        .expect("commit");
+}
+
+fn staged_content(repo: &Repository) -> String {
+    let mut index = repo.index().expect("index");
+    index.read(true).expect("read index");
+    let entry = index
+        .get_path(Path::new("lines.txt"), 0)
+        .expect("index entry");
+    let blob = repo.find_blob(entry.id).expect("find blob");
+    String::from_utf8(blob.content().to_vec()).expect("utf8 blob")
+}
+
+fn line_selection(line: u32) -> GitDiffSelection {
+    GitDiffSelection {
+        removed_lines: vec![GitLineRange {
+            start: line,
+            end: line,
+        }],
+        added_lines: vec![GitLineRange {
+            start: line,
+            end: line,
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn partial_selection_stages_unstages_and_reverts_single_lines() {
+    let (root, repo) = create_temp_repo();
+    commit_numbered_file(&root, &repo);
+    fs::write(root.join("lines.txt"), numbered_lines(&[2, 18])).expect("edit file");
+
+    apply_partial_selection(
+        &repo,
+        "lines.txt",
+        &line_selection(18),
+        PartialAction::Stage,
+    )
+    .expect("stage line 18");
+    assert_eq!(staged_content(&repo), numbered_lines(&[18]));
+
+    apply_partial_selection(
+        &repo,
+        "lines.txt",
+        &line_selection(18),
+        PartialAction::Unstage,
+    )
+    .expect("unstage line 18");
+    assert_eq!(staged_content(&repo), numbered_lines(&[]));
+
+    apply_partial_selection(
+        &repo,
+        "lines.txt",
+        &line_selection(2),
+        PartialAction::Revert,
+    )
+    .expect("revert line 2");
+    assert_eq!(
+        fs::read_to_string(root.join("lines.txt")).expect("read file"),
+        numbered_lines(&[18])
+    );
+    assert_eq!(staged_content(&repo), numbered_lines(&[]));
+}
+
+#[test]
+fn partial_selection_stages_whole_hunk_and_keeps_unselected_removals() {
+    let (root, repo) = create_temp_repo();
+    commit_numbered_file(&root, &repo);
+    fs::write(root.join("lines.txt"), numbered_lines(&[2, 18])).expect("edit file");
+
+    let hunk = GitDiffSelection {
+        hunks: vec![GitDiffHunkRange {
+            old_start: 1,
+            old_lines: 5,
+            new_start: 1,
+            new_lines: 5,
+        }],
+        ..Default::default()
+    };
+    apply_partial_selection(&repo, "lines.txt", &hunk, PartialAction::Stage)
+        .expect("stage first hunk");
+    assert_eq!(staged_content(&repo), numbered_lines(&[2]));
+
+    let added_only = GitDiffSelection {
+        added_lines: vec![GitLineRange { start: 18, end: 18 }],
+        ..Default::default()
+    };
+    apply_partial_selection(&repo, "lines.txt", &added_only, PartialAction::Stage)
+        .expect("stage added line only");
+    let mut expected = numbered_lines(&[2]);
+    expected = expected.replace("line 18\n", "line 18\nchanged 18\n");
+    assert_eq!(staged_content(&repo), expected);
+
+    let err = apply_partial_selection(
+        &repo,
+        "lines.txt",
+        &GitDiffSelection::default(),
+        PartialAction::Stage,
+    )
+    .expect_err("empty selection");
+    assert!(err.contains("Select at least one"));
+}
+
+#[test]
+fn partial_selection_rejects_malformed_ranges() {
+    let (root, repo) = create_temp_repo();
+    commit_numbered_file(&root, &repo);
+    fs::write(root.join("lines.txt"), numbered_lines(&[2])).expect("edit file");
+
+    let added = |start: u32, end: u32| GitDiffSelection {
+        added_lines: vec![GitLineRange { start, end }],
+        ..Default::default()
+    };
+    for (selection, expected) in [
+        (added(0, 2), "Invalid line range 0-2"),
+        (added(3, 2), "Invalid line range 3-2"),
+        (added(2, 21), "Line 21 is past the end of lines.txt"),
+        (
+            GitDiffSelection {
+                hunks: vec![GitDiffHunkRange {
+                    old_start: u32::MAX,
+                    old_lines: 2,
+                    new_start: 1,
+                    new_lines: 1,
+                }],
+                ..Default::default()
+            },
+            "Invalid hunk in selection",
+        ),
+    ] {
+        let err = apply_partial_selection(&repo, "lines.txt", &selection, PartialAction::Stage)
+            .expect_err("malformed selection");
+        assert!(err.contains(expected), "{err}");
+    }
+    assert_eq!(staged_content(&repo), numbered_lines(&[]));
+}
+
+#[test]
+fn partial_selection_translates_head_lines_past_staged_changes() {
+    let (root, repo) = create_temp_repo();
+    commit_numbered_file(&root, &repo);
+    let base = numbered_lines(&[]);
+    let edited = base
+        .replace("line 2\n", "line 2\nnew a\nnew b\nnew c\n")
+        .replace("line 15\n", "");
+    fs::write(root.join("lines.txt"), &edited).expect("edit file");
+
+    // Selections use the HEAD-to-workdir diff: the additions are workdir lines 3-5 and the
+    // removal is HEAD line 15, which is line 18 of the index once the additions are staged.
+    let added = GitDiffSelection {
+        added_lines: vec![GitLineRange { start: 3, end: 5 }],
+        ..Default::default()
+    };
+    apply_partial_selection(&repo, "lines.txt", &added, PartialAction::Stage)
+        .expect("stage additions");
+    apply_partial_selection(
+        &repo,
+        "lines.txt",
+        &line_selection(15),
+        PartialAction::Stage,
+    )
+    .expect("stage removal below the additions");
+    assert_eq!(staged_content(&repo), edited);
+
+    // An unstaged line at the top shifts workdir lines one past their index lines.
+    fs::write(root.join("lines.txt"), format!("top\n{edited}")).expect("edit file again");
+    let new_b = GitDiffSelection {
+        added_lines: vec![GitLineRange { start: 5, end: 5 }],
+        ..Default::default()
+    };
+    apply_partial_selection(&repo, "lines.txt", &new_b, PartialAction::Unstage)
+        .expect("unstage new b");
+    assert_eq!(staged_content(&repo), edited.replace("new b\n", ""));
+}
+
+#[test]
+fn stash_push_list_diff_and_pop_round_trip() {
+    let (root, repo) = create_temp_repo();
+    commit_numbered_file(&root, &repo);
+    let mut config = repo.config().expect("repo config");
+    config.set_str("user.name", "Test").expect("set user.name");
+    config
+        .set_str("user.email", "test@example.com")
+        .expect("set user.email");
+    fs::write(root.join("lines.txt"), numbered_lines(&[3])).expect("edit file");
+    fs::write(root.join("notes.txt"), "draft\n").expect("write untracked file");
+
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), workspace)]));
+    let app_settings = Mutex::new(AppSettings::default());
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    runtime
+        .block_on(stash::stash_push_inner(
+            &workspaces,
+            "w1".to_string(),
+            Some("wip lines".to_string()),
+            true,
+            Vec::new(),
+        ))
+        .expect("stash push");
+    assert_eq!(
+        fs::read_to_string(root.join("lines.txt")).expect("read file"),
+        numbered_lines(&[])
+    );
+    assert!(!root.join("notes.txt").exists());
+
+    let stashes = runtime
+        .block_on(stash::list_git_stashes_inner(&workspaces, "w1".to_string()))
+        .expect("list stashes");
+    assert_eq!(stashes.len(), 1);
+    assert_eq!(stashes[0].index, 0);
+    assert!(stashes[0].message.contains("wip lines"));
+
+    let diff = runtime
+        .block_on(stash::get_git_stash_diff_inner(
+            &workspaces,
+            &app_settings,
+            "w1".to_string(),
+            0,
+        ))
+        .expect("stash diff");
+    let paths: Vec<(&str, &str)> = diff
+        .iter()
+        .map(|entry| (entry.path.as_str(), entry.status.as_str()))
+        .collect();
+    assert_eq!(paths, vec![("lines.txt", "M"), ("notes.txt", "A")]);
+
+    let err = runtime
+        .block_on(stash::stash_push_inner(
+            &workspaces,
+            "w1".to_string(),
+            None,
+            false,
+            Vec::new(),
+        ))
+        .expect_err("nothing to stash");
+    assert!(err.contains("No local changes"));
+
+    let err = runtime
+        .block_on(stash::stash_drop_inner(
+            &workspaces,
+            "w1".to_string(),
+            0,
+            Some("0".repeat(40)),
+        ))
+        .expect_err("stale sha");
+    assert!(err.contains("changed since the stash"), "{err}");
+
+    runtime
+        .block_on(stash::stash_pop_inner(
+            &workspaces,
+            "w1".to_string(),
+            0,
+            Some(stashes[0].sha.clone()),
+        ))
+        .expect("stash pop");
+    assert_eq!(
+        fs::read_to_string(root.join("lines.txt")).expect("read file"),
+        numbered_lines(&[3])
+    );
+    assert!(root.join("notes.txt").exists());
+    let stashes = runtime
+        .block_on(stash::list_git_stashes_inner(&workspaces, "w1".to_string()))
+        .expect("list stashes");
+    assert!(stashes.is_empty());
+}
+
+fn commit_file_on(
+    repo: &Repository,
+    update_ref: &str,
+    parent: &git2::Commit<'_>,
+    content: &str,
+) -> git2::Oid {
+    let blob = repo.blob(content.as_bytes()).expect("write blob");
+    let mut builder = repo
+        .treebuilder(Some(&parent.tree().expect("parent tree")))
+        .expect("tree builder");
+    builder
+        .insert("lines.txt", blob, 0o100644)
+        .expect("insert blob");
+    let tree = repo
+        .find_tree(builder.write().expect("write tree"))
+        .expect("find tree");
+    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+    repo.commit(
+        Some(update_ref),
+        &sig,
+        &sig,
+        content.trim(),
+        &tree,
+        &[parent],
+    )
+    .expect("commit")
+}
+
+#[test]
+fn conflicted_merge_is_reported_and_resolved_with_theirs() {
+    let (root, repo) = create_temp_repo();
+    commit_numbered_file(&root, &repo);
+    let mut config = repo.config().expect("repo config");
+    config.set_str("user.name", "Test").expect("set user.name");
+    config
+        .set_str("user.email", "test@example.com")
+        .expect("set user.email");
+    let base = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .expect("base commit");
+    commit_file_on(&repo, "refs/heads/other", &base, "theirs\n");
+    commit_file_on(&repo, "HEAD", &base, "ours\n");
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    repo.checkout_head(Some(checkout.force()))
+        .expect("checkout head");
+    let other = repo
+        .find_reference("refs/heads/other")
+        .and_then(|reference| repo.reference_to_annotated_commit(&reference))
+        .expect("annotated commit");
+    repo.merge(&[&other], None, None).expect("merge");
+
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), workspace)]));
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let status = runtime
+        .block_on(diff::get_git_status_inner(&workspaces, "w1".to_string()))
+        .expect("get git status");
+    assert_eq!(status["operation"], "merge");
+    let conflicted = status["conflictedFiles"].as_array().expect("conflicts");
+    assert_eq!(conflicted.len(), 1);
+    assert_eq!(conflicted[0]["path"], "lines.txt");
+    assert_eq!(conflicted[0]["hasBase"], true);
+    assert!(
+        conflicted[0].get("ours").is_none(),
+        "status omits stage contents"
+    );
+    assert!(status["files"].as_array().expect("files").is_empty());
+
+    let contents = runtime
+        .block_on(conflicts::get_git_conflict_contents_inner(
+            &workspaces,
+            "w1".to_string(),
+            "lines.txt".to_string(),
+        ))
+        .expect("conflict contents");
+    assert_eq!(contents.base.as_deref(), Some(numbered_lines(&[]).as_str()));
+    assert_eq!(contents.ours.as_deref(), Some("ours\n"));
+    assert_eq!(contents.theirs.as_deref(), Some("theirs\n"));
+    assert!(!contents.is_binary);
+
+    let err = runtime
+        .block_on(conflicts::continue_git_operation_inner(
+            &workspaces,
+            "w1".to_string(),
+        ))
+        .expect_err("conflicts remain");
+    assert!(err.contains("Resolve all conflicts"));
+
+    runtime
+        .block_on(conflicts::resolve_git_conflict_with_side_inner(
+            &workspaces,
+            "w1".to_string(),
+            "lines.txt".to_string(),
+            "theirs".to_string(),
+        ))
+        .expect("take theirs");
+    assert_eq!(
+        fs::read_to_string(root.join("lines.txt")).expect("read file"),
+        "theirs\n"
+    );
+    runtime
+        .block_on(conflicts::continue_git_operation_inner(
+            &workspaces,
+            "w1".to_string(),
+        ))
+        .expect("continue merge");
+
+    let status = runtime
+        .block_on(diff::get_git_status_inner(&workspaces, "w1".to_string()))
+        .expect("get git status");
+    assert!(status["operation"].is_null());
+    assert!(status["conflictedFiles"]
+        .as_array()
+        .expect("conflicts")
+        .is_empty());
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .expect("merge commit");
+    assert_eq!(head.parent_count(), 2);
+}
+
+fn commit_history(repo: &Repository) -> Vec<String> {
+    let steps = [
+        ("a.txt", "one\n", "alice", "add a"),
+        ("b.txt", "one\n", "bob", "add b"),
+        ("a.txt", "two\n", "alice", "fix parser in a"),
+        ("b.txt", "two\n", "bob", "tweak b"),
+        ("a.txt", "three\n", "alice", "polish a"),
+    ];
+    let mut shas = Vec::new();
+    for (step, (path, content, author, message)) in steps.into_iter().enumerate() {
+        let time = git2::Time::new(1_000 * (step as i64 + 1), 0);
+        let sig = git2::Signature::new(author, &format!("{author}@example.com"), &time)
+            .expect("signature");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let blob = repo.blob(content.as_bytes()).expect("write blob");
+        let parent_tree = parent.as_ref().map(|commit| commit.tree().expect("tree"));
+        let mut builder = repo
+            .treebuilder(parent_tree.as_ref())
+            .expect("tree builder");
+        builder.insert(path, blob, 0o100644).expect("insert blob");
+        let tree = repo
+            .find_tree(builder.write().expect("write tree"))
+            .expect("find tree");
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .expect("commit");
+        shas.push(oid.to_string());
+    }
+    shas
+}
+
+fn log_shas(
+    runtime: &Runtime,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    limit: usize,
+    query: GitLogQuery,
+) -> (Vec<String>, Option<String>, usize) {
+    let response = runtime
+        .block_on(log::get_git_log_inner(
+            workspaces,
+            "w1".to_string(),
+            Some(limit),
+            query,
+        ))
+        .expect("get git log");
+    let shas = response
+        .entries
+        .into_iter()
+        .map(|entry| entry.sha)
+        .collect();
+    (shas, response.next_cursor, response.total)
+}
+
+#[test]
+fn get_git_log_pages_with_cursor_and_applies_filters() {
+    let (root, repo) = create_temp_repo();
+    let shas = commit_history(&repo);
+    let old = repo
+        .find_commit(git2::Oid::from_str(&shas[1]).expect("oid"))
+        .expect("find commit");
+    repo.branch("old", &old, false).expect("create branch");
+
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), workspace)]));
+    let runtime = Runtime::new().expect("create tokio runtime");
+    let expect = |indexes: &[usize]| -> Vec<String> {
+        indexes.iter().map(|index| shas[*index].clone()).collect()
+    };
+
+    let (page, cursor, total) = log_shas(&runtime, &workspaces, 2, GitLogQuery::default());
+    assert_eq!(page, expect(&[4, 3]));
+    assert_eq!(total, 5);
+    let query = GitLogQuery {
+        cursor,
+        ..Default::default()
+    };
+    let (page, cursor, total) = log_shas(&runtime, &workspaces, 2, query);
+    assert_eq!(page, expect(&[2, 1]));
+    assert_eq!(total, 5);
+    let query = GitLogQuery {
+        cursor,
+        ..Default::default()
+    };
+    let (page, cursor, _) = log_shas(&runtime, &workspaces, 2, query);
+    assert_eq!(page, expect(&[0]));
+    assert!(cursor.is_none());
+
+    let path_query = GitLogQuery {
+        path: Some("a.txt".to_string()),
+        ..Default::default()
+    };
+    let (page, cursor, total) = log_shas(&runtime, &workspaces, 2, path_query.clone());
+    assert_eq!(page, expect(&[4, 2]));
+    assert_eq!(total, 3);
+    let (page, _, _) = log_shas(
+        &runtime,
+        &workspaces,
+        2,
+        GitLogQuery {
+            cursor,
+            ..path_query
+        },
+    );
+    assert_eq!(page, expect(&[0]));
+
+    let filtered = |query: GitLogQuery| log_shas(&runtime, &workspaces, 10, query).0;
+    assert_eq!(
+        filtered(GitLogQuery {
+            author: Some("BOB".to_string()),
+            ..Default::default()
+        }),
+        expect(&[3, 1])
+    );
+    assert_eq!(
+        filtered(GitLogQuery {
+            message: Some("parser".to_string()),
+            ..Default::default()
+        }),
+        expect(&[2])
+    );
+    assert_eq!(
+        filtered(GitLogQuery {
+            since: Some(2_000),
+            until: Some(4_000),
+            ..Default::default()
+        }),
+        expect(&[3, 2, 1])
+    );
+    assert_eq!(
+        filtered(GitLogQuery {
+            reference: Some("old".to_string()),
+            ..Default::default()
+        }),
+        expect(&[1, 0])
+    );
+}
+
+#[test]
+fn get_git_log_cursor_resumes_across_merged_branches() {
+    let (root, repo) = create_temp_repo();
+    let tree_id = repo
+        .treebuilder(None)
+        .and_then(|builder| builder.write())
+        .expect("write tree");
+    let tree = repo.find_tree(tree_id).expect("find tree");
+    let commit = |seconds: i64, parents: &[git2::Oid]| {
+        let sig = git2::Signature::new("Test", "test@example.com", &git2::Time::new(seconds, 0))
+            .expect("signature");
+        let parents: Vec<git2::Commit<'_>> = parents
+            .iter()
+            .map(|oid| repo.find_commit(*oid).expect("find parent"))
+            .collect();
+        let parents: Vec<&git2::Commit<'_>> = parents.iter().collect();
+        repo.commit(None, &sig, &sig, &format!("at {seconds}"), &tree, &parents)
+            .expect("commit")
+    };
+    let base = commit(1_000, &[]);
+    let side_one = commit(2_000, &[base]);
+    let main_one = commit(3_000, &[base]);
+    let side_two = commit(4_000, &[side_one]);
+    let main_two = commit(5_000, &[main_one]);
+    let merge = commit(6_000, &[main_two, side_two]);
+    repo.reference("refs/heads/main", merge, true, "test")
+        .expect("main ref");
+    repo.set_head("refs/heads/main").expect("set head");
+
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let workspaces = Mutex::new(HashMap::from([("w1".to_string(), workspace)]));
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let (all, _, total) = log_shas(&runtime, &workspaces, 10, GitLogQuery::default());
+    assert_eq!(total, 6);
+    // After the merge and `main_two`, the walk has queued both branches.
+    let (_, first_cursor, _) = log_shas(&runtime, &workspaces, 2, GitLogQuery::default());
+    let first_cursor = first_cursor.expect("next cursor");
+    assert!(first_cursor.contains(&main_one.to_string()));
+    assert!(first_cursor.contains(&side_two.to_string()));
+
+    let mut paged = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (page, next, _) = log_shas(
+            &runtime,
+            &workspaces,
+            2,
+            GitLogQuery {
+                cursor,
+                ..Default::default()
+            },
+        );
+        paged.extend(page);
+        if next.is_none() {
+            break;
+        }
+        cursor = next;
+    }
+    assert_eq!(paged, all);
+    assert_eq!(
+        paged,
+        [merge, main_two, side_two, main_one, side_one, base]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn blame_path_reports_commits_ranges_and_uncommitted_lines() {
+    let (root, repo) = create_temp_repo();
+    commit_history(&repo);
+    fs::write(root.join("b.txt"), "two\nlocal\n").expect("edit file");
+
+    let blame = blame_path(&repo, "a.txt", None, false).expect("blame a.txt");
+    assert_eq!(blame.len(), 1);
+    assert_eq!(blame[0].start_line, 1);
+    assert_eq!(blame[0].line_count, 1);
+    assert_eq!(blame[0].author, "alice");
+    assert_eq!(blame[0].summary, "polish a");
+    assert_eq!(blame[0].timestamp, 5_000);
+
+    let blame = blame_path(&repo, "b.txt", None, true).expect("blame working tree");
+    let lines: Vec<(usize, usize, Option<&str>)> = blame
+        .iter()
+        .map(|hunk| (hunk.start_line, hunk.line_count, hunk.sha.as_deref()))
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].0, 1);
+    assert!(lines[0].2.is_some());
+    assert_eq!((lines[1].0, lines[1].1, lines[1].2), (2, 1, None));
+    assert_eq!(blame[0].summary, "tweak b");
+
+    let range = GitLineRange { start: 2, end: 2 };
+    let blame = blame_path(&repo, "b.txt", Some(range), true).expect("blame range");
+    assert_eq!(blame.len(), 1);
+    assert_eq!(blame[0].start_line, 2);
+    assert!(blame[0].sha.is_none());
+
+    let err = blame_path(
+        &repo,
+        "b.txt",
+        Some(GitLineRange { start: 0, end: 1 }),
+        false,
+    )
+    .expect_err("invalid range");
+    assert!(err.contains("1-based"));
+}
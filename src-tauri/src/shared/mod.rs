@@ -12,5 +12,8 @@ pub(crate) mod process_core;
 pub(crate) mod prompts_core;
 pub(crate) mod settings_core;
 pub(crate) mod supervisor_core;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub(crate) mod terminal_core;
+pub(crate) mod tls_core;
 pub(crate) mod workspaces_core;
 pub(crate) mod worktree_core;
@@ -2,16 +2,24 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::BTreeMap;
 
+use chat::SupervisorChatDispatchRequest;
+
 #[path = "supervisor_core/chat.rs"]
 pub(crate) mod chat;
+#[path = "supervisor_core/concurrency.rs"]
+pub(crate) mod concurrency;
 #[path = "supervisor_core/contract.rs"]
 pub(crate) mod contract;
 #[path = "supervisor_core/dispatch.rs"]
 pub(crate) mod dispatch;
 #[path = "supervisor_core/events.rs"]
 pub(crate) mod events;
+#[path = "supervisor_core/planner.rs"]
+pub(crate) mod planner;
 #[path = "supervisor_core/routing.rs"]
 pub(crate) mod routing;
+#[path = "supervisor_core/schedule.rs"]
+pub(crate) mod schedule;
 #[path = "supervisor_core/service.rs"]
 pub(crate) mod service;
 #[path = "supervisor_core/loop.rs"]
@@ -51,11 +59,73 @@ pub(crate) enum SupervisorJobStatus {
     WaitingForUser,
     Completed,
     Failed,
+    Cancelled,
 }
 
 impl SupervisorJobStatus {
     pub(crate) fn is_terminal(&self) -> bool {
-        matches!(self, Self::Completed | Self::Failed)
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SupervisorJobRetryThread {
+    /// Retry in the thread the failed attempt ran in.
+    #[default]
+    Reuse,
+    /// Start every retry in a new thread.
+    Fresh,
+}
+
+/// Retry and timeout rules chosen when a job is dispatched.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SupervisorJobPolicy {
+    #[serde(default = "default_job_max_attempts")]
+    pub(crate) max_attempts: u32,
+    /// Delay before the first retry; doubles for every further attempt.
+    #[serde(default = "default_job_retry_backoff_seconds")]
+    pub(crate) retry_backoff_seconds: u64,
+    /// Wall-clock limit for one running attempt.
+    #[serde(default)]
+    pub(crate) timeout_seconds: Option<u64>,
+    #[serde(default)]
+    pub(crate) retry_thread: SupervisorJobRetryThread,
+}
+
+impl Default for SupervisorJobPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_job_max_attempts(),
+            retry_backoff_seconds: default_job_retry_backoff_seconds(),
+            timeout_seconds: None,
+            retry_thread: SupervisorJobRetryThread::default(),
+        }
+    }
+}
+
+impl SupervisorJobPolicy {
+    pub(crate) const MAX_ATTEMPTS_LIMIT: u32 = 10;
+    const MAX_RETRY_DELAY_MS: i64 = 60 * 60 * 1_000;
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.max_attempts == 0 || self.max_attempts > Self::MAX_ATTEMPTS_LIMIT {
+            return Err(format!(
+                "max_attempts must be between 1 and {}",
+                Self::MAX_ATTEMPTS_LIMIT
+            ));
+        }
+        if self.timeout_seconds == Some(0) {
+            return Err("timeout_seconds must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Delay before starting attempt `attempt + 1`.
+    pub(crate) fn retry_delay_ms(&self, attempt: u32) -> i64 {
+        let base_ms = (self.retry_backoff_seconds as i64).saturating_mul(1_000);
+        let factor = 1i64 << attempt.saturating_sub(1).min(16);
+        base_ms.saturating_mul(factor).min(Self::MAX_RETRY_DELAY_MS)
     }
 }
 
@@ -160,6 +230,130 @@ pub(crate) struct SupervisorJobState {
     pub(crate) waiting_question_ids: Vec<String>,
     #[serde(default)]
     pub(crate) recent_events: Vec<SupervisorSubtaskEvent>,
+    #[serde(default)]
+    pub(crate) policy: SupervisorJobPolicy,
+    /// 1-based number of the current attempt; `0` while the job waits on its prerequisites
+    /// and for jobs recorded before retries existed.
+    #[serde(default)]
+    pub(crate) attempt: u32,
+    #[serde(default)]
+    pub(crate) next_retry_at_ms: Option<i64>,
+    /// Job ids that must complete before this job is dispatched.
+    #[serde(default)]
+    pub(crate) depends_on: Vec<String>,
+    /// Latest agent message of the job, available to dependents as `{{<job_id>.result}}`.
+    #[serde(default)]
+    pub(crate) result: Option<String>,
+    /// Set while the job is ready to start but waits for a free turn slot; orders the queue.
+    #[serde(default)]
+    pub(crate) queued_at_ms: Option<i64>,
+    /// 1-based position in the workspace's queue while `queued_at_ms` is set.
+    #[serde(default)]
+    pub(crate) queue_position: Option<u32>,
+    /// What the latest attempt did; `None` for jobs that never started.
+    #[serde(default)]
+    pub(crate) digest: Option<Box<SupervisorJobDigest>>,
+    /// Set while a job admitted from the queue is being dispatched, so it holds its turn slot
+    /// before the attempt is recorded as running.
+    #[serde(skip)]
+    pub(crate) starting: bool,
+}
+
+impl SupervisorJobState {
+    pub(crate) fn attempt_number(&self) -> u32 {
+        self.attempt.max(1)
+    }
+
+    /// Failed jobs with attempts left are retried; cancelled and completed jobs are final, and
+    /// so are jobs that failed before their first attempt started.
+    pub(crate) fn can_retry(&self) -> bool {
+        self.status == SupervisorJobStatus::Failed
+            && self.attempt > 0
+            && self.next_retry_at_ms.is_none()
+            && self.attempt < self.policy.max_attempts
+    }
+
+    pub(crate) fn waits_on_dependencies(&self) -> bool {
+        self.status == SupervisorJobStatus::Queued
+            && self.attempt == 0
+            && !self.depends_on.is_empty()
+            && self.queued_at_ms.is_none()
+            && !self.starting
+    }
+
+    /// Whether the job counts toward the concurrency limits.
+    pub(crate) fn holds_turn_slot(&self) -> bool {
+        match self.status {
+            SupervisorJobStatus::Running | SupervisorJobStatus::WaitingForUser => true,
+            SupervisorJobStatus::Queued => self.starting,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn waits_for_capacity(&self) -> bool {
+        self.status == SupervisorJobStatus::Queued && self.queued_at_ms.is_some()
+    }
+
+    /// Whether the job's status is owned by its retry/timeout policy, so late turn events from
+    /// an interrupted or failed attempt must not overwrite it.
+    pub(crate) fn awaits_policy(&self) -> bool {
+        match self.status {
+            SupervisorJobStatus::Failed => {
+                self.policy.max_attempts > 1 || self.policy.timeout_seconds.is_some()
+            }
+            SupervisorJobStatus::Queued => {
+                self.policy.max_attempts > 1 || self.queued_at_ms.is_some() || self.starting
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Final-result summary of a job attempt, shown by `/job <id>`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub(crate) struct SupervisorJobDigest {
+    /// Workspace `git status` taken after the job finished, without the files that were
+    /// already dirty in `baseline_files`; `None` until captured.
+    #[serde(default)]
+    pub(crate) files_changed: Option<Vec<SupervisorJobFileChange>>,
+    /// Workspace `git status` taken before the attempt started; `None` when it could not be
+    /// read.
+    #[serde(default)]
+    pub(crate) baseline_files: Option<Vec<SupervisorJobFileChange>>,
+    #[serde(default)]
+    pub(crate) files_error: Option<String>,
+    #[serde(default)]
+    pub(crate) commands: Vec<SupervisorJobCommand>,
+    #[serde(default)]
+    pub(crate) last_message: Option<String>,
+    #[serde(default)]
+    pub(crate) captured_at_ms: Option<i64>,
+}
+
+impl SupervisorJobDigest {
+    pub(crate) fn tests(&self) -> impl Iterator<Item = &SupervisorJobCommand> {
+        self.commands.iter().filter(|command| command.is_test)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SupervisorJobFileChange {
+    pub(crate) path: String,
+    pub(crate) status: String,
+    /// Line counts tell a further edit to an already dirty file apart from the baseline.
+    #[serde(default)]
+    pub(crate) additions: i64,
+    #[serde(default)]
+    pub(crate) deletions: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SupervisorJobCommand {
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i64>,
+    #[serde(default)]
+    pub(crate) is_test: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -221,6 +415,8 @@ pub(crate) struct SupervisorPendingApproval {
     #[serde(default)]
     pub(crate) item_id: Option<String>,
     pub(crate) request_id: String,
+    #[serde(default = "default_json_null")]
+    pub(crate) request_id_value: Value,
     pub(crate) method: String,
     #[serde(default = "default_json_null")]
     pub(crate) params: Value,
@@ -229,6 +425,18 @@ pub(crate) struct SupervisorPendingApproval {
     pub(crate) resolved_at_ms: Option<i64>,
 }
 
+impl SupervisorPendingApproval {
+    /// The JSON-RPC id exactly as the app-server sent it; entries persisted before the raw
+    /// value was stored fall back to the string form.
+    pub(crate) fn original_request_id(&self) -> Value {
+        if self.request_id_value.is_null() {
+            Value::String(self.request_id.clone())
+        } else {
+            self.request_id_value.clone()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum SupervisorChatMessageRole {
@@ -245,6 +453,40 @@ pub(crate) struct SupervisorChatMessage {
     pub(crate) created_at_ms: i64,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SupervisorScheduleCatchUp {
+    /// Run once for all occurrences missed while the supervisor was down.
+    #[default]
+    Once,
+    /// Drop missed occurrences and wait for the next one.
+    Skip,
+}
+
+/// A cron expression plus the dispatch it sends each time the expression fires.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub(crate) struct SupervisorSchedule {
+    pub(crate) id: String,
+    pub(crate) cron: String,
+    pub(crate) dispatch: SupervisorChatDispatchRequest,
+    #[serde(default)]
+    pub(crate) catch_up: SupervisorScheduleCatchUp,
+    #[serde(default)]
+    pub(crate) paused: bool,
+    pub(crate) created_at_ms: i64,
+    #[serde(default)]
+    pub(crate) next_run_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) last_run_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) last_error: Option<String>,
+    /// Jobs started by the most recent run.
+    #[serde(default)]
+    pub(crate) last_job_ids: Vec<String>,
+    #[serde(default)]
+    pub(crate) run_count: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub(crate) struct SupervisorState {
     #[serde(default)]
@@ -263,6 +505,8 @@ pub(crate) struct SupervisorState {
     pub(crate) pending_approvals: BTreeMap<String, SupervisorPendingApproval>,
     #[serde(default)]
     pub(crate) chat_history: Vec<SupervisorChatMessage>,
+    #[serde(default)]
+    pub(crate) schedules: BTreeMap<String, SupervisorSchedule>,
 }
 
 #[derive(Debug, Clone)]
@@ -310,6 +554,10 @@ pub(crate) enum SupervisorStateUpdate {
         message: SupervisorChatMessage,
         max_items: usize,
     },
+    UpsertSchedule(SupervisorSchedule),
+    RemoveSchedule {
+        schedule_id: String,
+    },
 }
 
 pub(crate) fn thread_map_key(workspace_id: &str, thread_id: &str) -> String {
@@ -402,7 +650,9 @@ pub(crate) fn apply_update(state: &mut SupervisorState, update: SupervisorStateU
                         }
                         job.completed_at_ms = None;
                     }
-                    SupervisorJobStatus::Completed | SupervisorJobStatus::Failed => {
+                    SupervisorJobStatus::Completed
+                    | SupervisorJobStatus::Failed
+                    | SupervisorJobStatus::Cancelled => {
                         job.completed_at_ms = Some(at_ms);
                     }
                     SupervisorJobStatus::Queued => {
@@ -488,6 +738,12 @@ pub(crate) fn apply_update(state: &mut SupervisorState, update: SupervisorStateU
                 state.chat_history.drain(0..to_drop);
             }
         }
+        SupervisorStateUpdate::UpsertSchedule(schedule) => {
+            state.schedules.insert(schedule.id.clone(), schedule);
+        }
+        SupervisorStateUpdate::RemoveSchedule { schedule_id } => {
+            state.schedules.remove(&schedule_id);
+        }
     }
 }
 
@@ -495,6 +751,14 @@ fn default_json_null() -> Value {
     Value::Null
 }
 
+fn default_job_max_attempts() -> u32 {
+    1
+}
+
+fn default_job_retry_backoff_seconds() -> u64 {
+    30
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -547,6 +811,15 @@ mod tests {
                 waiting_request_id: None,
                 waiting_question_ids: Vec::new(),
                 recent_events: Vec::new(),
+                policy: SupervisorJobPolicy::default(),
+                attempt: 1,
+                next_retry_at_ms: None,
+                depends_on: Vec::new(),
+                result: None,
+                queued_at_ms: None,
+                queue_position: None,
+                digest: None,
+                starting: false,
             }),
         ];
 
@@ -677,6 +950,7 @@ mod tests {
                 turn_id: Some("turn-1".to_string()),
                 item_id: Some("item-1".to_string()),
                 request_id: "42".to_string(),
+                request_id_value: json!(42),
                 method: "workspace/requestApproval".to_string(),
                 params: json!({ "mode": "full" }),
                 created_at_ms: 20,
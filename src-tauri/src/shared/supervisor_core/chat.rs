@@ -1,14 +1,26 @@
+use chrono::{Local, TimeZone};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use super::contract::SUPERVISOR_ACTION_CONTRACT_VERSION;
 use super::dispatch::{SupervisorDispatchBatchResult, SupervisorDispatchStatus};
-use super::{SupervisorActivityEntry, SupervisorChatMessage, SupervisorState};
+use super::schedule::SupervisorCronSchedule;
+use super::supervisor_loop::summarize_text;
+use super::{
+    SupervisorActivityEntry, SupervisorChatMessage, SupervisorJobCommand, SupervisorJobPolicy,
+    SupervisorJobRetryThread, SupervisorJobState, SupervisorJobStatus, SupervisorSchedule,
+    SupervisorScheduleCatchUp, SupervisorState,
+};
 
 pub(crate) const SUPERVISOR_CHAT_FEED_LIMIT: usize = 20;
 const STATUS_THREADS_PER_WORKSPACE_LIMIT: usize = 10;
+const JOB_DIGEST_LIST_LIMIT: usize = 10;
+const JOB_LAST_MESSAGE_MAX_CHARS: usize = 1_500;
+/// Stays below Telegram's 4096-character message limit, including the truncation marker.
+const JOB_MESSAGE_MAX_CHARS: usize = 4_000;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default)]
 pub(crate) struct SupervisorChatDispatchRequest {
     pub(crate) workspace_ids: Vec<String>,
     pub(crate) prompt: String,
@@ -20,14 +32,21 @@ pub(crate) struct SupervisorChatDispatchRequest {
     pub(crate) route_kind: Option<String>,
     pub(crate) route_reason: Option<String>,
     pub(crate) route_fallback: Option<String>,
+    pub(crate) policy: SupervisorJobPolicy,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum SupervisorChatCommand {
-    Dispatch(SupervisorChatDispatchRequest),
+    Dispatch(Box<SupervisorChatDispatchRequest>),
     Ack {
         signal_id: String,
     },
+    Cancel {
+        job_id: String,
+    },
+    Job {
+        job_id: String,
+    },
     Status {
         workspace_id: Option<String>,
         thread_id: Option<String>,
@@ -35,9 +54,29 @@ pub(crate) enum SupervisorChatCommand {
     Feed {
         needs_input_only: bool,
     },
+    Schedule(SupervisorScheduleCommand),
     Help,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SupervisorScheduleCommand {
+    Add {
+        cron: String,
+        catch_up: SupervisorScheduleCatchUp,
+        dispatch: Box<SupervisorChatDispatchRequest>,
+    },
+    List,
+    Remove {
+        schedule_id: String,
+    },
+    Pause {
+        schedule_id: String,
+    },
+    Resume {
+        schedule_id: String,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub(crate) struct SupervisorChatHistoryResponse {
     pub(crate) messages: Vec<SupervisorChatMessage>,
@@ -64,9 +103,18 @@ pub(crate) fn parse_supervisor_chat_command(input: &str) -> Result<SupervisorCha
     }
 
     match tokens[0].as_str() {
-        "/dispatch" => parse_dispatch_command(&tokens[1..]).map(SupervisorChatCommand::Dispatch),
+        "/dispatch" => parse_dispatch_command(&tokens[1..])
+            .map(|request| SupervisorChatCommand::Dispatch(Box::new(request))),
         "/ack" => parse_ack_command(&tokens[1..])
             .map(|signal_id| SupervisorChatCommand::Ack { signal_id }),
+        "/cancel" => parse_cancel_command(&tokens[1..])
+            .map(|job_id| SupervisorChatCommand::Cancel { job_id }),
+        "/job" => match &tokens[1..] {
+            [job_id] if !job_id.trim().is_empty() => Ok(SupervisorChatCommand::Job {
+                job_id: job_id.trim().to_string(),
+            }),
+            _ => Err("usage: /job <subtask_id>".to_string()),
+        },
         "/status" | "/статус" => {
             parse_status_command(&tokens[1..]).map(|(workspace_id, thread_id)| {
                 SupervisorChatCommand::Status {
@@ -77,6 +125,7 @@ pub(crate) fn parse_supervisor_chat_command(input: &str) -> Result<SupervisorCha
         }
         "/feed" => parse_feed_command(&tokens[1..])
             .map(|needs_input_only| SupervisorChatCommand::Feed { needs_input_only }),
+        "/schedule" => parse_schedule_command(&tokens[1..]).map(SupervisorChatCommand::Schedule),
         "/help" => {
             ensure_no_extra_args("/help", &tokens[1..])?;
             Ok(SupervisorChatCommand::Help)
@@ -95,6 +144,7 @@ fn parse_dispatch_command(tokens: &[String]) -> Result<SupervisorChatDispatchReq
     let mut model: Option<String> = None;
     let mut effort: Option<String> = None;
     let mut access_mode: Option<String> = None;
+    let mut policy = SupervisorJobPolicy::default();
     let mut index = 0usize;
 
     while index < tokens.len() {
@@ -159,9 +209,25 @@ fn parse_dispatch_command(tokens: &[String]) -> Result<SupervisorChatDispatchReq
                 }
                 access_mode = Some(parse_access_mode(next_access_mode)?);
             }
+            "--max-attempts" => {
+                policy.max_attempts = parse_number_flag(flag, value)?;
+            }
+            "--backoff" => {
+                policy.retry_backoff_seconds = parse_number_flag(flag, value)?;
+            }
+            "--timeout" => {
+                policy.timeout_seconds = Some(parse_number_flag(flag, value)?);
+            }
+            "--retry-thread" => {
+                policy.retry_thread = match value.trim() {
+                    "reuse" => SupervisorJobRetryThread::Reuse,
+                    "fresh" => SupervisorJobRetryThread::Fresh,
+                    _ => return Err("`--retry-thread` must be `reuse` or `fresh`".to_string()),
+                };
+            }
             unknown => {
                 return Err(format!(
-                    "unknown `/dispatch` flag `{unknown}` (supported: --ws --prompt --thread --dedupe --model --effort --access-mode)"
+                    "unknown `/dispatch` flag `{unknown}` (supported: --ws --prompt --thread --dedupe --model --effort --access-mode --max-attempts --backoff --timeout --retry-thread)"
                 ));
             }
         }
@@ -171,6 +237,7 @@ fn parse_dispatch_command(tokens: &[String]) -> Result<SupervisorChatDispatchReq
 
     let workspace_ids = workspace_ids.ok_or_else(|| "`--ws` is required".to_string())?;
     let prompt = prompt.ok_or_else(|| "`--prompt` is required".to_string())?;
+    policy.validate()?;
     Ok(SupervisorChatDispatchRequest {
         workspace_ids,
         prompt,
@@ -182,9 +249,17 @@ fn parse_dispatch_command(tokens: &[String]) -> Result<SupervisorChatDispatchReq
         route_kind: None,
         route_reason: None,
         route_fallback: None,
+        policy,
     })
 }
 
+fn parse_number_flag<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("`{flag}` must be a non-negative whole number"))
+}
+
 fn parse_access_mode(value: &str) -> Result<String, String> {
     match value {
         "read-only" | "current" | "full-access" => Ok(value.to_string()),
@@ -208,6 +283,66 @@ fn parse_ack_command(tokens: &[String]) -> Result<String, String> {
     Ok(signal_id.to_string())
 }
 
+fn parse_cancel_command(tokens: &[String]) -> Result<String, String> {
+    match tokens {
+        [job_id] if !job_id.trim().is_empty() => Ok(job_id.trim().to_string()),
+        _ => Err("usage: /cancel <subtask_id>".to_string()),
+    }
+}
+
+fn parse_schedule_command(tokens: &[String]) -> Result<SupervisorScheduleCommand, String> {
+    let usage = "usage: /schedule add \"<cron>\" --ws <ids> --prompt \"...\" [dispatch flags] [--catch-up once|skip] | /schedule list | /schedule remove|pause|resume <schedule_id>";
+    let schedule_id = |value: &String| {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(usage.to_string());
+        }
+        Ok(value.to_string())
+    };
+
+    match tokens {
+        [subcommand, cron, flags @ ..] if subcommand == "add" => {
+            SupervisorCronSchedule::parse(cron)?;
+            let mut catch_up = SupervisorScheduleCatchUp::default();
+            let mut dispatch_flags = Vec::with_capacity(flags.len());
+            for pair in flags.chunks(2) {
+                match pair {
+                    [flag, value] if flag == "--catch-up" => {
+                        catch_up = match value.trim() {
+                            "once" => SupervisorScheduleCatchUp::Once,
+                            "skip" => SupervisorScheduleCatchUp::Skip,
+                            _ => return Err("`--catch-up` must be `once` or `skip`".to_string()),
+                        };
+                    }
+                    [flag, ..] if flag == "--dedupe" => {
+                        return Err(
+                            "`--dedupe` is not supported by `/schedule`; every run is a new dispatch"
+                                .to_string(),
+                        );
+                    }
+                    _ => dispatch_flags.extend_from_slice(pair),
+                }
+            }
+            Ok(SupervisorScheduleCommand::Add {
+                cron: cron.trim().to_string(),
+                catch_up,
+                dispatch: Box::new(parse_dispatch_command(&dispatch_flags)?),
+            })
+        }
+        [subcommand] if subcommand == "list" => Ok(SupervisorScheduleCommand::List),
+        [subcommand, id] if subcommand == "remove" => Ok(SupervisorScheduleCommand::Remove {
+            schedule_id: schedule_id(id)?,
+        }),
+        [subcommand, id] if subcommand == "pause" => Ok(SupervisorScheduleCommand::Pause {
+            schedule_id: schedule_id(id)?,
+        }),
+        [subcommand, id] if subcommand == "resume" => Ok(SupervisorScheduleCommand::Resume {
+            schedule_id: schedule_id(id)?,
+        }),
+        _ => Err(usage.to_string()),
+    }
+}
+
 fn parse_status_command(tokens: &[String]) -> Result<(Option<String>, Option<String>), String> {
     let usage =
         "usage: /status [workspace_id] [thread_id] | /status [workspace_id] --thread <thread_id>";
@@ -297,6 +432,10 @@ pub(crate) fn build_dispatch_contract(
                 "route_kind": request.route_kind,
                 "route_reason": request.route_reason,
                 "route_fallback": request.route_fallback,
+                "max_attempts": request.policy.max_attempts,
+                "retry_backoff_seconds": request.policy.retry_backoff_seconds,
+                "timeout_seconds": request.policy.timeout_seconds,
+                "retry_thread": request.policy.retry_thread,
             })
         })
         .collect::<Vec<_>>();
@@ -310,12 +449,17 @@ pub(crate) fn build_dispatch_contract(
 pub(crate) fn format_help_message() -> String {
     [
         "Supported commands:",
-        "- /dispatch --ws ws-1,ws-2 --prompt \"...\" [--thread ...] [--dedupe ...] [--model ...] [--effort ...] [--access-mode read-only|current|full-access]",
+        "- /dispatch --ws ws-1,ws-2 --prompt \"...\" [--thread ...] [--dedupe ...] [--model ...] [--effort ...] [--access-mode read-only|current|full-access] [--max-attempts N] [--backoff <seconds>] [--timeout <seconds>] [--retry-thread reuse|fresh]",
         "- /ack <signal_id>",
+        "- /cancel <subtask_id>",
+        "- /job <subtask_id>",
         "- /status [workspace_id] [thread_id]",
         "- /status [workspace_id] --thread <thread_id>",
         "- /статус [workspace_id] [thread_id] (alias)",
         "- /feed [needs_input]",
+        "- /schedule add \"<cron>\" --ws ws-1 --prompt \"...\" [dispatch flags] [--catch-up once|skip]",
+        "- /schedule list",
+        "- /schedule remove|pause|resume <schedule_id>",
         "- /help",
         "",
         "Free-form chat:",
@@ -416,10 +560,22 @@ pub(crate) fn format_status_message(
             STATUS_THREADS_PER_WORKSPACE_LIMIT,
             "",
         );
-        lines.extend([
-            format!("- jobs: {job_count}"),
-            format!("- pending_signals: {workspace_pending_signals}"),
-        ]);
+        lines.push(format!("- jobs: {job_count}"));
+        let queued_jobs = collect_queued_jobs(state, Some(workspace_id));
+        if queued_jobs.is_empty() {
+            lines.push("- queue: empty".to_string());
+        } else {
+            lines.push("- queue:".to_string());
+            for job in queued_jobs {
+                lines.push(format!(
+                    "  - #{} `{}`: {}",
+                    job.queue_position.unwrap_or_default(),
+                    job.id,
+                    summarize_prompt(&job.description)
+                ));
+            }
+        }
+        lines.push(format!("- pending_signals: {workspace_pending_signals}"));
         return Ok(lines.join("\n"));
     }
 
@@ -428,6 +584,7 @@ pub(crate) fn format_status_message(
         format!("- workspaces: {}", state.workspaces.len()),
         format!("- threads: {}", state.threads.len()),
         format!("- jobs: {}", state.jobs.len()),
+        format!("- queued_jobs: {}", collect_queued_jobs(state, None).len()),
         format!("- pending_signals: {pending_signals}"),
         format!("- pending_approvals: {}", state.pending_approvals.len()),
         format!("- open_questions: {}", state.open_questions.len()),
@@ -446,6 +603,10 @@ pub(crate) fn format_status_message(
                 workspace.current_task.as_deref().unwrap_or("idle")
             ));
             lines.push(format!("    - threads: {} active", thread_summaries.len()));
+            let queued_count = collect_queued_jobs(state, Some(&workspace.id)).len();
+            if queued_count > 0 {
+                lines.push(format!("    - queued jobs: {queued_count}"));
+            }
             append_thread_details_lines(
                 &mut lines,
                 &thread_summaries,
@@ -458,6 +619,21 @@ pub(crate) fn format_status_message(
     Ok(lines.join("\n"))
 }
 
+/// Jobs waiting for a free turn slot, in queue order.
+fn collect_queued_jobs<'a>(
+    state: &'a SupervisorState,
+    workspace_id: Option<&str>,
+) -> Vec<&'a SupervisorJobState> {
+    let mut queued = state
+        .jobs
+        .values()
+        .filter(|job| job.waits_for_capacity())
+        .filter(|job| workspace_id.is_none_or(|workspace_id| job.workspace_id == workspace_id))
+        .collect::<Vec<_>>();
+    queued.sort_by_key(|job| (job.queue_position, job.queued_at_ms));
+    queued
+}
+
 #[derive(Debug, Clone)]
 struct StatusThreadSummary {
     id: String,
@@ -724,6 +900,196 @@ pub(crate) fn format_ack_message(signal_id: &str) -> String {
     format!("Signal `{signal_id}` acknowledged.")
 }
 
+pub(crate) fn format_schedule_list_message(schedules: &[SupervisorSchedule]) -> String {
+    if schedules.is_empty() {
+        return "No schedules. Add one with `/schedule add \"<cron>\" --ws <id> --prompt \"...\"`."
+            .to_string();
+    }
+
+    let mut lines = vec![format!("Schedules ({}):", schedules.len())];
+    for schedule in schedules {
+        let next_run = if schedule.paused {
+            "paused".to_string()
+        } else {
+            schedule
+                .next_run_at_ms
+                .map(|next_run_at_ms| format!("next {}", format_local_time(next_run_at_ms)))
+                .unwrap_or_else(|| "no upcoming run".to_string())
+        };
+        lines.push(format!(
+            "- `{}` `{}` -> {} ({next_run}, {} run(s)): {}",
+            schedule.id,
+            schedule.cron,
+            schedule.dispatch.workspace_ids.join(","),
+            schedule.run_count,
+            summarize_prompt(&schedule.dispatch.prompt)
+        ));
+        if let Some(error) = schedule.last_error.as_deref() {
+            lines.push(format!("  last error: {error}"));
+        }
+    }
+    lines.join("\n")
+}
+
+pub(crate) fn format_schedule_saved_message(schedule: &SupervisorSchedule, action: &str) -> String {
+    match (schedule.paused, schedule.next_run_at_ms) {
+        (true, _) => format!(
+            "Schedule `{}` {action}; it will not run until resumed.",
+            schedule.id
+        ),
+        (false, Some(next_run_at_ms)) => format!(
+            "Schedule `{}` {action}; next run at {}.",
+            schedule.id,
+            format_local_time(next_run_at_ms)
+        ),
+        (false, None) => format!(
+            "Schedule `{}` {action}, but `{}` has no upcoming run.",
+            schedule.id, schedule.cron
+        ),
+    }
+}
+
+fn summarize_prompt(prompt: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let trimmed = prompt.trim();
+    if trimmed.chars().count() <= MAX_CHARS {
+        return trimmed.to_string();
+    }
+    format!("{}...", trimmed.chars().take(MAX_CHARS).collect::<String>())
+}
+
+fn format_local_time(timestamp_ms: i64) -> String {
+    Local
+        .timestamp_millis_opt(timestamp_ms)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M %Z").to_string())
+        .unwrap_or_else(|| timestamp_ms.to_string())
+}
+
+/// Result digest of one job: files changed, commands and tests run, and the final answer.
+/// `job_id` may be a unique prefix of the subtask id.
+pub(crate) fn format_job_message(state: &SupervisorState, job_id: &str) -> Result<String, String> {
+    let job = match state.jobs.get(job_id) {
+        Some(job) => job,
+        None => {
+            let matches = state
+                .jobs
+                .values()
+                .filter(|job| job.id.starts_with(job_id))
+                .collect::<Vec<_>>();
+            match matches.as_slice() {
+                [job] => *job,
+                [] => return Err(format!("subtask `{job_id}` not found")),
+                _ => {
+                    return Err(format!(
+                        "`{job_id}` matches {} subtasks; use the full id",
+                        matches.len()
+                    ))
+                }
+            }
+        }
+    };
+
+    let mut lines = vec![
+        format!("Subtask `{}` in `{}`:", job.id, job.workspace_id),
+        format!(
+            "- status: {} (attempt {} of {})",
+            job_status_label(&job.status),
+            job.attempt_number(),
+            job.policy.max_attempts
+        ),
+        format!("- task: {}", summarize_prompt(&job.description)),
+    ];
+    if let Some(error) = job.error.as_deref() {
+        lines.push(format!("- error: {error}"));
+    }
+    let Some(digest) = job.digest.as_ref() else {
+        lines.push("- digest: not available (subtask has not started)".to_string());
+        return Ok(lines.join("\n"));
+    };
+
+    match (&digest.files_changed, &digest.files_error) {
+        (Some(files), _) if files.is_empty() => {
+            lines.push("- files changed: none".to_string());
+        }
+        (Some(files), _) => {
+            lines.push(format!("- files changed ({}):", files.len()));
+            for file in files.iter().take(JOB_DIGEST_LIST_LIMIT) {
+                lines.push(format!("  - {} {}", file.status, file.path));
+            }
+            if files.len() > JOB_DIGEST_LIST_LIMIT {
+                lines.push(format!(
+                    "  - ... {} more",
+                    files.len() - JOB_DIGEST_LIST_LIMIT
+                ));
+            }
+        }
+        (None, Some(error)) => lines.push(format!("- files changed: unavailable ({error})")),
+        (None, None) if job.status.is_terminal() => {
+            lines.push("- files changed: pending git status".to_string());
+        }
+        (None, None) => {
+            lines.push("- files changed: captured when the subtask finishes".to_string())
+        }
+    }
+
+    let tests = digest.tests().collect::<Vec<_>>();
+    lines.push(format!("- commands run: {}", digest.commands.len()));
+    let skipped = digest.commands.len().saturating_sub(JOB_DIGEST_LIST_LIMIT);
+    for command in &digest.commands[skipped..] {
+        lines.push(format!("  - {}", format_job_command(command)));
+    }
+    if tests.is_empty() {
+        lines.push("- tests: none detected".to_string());
+    } else {
+        lines.push(format!("- tests ({}):", tests.len()));
+        for command in &tests[tests.len().saturating_sub(JOB_DIGEST_LIST_LIMIT)..] {
+            lines.push(format!("  - {}", format_job_command(command)));
+        }
+    }
+    match digest.last_message.as_deref().or(job.result.as_deref()) {
+        Some(message) => lines.push(format!(
+            "- last message:\n{}",
+            summarize_text(message, JOB_LAST_MESSAGE_MAX_CHARS)
+        )),
+        None => lines.push("- last message: none".to_string()),
+    }
+    Ok(summarize_text(&lines.join("\n"), JOB_MESSAGE_MAX_CHARS))
+}
+
+fn format_job_command(command: &SupervisorJobCommand) -> String {
+    match command.exit_code {
+        Some(exit_code) => format!("`{}` (exit {exit_code})", command.command),
+        None => format!("`{}`", command.command),
+    }
+}
+
+fn job_status_label(status: &SupervisorJobStatus) -> &'static str {
+    match status {
+        SupervisorJobStatus::Queued => "queued",
+        SupervisorJobStatus::Running => "running",
+        SupervisorJobStatus::WaitingForUser => "waiting_for_user",
+        SupervisorJobStatus::Completed => "completed",
+        SupervisorJobStatus::Failed => "failed",
+        SupervisorJobStatus::Cancelled => "cancelled",
+    }
+}
+
+pub(crate) fn format_cancel_message(
+    job_id: &str,
+    interrupted_turn: Option<(&str, Result<(), String>)>,
+) -> String {
+    match interrupted_turn {
+        None => format!("Subtask `{job_id}` cancelled."),
+        Some((turn_id, Ok(()))) => {
+            format!("Subtask `{job_id}` cancelled; interrupted turn `{turn_id}`.")
+        }
+        Some((turn_id, Err(error))) => format!(
+            "Subtask `{job_id}` cancelled, but interrupting turn `{turn_id}` failed: {error}"
+        ),
+    }
+}
+
 pub(crate) fn format_dispatch_message(
     request: &SupervisorChatDispatchRequest,
     dispatch: &SupervisorDispatchBatchResult,
@@ -732,7 +1098,7 @@ pub(crate) fn format_dispatch_message(
     let dispatched = dispatch
         .results
         .iter()
-        .filter(|item| item.status == SupervisorDispatchStatus::Dispatched)
+        .filter(|item| item.status != SupervisorDispatchStatus::Failed)
         .count();
     let failed = total.saturating_sub(dispatched);
     let mut lines = vec![
@@ -750,14 +1116,20 @@ pub(crate) fn format_dispatch_message(
 
     for item in &dispatch.results {
         match item.status {
-            SupervisorDispatchStatus::Dispatched => lines.push(format!(
-                "- {}: started{}",
-                item.workspace_id,
-                if item.idempotent_replay {
-                    " (reused existing run)"
-                } else {
-                    ""
-                }
+            SupervisorDispatchStatus::Dispatched | SupervisorDispatchStatus::Applied => {
+                lines.push(format!(
+                    "- {}: started{}",
+                    item.workspace_id,
+                    if item.idempotent_replay {
+                        " (reused existing run)"
+                    } else {
+                        ""
+                    }
+                ))
+            }
+            SupervisorDispatchStatus::Queued => lines.push(format!(
+                "- {}: queued until a turn slot frees up (`{}`)",
+                item.workspace_id, item.action_id
             )),
             SupervisorDispatchStatus::Failed => lines.push(format!(
                 "- {}: failed to start ({})",
@@ -797,7 +1169,7 @@ fn workspace_summary_label(workspace: &super::SupervisorWorkspaceState) -> Strin
 
 #[cfg(test)]
 mod tests {
-    use super::super::dispatch::SupervisorDispatchActionResult;
+    use super::super::dispatch::{SupervisorActionType, SupervisorDispatchActionResult};
     use super::super::SupervisorHealth;
     use super::super::SupervisorOpenQuestion;
     use super::super::SupervisorPendingApproval;
@@ -833,6 +1205,95 @@ mod tests {
         assert!(payload.route_kind.is_none());
         assert!(payload.route_reason.is_none());
         assert!(payload.route_fallback.is_none());
+        assert_eq!(payload.policy, SupervisorJobPolicy::default());
+    }
+
+    #[test]
+    fn parses_dispatch_policy_flags_and_cancel_command() {
+        let command = parse_supervisor_chat_command(
+            "/dispatch --ws ws-1 --prompt \"run e2e\" --max-attempts 3 --backoff 5 --timeout 600 --retry-thread fresh",
+        )
+        .expect("parse command");
+        let SupervisorChatCommand::Dispatch(payload) = command else {
+            panic!("expected dispatch command");
+        };
+        assert_eq!(
+            payload.policy,
+            SupervisorJobPolicy {
+                max_attempts: 3,
+                retry_backoff_seconds: 5,
+                timeout_seconds: Some(600),
+                retry_thread: SupervisorJobRetryThread::Fresh,
+            }
+        );
+        let contract = build_dispatch_contract(&payload, "chat");
+        assert_eq!(contract["actions"][0]["max_attempts"], 3);
+        assert_eq!(contract["actions"][0]["retry_thread"], "fresh");
+
+        let error =
+            parse_supervisor_chat_command("/dispatch --ws ws-1 --prompt run --max-attempts 50")
+                .expect_err("too many attempts");
+        assert!(error.contains("max_attempts must be between 1 and 10"));
+
+        assert_eq!(
+            parse_supervisor_chat_command("/cancel chat-dispatch-1").expect("cancel"),
+            SupervisorChatCommand::Cancel {
+                job_id: "chat-dispatch-1".to_string(),
+            }
+        );
+        assert!(parse_supervisor_chat_command("/cancel").is_err());
+        assert_eq!(
+            parse_supervisor_chat_command("/job chat-dispatch-1").expect("job"),
+            SupervisorChatCommand::Job {
+                job_id: "chat-dispatch-1".to_string(),
+            }
+        );
+        assert!(parse_supervisor_chat_command("/job a b").is_err());
+    }
+
+    #[test]
+    fn parses_schedule_commands() {
+        let command = parse_supervisor_chat_command(
+            "/schedule add \"0 9 * * 1-5\" --ws ws-1 --prompt \"triage new issues\" --catch-up skip --timeout 900",
+        )
+        .expect("schedule add");
+        let SupervisorChatCommand::Schedule(SupervisorScheduleCommand::Add {
+            cron,
+            catch_up,
+            dispatch,
+        }) = command
+        else {
+            panic!("expected schedule add command");
+        };
+        assert_eq!(cron, "0 9 * * 1-5");
+        assert_eq!(catch_up, SupervisorScheduleCatchUp::Skip);
+        assert_eq!(dispatch.workspace_ids, vec!["ws-1".to_string()]);
+        assert_eq!(dispatch.prompt, "triage new issues");
+        assert_eq!(dispatch.policy.timeout_seconds, Some(900));
+
+        assert_eq!(
+            parse_supervisor_chat_command("/schedule list").expect("list"),
+            SupervisorChatCommand::Schedule(SupervisorScheduleCommand::List)
+        );
+        assert_eq!(
+            parse_supervisor_chat_command("/schedule pause sched-1").expect("pause"),
+            SupervisorChatCommand::Schedule(SupervisorScheduleCommand::Pause {
+                schedule_id: "sched-1".to_string(),
+            })
+        );
+
+        for invalid in [
+            "/schedule",
+            "/schedule remove",
+            "/schedule add \"0 25 * * *\" --ws ws-1 --prompt run",
+            "/schedule add @daily --ws ws-1 --prompt run --dedupe nightly",
+            "/schedule add @daily --ws ws-1 --prompt run --catch-up always",
+        ] {
+            assert!(
+                parse_supervisor_chat_command(invalid).is_err(),
+                "expected `{invalid}` to be rejected"
+            );
+        }
     }
 
     #[test]
@@ -917,11 +1378,13 @@ mod tests {
             route_kind: Some("workspace_metadata_match".to_string()),
             route_reason: Some("prompt matched workspace metadata".to_string()),
             route_fallback: Some("manual_dispatch".to_string()),
+            policy: SupervisorJobPolicy::default(),
         };
         let dispatch = SupervisorDispatchBatchResult {
             results: vec![
                 SupervisorDispatchActionResult {
                     action_id: "action-1".to_string(),
+                    action_type: SupervisorActionType::DispatchTurn,
                     workspace_id: "ws-1".to_string(),
                     dedupe_key: "ws-1:dispatch-1".to_string(),
                     status: SupervisorDispatchStatus::Dispatched,
@@ -932,6 +1395,7 @@ mod tests {
                 },
                 SupervisorDispatchActionResult {
                     action_id: "action-2".to_string(),
+                    action_type: SupervisorActionType::DispatchTurn,
                     workspace_id: "ws-2".to_string(),
                     dedupe_key: "ws-2:dispatch-1".to_string(),
                     status: SupervisorDispatchStatus::Failed,
@@ -1085,6 +1549,7 @@ mod tests {
                 turn_id: None,
                 item_id: None,
                 request_id: "1".to_string(),
+                request_id_value: json!(1),
                 method: "workspace/requestApproval".to_string(),
                 params: Value::Null,
                 created_at_ms: 13,
@@ -1139,9 +1604,22 @@ mod tests {
             needs_input: false,
             metadata: Value::Null,
         }];
+        state.jobs.insert(
+            "job-2".to_string(),
+            SupervisorJobState {
+                id: "job-2".to_string(),
+                workspace_id: "ws-1".to_string(),
+                description: "Fix flaky test".to_string(),
+                status: SupervisorJobStatus::Queued,
+                queued_at_ms: Some(50),
+                queue_position: Some(1),
+                ..Default::default()
+            },
+        );
 
         let message = format_status_message(&state, Some("ws-1"), None).expect("status");
         assert!(message.contains("Status for workspace"));
+        assert!(message.contains("- queue:\n  - #1 `job-2`: Fix flaky test"));
         assert!(message.contains("- threads: 1"));
         assert!(message.contains("- threads_detail (showing 1 of 1):"));
         assert!(message.contains(
@@ -1200,4 +1678,67 @@ mod tests {
         assert!(message.contains("- unread: 0"));
         assert!(message.contains("- active_turn_id: turn-7"));
     }
+
+    #[test]
+    fn formats_job_digest_message() {
+        let mut state = SupervisorState::default();
+        state.jobs.insert(
+            "chat-dispatch-12".to_string(),
+            SupervisorJobState {
+                id: "chat-dispatch-12".to_string(),
+                workspace_id: "ws-1".to_string(),
+                description: "Fix the retry loop".to_string(),
+                status: SupervisorJobStatus::Completed,
+                attempt: 1,
+                digest: Some(Box::new(super::super::SupervisorJobDigest {
+                    files_changed: Some(vec![super::super::SupervisorJobFileChange {
+                        path: "src/retry.rs".to_string(),
+                        status: "M".to_string(),
+                        additions: 3,
+                        deletions: 1,
+                    }]),
+                    commands: vec![
+                        SupervisorJobCommand {
+                            command: "rg retry".to_string(),
+                            exit_code: Some(0),
+                            is_test: false,
+                        },
+                        SupervisorJobCommand {
+                            command: "cargo test".to_string(),
+                            exit_code: Some(0),
+                            is_test: true,
+                        },
+                    ],
+                    last_message: Some("Retries now back off.".to_string()),
+                    captured_at_ms: Some(20),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            },
+        );
+
+        let message = format_job_message(&state, "chat-dispatch-1").expect("job by prefix");
+        assert!(message.contains("Subtask `chat-dispatch-12` in `ws-1`:"));
+        assert!(message.contains("- status: completed (attempt 1 of 1)"));
+        assert!(message.contains("- files changed (1):\n  - M src/retry.rs"));
+        assert!(message.contains("- commands run: 2"));
+        assert!(message.contains("- tests (1):\n  - `cargo test` (exit 0)"));
+        assert!(message.contains("- last message:\nRetries now back off."));
+        assert!(format_job_message(&state, "missing").is_err());
+
+        let job = state.jobs.get_mut("chat-dispatch-12").expect("job");
+        let digest = job.digest.as_mut().expect("digest");
+        digest.last_message = Some("x".repeat(10_000));
+        digest.commands = (0..200)
+            .map(|index| SupervisorJobCommand {
+                command: format!("cargo test {}", "y".repeat(300 + index)),
+                exit_code: Some(0),
+                is_test: true,
+            })
+            .collect();
+        let message = format_job_message(&state, "chat-dispatch-12").expect("long job");
+        assert!(message.chars().count() < 4096, "{}", message.len());
+        assert!(message.ends_with("..."));
+        assert!(!message.contains(&"x".repeat(JOB_LAST_MESSAGE_MAX_CHARS + 1)));
+    }
 }
@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use super::SupervisorJobState;
+use crate::types::{SupervisorConcurrencySettings, WorkspaceEntry};
+
+/// Turn limits in effect for supervisor jobs. `None` means unlimited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SupervisorConcurrencyPolicy {
+    pub(crate) max_writing_turns_per_workspace: Option<u32>,
+    pub(crate) workspace_limits: HashMap<String, Option<u32>>,
+    pub(crate) max_running_turns: Option<u32>,
+    /// Read-only jobs skip the per-workspace limit but still count toward the global cap.
+    pub(crate) exempt_read_only: bool,
+}
+
+impl Default for SupervisorConcurrencyPolicy {
+    fn default() -> Self {
+        Self::from_settings(&SupervisorConcurrencySettings::default(), [])
+    }
+}
+
+impl SupervisorConcurrencyPolicy {
+    pub(crate) fn from_settings<'a>(
+        settings: &SupervisorConcurrencySettings,
+        workspaces: impl IntoIterator<Item = &'a WorkspaceEntry>,
+    ) -> Self {
+        let limit = |value: u32| (value > 0).then_some(value);
+        Self {
+            max_writing_turns_per_workspace: limit(settings.max_writing_turns_per_workspace),
+            workspace_limits: workspaces
+                .into_iter()
+                .filter_map(|workspace| {
+                    workspace
+                        .settings
+                        .supervisor_max_writing_turns
+                        .map(|value| (workspace.id.clone(), limit(value)))
+                })
+                .collect(),
+            max_running_turns: limit(settings.max_running_turns),
+            exempt_read_only: settings.exempt_read_only,
+        }
+    }
+
+    fn workspace_limit(&self, workspace_id: &str) -> Option<u32> {
+        self.workspace_limits
+            .get(workspace_id)
+            .copied()
+            .unwrap_or(self.max_writing_turns_per_workspace)
+    }
+
+    fn counts_toward_workspace(&self, access_mode: Option<&str>) -> bool {
+        !(self.exempt_read_only && access_mode == Some("read-only"))
+    }
+}
+
+/// Running-turn bookkeeping used to decide, in FIFO order, which jobs may start now.
+/// Once a job is held back, later jobs it would compete with are held back too, so nothing
+/// overtakes the queue head.
+pub(crate) struct SupervisorConcurrencyGate<'a> {
+    policy: &'a SupervisorConcurrencyPolicy,
+    running_total: u32,
+    running_by_workspace: HashMap<String, u32>,
+    blocked_workspaces: HashSet<String>,
+    blocked_globally: bool,
+}
+
+impl<'a> SupervisorConcurrencyGate<'a> {
+    pub(crate) fn new<'j>(
+        policy: &'a SupervisorConcurrencyPolicy,
+        jobs: impl IntoIterator<Item = &'j SupervisorJobState>,
+    ) -> Self {
+        let mut gate = Self {
+            policy,
+            running_total: 0,
+            running_by_workspace: HashMap::new(),
+            blocked_workspaces: HashSet::new(),
+            blocked_globally: false,
+        };
+        for job in jobs.into_iter().filter(|job| job.holds_turn_slot()) {
+            gate.record_start(&job.workspace_id, job.access_mode.as_deref());
+        }
+        gate
+    }
+
+    /// Admits a turn if both the global cap and its workspace limit leave room, and counts it.
+    pub(crate) fn try_admit(&mut self, workspace_id: &str, access_mode: Option<&str>) -> bool {
+        let counted = self.policy.counts_toward_workspace(access_mode);
+        if self.blocked_globally || (counted && self.blocked_workspaces.contains(workspace_id)) {
+            return false;
+        }
+        if self
+            .policy
+            .max_running_turns
+            .is_some_and(|limit| self.running_total >= limit)
+        {
+            self.blocked_globally = true;
+            return false;
+        }
+        if counted {
+            let running = self
+                .running_by_workspace
+                .get(workspace_id)
+                .copied()
+                .unwrap_or(0);
+            if self
+                .policy
+                .workspace_limit(workspace_id)
+                .is_some_and(|limit| running >= limit)
+            {
+                self.blocked_workspaces.insert(workspace_id.to_string());
+                return false;
+            }
+        }
+        self.record_start(workspace_id, access_mode);
+        true
+    }
+
+    /// Counts a turn that already holds a slot without checking the limits.
+    pub(crate) fn hold(&mut self, workspace_id: &str, access_mode: Option<&str>) {
+        self.record_start(workspace_id, access_mode);
+    }
+
+    fn record_start(&mut self, workspace_id: &str, access_mode: Option<&str>) {
+        self.running_total += 1;
+        if self.policy.counts_toward_workspace(access_mode) {
+            *self
+                .running_by_workspace
+                .entry(workspace_id.to_string())
+                .or_default() += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SupervisorJobStatus;
+    use super::*;
+
+    fn running_job(id: &str, workspace_id: &str, access_mode: Option<&str>) -> SupervisorJobState {
+        SupervisorJobState {
+            id: id.to_string(),
+            workspace_id: workspace_id.to_string(),
+            status: SupervisorJobStatus::Running,
+            access_mode: access_mode.map(ToString::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn gate_applies_workspace_limit_read_only_exemption_and_global_cap() {
+        let policy = SupervisorConcurrencyPolicy {
+            max_writing_turns_per_workspace: Some(1),
+            workspace_limits: HashMap::from([("ws-wide".to_string(), None)]),
+            max_running_turns: Some(4),
+            exempt_read_only: true,
+        };
+        let jobs = [running_job("job-1", "ws-1", Some("current"))];
+        let mut gate = SupervisorConcurrencyGate::new(&policy, &jobs);
+
+        assert!(
+            !gate.try_admit("ws-1", None),
+            "ws-1 already runs a writing turn"
+        );
+        assert!(gate.try_admit("ws-1", Some("read-only")));
+        assert!(gate.try_admit("ws-2", Some("full-access")));
+        assert!(gate.try_admit("ws-wide", None), "override lifts the limit");
+        assert!(!gate.try_admit("ws-wide", None), "global cap of 4 reached");
+        assert!(
+            !gate.try_admit("ws-3", Some("read-only")),
+            "read-only jobs still count toward the global cap"
+        );
+    }
+
+    #[test]
+    fn gate_keeps_fifo_order_within_a_blocked_workspace() {
+        let policy = SupervisorConcurrencyPolicy {
+            exempt_read_only: false,
+            ..Default::default()
+        };
+        let mut gate = SupervisorConcurrencyGate::new(&policy, []);
+
+        assert!(gate.try_admit("ws-1", Some("read-only")));
+        assert!(!gate.try_admit("ws-1", Some("read-only")));
+        assert!(gate.try_admit("ws-2", None));
+        assert!(!gate.try_admit("ws-1", None));
+    }
+}
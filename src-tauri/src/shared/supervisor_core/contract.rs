@@ -3,21 +3,45 @@ use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::dispatch::SupervisorDispatchAction;
+use super::dispatch::{
+    SupervisorControlAction, SupervisorDispatchAction, SupervisorExecutableAction,
+};
+use super::{SupervisorJobPolicy, SupervisorJobRetryThread};
 
 pub(crate) const SUPERVISOR_ACTION_CONTRACT_VERSION: &str = "supervisor.dispatch.v1";
+pub(crate) const SUPERVISOR_ACTION_CONTRACT_VERSION_V2: &str = "supervisor.actions.v2";
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct SupervisorActionContract {
     pub(crate) version: String,
     pub(crate) actions: Vec<SupervisorPlannerAction>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub(crate) enum SupervisorPlannerAction {
     DispatchTurn(SupervisorDispatchTurnAction),
+    InterruptTurn(SupervisorInterruptTurnAction),
+    RespondApproval(SupervisorRespondApprovalAction),
+    AnswerQuestion(SupervisorAnswerQuestionAction),
+    ArchiveThread(SupervisorThreadControlAction),
+    CompactThread(SupervisorThreadControlAction),
+    AckSignal(SupervisorAckSignalAction),
+}
+
+impl SupervisorPlannerAction {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::DispatchTurn(_) => "dispatch_turn",
+            Self::InterruptTurn(_) => "interrupt_turn",
+            Self::RespondApproval(_) => "respond_approval",
+            Self::AnswerQuestion(_) => "answer_question",
+            Self::ArchiveThread(_) => "archive_thread",
+            Self::CompactThread(_) => "compact_thread",
+            Self::AckSignal(_) => "ack_signal",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -42,12 +66,79 @@ pub(crate) struct SupervisorDispatchTurnAction {
     pub(crate) route_reason: Option<String>,
     #[serde(default)]
     pub(crate) route_fallback: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_attempts: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) retry_backoff_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) timeout_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) retry_thread: Option<SupervisorJobRetryThread>,
+    /// `action_id`s of other `dispatch_turn` actions in the same contract that must complete
+    /// first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SupervisorInterruptTurnAction {
+    pub(crate) action_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    #[serde(default)]
+    pub(crate) dedupe_key: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SupervisorRespondApprovalAction {
+    pub(crate) action_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) request_id: Value,
+    pub(crate) decision: String,
+    #[serde(default)]
+    pub(crate) dedupe_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SupervisorAnswerQuestionAction {
+    pub(crate) action_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) request_id: Value,
+    pub(crate) answer: String,
+    #[serde(default)]
+    pub(crate) question_ids: Vec<String>,
+    #[serde(default)]
+    pub(crate) dedupe_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SupervisorThreadControlAction {
+    pub(crate) action_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    #[serde(default)]
+    pub(crate) dedupe_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SupervisorAckSignalAction {
+    pub(crate) action_id: String,
+    pub(crate) signal_id: String,
+    #[serde(default)]
+    pub(crate) dedupe_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct ValidatedSupervisorActionContract {
     pub(crate) version: String,
     pub(crate) dispatch_actions: Vec<SupervisorDispatchAction>,
+    pub(crate) actions: Vec<SupervisorExecutableAction>,
 }
 
 #[cfg(test)]
@@ -70,13 +161,16 @@ pub(crate) fn parse_supervisor_action_contract_value(
 pub(crate) fn validate_supervisor_action_contract(
     contract: SupervisorActionContract,
 ) -> Result<ValidatedSupervisorActionContract, String> {
-    let version = contract.version.trim();
-    if version != SUPERVISOR_ACTION_CONTRACT_VERSION {
-        return Err(format!(
-            "unsupported supervisor contract version `{}` (expected `{}`)",
-            version, SUPERVISOR_ACTION_CONTRACT_VERSION
-        ));
-    }
+    let version = match contract.version.trim() {
+        SUPERVISOR_ACTION_CONTRACT_VERSION => SUPERVISOR_ACTION_CONTRACT_VERSION,
+        SUPERVISOR_ACTION_CONTRACT_VERSION_V2 => SUPERVISOR_ACTION_CONTRACT_VERSION_V2,
+        other => {
+            return Err(format!(
+                "unsupported supervisor contract version `{}` (expected `{}` or `{}`)",
+                other, SUPERVISOR_ACTION_CONTRACT_VERSION, SUPERVISOR_ACTION_CONTRACT_VERSION_V2
+            ));
+        }
+    };
 
     if contract.actions.is_empty() {
         return Err("actions must contain at least one item".to_string());
@@ -84,51 +178,206 @@ pub(crate) fn validate_supervisor_action_contract(
 
     let mut seen_action_ids = HashSet::new();
     let mut seen_dedupe_keys = HashSet::new();
-    let mut dispatch_actions = Vec::with_capacity(contract.actions.len());
+    let mut dispatch_actions = Vec::new();
+    let mut actions = Vec::with_capacity(contract.actions.len());
 
     for action in contract.actions {
-        let dispatch_action = match action {
-            SupervisorPlannerAction::DispatchTurn(action) => {
-                normalize_dispatch_turn_action(action)?
-            }
+        if version == SUPERVISOR_ACTION_CONTRACT_VERSION
+            && !matches!(action, SupervisorPlannerAction::DispatchTurn(_))
+        {
+            return Err(format!(
+                "action type `{}` requires contract version `{}`",
+                action.type_name(),
+                SUPERVISOR_ACTION_CONTRACT_VERSION_V2
+            ));
+        }
+
+        let action = normalize_planner_action(action)?;
+        let (action_id, dedupe_scope, dedupe_token) = match &action {
+            SupervisorExecutableAction::DispatchTurn(action) => (
+                action.action_id.as_str(),
+                action.workspace_id.as_str(),
+                action
+                    .dedupe_key
+                    .as_deref()
+                    .unwrap_or(action.action_id.as_str()),
+            ),
+            SupervisorExecutableAction::Control(action) => (
+                action.action_id(),
+                action.dedupe_scope(),
+                action.dedupe_token(),
+            ),
         };
 
-        if !seen_action_ids.insert(dispatch_action.action_id.clone()) {
+        if !seen_action_ids.insert(action_id.to_string()) {
             return Err(format!(
                 "duplicate action_id `{}` in supervisor contract",
-                dispatch_action.action_id
+                action_id
             ));
         }
 
-        let dedupe_token = dispatch_action
-            .dedupe_key
-            .as_deref()
-            .map(str::trim)
-            .filter(|value| !value.is_empty())
-            .unwrap_or(dispatch_action.action_id.as_str());
-        let scoped_dedupe_key = format!("{}:{dedupe_token}", dispatch_action.workspace_id);
+        let scoped_dedupe_key = format!("{dedupe_scope}:{dedupe_token}");
         if !seen_dedupe_keys.insert(scoped_dedupe_key) {
             return Err(format!(
                 "duplicate dedupe key `{}` for workspace `{}`",
-                dedupe_token, dispatch_action.workspace_id
+                dedupe_token, dedupe_scope
             ));
         }
 
-        dispatch_actions.push(dispatch_action);
+        if let SupervisorExecutableAction::DispatchTurn(dispatch_action) = &action {
+            dispatch_actions.push(dispatch_action.clone());
+        }
+        actions.push(action);
     }
+    validate_dispatch_dependencies(&dispatch_actions)?;
 
     Ok(ValidatedSupervisorActionContract {
-        version: SUPERVISOR_ACTION_CONTRACT_VERSION.to_string(),
+        version: version.to_string(),
         dispatch_actions,
+        actions,
     })
 }
 
+/// Dependencies must name other `dispatch_turn` actions of the contract and form a DAG.
+fn validate_dispatch_dependencies(actions: &[SupervisorDispatchAction]) -> Result<(), String> {
+    let action_ids = actions
+        .iter()
+        .map(|action| action.action_id.as_str())
+        .collect::<HashSet<_>>();
+    for action in actions {
+        for dependency in &action.depends_on {
+            if dependency == &action.action_id {
+                return Err(format!(
+                    "action `{}` cannot depend on itself",
+                    action.action_id
+                ));
+            }
+            if !action_ids.contains(dependency.as_str()) {
+                return Err(format!(
+                    "action `{}` depends on unknown dispatch_turn action `{dependency}`",
+                    action.action_id
+                ));
+            }
+        }
+    }
+
+    let mut resolved = HashSet::new();
+    while resolved.len() < actions.len() {
+        let ready = actions
+            .iter()
+            .filter(|action| !resolved.contains(action.action_id.as_str()))
+            .filter(|action| {
+                action
+                    .depends_on
+                    .iter()
+                    .all(|dependency| resolved.contains(dependency.as_str()))
+            })
+            .map(|action| action.action_id.as_str())
+            .collect::<Vec<_>>();
+        if ready.is_empty() {
+            let mut cyclic = actions
+                .iter()
+                .map(|action| action.action_id.as_str())
+                .filter(|action_id| !resolved.contains(action_id))
+                .collect::<Vec<_>>();
+            cyclic.sort_unstable();
+            return Err(format!(
+                "dispatch dependencies contain a cycle between {}",
+                cyclic.join(", ")
+            ));
+        }
+        resolved.extend(ready);
+    }
+    Ok(())
+}
+
+fn normalize_planner_action(
+    action: SupervisorPlannerAction,
+) -> Result<SupervisorExecutableAction, String> {
+    let control = match action {
+        SupervisorPlannerAction::DispatchTurn(action) => {
+            return normalize_dispatch_turn_action(action)
+                .map(SupervisorExecutableAction::DispatchTurn);
+        }
+        SupervisorPlannerAction::InterruptTurn(action) => SupervisorControlAction::InterruptTurn {
+            action_id: normalize_required("action_id", action.action_id)?,
+            workspace_id: normalize_required("workspace_id", action.workspace_id)?,
+            thread_id: normalize_required("thread_id", action.thread_id)?,
+            turn_id: normalize_required("turn_id", action.turn_id)?,
+            dedupe_key: normalize_optional(action.dedupe_key),
+        },
+        SupervisorPlannerAction::RespondApproval(action) => {
+            let decision = normalize_required("decision", action.decision)?;
+            if !matches!(decision.as_str(), "accept" | "decline") {
+                return Err("decision must be one of `accept` or `decline`".to_string());
+            }
+            SupervisorControlAction::RespondApproval {
+                action_id: normalize_required("action_id", action.action_id)?,
+                workspace_id: normalize_required("workspace_id", action.workspace_id)?,
+                request_id: normalize_request_id(action.request_id)?,
+                decision,
+                dedupe_key: normalize_optional(action.dedupe_key),
+            }
+        }
+        SupervisorPlannerAction::AnswerQuestion(action) => {
+            SupervisorControlAction::AnswerQuestion {
+                action_id: normalize_required("action_id", action.action_id)?,
+                workspace_id: normalize_required("workspace_id", action.workspace_id)?,
+                request_id: normalize_request_id(action.request_id)?,
+                answer: normalize_required("answer", action.answer)?,
+                question_ids: action
+                    .question_ids
+                    .into_iter()
+                    .filter_map(|question_id| normalize_optional(Some(question_id)))
+                    .collect(),
+                dedupe_key: normalize_optional(action.dedupe_key),
+            }
+        }
+        SupervisorPlannerAction::ArchiveThread(action) => SupervisorControlAction::ArchiveThread {
+            action_id: normalize_required("action_id", action.action_id)?,
+            workspace_id: normalize_required("workspace_id", action.workspace_id)?,
+            thread_id: normalize_required("thread_id", action.thread_id)?,
+            dedupe_key: normalize_optional(action.dedupe_key),
+        },
+        SupervisorPlannerAction::CompactThread(action) => SupervisorControlAction::CompactThread {
+            action_id: normalize_required("action_id", action.action_id)?,
+            workspace_id: normalize_required("workspace_id", action.workspace_id)?,
+            thread_id: normalize_required("thread_id", action.thread_id)?,
+            dedupe_key: normalize_optional(action.dedupe_key),
+        },
+        SupervisorPlannerAction::AckSignal(action) => SupervisorControlAction::AckSignal {
+            action_id: normalize_required("action_id", action.action_id)?,
+            signal_id: normalize_required("signal_id", action.signal_id)?,
+            dedupe_key: normalize_optional(action.dedupe_key),
+        },
+    };
+    Ok(SupervisorExecutableAction::Control(control))
+}
+
 fn normalize_dispatch_turn_action(
     action: SupervisorDispatchTurnAction,
 ) -> Result<SupervisorDispatchAction, String> {
     let action_id = normalize_required("action_id", action.action_id)?;
     let workspace_id = normalize_required("workspace_id", action.workspace_id)?;
     let prompt = normalize_required("prompt", action.prompt)?;
+    let defaults = SupervisorJobPolicy::default();
+    let policy = SupervisorJobPolicy {
+        max_attempts: action.max_attempts.unwrap_or(defaults.max_attempts),
+        retry_backoff_seconds: action
+            .retry_backoff_seconds
+            .unwrap_or(defaults.retry_backoff_seconds),
+        timeout_seconds: action.timeout_seconds,
+        retry_thread: action.retry_thread.unwrap_or(defaults.retry_thread),
+    };
+    policy.validate()?;
+    let mut depends_on = Vec::new();
+    for dependency in action.depends_on {
+        if let Some(dependency) = normalize_optional(Some(dependency)) {
+            if !depends_on.contains(&dependency) {
+                depends_on.push(dependency);
+            }
+        }
+    }
 
     Ok(SupervisorDispatchAction {
         action_id,
@@ -142,9 +391,28 @@ fn normalize_dispatch_turn_action(
         route_kind: normalize_optional(action.route_kind),
         route_reason: normalize_optional(action.route_reason),
         route_fallback: normalize_optional(action.route_fallback),
+        policy,
+        depends_on,
+        wait_for_capacity: false,
     })
 }
 
+/// App-server request ids are JSON-RPC ids, so the JSON type is kept as given; the dispatch
+/// path swaps in the id of the tracked server request when the request key matches.
+fn normalize_request_id(value: Value) -> Result<Value, String> {
+    match value {
+        Value::Number(number) => Ok(Value::Number(number)),
+        Value::String(raw) => {
+            let trimmed = raw.trim();
+            if trimmed.is_empty() {
+                return Err("request_id is required".to_string());
+            }
+            Ok(Value::String(trimmed.to_string()))
+        }
+        _ => Err("request_id must be a string or number".to_string()),
+    }
+}
+
 fn normalize_required(field_name: &str, value: String) -> Result<String, String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -239,6 +507,78 @@ mod tests {
         assert_eq!(validated.dispatch_actions[1].dedupe_key, None);
     }
 
+    #[test]
+    fn dispatch_actions_carry_retry_and_timeout_policy() {
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION,
+            "actions": [{
+                "type": "dispatch_turn",
+                "action_id": "action-1",
+                "workspace_id": "ws-1",
+                "prompt": "run flaky suite",
+                "max_attempts": 3,
+                "retry_backoff_seconds": 10,
+                "timeout_seconds": 900,
+                "retry_thread": "fresh"
+            }]
+        });
+
+        let validated = parse_supervisor_action_contract_value(&value).expect("valid contract");
+        assert_eq!(
+            validated.dispatch_actions[0].policy,
+            SupervisorJobPolicy {
+                max_attempts: 3,
+                retry_backoff_seconds: 10,
+                timeout_seconds: Some(900),
+                retry_thread: SupervisorJobRetryThread::Fresh,
+            }
+        );
+
+        let mut invalid = value.clone();
+        invalid["actions"][0]["max_attempts"] = json!(0);
+        let error = parse_supervisor_action_contract_value(&invalid).expect_err("invalid attempts");
+        assert!(error.contains("max_attempts"));
+    }
+
+    #[test]
+    fn validates_dispatch_dependencies() {
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION,
+            "actions": [
+                {
+                    "type": "dispatch_turn",
+                    "action_id": "implement",
+                    "workspace_id": "ws-1",
+                    "prompt": "implement the feature"
+                },
+                {
+                    "type": "dispatch_turn",
+                    "action_id": "review",
+                    "workspace_id": "ws-2",
+                    "prompt": "review: {{implement.result}}",
+                    "depends_on": [" implement ", "implement"]
+                }
+            ]
+        });
+
+        let validated = parse_supervisor_action_contract_value(&value).expect("valid contract");
+        assert!(validated.dispatch_actions[0].depends_on.is_empty());
+        assert_eq!(validated.dispatch_actions[1].depends_on, vec!["implement"]);
+
+        let mut unknown = value.clone();
+        unknown["actions"][1]["depends_on"] = json!(["deploy"]);
+        let error = parse_supervisor_action_contract_value(&unknown).expect_err("unknown dep");
+        assert!(error.contains("unknown dispatch_turn action `deploy`"));
+
+        let mut cyclic = value.clone();
+        cyclic["actions"][0]["depends_on"] = json!(["review"]);
+        let error = parse_supervisor_action_contract_value(&cyclic).expect_err("cycle");
+        assert_eq!(
+            error,
+            "dispatch dependencies contain a cycle between implement, review"
+        );
+    }
+
     #[test]
     fn rejects_unknown_contract_version() {
         let value = json!({
@@ -432,4 +772,167 @@ mod tests {
         assert_eq!(validated.dispatch_actions.len(), 1);
         assert_eq!(validated.dispatch_actions[0].action_id, "action-1");
     }
+
+    #[test]
+    fn v1_contract_rejects_control_actions() {
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION,
+            "actions": [{
+                "type": "interrupt_turn",
+                "action_id": "action-1",
+                "workspace_id": "ws-1",
+                "thread_id": "thread-1",
+                "turn_id": "turn-1"
+            }]
+        });
+
+        let error = parse_supervisor_action_contract_value(&value).expect_err("v1 control");
+        assert_eq!(
+            error,
+            "action type `interrupt_turn` requires contract version `supervisor.actions.v2`"
+        );
+    }
+
+    #[test]
+    fn v2_contract_validates_mixed_actions_in_order() {
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION_V2,
+            "actions": [
+                {
+                    "type": "dispatch_turn",
+                    "action_id": "action-1",
+                    "workspace_id": "ws-1",
+                    "prompt": "run tests"
+                },
+                {
+                    "type": "respond_approval",
+                    "action_id": "action-2",
+                    "workspace_id": " ws-1 ",
+                    "request_id": " 42 ",
+                    "decision": "accept"
+                },
+                {
+                    "type": "answer_question",
+                    "action_id": "action-3",
+                    "workspace_id": "ws-2",
+                    "request_id": "req-7",
+                    "answer": " use main ",
+                    "question_ids": ["q1", " "]
+                },
+                {
+                    "type": "compact_thread",
+                    "action_id": "action-4",
+                    "workspace_id": "ws-2",
+                    "thread_id": "thread-2"
+                },
+                {
+                    "type": "ack_signal",
+                    "action_id": "action-5",
+                    "signal_id": "approval:ws-1:42"
+                }
+            ]
+        });
+
+        let validated = parse_supervisor_action_contract_value(&value).expect("valid v2");
+
+        assert_eq!(validated.version, SUPERVISOR_ACTION_CONTRACT_VERSION_V2);
+        assert_eq!(validated.dispatch_actions.len(), 1);
+        assert_eq!(validated.actions.len(), 5);
+        assert!(matches!(
+            validated.actions[0],
+            SupervisorExecutableAction::DispatchTurn(_)
+        ));
+        assert_eq!(
+            validated.actions[1],
+            SupervisorExecutableAction::Control(SupervisorControlAction::RespondApproval {
+                action_id: "action-2".to_string(),
+                workspace_id: "ws-1".to_string(),
+                request_id: json!("42"),
+                decision: "accept".to_string(),
+                dedupe_key: None,
+            })
+        );
+        assert_eq!(
+            validated.actions[2],
+            SupervisorExecutableAction::Control(SupervisorControlAction::AnswerQuestion {
+                action_id: "action-3".to_string(),
+                workspace_id: "ws-2".to_string(),
+                request_id: json!("req-7"),
+                answer: "use main".to_string(),
+                question_ids: vec!["q1".to_string()],
+                dedupe_key: None,
+            })
+        );
+    }
+
+    #[test]
+    fn v2_contract_rejects_unknown_approval_decision() {
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION_V2,
+            "actions": [{
+                "type": "respond_approval",
+                "action_id": "action-1",
+                "workspace_id": "ws-1",
+                "request_id": 1,
+                "decision": "maybe"
+            }]
+        });
+
+        let error = parse_supervisor_action_contract_value(&value).expect_err("bad decision");
+        assert_eq!(error, "decision must be one of `accept` or `decline`");
+    }
+
+    #[test]
+    fn v2_contract_rejects_invalid_request_id_and_missing_fields() {
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION_V2,
+            "actions": [{
+                "type": "answer_question",
+                "action_id": "action-1",
+                "workspace_id": "ws-1",
+                "request_id": { "id": 1 },
+                "answer": "yes"
+            }]
+        });
+        let error = parse_supervisor_action_contract_value(&value).expect_err("bad request id");
+        assert_eq!(error, "request_id must be a string or number");
+
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION_V2,
+            "actions": [{
+                "type": "archive_thread",
+                "action_id": "action-1",
+                "workspace_id": "ws-1"
+            }]
+        });
+        let error = parse_supervisor_action_contract_value(&value).expect_err("missing thread");
+        assert!(error.contains("missing field `thread_id`"));
+    }
+
+    #[test]
+    fn v2_contract_rejects_duplicate_dedupe_key_across_action_types() {
+        let value = json!({
+            "version": SUPERVISOR_ACTION_CONTRACT_VERSION_V2,
+            "actions": [
+                {
+                    "type": "dispatch_turn",
+                    "action_id": "action-1",
+                    "workspace_id": "ws-1",
+                    "prompt": "run",
+                    "dedupe_key": "same"
+                },
+                {
+                    "type": "interrupt_turn",
+                    "action_id": "action-2",
+                    "workspace_id": "ws-1",
+                    "thread_id": "thread-1",
+                    "turn_id": "turn-1",
+                    "dedupe_key": "same"
+                }
+            ]
+        });
+
+        let error = parse_supervisor_action_contract_value(&value).expect_err("duplicate");
+        assert!(error.contains("duplicate dedupe key `same` for workspace `ws-1`"));
+    }
 }
@@ -9,6 +9,9 @@ use tokio::sync::Mutex;
 
 use crate::backend::app_server::WorkspaceSession;
 
+use super::supervisor_loop::{now_timestamp_ms, SupervisorLoop};
+use super::SupervisorJobPolicy;
+
 type DispatchFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -32,6 +35,165 @@ pub(crate) struct SupervisorDispatchAction {
     pub(crate) route_reason: Option<String>,
     #[serde(default)]
     pub(crate) route_fallback: Option<String>,
+    #[serde(default)]
+    pub(crate) policy: SupervisorJobPolicy,
+    #[serde(default)]
+    pub(crate) depends_on: Vec<String>,
+    /// Set by admission control when the workspace has no free turn slot; never part of a
+    /// contract.
+    #[serde(skip)]
+    pub(crate) wait_for_capacity: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SupervisorControlAction {
+    InterruptTurn {
+        action_id: String,
+        workspace_id: String,
+        thread_id: String,
+        turn_id: String,
+        dedupe_key: Option<String>,
+    },
+    RespondApproval {
+        action_id: String,
+        workspace_id: String,
+        request_id: Value,
+        decision: String,
+        dedupe_key: Option<String>,
+    },
+    AnswerQuestion {
+        action_id: String,
+        workspace_id: String,
+        request_id: Value,
+        answer: String,
+        question_ids: Vec<String>,
+        dedupe_key: Option<String>,
+    },
+    ArchiveThread {
+        action_id: String,
+        workspace_id: String,
+        thread_id: String,
+        dedupe_key: Option<String>,
+    },
+    CompactThread {
+        action_id: String,
+        workspace_id: String,
+        thread_id: String,
+        dedupe_key: Option<String>,
+    },
+    AckSignal {
+        action_id: String,
+        signal_id: String,
+        dedupe_key: Option<String>,
+    },
+}
+
+impl SupervisorControlAction {
+    pub(crate) fn action_id(&self) -> &str {
+        match self {
+            Self::InterruptTurn { action_id, .. }
+            | Self::RespondApproval { action_id, .. }
+            | Self::AnswerQuestion { action_id, .. }
+            | Self::ArchiveThread { action_id, .. }
+            | Self::CompactThread { action_id, .. }
+            | Self::AckSignal { action_id, .. } => action_id,
+        }
+    }
+
+    /// Signal acknowledgements are not bound to a workspace, so they return an empty id.
+    pub(crate) fn workspace_id(&self) -> &str {
+        match self {
+            Self::InterruptTurn { workspace_id, .. }
+            | Self::RespondApproval { workspace_id, .. }
+            | Self::AnswerQuestion { workspace_id, .. }
+            | Self::ArchiveThread { workspace_id, .. }
+            | Self::CompactThread { workspace_id, .. } => workspace_id,
+            Self::AckSignal { .. } => "",
+        }
+    }
+
+    pub(crate) fn thread_id(&self) -> Option<&str> {
+        match self {
+            Self::InterruptTurn { thread_id, .. }
+            | Self::ArchiveThread { thread_id, .. }
+            | Self::CompactThread { thread_id, .. } => Some(thread_id),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn action_type(&self) -> SupervisorActionType {
+        match self {
+            Self::InterruptTurn { .. } => SupervisorActionType::InterruptTurn,
+            Self::RespondApproval { .. } => SupervisorActionType::RespondApproval,
+            Self::AnswerQuestion { .. } => SupervisorActionType::AnswerQuestion,
+            Self::ArchiveThread { .. } => SupervisorActionType::ArchiveThread,
+            Self::CompactThread { .. } => SupervisorActionType::CompactThread,
+            Self::AckSignal { .. } => SupervisorActionType::AckSignal,
+        }
+    }
+
+    pub(crate) fn dedupe_token(&self) -> &str {
+        let dedupe_key = match self {
+            Self::InterruptTurn { dedupe_key, .. }
+            | Self::RespondApproval { dedupe_key, .. }
+            | Self::AnswerQuestion { dedupe_key, .. }
+            | Self::ArchiveThread { dedupe_key, .. }
+            | Self::CompactThread { dedupe_key, .. }
+            | Self::AckSignal { dedupe_key, .. } => dedupe_key,
+        };
+        dedupe_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .unwrap_or(self.action_id())
+    }
+
+    /// Scope used for dedupe checks; signal acks share a single supervisor-wide scope.
+    pub(crate) fn dedupe_scope(&self) -> &str {
+        match self {
+            Self::AckSignal { .. } => SUPERVISOR_CONTROL_SCOPE,
+            _ => self.workspace_id(),
+        }
+    }
+
+    fn idempotency_key(&self) -> String {
+        format!("{}:{}", self.dedupe_scope(), self.dedupe_token())
+    }
+}
+
+const SUPERVISOR_CONTROL_SCOPE: &str = "supervisor";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SupervisorExecutableAction {
+    DispatchTurn(SupervisorDispatchAction),
+    Control(SupervisorControlAction),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SupervisorActionType {
+    #[default]
+    DispatchTurn,
+    InterruptTurn,
+    RespondApproval,
+    AnswerQuestion,
+    ArchiveThread,
+    CompactThread,
+    AckSignal,
+}
+
+impl SupervisorActionType {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::DispatchTurn => "dispatch_turn",
+            Self::InterruptTurn => "interrupt_turn",
+            Self::RespondApproval => "respond_approval",
+            Self::AnswerQuestion => "answer_question",
+            Self::ArchiveThread => "archive_thread",
+            Self::CompactThread => "compact_thread",
+            Self::AckSignal => "ack_signal",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -39,12 +201,17 @@ pub(crate) struct SupervisorDispatchAction {
 pub(crate) enum SupervisorDispatchStatus {
     #[default]
     Dispatched,
+    Applied,
+    /// Accepted, but held until the actions it depends on complete.
+    Queued,
     Failed,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub(crate) struct SupervisorDispatchActionResult {
     pub(crate) action_id: String,
+    #[serde(default)]
+    pub(crate) action_type: SupervisorActionType,
     pub(crate) workspace_id: String,
     pub(crate) dedupe_key: String,
     pub(crate) status: SupervisorDispatchStatus,
@@ -103,15 +270,50 @@ pub(crate) trait SupervisorDispatchBackend {
         effort: Option<&'a str>,
         access_mode: Option<&'a str>,
     ) -> DispatchFuture<'a, Result<Value, String>>;
+    fn interrupt_turn<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        thread_id: &'a str,
+        turn_id: &'a str,
+    ) -> DispatchFuture<'a, Result<Value, String>>;
+    fn respond_to_request<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        request_id: &'a Value,
+        result: Value,
+    ) -> DispatchFuture<'a, Result<(), String>>;
+    fn archive_thread<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        thread_id: &'a str,
+    ) -> DispatchFuture<'a, Result<Value, String>>;
+    fn compact_thread<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        thread_id: &'a str,
+    ) -> DispatchFuture<'a, Result<Value, String>>;
+    fn ack_signal<'a>(&'a self, signal_id: &'a str) -> DispatchFuture<'a, Result<(), String>>;
 }
 
 pub(crate) struct WorkspaceSessionDispatchBackend<'a> {
     sessions: &'a Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    supervisor_loop: Option<&'a Arc<Mutex<SupervisorLoop>>>,
 }
 
 impl<'a> WorkspaceSessionDispatchBackend<'a> {
     pub(crate) fn new(sessions: &'a Mutex<HashMap<String, Arc<WorkspaceSession>>>) -> Self {
-        Self { sessions }
+        Self {
+            sessions,
+            supervisor_loop: None,
+        }
+    }
+
+    pub(crate) fn with_supervisor_loop(
+        mut self,
+        supervisor_loop: &'a Arc<Mutex<SupervisorLoop>>,
+    ) -> Self {
+        self.supervisor_loop = Some(supervisor_loop);
+        self
     }
 
     async fn session_for_workspace(
@@ -183,6 +385,74 @@ impl SupervisorDispatchBackend for WorkspaceSessionDispatchBackend<'_> {
             session.send_request("turn/start", params).await
         })
     }
+
+    fn interrupt_turn<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        thread_id: &'a str,
+        turn_id: &'a str,
+    ) -> DispatchFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let session = self.session_for_workspace(workspace_id).await?;
+            let params = json!({ "threadId": thread_id, "turnId": turn_id });
+            session.send_request("turn/interrupt", params).await
+        })
+    }
+
+    fn respond_to_request<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        request_id: &'a Value,
+        result: Value,
+    ) -> DispatchFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let session = self.session_for_workspace(workspace_id).await?;
+            session.send_response(request_id.clone(), result).await
+        })
+    }
+
+    fn archive_thread<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        thread_id: &'a str,
+    ) -> DispatchFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let session = self.session_for_workspace(workspace_id).await?;
+            let params = json!({ "threadId": thread_id });
+            session.send_request("thread/archive", params).await
+        })
+    }
+
+    fn compact_thread<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        thread_id: &'a str,
+    ) -> DispatchFuture<'a, Result<Value, String>> {
+        Box::pin(async move {
+            let session = self.session_for_workspace(workspace_id).await?;
+            let params = json!({ "threadId": thread_id });
+            session.send_request("thread/compact/start", params).await
+        })
+    }
+
+    fn ack_signal<'a>(&'a self, signal_id: &'a str) -> DispatchFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let supervisor_loop = self
+                .supervisor_loop
+                .ok_or_else(|| "supervisor loop is not available".to_string())?;
+            let mut supervisor_loop = supervisor_loop.lock().await;
+            let signal_exists = supervisor_loop
+                .snapshot()
+                .signals
+                .iter()
+                .any(|signal| signal.id == signal_id);
+            if !signal_exists {
+                return Err(format!("signal `{signal_id}` not found"));
+            }
+            supervisor_loop.ack_signal(signal_id, now_timestamp_ms());
+            Ok(())
+        })
+    }
 }
 
 fn resolve_access_mode(access_mode: Option<&str>) -> &str {
@@ -233,6 +503,7 @@ impl SupervisorDispatchExecutor {
         self.idempotency.snapshot()
     }
 
+    #[cfg(test)]
     pub(crate) async fn dispatch_batch<B>(
         &mut self,
         backend: &B,
@@ -248,6 +519,139 @@ impl SupervisorDispatchExecutor {
         SupervisorDispatchBatchResult { results }
     }
 
+    /// Executes a mixed batch of dispatch and control actions in contract order.
+    pub(crate) async fn execute_batch<B>(
+        &mut self,
+        backend: &B,
+        actions: Vec<SupervisorExecutableAction>,
+    ) -> SupervisorDispatchBatchResult
+    where
+        B: SupervisorDispatchBackend,
+    {
+        let mut results = Vec::with_capacity(actions.len());
+        for action in actions {
+            let result = match action {
+                SupervisorExecutableAction::DispatchTurn(action) => {
+                    self.dispatch_action(backend, action).await
+                }
+                SupervisorExecutableAction::Control(action) => {
+                    self.execute_control_action(backend, action).await
+                }
+            };
+            results.push(result);
+        }
+        SupervisorDispatchBatchResult { results }
+    }
+
+    async fn execute_control_action<B>(
+        &mut self,
+        backend: &B,
+        action: SupervisorControlAction,
+    ) -> SupervisorDispatchActionResult
+    where
+        B: SupervisorDispatchBackend,
+    {
+        let idempotency_key = action.idempotency_key();
+        if let Some(cached) = self.idempotency.get(&idempotency_key) {
+            let mut replay = cached.clone();
+            replay.action_id = action.action_id().to_string();
+            replay.idempotent_replay = true;
+            return replay;
+        }
+
+        let mut result = SupervisorDispatchActionResult {
+            action_id: action.action_id().to_string(),
+            action_type: action.action_type(),
+            workspace_id: action.workspace_id().to_string(),
+            dedupe_key: action.dedupe_token().to_string(),
+            status: SupervisorDispatchStatus::Applied,
+            thread_id: action.thread_id().map(ToOwned::to_owned),
+            turn_id: None,
+            error: None,
+            idempotent_replay: false,
+        };
+
+        let outcome = match &action {
+            SupervisorControlAction::InterruptTurn {
+                workspace_id,
+                thread_id,
+                turn_id,
+                ..
+            } => {
+                result.turn_id = Some(turn_id.clone());
+                backend
+                    .interrupt_turn(workspace_id, thread_id, turn_id)
+                    .await
+                    .and_then(ensure_response_ok)
+            }
+            SupervisorControlAction::RespondApproval {
+                workspace_id,
+                request_id,
+                decision,
+                ..
+            } => {
+                backend
+                    .respond_to_request(workspace_id, request_id, json!({ "decision": decision }))
+                    .await
+            }
+            SupervisorControlAction::AnswerQuestion {
+                workspace_id,
+                request_id,
+                answer,
+                question_ids,
+                ..
+            } => {
+                let mut answers = serde_json::Map::new();
+                if question_ids.is_empty() {
+                    answers.insert("response".to_string(), json!({ "answers": [answer] }));
+                } else {
+                    for question_id in question_ids {
+                        answers.insert(question_id.clone(), json!({ "answers": [answer] }));
+                    }
+                }
+                backend
+                    .respond_to_request(
+                        workspace_id,
+                        request_id,
+                        json!({ "answers": Value::Object(answers) }),
+                    )
+                    .await
+            }
+            SupervisorControlAction::ArchiveThread {
+                workspace_id,
+                thread_id,
+                ..
+            } => backend
+                .archive_thread(workspace_id, thread_id)
+                .await
+                .and_then(ensure_response_ok),
+            SupervisorControlAction::CompactThread {
+                workspace_id,
+                thread_id,
+                ..
+            } => backend
+                .compact_thread(workspace_id, thread_id)
+                .await
+                .and_then(ensure_response_ok),
+            SupervisorControlAction::AckSignal { signal_id, .. } => {
+                backend.ack_signal(signal_id).await
+            }
+        };
+
+        // Failed control actions are not cached so callers can retry them once the
+        // underlying condition (for example a disconnected workspace) clears.
+        match outcome {
+            Ok(()) => {
+                self.idempotency.insert(idempotency_key, result.clone());
+            }
+            Err(error) => {
+                result.status = SupervisorDispatchStatus::Failed;
+                result.error = Some(error);
+            }
+        }
+        result
+    }
+
     async fn dispatch_action<B>(
         &mut self,
         backend: &B,
@@ -261,6 +665,7 @@ impl SupervisorDispatchExecutor {
             Err(error) => {
                 return SupervisorDispatchActionResult {
                     action_id: action.action_id,
+                    action_type: SupervisorActionType::DispatchTurn,
                     workspace_id: action.workspace_id,
                     dedupe_key: action.dedupe_key.unwrap_or_default(),
                     status: SupervisorDispatchStatus::Failed,
@@ -272,6 +677,20 @@ impl SupervisorDispatchExecutor {
             }
         };
 
+        if !action.depends_on.is_empty() || action.wait_for_capacity {
+            return SupervisorDispatchActionResult {
+                action_id: normalized.action_id,
+                action_type: SupervisorActionType::DispatchTurn,
+                workspace_id: normalized.workspace_id,
+                dedupe_key: normalized.dedupe_token,
+                status: SupervisorDispatchStatus::Queued,
+                thread_id: normalized.thread_id,
+                turn_id: None,
+                error: None,
+                idempotent_replay: false,
+            };
+        }
+
         let idempotency_key = normalized.idempotency_key();
         if let Some(cached) = self.idempotency.get(&idempotency_key) {
             let mut replay = cached.clone();
@@ -280,89 +699,110 @@ impl SupervisorDispatchExecutor {
             return replay;
         }
 
-        let result = self.dispatch_normalized(backend, &normalized).await;
+        let result = dispatch_normalized(backend, &normalized).await;
         self.idempotency.insert(idempotency_key, result.clone());
         result
     }
+}
 
-    async fn dispatch_normalized<B>(
-        &self,
-        backend: &B,
-        action: &NormalizedDispatchAction,
-    ) -> SupervisorDispatchActionResult
-    where
-        B: SupervisorDispatchBackend,
-    {
-        let thread_id = match self.ensure_thread(backend, action).await {
-            Ok(value) => value,
-            Err(error) => {
-                return failed_dispatch_result(action, error, None, None, false);
-            }
-        };
+/// Starts another attempt of an action that already ran. Retries bypass the idempotency
+/// store on purpose: the cached entry is the result of the attempt being retried.
+pub(crate) async fn redispatch_action<B>(
+    backend: &B,
+    action: SupervisorDispatchAction,
+) -> SupervisorDispatchActionResult
+where
+    B: SupervisorDispatchBackend,
+{
+    match NormalizedDispatchAction::try_from(action.clone()) {
+        Ok(normalized) => dispatch_normalized(backend, &normalized).await,
+        Err(error) => SupervisorDispatchActionResult {
+            action_id: action.action_id,
+            action_type: SupervisorActionType::DispatchTurn,
+            workspace_id: action.workspace_id,
+            dedupe_key: action.dedupe_key.unwrap_or_default(),
+            status: SupervisorDispatchStatus::Failed,
+            thread_id: None,
+            turn_id: None,
+            error: Some(error),
+            idempotent_replay: false,
+        },
+    }
+}
 
-        let turn_response = match backend
-            .start_turn(
-                &action.workspace_id,
-                &thread_id,
-                &action.prompt,
-                action.model.as_deref(),
-                action.effort.as_deref(),
-                action.access_mode.as_deref(),
-            )
-            .await
-        {
-            Ok(value) => value,
-            Err(error) => {
-                return failed_dispatch_result(action, error, Some(thread_id), None, false);
-            }
-        };
+async fn dispatch_normalized<B>(
+    backend: &B,
+    action: &NormalizedDispatchAction,
+) -> SupervisorDispatchActionResult
+where
+    B: SupervisorDispatchBackend,
+{
+    let thread_id = match ensure_thread(backend, action).await {
+        Ok(value) => value,
+        Err(error) => {
+            return failed_dispatch_result(action, error, None, None, false);
+        }
+    };
 
-        if let Some(error) = response_error_message(&turn_response) {
+    let turn_response = match backend
+        .start_turn(
+            &action.workspace_id,
+            &thread_id,
+            &action.prompt,
+            action.model.as_deref(),
+            action.effort.as_deref(),
+            action.access_mode.as_deref(),
+        )
+        .await
+    {
+        Ok(value) => value,
+        Err(error) => {
             return failed_dispatch_result(action, error, Some(thread_id), None, false);
         }
+    };
 
-        SupervisorDispatchActionResult {
-            action_id: action.action_id.clone(),
-            workspace_id: action.workspace_id.clone(),
-            dedupe_key: action.dedupe_token.clone(),
-            status: SupervisorDispatchStatus::Dispatched,
-            thread_id: Some(thread_id),
-            turn_id: extract_turn_id(&turn_response),
-            error: None,
-            idempotent_replay: false,
-        }
+    if let Some(error) = response_error_message(&turn_response) {
+        return failed_dispatch_result(action, error, Some(thread_id), None, false);
     }
 
-    async fn ensure_thread<B>(
-        &self,
-        backend: &B,
-        action: &NormalizedDispatchAction,
-    ) -> Result<String, String>
-    where
-        B: SupervisorDispatchBackend,
-    {
-        if let Some(thread_id) = action.thread_id.as_deref() {
-            let response = backend
-                .resume_thread(&action.workspace_id, thread_id)
-                .await?;
-            if let Some(error) = response_error_message(&response) {
-                return Err(error);
-            }
-            return Ok(extract_thread_id(&response).unwrap_or_else(|| thread_id.to_string()));
-        }
+    SupervisorDispatchActionResult {
+        action_id: action.action_id.clone(),
+        action_type: SupervisorActionType::DispatchTurn,
+        workspace_id: action.workspace_id.clone(),
+        dedupe_key: action.dedupe_token.clone(),
+        status: SupervisorDispatchStatus::Dispatched,
+        thread_id: Some(thread_id),
+        turn_id: extract_turn_id(&turn_response),
+        error: None,
+        idempotent_replay: false,
+    }
+}
 
-        let response = backend.start_thread(&action.workspace_id).await?;
+async fn ensure_thread<B>(backend: &B, action: &NormalizedDispatchAction) -> Result<String, String>
+where
+    B: SupervisorDispatchBackend,
+{
+    if let Some(thread_id) = action.thread_id.as_deref() {
+        let response = backend
+            .resume_thread(&action.workspace_id, thread_id)
+            .await?;
         if let Some(error) = response_error_message(&response) {
             return Err(error);
         }
+        return Ok(extract_thread_id(&response).unwrap_or_else(|| thread_id.to_string()));
+    }
 
-        extract_thread_id(&response).ok_or_else(|| {
-            format!(
-                "thread/start response did not include threadId for workspace `{}`",
-                action.workspace_id
-            )
-        })
+    let response = backend.start_thread(&action.workspace_id).await?;
+    if let Some(error) = response_error_message(&response) {
+        return Err(error);
     }
+
+    extract_thread_id(&response).ok_or_else(|| {
+        format!(
+            "thread/start response did not include threadId for workspace `{}`",
+            action.workspace_id
+        )
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -458,6 +898,7 @@ fn failed_dispatch_result(
 ) -> SupervisorDispatchActionResult {
     SupervisorDispatchActionResult {
         action_id: action.action_id.clone(),
+        action_type: SupervisorActionType::DispatchTurn,
         workspace_id: action.workspace_id.clone(),
         dedupe_key: action.dedupe_token.clone(),
         status: SupervisorDispatchStatus::Failed,
@@ -468,6 +909,13 @@ fn failed_dispatch_result(
     }
 }
 
+pub(super) fn ensure_response_ok(response: Value) -> Result<(), String> {
+    match response_error_message(&response) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
 fn response_error_message(response: &Value) -> Option<String> {
     let error = response.get("error")?;
 
@@ -614,6 +1062,69 @@ mod tests {
                 }))
             })
         }
+
+        fn interrupt_turn<'a>(
+            &'a self,
+            workspace_id: &'a str,
+            thread_id: &'a str,
+            turn_id: &'a str,
+        ) -> DispatchFuture<'a, Result<Value, String>> {
+            Box::pin(async move {
+                self.push_call(format!(
+                    "turn/interrupt:{workspace_id}:{thread_id}:{turn_id}"
+                ));
+                Ok(json!({ "result": {} }))
+            })
+        }
+
+        fn respond_to_request<'a>(
+            &'a self,
+            workspace_id: &'a str,
+            request_id: &'a Value,
+            result: Value,
+        ) -> DispatchFuture<'a, Result<(), String>> {
+            Box::pin(async move {
+                self.push_call(format!("response:{workspace_id}:{request_id}:{result}"));
+                Ok(())
+            })
+        }
+
+        fn archive_thread<'a>(
+            &'a self,
+            workspace_id: &'a str,
+            thread_id: &'a str,
+        ) -> DispatchFuture<'a, Result<Value, String>> {
+            Box::pin(async move {
+                self.push_call(format!("thread/archive:{workspace_id}:{thread_id}"));
+                Ok(json!({ "result": {} }))
+            })
+        }
+
+        fn compact_thread<'a>(
+            &'a self,
+            workspace_id: &'a str,
+            thread_id: &'a str,
+        ) -> DispatchFuture<'a, Result<Value, String>> {
+            Box::pin(async move {
+                self.push_call(format!("thread/compact/start:{workspace_id}:{thread_id}"));
+                if self
+                    .resume_failures
+                    .lock()
+                    .expect("resume failures lock poisoned")
+                    .contains(workspace_id)
+                {
+                    return Ok(json!({ "error": { "message": "compact failed" } }));
+                }
+                Ok(json!({ "result": {} }))
+            })
+        }
+
+        fn ack_signal<'a>(&'a self, signal_id: &'a str) -> DispatchFuture<'a, Result<(), String>> {
+            Box::pin(async move {
+                self.push_call(format!("ack:{signal_id}"));
+                Ok(())
+            })
+        }
     }
 
     fn action(
@@ -635,6 +1146,9 @@ mod tests {
             route_kind: None,
             route_reason: None,
             route_fallback: None,
+            policy: SupervisorJobPolicy::default(),
+            depends_on: Vec::new(),
+            wait_for_capacity: false,
         }
     }
 
@@ -716,6 +1230,41 @@ mod tests {
         });
     }
 
+    #[test]
+    fn holds_actions_with_dependencies_without_touching_the_backend() {
+        run_async(async {
+            let backend = MockDispatchBackend::default();
+            let mut executor = SupervisorDispatchExecutor::new();
+            let mut review = action("review", "ws-2", None, "Review the change", None);
+            review.depends_on = vec!["implement".to_string()];
+
+            let result = executor
+                .dispatch_batch(
+                    &backend,
+                    vec![
+                        action("implement", "ws-1", None, "Implement the change", None),
+                        review,
+                    ],
+                )
+                .await;
+
+            assert_eq!(
+                result
+                    .results
+                    .iter()
+                    .map(|entry| entry.status.clone())
+                    .collect::<Vec<_>>(),
+                vec![
+                    SupervisorDispatchStatus::Dispatched,
+                    SupervisorDispatchStatus::Queued
+                ]
+            );
+            assert_eq!(result.results[1].dedupe_key, "review");
+            assert!(backend.calls().iter().all(|call| call.contains("ws-1")));
+            assert!(!executor.idempotency_snapshot().contains_key("ws-2:review"));
+        });
+    }
+
     #[test]
     fn deduplicates_actions_by_workspace_and_dedupe_key() {
         run_async(async {
@@ -890,6 +1439,7 @@ mod tests {
                 "ws-1:dispatch".to_string(),
                 SupervisorDispatchActionResult {
                     action_id: "action-1".to_string(),
+                    action_type: SupervisorActionType::DispatchTurn,
                     workspace_id: "ws-1".to_string(),
                     dedupe_key: "dispatch".to_string(),
                     status: SupervisorDispatchStatus::Dispatched,
@@ -934,6 +1484,7 @@ mod tests {
             "ws-1:dispatch".to_string(),
             SupervisorDispatchActionResult {
                 action_id: "action-1".to_string(),
+                action_type: SupervisorActionType::DispatchTurn,
                 workspace_id: "ws-1".to_string(),
                 dedupe_key: "dispatch".to_string(),
                 status: SupervisorDispatchStatus::Dispatched,
@@ -949,6 +1500,7 @@ mod tests {
             "ws-2:dispatch".to_string(),
             SupervisorDispatchActionResult {
                 action_id: "action-2".to_string(),
+                action_type: SupervisorActionType::DispatchTurn,
                 workspace_id: "ws-2".to_string(),
                 dedupe_key: "dispatch".to_string(),
                 status: SupervisorDispatchStatus::Dispatched,
@@ -979,6 +1531,9 @@ mod tests {
             route_kind: Some(" workspace_delegate ".to_string()),
             route_reason: Some(" explicit route ".to_string()),
             route_fallback: Some(" fallback ".to_string()),
+            policy: SupervisorJobPolicy::default(),
+            depends_on: Vec::new(),
+            wait_for_capacity: false,
         })
         .expect("normalized action");
 
@@ -1006,6 +1561,9 @@ mod tests {
             route_kind: None,
             route_reason: None,
             route_fallback: None,
+            policy: SupervisorJobPolicy::default(),
+            depends_on: Vec::new(),
+            wait_for_capacity: false,
         })
         .expect_err("unknown access mode should fail");
 
@@ -1029,6 +1587,9 @@ mod tests {
             route_kind: None,
             route_reason: None,
             route_fallback: None,
+            policy: SupervisorJobPolicy::default(),
+            depends_on: Vec::new(),
+            wait_for_capacity: false,
         })
         .expect_err("missing action id should fail");
 
@@ -1049,6 +1610,9 @@ mod tests {
             route_kind: None,
             route_reason: None,
             route_fallback: None,
+            policy: SupervisorJobPolicy::default(),
+            depends_on: Vec::new(),
+            wait_for_capacity: false,
         })
         .expect_err("missing workspace id should fail");
 
@@ -1069,6 +1633,9 @@ mod tests {
             route_kind: None,
             route_reason: None,
             route_fallback: None,
+            policy: SupervisorJobPolicy::default(),
+            depends_on: Vec::new(),
+            wait_for_capacity: false,
         })
         .expect_err("missing prompt should fail");
 
@@ -1148,4 +1715,167 @@ mod tests {
         keys.insert(b.idempotency_key(), 2u8);
         assert_eq!(keys.len(), 2);
     }
+
+    #[test]
+    fn execute_batch_runs_control_actions_in_order() {
+        run_async(async {
+            let backend = MockDispatchBackend::default();
+            let mut executor = SupervisorDispatchExecutor::new();
+
+            let result = executor
+                .execute_batch(
+                    &backend,
+                    vec![
+                        SupervisorExecutableAction::Control(
+                            SupervisorControlAction::InterruptTurn {
+                                action_id: "action-1".to_string(),
+                                workspace_id: "ws-1".to_string(),
+                                thread_id: "thread-1".to_string(),
+                                turn_id: "turn-1".to_string(),
+                                dedupe_key: None,
+                            },
+                        ),
+                        SupervisorExecutableAction::Control(
+                            SupervisorControlAction::RespondApproval {
+                                action_id: "action-2".to_string(),
+                                workspace_id: "ws-1".to_string(),
+                                request_id: json!(42),
+                                decision: "accept".to_string(),
+                                dedupe_key: None,
+                            },
+                        ),
+                        SupervisorExecutableAction::Control(
+                            SupervisorControlAction::AnswerQuestion {
+                                action_id: "action-3".to_string(),
+                                workspace_id: "ws-1".to_string(),
+                                request_id: json!("req-7"),
+                                answer: "yes".to_string(),
+                                question_ids: vec!["q1".to_string()],
+                                dedupe_key: None,
+                            },
+                        ),
+                        SupervisorExecutableAction::DispatchTurn(action(
+                            "action-4",
+                            "ws-2",
+                            None,
+                            "Follow up",
+                            None,
+                        )),
+                        SupervisorExecutableAction::Control(SupervisorControlAction::AckSignal {
+                            action_id: "action-5".to_string(),
+                            signal_id: "signal-1".to_string(),
+                            dedupe_key: None,
+                        }),
+                    ],
+                )
+                .await;
+
+            assert_eq!(result.results.len(), 5);
+            assert_eq!(
+                result
+                    .results
+                    .iter()
+                    .map(|entry| entry.action_type)
+                    .collect::<Vec<_>>(),
+                vec![
+                    SupervisorActionType::InterruptTurn,
+                    SupervisorActionType::RespondApproval,
+                    SupervisorActionType::AnswerQuestion,
+                    SupervisorActionType::DispatchTurn,
+                    SupervisorActionType::AckSignal,
+                ]
+            );
+            assert_eq!(result.results[0].status, SupervisorDispatchStatus::Applied);
+            assert_eq!(result.results[0].turn_id.as_deref(), Some("turn-1"));
+            assert_eq!(
+                result.results[3].status,
+                SupervisorDispatchStatus::Dispatched
+            );
+            assert_eq!(result.results[4].workspace_id, "");
+            assert_eq!(
+                backend.calls(),
+                vec![
+                    "turn/interrupt:ws-1:thread-1:turn-1",
+                    "response:ws-1:42:{\"decision\":\"accept\"}",
+                    "response:ws-1:\"req-7\":{\"answers\":{\"q1\":{\"answers\":[\"yes\"]}}}",
+                    "thread/start:ws-2",
+                    "turn/start:ws-2:thread-ws-2",
+                    "ack:signal-1",
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn control_actions_replay_from_idempotency_store() {
+        run_async(async {
+            let backend = MockDispatchBackend::default();
+            let mut executor = SupervisorDispatchExecutor::new();
+            let archive = |action_id: &str| {
+                SupervisorExecutableAction::Control(SupervisorControlAction::ArchiveThread {
+                    action_id: action_id.to_string(),
+                    workspace_id: "ws-1".to_string(),
+                    thread_id: "thread-1".to_string(),
+                    dedupe_key: Some("archive-thread-1".to_string()),
+                })
+            };
+
+            let result = executor
+                .execute_batch(&backend, vec![archive("action-1"), archive("action-2")])
+                .await;
+
+            assert!(!result.results[0].idempotent_replay);
+            assert!(result.results[1].idempotent_replay);
+            assert_eq!(result.results[1].action_id, "action-2");
+            assert_eq!(result.results[1].status, SupervisorDispatchStatus::Applied);
+            assert_eq!(backend.calls(), vec!["thread/archive:ws-1:thread-1"]);
+            assert!(executor
+                .idempotency_snapshot()
+                .contains_key("ws-1:archive-thread-1"));
+        });
+    }
+
+    #[test]
+    fn failed_control_actions_are_not_cached() {
+        run_async(async {
+            let backend = MockDispatchBackend::default();
+            backend.fail_resume_for("ws-1");
+            let mut executor = SupervisorDispatchExecutor::new();
+            let compact = || {
+                SupervisorExecutableAction::Control(SupervisorControlAction::CompactThread {
+                    action_id: "action-1".to_string(),
+                    workspace_id: "ws-1".to_string(),
+                    thread_id: "thread-1".to_string(),
+                    dedupe_key: None,
+                })
+            };
+
+            let first = executor.execute_batch(&backend, vec![compact()]).await;
+            let second = executor.execute_batch(&backend, vec![compact()]).await;
+
+            assert_eq!(first.results[0].status, SupervisorDispatchStatus::Failed);
+            assert_eq!(first.results[0].error.as_deref(), Some("compact failed"));
+            assert!(!second.results[0].idempotent_replay);
+            assert_eq!(
+                backend.calls(),
+                vec![
+                    "thread/compact/start:ws-1:thread-1",
+                    "thread/compact/start:ws-1:thread-1",
+                ]
+            );
+            assert!(executor.idempotency_snapshot().is_empty());
+        });
+    }
+
+    #[test]
+    fn ack_signal_uses_supervisor_scope_for_idempotency() {
+        let action = SupervisorControlAction::AckSignal {
+            action_id: "action-1".to_string(),
+            signal_id: "signal-1".to_string(),
+            dedupe_key: None,
+        };
+
+        assert_eq!(action.idempotency_key(), "supervisor:action-1");
+        assert_eq!(action.action_type().as_str(), "ack_signal");
+    }
 }
@@ -34,6 +34,11 @@ pub(crate) enum SupervisorEvent {
         item_type: Option<String>,
         task: Option<String>,
         item_content: Option<String>,
+        /// Command line of a `commandExecution` item.
+        #[serde(default)]
+        command: Option<String>,
+        #[serde(default)]
+        exit_code: Option<i64>,
         received_at_ms: i64,
     },
     UserInputRequested {
@@ -53,6 +58,7 @@ pub(crate) enum SupervisorEvent {
         workspace_id: String,
         request_key: String,
         request_id: String,
+        request_id_value: Value,
         method: String,
         thread_id: Option<String>,
         turn_id: Option<String>,
@@ -168,6 +174,12 @@ fn normalize_item_event(
             item_type,
             task,
             item_content,
+            command: item.and_then(extract_command),
+            exit_code: item.and_then(|value| {
+                ["exitCode", "exit_code"]
+                    .iter()
+                    .find_map(|key| value.get(*key).and_then(Value::as_i64))
+            }),
             received_at_ms,
         })
     }
@@ -210,7 +222,8 @@ fn normalize_approval_event(
     params: &Map<String, Value>,
     received_at_ms: i64,
 ) -> Option<SupervisorEvent> {
-    let request_id = extract_request_id(message)?;
+    let request_id_value = extract_request_id_value(message)?;
+    let request_id = request_id_to_string(&request_id_value)?;
     let thread_id = extract_field(params, &["threadId", "thread_id"]);
     let turn_id = extract_field(params, &["turnId", "turn_id"]);
     let item_id = extract_field(params, &["itemId", "item_id"]);
@@ -219,6 +232,7 @@ fn normalize_approval_event(
         workspace_id: workspace_id.to_string(),
         request_key: format!("{workspace_id}:{request_id}"),
         request_id,
+        request_id_value,
         method: method.to_string(),
         thread_id,
         turn_id,
@@ -279,11 +293,6 @@ fn extract_bool(map: &Map<String, Value>, keys: &[&str]) -> Option<bool> {
     None
 }
 
-fn extract_request_id(message: &Map<String, Value>) -> Option<String> {
-    let id = extract_request_id_value(message)?;
-    request_id_to_string(&id)
-}
-
 fn extract_request_id_value(message: &Map<String, Value>) -> Option<Value> {
     let id = message.get("id")?;
     if id.is_i64() || id.is_u64() || id.is_string() {
@@ -328,6 +337,20 @@ fn extract_item_content(map: &Map<String, Value>) -> Option<String> {
     )
 }
 
+/// Item `command` may be a single string or an argv array.
+fn extract_command(map: &Map<String, Value>) -> Option<String> {
+    let command = match map.get("command")? {
+        Value::String(command) => command.trim().to_string(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => return None,
+    };
+    (!command.is_empty()).then_some(command)
+}
+
 fn extract_primary_question(params: &Map<String, Value>) -> Option<String> {
     let questions = params.get("questions")?.as_array()?;
     questions
@@ -412,11 +435,42 @@ mod tests {
                 item_type: Some("agentMessage".to_string()),
                 task: Some("Answer user".to_string()),
                 item_content: Some("Answer user".to_string()),
+                command: None,
+                exit_code: None,
                 received_at_ms: 222,
             })
         );
     }
 
+    #[test]
+    fn normalizes_command_execution_item_with_exit_code() {
+        let event = normalize_app_server_event(
+            "ws-2",
+            &json!({
+                "method": "item/completed",
+                "params": {
+                    "threadId": "thread-2",
+                    "item": {
+                        "id": "item-3",
+                        "type": "commandExecution",
+                        "command": ["bash", "-lc", "cargo test"],
+                        "exitCode": 101
+                    }
+                }
+            }),
+            230,
+        );
+
+        let Some(SupervisorEvent::ItemCompleted {
+            command, exit_code, ..
+        }) = event
+        else {
+            panic!("expected item completed event");
+        };
+        assert_eq!(command.as_deref(), Some("bash -lc cargo test"));
+        assert_eq!(exit_code, Some(101));
+    }
+
     #[test]
     fn normalizes_user_input_request_event() {
         let event = normalize_app_server_event(
@@ -492,6 +546,7 @@ mod tests {
                 workspace_id: "ws-3".to_string(),
                 request_key: "ws-3:7".to_string(),
                 request_id: "7".to_string(),
+                request_id_value: json!(7),
                 method: "workspace/requestApproval".to_string(),
                 thread_id: Some("thread-3".to_string()),
                 turn_id: Some("turn-3".to_string()),
@@ -5,19 +5,35 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
+use super::concurrency::{SupervisorConcurrencyGate, SupervisorConcurrencyPolicy};
+use super::dispatch::{
+    SupervisorDispatchActionResult, SupervisorDispatchStatus, SupervisorExecutableAction,
+};
 use super::events::{normalize_app_server_event, SupervisorEvent};
+use super::schedule::SupervisorCronSchedule;
 use super::{
     apply_update, SupervisorActivityEntry, SupervisorChatMessage, SupervisorChatMessageRole,
-    SupervisorHealth, SupervisorJobState, SupervisorJobStatus, SupervisorOpenQuestion,
-    SupervisorPendingApproval, SupervisorSignal, SupervisorSignalKind, SupervisorState,
+    SupervisorHealth, SupervisorJobCommand, SupervisorJobFileChange, SupervisorJobState,
+    SupervisorJobStatus, SupervisorOpenQuestion, SupervisorPendingApproval, SupervisorSchedule,
+    SupervisorScheduleCatchUp, SupervisorSignal, SupervisorSignalKind, SupervisorState,
     SupervisorStateUpdate, SupervisorSubtaskEvent, SupervisorThreadState, SupervisorThreadStatus,
     SupervisorWorkspaceState, DEFAULT_ACTIVITY_FEED_LIMIT, DEFAULT_CHAT_HISTORY_LIMIT,
 };
 use crate::backend::app_server::WorkspaceSession;
-use crate::types::WorkspaceEntry;
+use crate::shared::{codex_core, git_ui_core};
+use crate::types::{AppSettings, SupervisorHealthSettings, SupervisorStallAction, WorkspaceEntry};
 
 pub(crate) const SUPERVISOR_HEALTH_TICK_MS: u64 = 10_000;
 pub(crate) const SUPERVISOR_SUBTASK_EVENT_LIMIT: usize = 24;
+/// A schedule occurrence found later than this is treated as missed during downtime.
+const SCHEDULE_MISSED_RUN_GRACE_MS: i64 = 2 * SUPERVISOR_HEALTH_TICK_MS as i64;
+/// Upper bound when counting missed occurrences, so a long outage of a per-minute schedule
+/// stays cheap.
+const SCHEDULE_MISSED_RUN_COUNT_LIMIT: u32 = 10_000;
+/// Most recent commands kept in a job digest.
+const JOB_DIGEST_COMMAND_LIMIT: usize = 50;
+const DEFAULT_STALL_NUDGE_PROMPT: &str =
+    "You have not made progress for a while. Briefly say what you are blocked on, then continue with the next step.";
 
 pub(crate) fn now_timestamp_ms() -> i64 {
     SystemTime::now()
@@ -30,21 +46,63 @@ pub(crate) async fn run_health_pull_tick(
     supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
     now_ms: i64,
 ) {
-    let snapshots = collect_health_inputs(workspaces, sessions).await;
-    let mut supervisor_loop = supervisor_loop.lock().await;
-    supervisor_loop.run_health_check(&snapshots, now_ms);
+    let snapshots = collect_health_inputs(workspaces, sessions, app_settings).await;
+    let concurrency = collect_concurrency_policy(workspaces, app_settings).await;
+    capture_job_digests(supervisor_loop, workspaces, now_ms).await;
+    let stalled_turns = {
+        let mut supervisor_loop = supervisor_loop.lock().await;
+        supervisor_loop.set_concurrency_policy(concurrency);
+        supervisor_loop.run_health_check(&snapshots, now_ms)
+    };
+
+    for stalled in stalled_turns {
+        let result = match stalled.action {
+            SupervisorStallAction::None => continue,
+            SupervisorStallAction::Interrupt => codex_core::turn_interrupt_core(
+                sessions,
+                stalled.workspace_id.clone(),
+                stalled.thread_id.clone(),
+                stalled.turn_id.clone(),
+            )
+            .await
+            .map(|_| ()),
+            SupervisorStallAction::Nudge => codex_core::turn_steer_core(
+                sessions,
+                stalled.workspace_id.clone(),
+                stalled.thread_id.clone(),
+                stalled.turn_id.clone(),
+                stalled
+                    .nudge_prompt
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_STALL_NUDGE_PROMPT.to_string()),
+                None,
+                None,
+            )
+            .await
+            .map(|_| ()),
+        };
+        let mut supervisor_loop = supervisor_loop.lock().await;
+        supervisor_loop.record_stall_action(&stalled, result, now_timestamp_ms());
+    }
 }
 
 async fn collect_health_inputs(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    app_settings: &Mutex<AppSettings>,
 ) -> Vec<SupervisorWorkspaceHealthInput> {
     let connected_workspace_ids = {
         let sessions = sessions.lock().await;
-        sessions.keys().cloned().collect::<HashSet<_>>()
+        sessions
+            .iter()
+            .filter(|(_, session)| session.is_available())
+            .map(|(workspace_id, _)| workspace_id.clone())
+            .collect::<HashSet<_>>()
     };
+    let health_settings = app_settings.lock().await.supervisor_health.clone();
     let workspaces = workspaces.lock().await;
 
     workspaces
@@ -53,22 +111,145 @@ async fn collect_health_inputs(
             workspace_id: workspace.id.clone(),
             workspace_name: Some(workspace.name.clone()),
             connected: connected_workspace_ids.contains(&workspace.id),
+            health: Some(SupervisorHealthPolicy::from(
+                &health_settings.with_override(workspace.settings.supervisor_health.as_ref()),
+            )),
         })
         .collect::<Vec<_>>()
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct SupervisorLoopConfig {
+async fn collect_concurrency_policy(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+) -> SupervisorConcurrencyPolicy {
+    let settings = app_settings.lock().await.supervisor_concurrency.clone();
+    let workspaces = workspaces.lock().await;
+    SupervisorConcurrencyPolicy::from_settings(&settings, workspaces.values())
+}
+
+/// Fills in files changed for finished jobs from their workspace's `git status`.
+async fn capture_job_digests(
+    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    now_ms: i64,
+) {
+    let pending = supervisor_loop.lock().await.pending_job_digests();
+    if pending.is_empty() {
+        return;
+    }
+
+    let mut statuses = HashMap::new();
+    for (_, workspace_id) in &pending {
+        if statuses.contains_key(workspace_id) {
+            continue;
+        }
+        let files = workspace_file_changes(workspaces, workspace_id).await;
+        statuses.insert(workspace_id.clone(), files);
+    }
+
+    let mut supervisor_loop = supervisor_loop.lock().await;
+    for (job_id, workspace_id) in pending {
+        if let Some(files) = statuses.get(&workspace_id) {
+            supervisor_loop.record_job_digest(&job_id, files.clone(), now_ms);
+        }
+    }
+}
+
+/// `git status` of the workspaces jobs are about to start in, so their digests can leave out
+/// files that were already dirty. Workspaces whose status cannot be read are skipped.
+pub(crate) async fn collect_job_baselines(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_ids: impl IntoIterator<Item = String>,
+) -> HashMap<String, Vec<SupervisorJobFileChange>> {
+    let workspaces = {
+        let sessions = sessions.lock().await;
+        workspace_ids
+            .into_iter()
+            .filter_map(|workspace_id| {
+                let entry = sessions.get(&workspace_id)?.entry.clone();
+                Some((workspace_id, entry))
+            })
+            .collect::<HashMap<_, _>>()
+    };
+    let workspace_ids = workspaces.keys().cloned().collect::<Vec<_>>();
+    let workspaces = Mutex::new(workspaces);
+
+    let mut baselines = HashMap::new();
+    for workspace_id in workspace_ids {
+        if let Ok(files) = workspace_file_changes(&workspaces, &workspace_id).await {
+            baselines.insert(workspace_id, files);
+        }
+    }
+    baselines
+}
+
+async fn workspace_file_changes(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<Vec<SupervisorJobFileChange>, String> {
+    let status = git_ui_core::get_git_status_core(workspaces, workspace_id.to_string()).await?;
+    Ok(status
+        .get("files")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|file| {
+            Some(SupervisorJobFileChange {
+                path: file.get("path")?.as_str()?.to_string(),
+                status: file.get("status")?.as_str()?.to_string(),
+                additions: file.get("additions").and_then(Value::as_i64).unwrap_or(0),
+                deletions: file.get("deletions").and_then(Value::as_i64).unwrap_or(0),
+            })
+        })
+        .collect())
+}
+
+/// Health thresholds in effect for one workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SupervisorHealthPolicy {
     pub(crate) stale_after_ms: i64,
     pub(crate) disconnected_after_ms: i64,
+    /// `None` disables per-thread stall detection.
+    pub(crate) thread_stall_after_ms: Option<i64>,
+    pub(crate) stall_action: SupervisorStallAction,
+    pub(crate) nudge_prompt: Option<String>,
+}
+
+impl Default for SupervisorHealthPolicy {
+    fn default() -> Self {
+        Self::from(&SupervisorHealthSettings::default())
+    }
+}
+
+impl From<&SupervisorHealthSettings> for SupervisorHealthPolicy {
+    fn from(settings: &SupervisorHealthSettings) -> Self {
+        let seconds_to_ms = |seconds: u64| (seconds as i64).saturating_mul(1_000);
+        Self {
+            stale_after_ms: seconds_to_ms(settings.stale_after_seconds),
+            disconnected_after_ms: seconds_to_ms(settings.disconnected_after_seconds),
+            thread_stall_after_ms: (settings.thread_stall_after_seconds > 0)
+                .then(|| seconds_to_ms(settings.thread_stall_after_seconds)),
+            stall_action: settings.stall_action,
+            nudge_prompt: settings
+                .nudge_prompt
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SupervisorLoopConfig {
+    pub(crate) health: SupervisorHealthPolicy,
     pub(crate) activity_feed_limit: usize,
 }
 
 impl Default for SupervisorLoopConfig {
     fn default() -> Self {
         Self {
-            stale_after_ms: 90_000,
-            disconnected_after_ms: 300_000,
+            health: SupervisorHealthPolicy::default(),
             activity_feed_limit: DEFAULT_ACTIVITY_FEED_LIMIT,
         }
     }
@@ -79,13 +260,56 @@ pub(crate) struct SupervisorWorkspaceHealthInput {
     pub(crate) workspace_id: String,
     pub(crate) workspace_name: Option<String>,
     pub(crate) connected: bool,
+    /// Overrides the loop config thresholds for this workspace.
+    pub(crate) health: Option<SupervisorHealthPolicy>,
+}
+
+/// A running turn that went quiet past its stall threshold, plus the configured reaction.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SupervisorStalledTurn {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) action: SupervisorStallAction,
+    pub(crate) nudge_prompt: Option<String>,
+}
+
+/// Work produced by job policies and dependencies that needs a workspace session.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SupervisorJobPolicyTask {
+    /// The attempt ran past its timeout and its turn should be interrupted.
+    Interrupt {
+        job_id: String,
+        workspace_id: String,
+        thread_id: String,
+        turn_id: String,
+    },
+    /// A retry whose backoff elapsed, or a dependent whose prerequisites all completed. The
+    /// prompt has `{{<job_id>.result}}` placeholders filled in.
+    Dispatch {
+        job: Box<SupervisorJobState>,
+        prompt: String,
+    },
+}
+
+/// A schedule occurrence that is due and should be dispatched.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SupervisorScheduleRun {
+    pub(crate) schedule: SupervisorSchedule,
+    pub(crate) scheduled_for_ms: i64,
+    /// Occurrences folded into this run because the supervisor was not running.
+    pub(crate) missed_runs: u32,
 }
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct SupervisorLoop {
     state: SupervisorState,
     config: SupervisorLoopConfig,
+    concurrency: SupervisorConcurrencyPolicy,
     workspace_last_event_at_ms: BTreeMap<String, i64>,
+    /// Turn slots held for admitted dispatches between admission and their outcome, keyed by
+    /// action id, with the workspace id and access mode they count against.
+    admitted_dispatches: HashMap<String, (String, Option<String>)>,
 }
 
 impl SupervisorLoop {
@@ -94,7 +318,9 @@ impl SupervisorLoop {
         Self {
             state: SupervisorState::default(),
             config,
+            concurrency: SupervisorConcurrencyPolicy::default(),
             workspace_last_event_at_ms: BTreeMap::new(),
+            admitted_dispatches: HashMap::new(),
         }
     }
 
@@ -112,7 +338,9 @@ impl SupervisorLoop {
         Self {
             state,
             config,
+            concurrency: SupervisorConcurrencyPolicy::default(),
             workspace_last_event_at_ms,
+            admitted_dispatches: HashMap::new(),
         }
     }
 
@@ -127,6 +355,7 @@ impl SupervisorLoop {
         received_at_ms: i64,
     ) {
         self.record_workspace_heartbeat(workspace_id, received_at_ms);
+        self.touch_thread_activity(workspace_id, message, received_at_ms);
 
         if let Some(event) = normalize_app_server_event(workspace_id, message, received_at_ms) {
             self.apply_supervisor_event(event);
@@ -138,33 +367,139 @@ impl SupervisorLoop {
             .and_then(|payload| payload.get("method"))
             .and_then(Value::as_str)
             .unwrap_or_default();
-        if method == "codex/connected" {
-            let mut workspace = self.workspace_state(workspace_id);
-            workspace.connected = true;
-            workspace.health = SupervisorHealth::Healthy;
-            workspace.last_activity_at_ms = Some(received_at_ms);
-            apply_update(
-                &mut self.state,
-                SupervisorStateUpdate::UpsertWorkspace(workspace),
-            );
-            self.push_activity(
-                format!("connected:{workspace_id}:{received_at_ms}"),
-                "workspace_connected",
-                "Workspace connected".to_string(),
-                Some(workspace_id.to_string()),
-                None,
-                false,
-                received_at_ms,
-                Value::Null,
-            );
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        match method {
+            "codex/connected" => {
+                let mut workspace = self.workspace_state(workspace_id);
+                workspace.connected = true;
+                workspace.health = SupervisorHealth::Healthy;
+                workspace.last_activity_at_ms = Some(received_at_ms);
+                apply_update(
+                    &mut self.state,
+                    SupervisorStateUpdate::UpsertWorkspace(workspace),
+                );
+                self.push_activity(
+                    format!("connected:{workspace_id}:{received_at_ms}"),
+                    "workspace_connected",
+                    "Workspace connected".to_string(),
+                    Some(workspace_id.to_string()),
+                    None,
+                    false,
+                    received_at_ms,
+                    Value::Null,
+                );
+            }
+            "codex/disconnected" => {
+                self.apply_session_disconnected(workspace_id, params, received_at_ms)
+            }
+            "codex/reconnecting" => {
+                let attempt = params.get("attempt").and_then(Value::as_u64).unwrap_or(1);
+                self.push_activity(
+                    format!("session_reconnecting:{workspace_id}:{attempt}:{received_at_ms}"),
+                    "session_reconnecting",
+                    format!("Restarting Codex app-server (attempt {attempt})"),
+                    Some(workspace_id.to_string()),
+                    None,
+                    false,
+                    received_at_ms,
+                    params,
+                );
+            }
+            "codex/reconnected" => {
+                self.apply_session_recovered(workspace_id, params, received_at_ms)
+            }
+            _ => {}
         }
     }
 
+    fn apply_session_disconnected(&mut self, workspace_id: &str, params: Value, at_ms: i64) {
+        let reason = params
+            .get("reason")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("Codex app-server exited")
+            .to_string();
+        let will_restart = params
+            .get("willRestart")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        let mut workspace = self.workspace_state(workspace_id);
+        workspace.connected = false;
+        workspace.health = SupervisorHealth::Disconnected;
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::UpsertWorkspace(workspace),
+        );
+
+        let message = if will_restart {
+            format!("{reason}. Restarting session.")
+        } else {
+            format!("{reason}. Reconnect the workspace to continue.")
+        };
+        self.push_signal(
+            session_disconnected_signal_id(workspace_id),
+            SupervisorSignalKind::Disconnected,
+            Some(workspace_id.to_string()),
+            None,
+            None,
+            message.clone(),
+            at_ms,
+            params.clone(),
+        );
+        self.push_activity(
+            format!("session_disconnected:{workspace_id}:{at_ms}"),
+            "session_disconnected",
+            message,
+            Some(workspace_id.to_string()),
+            None,
+            !will_restart,
+            at_ms,
+            params,
+        );
+    }
+
+    fn apply_session_recovered(&mut self, workspace_id: &str, params: Value, at_ms: i64) {
+        let mut workspace = self.workspace_state(workspace_id);
+        workspace.connected = true;
+        workspace.health = SupervisorHealth::Healthy;
+        workspace.last_activity_at_ms = Some(at_ms);
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::UpsertWorkspace(workspace),
+        );
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::AckSignal {
+                signal_id: session_disconnected_signal_id(workspace_id),
+                acknowledged_at_ms: at_ms,
+            },
+        );
+
+        let resumed = params
+            .get("resumedThreadIds")
+            .and_then(Value::as_array)
+            .map(Vec::len)
+            .unwrap_or_default();
+        self.push_activity(
+            format!("session_recovered:{workspace_id}:{at_ms}"),
+            "session_recovered",
+            format!("Codex app-server recovered; resumed {resumed} thread(s)"),
+            Some(workspace_id.to_string()),
+            None,
+            false,
+            at_ms,
+            params,
+        );
+    }
+
+    /// Refreshes workspace health and returns turns that just stalled.
     pub(crate) fn run_health_check(
         &mut self,
         snapshots: &[SupervisorWorkspaceHealthInput],
         now_ms: i64,
-    ) {
+    ) -> Vec<SupervisorStalledTurn> {
         for snapshot in snapshots {
             let previous_health = self
                 .state
@@ -232,6 +567,157 @@ impl SupervisorLoop {
                 SupervisorHealth::Healthy => {}
             }
         }
+
+        self.detect_stalled_turns(snapshots, now_ms)
+    }
+
+    fn detect_stalled_turns(
+        &mut self,
+        snapshots: &[SupervisorWorkspaceHealthInput],
+        now_ms: i64,
+    ) -> Vec<SupervisorStalledTurn> {
+        let policies = snapshots
+            .iter()
+            .filter(|snapshot| snapshot.connected)
+            .map(|snapshot| {
+                (
+                    snapshot.workspace_id.as_str(),
+                    snapshot.health.as_ref().unwrap_or(&self.config.health),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        // Time spent waiting on an approval or a question is not a stall; the idle clock
+        // restarts once the person answers.
+        for key in self.threads_waiting_for_user() {
+            if let Some(thread) = self.state.threads.get_mut(&key) {
+                thread.last_activity_at_ms = thread.last_activity_at_ms.max(Some(now_ms));
+            }
+        }
+
+        let candidates = self
+            .state
+            .threads
+            .values()
+            .filter(|thread| matches!(thread.status, SupervisorThreadStatus::Running))
+            .filter_map(|thread| {
+                let policy = policies.get(thread.workspace_id.as_str())?;
+                let stall_after_ms = policy.thread_stall_after_ms?;
+                let turn_id = thread.active_turn_id.clone()?;
+                let idle_ms = now_ms.saturating_sub(thread.last_activity_at_ms?);
+                (idle_ms >= stall_after_ms).then(|| {
+                    (
+                        thread.clone(),
+                        SupervisorStalledTurn {
+                            workspace_id: thread.workspace_id.clone(),
+                            thread_id: thread.id.clone(),
+                            turn_id,
+                            action: policy.stall_action,
+                            nudge_prompt: policy.nudge_prompt.clone(),
+                        },
+                        idle_ms,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut stalled_turns = Vec::with_capacity(candidates.len());
+        for (mut thread, stalled, idle_ms) in candidates {
+            let thread_label = thread
+                .name
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .unwrap_or(&stalled.thread_id)
+                .to_string();
+            let message = format!(
+                "Thread {thread_label} has had no activity for {} during turn {}.",
+                format_idle_duration(idle_ms),
+                stalled.turn_id
+            );
+            let context = json!({
+                "threadId": stalled.thread_id,
+                "turnId": stalled.turn_id,
+                "idleMs": idle_ms,
+                "autoAction": stalled.action,
+            });
+
+            thread.status = SupervisorThreadStatus::Stalled;
+            apply_update(&mut self.state, SupervisorStateUpdate::UpsertThread(thread));
+            self.push_signal(
+                format!(
+                    "stall:{}:{}:{}",
+                    stalled.workspace_id, stalled.thread_id, stalled.turn_id
+                ),
+                SupervisorSignalKind::Stalled,
+                Some(stalled.workspace_id.clone()),
+                Some(stalled.thread_id.clone()),
+                None,
+                message.clone(),
+                now_ms,
+                context.clone(),
+            );
+            self.push_activity(
+                format!(
+                    "thread_stalled:{}:{}:{now_ms}",
+                    stalled.workspace_id, stalled.thread_id
+                ),
+                "thread_stalled",
+                message,
+                Some(stalled.workspace_id.clone()),
+                Some(stalled.thread_id.clone()),
+                matches!(stalled.action, SupervisorStallAction::None),
+                now_ms,
+                context,
+            );
+            stalled_turns.push(stalled);
+        }
+        stalled_turns
+    }
+
+    pub(crate) fn record_stall_action(
+        &mut self,
+        stalled: &SupervisorStalledTurn,
+        result: Result<(), String>,
+        created_at_ms: i64,
+    ) {
+        let (action, verb) = match stalled.action {
+            SupervisorStallAction::None => return,
+            SupervisorStallAction::Interrupt => ("interrupt", "Interrupted"),
+            SupervisorStallAction::Nudge => ("nudge", "Nudged"),
+        };
+        let (message, needs_input, error) = match result {
+            Ok(()) => (
+                format!("{verb} stalled turn {}.", stalled.turn_id),
+                false,
+                None,
+            ),
+            Err(error) => (
+                format!(
+                    "Failed to {action} stalled turn {}: {error}",
+                    stalled.turn_id
+                ),
+                true,
+                Some(error),
+            ),
+        };
+        self.push_activity(
+            format!(
+                "stall_action:{}:{}:{created_at_ms}",
+                stalled.workspace_id, stalled.thread_id
+            ),
+            "stall_action",
+            message,
+            Some(stalled.workspace_id.clone()),
+            Some(stalled.thread_id.clone()),
+            needs_input,
+            created_at_ms,
+            json!({
+                "turnId": stalled.turn_id,
+                "action": action,
+                "error": error,
+            }),
+        );
     }
 
     pub(crate) fn ack_signal(&mut self, signal_id: &str, acknowledged_at_ms: i64) {
@@ -262,6 +748,10 @@ impl SupervisorLoop {
         apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
     }
 
+    pub(crate) fn has_job(&self, job_id: &str) -> bool {
+        self.state.jobs.contains_key(job_id)
+    }
+
     pub(crate) fn waiting_jobs(&self) -> Vec<SupervisorJobState> {
         let mut waiting = self
             .state
@@ -297,6 +787,54 @@ impl SupervisorLoop {
         );
     }
 
+    pub(crate) fn record_control_action(
+        &mut self,
+        action_id: &str,
+        message: String,
+        workspace_id: Option<String>,
+        thread_id: Option<String>,
+        created_at_ms: i64,
+        metadata: Value,
+    ) {
+        self.push_activity(
+            format!("control_action:{action_id}:{created_at_ms}"),
+            "control_action",
+            message,
+            workspace_id,
+            thread_id,
+            false,
+            created_at_ms,
+            metadata,
+        );
+    }
+
+    pub(crate) fn resolve_pending_approval(&mut self, request_key: &str, resolved_at_ms: i64) {
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::ResolvePendingApproval {
+                request_key: request_key.to_string(),
+                resolved_at_ms,
+            },
+        );
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::AckSignal {
+                signal_id: format!("approval:{request_key}"),
+                acknowledged_at_ms: resolved_at_ms,
+            },
+        );
+    }
+
+    pub(crate) fn resolve_open_question(&mut self, request_key: &str, resolved_at_ms: i64) {
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::ResolveOpenQuestion {
+                question_id: request_key.to_string(),
+                resolved_at_ms,
+            },
+        );
+    }
+
     pub(crate) fn mark_reply_delivered(
         &mut self,
         job_id: &str,
@@ -519,7 +1057,10 @@ impl SupervisorLoop {
                     json!({ "turnId": turn_id, "task": task }),
                 );
 
-                if let Some(mut job) = self.job_for_event(&workspace_id, Some(&thread_id)) {
+                if let Some(mut job) = self
+                    .job_for_event(&workspace_id, Some(&thread_id))
+                    .filter(|job| !job.awaits_policy())
+                {
                     job.status = SupervisorJobStatus::Completed;
                     job.completed_at_ms = Some(received_at_ms);
                     job.waiting_request_id = None;
@@ -585,7 +1126,10 @@ impl SupervisorLoop {
                     }),
                 );
 
-                if let Some(mut job) = self.job_for_event(&workspace_id, Some(&thread_id)) {
+                if let Some(mut job) = self
+                    .job_for_event(&workspace_id, Some(&thread_id))
+                    .filter(|job| !job.awaits_policy())
+                {
                     job.status = SupervisorJobStatus::Running;
                     Self::append_subtask_event(
                         &mut job,
@@ -618,6 +1162,8 @@ impl SupervisorLoop {
                 item_type,
                 task,
                 item_content,
+                command,
+                exit_code,
                 received_at_ms,
             } => {
                 self.apply_thread_activity(
@@ -644,8 +1190,19 @@ impl SupervisorLoop {
                     }),
                 );
 
-                if let Some(mut job) = self.job_for_event(&workspace_id, Some(&thread_id)) {
+                if let Some(mut job) = self
+                    .job_for_event(&workspace_id, Some(&thread_id))
+                    .filter(|job| !job.awaits_policy())
+                {
                     job.status = SupervisorJobStatus::Running;
+                    if item_type
+                        .as_deref()
+                        .is_some_and(|value| value.eq_ignore_ascii_case("agentMessage"))
+                    {
+                        if let Some(content) = item_content.as_deref() {
+                            job.result = Some(content.to_string());
+                        }
+                    }
                     let added = Self::append_subtask_event(
                         &mut job,
                         SupervisorSubtaskEvent {
@@ -664,6 +1221,17 @@ impl SupervisorLoop {
                             }),
                         },
                     );
+                    if let Some(command) = command.filter(|_| added) {
+                        let digest = job.digest.get_or_insert_with(Default::default);
+                        digest.commands.push(SupervisorJobCommand {
+                            is_test: is_test_command(&command),
+                            command,
+                            exit_code,
+                        });
+                        if digest.commands.len() > JOB_DIGEST_COMMAND_LIMIT {
+                            digest.commands.remove(0);
+                        }
+                    }
                     apply_update(
                         &mut self.state,
                         SupervisorStateUpdate::UpsertJob(job.clone()),
@@ -776,6 +1344,7 @@ impl SupervisorLoop {
                 workspace_id,
                 request_key,
                 request_id,
+                request_id_value,
                 method,
                 thread_id,
                 turn_id,
@@ -792,6 +1361,7 @@ impl SupervisorLoop {
                         turn_id,
                         item_id: item_id.clone(),
                         request_id,
+                        request_id_value,
                         method,
                         params: params.clone(),
                         created_at_ms: received_at_ms,
@@ -904,7 +1474,10 @@ impl SupervisorLoop {
                     json!({ "willRetry": will_retry, "turnId": turn_id }),
                 );
 
-                if let Some(mut job) = self.job_for_event(&workspace_id, thread_id.as_deref()) {
+                if let Some(mut job) = self
+                    .job_for_event(&workspace_id, thread_id.as_deref())
+                    .filter(|job| !job.awaits_policy())
+                {
                     job.status = if will_retry {
                         SupervisorJobStatus::Running
                     } else {
@@ -953,34 +1526,893 @@ impl SupervisorLoop {
         }
     }
 
-    fn compute_health(
-        &self,
-        snapshot: &SupervisorWorkspaceHealthInput,
-        now_ms: i64,
-    ) -> SupervisorHealth {
-        if !snapshot.connected {
-            return SupervisorHealth::Disconnected;
-        }
+    /// Times out overdue attempts, schedules retries for failed jobs, and returns the retries
+    /// whose backoff has elapsed plus the dependents whose prerequisites completed.
+    pub(crate) fn apply_job_policies(&mut self, now_ms: i64) -> Vec<SupervisorJobPolicyTask> {
+        let jobs = self.state.jobs.values().cloned().collect::<Vec<_>>();
+        let mut tasks = Vec::new();
+        for mut job in jobs {
+            let before = job.clone();
+            if let Some(task) = self.expire_timed_out_job(&mut job, now_ms) {
+                tasks.push(task);
+            }
 
-        let Some(last_activity) = self
-            .workspace_last_event_at_ms
-            .get(&snapshot.workspace_id)
-            .copied()
-        else {
-            return SupervisorHealth::Stale;
-        };
+            if job.can_retry() {
+                let attempt = job.attempt_number();
+                let delay_ms = job.policy.retry_delay_ms(attempt);
+                job.status = SupervisorJobStatus::Queued;
+                job.completed_at_ms = None;
+                job.next_retry_at_ms = Some(now_ms.saturating_add(delay_ms));
+                let message = format!(
+                    "Attempt {attempt} of {} failed; retrying in {}s.",
+                    job.policy.max_attempts,
+                    delay_ms / 1_000
+                );
+                let event = SupervisorSubtaskEvent {
+                    id: format!("retry_scheduled:{}:{}", job.id, attempt + 1),
+                    kind: "retry_scheduled".to_string(),
+                    message: message.clone(),
+                    created_at_ms: now_ms,
+                    metadata: json!({
+                        "attempt": attempt,
+                        "nextAttempt": attempt + 1,
+                        "retryAtMs": job.next_retry_at_ms,
+                        "error": job.error,
+                    }),
+                };
+                Self::append_subtask_event(&mut job, event);
+                self.push_activity(
+                    format!("job_retry_scheduled:{}:{}", job.id, attempt + 1),
+                    "job_retry_scheduled",
+                    message,
+                    Some(job.workspace_id.clone()),
+                    job.thread_id.clone(),
+                    false,
+                    now_ms,
+                    json!({ "subtaskId": job.id, "nextAttempt": attempt + 1 }),
+                );
+            } else if job.status == SupervisorJobStatus::Queued
+                && job
+                    .next_retry_at_ms
+                    .is_some_and(|retry_at| retry_at <= now_ms)
+            {
+                job.queued_at_ms = job.next_retry_at_ms.take();
+            }
 
-        let age = now_ms.saturating_sub(last_activity);
-        if age >= self.config.disconnected_after_ms {
-            SupervisorHealth::Disconnected
-        } else if age >= self.config.stale_after_ms {
-            SupervisorHealth::Stale
-        } else {
-            SupervisorHealth::Healthy
+            if job != before {
+                apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
+            }
         }
+        self.release_dependent_jobs(now_ms);
+        tasks.extend(self.start_queued_jobs(now_ms));
+        tasks
     }
 
-    fn workspace_has_active_workload(&self, workspace_id: &str) -> bool {
+    /// Queues dependents whose prerequisites all completed and fails those with a failed or
+    /// cancelled prerequisite. Failures cascade down the whole chain in one call.
+    fn release_dependent_jobs(&mut self, now_ms: i64) {
+        loop {
+            let waiting = self
+                .state
+                .jobs
+                .values()
+                .filter(|job| job.waits_on_dependencies())
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut failed_any = false;
+            for mut job in waiting {
+                let mut blocker = None;
+                let mut pending = false;
+                for dependency_id in &job.depends_on {
+                    match self.state.jobs.get(dependency_id) {
+                        None => {
+                            blocker = Some(format!("prerequisite `{dependency_id}` is not tracked"))
+                        }
+                        Some(dependency) => match dependency.status {
+                            SupervisorJobStatus::Completed => {}
+                            SupervisorJobStatus::Cancelled => {
+                                blocker =
+                                    Some(format!("prerequisite `{dependency_id}` was cancelled"))
+                            }
+                            SupervisorJobStatus::Failed if !dependency.can_retry() => {
+                                blocker = Some(format!(
+                                    "prerequisite `{dependency_id}` failed: {}",
+                                    dependency.error.as_deref().unwrap_or("unknown error")
+                                ))
+                            }
+                            _ => pending = true,
+                        },
+                    }
+                    if blocker.is_some() {
+                        break;
+                    }
+                }
+
+                if let Some(message) = blocker {
+                    failed_any = true;
+                    job.status = SupervisorJobStatus::Failed;
+                    job.error = Some(message.clone());
+                    job.completed_at_ms = Some(now_ms);
+                    let event = SupervisorSubtaskEvent {
+                        id: format!("dependency_failed:{}", job.id),
+                        kind: "failed".to_string(),
+                        message,
+                        created_at_ms: now_ms,
+                        metadata: json!({ "dependsOn": job.depends_on }),
+                    };
+                    Self::append_subtask_event(&mut job, event);
+                    self.push_job_failed_signal(&job, now_ms);
+                    apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
+                } else if !pending {
+                    job.queued_at_ms = Some(now_ms);
+                    apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
+                }
+            }
+            if !failed_any {
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn set_concurrency_policy(&mut self, policy: SupervisorConcurrencyPolicy) {
+        self.concurrency = policy;
+    }
+
+    /// Holds back new `dispatch_turn` actions that would exceed the concurrency limits. Jobs
+    /// already in the queue are admitted first, so new work lines up behind them. Actions whose
+    /// job is already tracked are left to the executor's idempotency check.
+    ///
+    /// Admitted actions keep their turn slot until `release_dispatch_admissions` is called with
+    /// the returned ids, so dispatches running concurrently cannot both take the last slot.
+    pub(crate) fn admit_dispatch_actions(
+        &mut self,
+        actions: &mut [SupervisorExecutableAction],
+    ) -> Vec<String> {
+        let mut gate = self.concurrency_gate();
+        for job in self.queued_jobs() {
+            gate.try_admit(&job.workspace_id, job.access_mode.as_deref());
+        }
+        let mut admitted = Vec::new();
+        for action in actions.iter_mut() {
+            let SupervisorExecutableAction::DispatchTurn(action) = action else {
+                continue;
+            };
+            if !action.depends_on.is_empty()
+                || self.state.jobs.contains_key(&action.action_id)
+                || self.admitted_dispatches.contains_key(&action.action_id)
+            {
+                continue;
+            }
+            action.wait_for_capacity =
+                !gate.try_admit(&action.workspace_id, action.access_mode.as_deref());
+            if !action.wait_for_capacity {
+                admitted.push((
+                    action.action_id.clone(),
+                    (action.workspace_id.clone(), action.access_mode.clone()),
+                ));
+            }
+        }
+        let ids = admitted.iter().map(|(id, _)| id.clone()).collect();
+        self.admitted_dispatches.extend(admitted);
+        ids
+    }
+
+    /// Frees the turn slots held by `admit_dispatch_actions`. Call it once the dispatch outcomes
+    /// are recorded, so started jobs take over the slot and failed ones give it back.
+    pub(crate) fn release_dispatch_admissions(&mut self, action_ids: &[String]) {
+        for action_id in action_ids {
+            self.admitted_dispatches.remove(action_id);
+        }
+    }
+
+    fn concurrency_gate(&self) -> SupervisorConcurrencyGate<'_> {
+        let mut gate = SupervisorConcurrencyGate::new(&self.concurrency, self.state.jobs.values());
+        for (workspace_id, access_mode) in self.admitted_dispatches.values() {
+            gate.hold(workspace_id, access_mode.as_deref());
+        }
+        gate
+    }
+
+    fn queued_jobs(&self) -> Vec<&SupervisorJobState> {
+        let mut queued = self
+            .state
+            .jobs
+            .values()
+            .filter(|job| job.waits_for_capacity())
+            .collect::<Vec<_>>();
+        queued.sort_by(|left, right| {
+            left.queued_at_ms
+                .cmp(&right.queued_at_ms)
+                .then_with(|| left.requested_at_ms.cmp(&right.requested_at_ms))
+                .then_with(|| left.id.cmp(&right.id))
+        });
+        queued
+    }
+
+    /// Starts queued jobs in FIFO order while the concurrency limits allow it and renumbers the
+    /// rest of each workspace queue.
+    fn start_queued_jobs(&mut self, now_ms: i64) -> Vec<SupervisorJobPolicyTask> {
+        let queued = self.queued_jobs().into_iter().cloned().collect::<Vec<_>>();
+        if queued.is_empty() {
+            return Vec::new();
+        }
+
+        let mut gate = self.concurrency_gate();
+        let admitted = queued
+            .iter()
+            .filter(|job| gate.try_admit(&job.workspace_id, job.access_mode.as_deref()))
+            .map(|job| job.id.clone())
+            .collect::<HashSet<_>>();
+
+        let mut tasks = Vec::new();
+        for mut job in queued.into_iter().filter(|job| admitted.contains(&job.id)) {
+            job.queued_at_ms = None;
+            job.queue_position = None;
+            job.starting = true;
+            apply_update(
+                &mut self.state,
+                SupervisorStateUpdate::UpsertJob(job.clone()),
+            );
+            tasks.push(SupervisorJobPolicyTask::Dispatch {
+                prompt: self.render_job_prompt(&job),
+                job: Box::new(job),
+            });
+        }
+        self.refresh_queue_positions(now_ms);
+        tasks
+    }
+
+    /// Numbers each workspace queue from 1 and notes on the job when it first has to wait.
+    pub(crate) fn refresh_queue_positions(&mut self, now_ms: i64) {
+        let mut next_position = HashMap::<String, u32>::new();
+        let queued = self.queued_jobs().into_iter().cloned().collect::<Vec<_>>();
+        for mut job in queued {
+            let position = next_position.entry(job.workspace_id.clone()).or_insert(0);
+            *position += 1;
+            let position = *position;
+            if job.queue_position == Some(position) {
+                continue;
+            }
+            if job.queue_position.is_none() {
+                let event = SupervisorSubtaskEvent {
+                    id: format!(
+                        "waiting_slot:{}:{}",
+                        job.id,
+                        job.queued_at_ms.unwrap_or(now_ms)
+                    ),
+                    kind: "queued".to_string(),
+                    message: format!(
+                        "Waiting for a free turn slot in `{}` (position {position}).",
+                        job.workspace_id
+                    ),
+                    created_at_ms: now_ms,
+                    metadata: json!({
+                        "workspaceId": job.workspace_id,
+                        "queuePosition": position,
+                    }),
+                };
+                Self::append_subtask_event(&mut job, event);
+            }
+            job.queue_position = Some(position);
+            apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
+        }
+    }
+
+    fn expire_timed_out_job(
+        &mut self,
+        job: &mut SupervisorJobState,
+        now_ms: i64,
+    ) -> Option<SupervisorJobPolicyTask> {
+        let timeout_seconds = job.policy.timeout_seconds?;
+        let started_at_ms = job.started_at_ms?;
+        if job.status != SupervisorJobStatus::Running
+            || now_ms.saturating_sub(started_at_ms) < (timeout_seconds as i64).saturating_mul(1_000)
+        {
+            return None;
+        }
+
+        let attempt = job.attempt_number();
+        let turn_id = job.thread_id.as_deref().and_then(|thread_id| {
+            self.state
+                .threads
+                .get(&super::thread_map_key(&job.workspace_id, thread_id))
+                .and_then(|thread| thread.active_turn_id.clone())
+        });
+        let message = format!("Attempt {attempt} timed out after {timeout_seconds}s.");
+        job.status = SupervisorJobStatus::Failed;
+        job.error = Some(message.clone());
+        job.completed_at_ms = Some(now_ms);
+        let job_id = job.id.clone();
+        Self::append_subtask_event(
+            job,
+            SupervisorSubtaskEvent {
+                id: format!("timed_out:{job_id}:{attempt}"),
+                kind: "timed_out".to_string(),
+                message: message.clone(),
+                created_at_ms: now_ms,
+                metadata: json!({
+                    "attempt": attempt,
+                    "timeoutSeconds": timeout_seconds,
+                    "turnId": turn_id,
+                }),
+            },
+        );
+        if !job.can_retry() {
+            self.push_job_failed_signal(job, now_ms);
+        }
+
+        Some(SupervisorJobPolicyTask::Interrupt {
+            job_id,
+            workspace_id: job.workspace_id.clone(),
+            thread_id: job.thread_id.clone()?,
+            turn_id: turn_id?,
+        })
+    }
+
+    fn render_job_prompt(&self, job: &SupervisorJobState) -> String {
+        let results = job
+            .depends_on
+            .iter()
+            .filter_map(|dependency_id| {
+                let result = self.state.jobs.get(dependency_id)?.result.clone();
+                Some((dependency_id.clone(), result.unwrap_or_default()))
+            })
+            .collect::<Vec<_>>();
+        render_dependent_prompt(&job.description, &results)
+    }
+
+    /// Records the result of dispatching a retry or a released dependent. Returns `true` when
+    /// the job was cancelled while the attempt was starting, so the caller can interrupt the
+    /// new turn.
+    pub(crate) fn record_attempt_outcome(
+        &mut self,
+        job_id: &str,
+        result: &SupervisorDispatchActionResult,
+        at_ms: i64,
+    ) -> bool {
+        let Some(mut job) = self.state.jobs.get(job_id).cloned() else {
+            return false;
+        };
+        if job.status == SupervisorJobStatus::Cancelled {
+            return true;
+        }
+
+        job.starting = false;
+        job.attempt += 1;
+        let attempt = job.attempt;
+        let started = result.status != SupervisorDispatchStatus::Failed;
+        let message = if started {
+            job.status = SupervisorJobStatus::Running;
+            job.thread_id = result.thread_id.clone().or(job.thread_id);
+            job.started_at_ms = Some(at_ms);
+            job.completed_at_ms = None;
+            job.error = None;
+            job.digest = Some(Box::default());
+            format!("Attempt {attempt} of {} started.", job.policy.max_attempts)
+        } else {
+            let error = result
+                .error
+                .clone()
+                .unwrap_or_else(|| "Supervisor dispatch failed".to_string());
+            job.status = SupervisorJobStatus::Failed;
+            job.completed_at_ms = Some(at_ms);
+            job.error = Some(error.clone());
+            format!(
+                "Attempt {attempt} of {} failed to start: {error}",
+                job.policy.max_attempts
+            )
+        };
+        Self::append_subtask_event(
+            &mut job,
+            SupervisorSubtaskEvent {
+                id: format!("attempt:{job_id}:{attempt}"),
+                kind: if started { "running" } else { "failed" }.to_string(),
+                message: message.clone(),
+                created_at_ms: at_ms,
+                metadata: json!({
+                    "attempt": attempt,
+                    "threadId": result.thread_id,
+                    "turnId": result.turn_id,
+                    "error": result.error,
+                }),
+            },
+        );
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::UpsertJob(job.clone()),
+        );
+        let activity_kind = if attempt == 1 {
+            "job_started"
+        } else {
+            "job_retry"
+        };
+        self.push_activity(
+            format!("{activity_kind}:{job_id}:{attempt}"),
+            activity_kind,
+            message,
+            Some(job.workspace_id.clone()),
+            job.thread_id.clone(),
+            false,
+            at_ms,
+            json!({ "subtaskId": job_id, "attempt": attempt }),
+        );
+
+        if !started {
+            if !job.can_retry() {
+                self.push_job_failed_signal(&job, at_ms);
+            }
+            return false;
+        }
+        if let (Some(thread_id), Some(turn_id)) = (job.thread_id.as_ref(), result.turn_id.as_ref())
+        {
+            self.apply_app_server_event(
+                &job.workspace_id,
+                &json!({
+                    "method": "turn/started",
+                    "params": {
+                        "threadId": thread_id,
+                        "turnId": turn_id,
+                    }
+                }),
+                at_ms,
+            );
+        }
+        false
+    }
+
+    pub(crate) fn record_job_interrupt(
+        &mut self,
+        job_id: &str,
+        turn_id: &str,
+        result: Result<(), String>,
+        at_ms: i64,
+    ) {
+        let Some(mut job) = self.state.jobs.get(job_id).cloned() else {
+            return;
+        };
+        let (kind, message) = match &result {
+            Ok(()) => ("interrupted", format!("Interrupted turn `{turn_id}`.")),
+            Err(error) => (
+                "failed",
+                format!("Failed to interrupt turn `{turn_id}`: {error}"),
+            ),
+        };
+        Self::append_subtask_event(
+            &mut job,
+            SupervisorSubtaskEvent {
+                id: format!("interrupt:{job_id}:{turn_id}"),
+                kind: kind.to_string(),
+                message,
+                created_at_ms: at_ms,
+                metadata: json!({ "turnId": turn_id, "error": result.err() }),
+            },
+        );
+        apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
+    }
+
+    /// Marks a job cancelled and returns `(workspace_id, thread_id, turn_id)` of the turn that
+    /// is still running for it, if any.
+    pub(crate) fn cancel_job(
+        &mut self,
+        job_id: &str,
+        at_ms: i64,
+    ) -> Result<Option<(String, String, String)>, String> {
+        let Some(mut job) = self.state.jobs.get(job_id).cloned() else {
+            return Err(format!("subtask `{job_id}` is not tracked"));
+        };
+        if job.status.is_terminal() && !job.can_retry() {
+            return Err(format!("subtask `{job_id}` has already finished"));
+        }
+
+        let running_turn = if matches!(
+            job.status,
+            SupervisorJobStatus::Running | SupervisorJobStatus::WaitingForUser
+        ) {
+            job.thread_id.as_ref().and_then(|thread_id| {
+                self.state
+                    .threads
+                    .get(&super::thread_map_key(&job.workspace_id, thread_id))
+                    .and_then(|thread| thread.active_turn_id.clone())
+                    .map(|turn_id| (job.workspace_id.clone(), thread_id.clone(), turn_id))
+            })
+        } else {
+            None
+        };
+
+        job.status = SupervisorJobStatus::Cancelled;
+        job.completed_at_ms = Some(at_ms);
+        job.next_retry_at_ms = None;
+        job.starting = false;
+        let was_queued = job.queued_at_ms.is_some();
+        job.queued_at_ms = None;
+        job.queue_position = None;
+        job.waiting_request_id = None;
+        job.waiting_question_ids.clear();
+        let event = SupervisorSubtaskEvent {
+            id: format!("cancelled:{job_id}"),
+            kind: "cancelled".to_string(),
+            message: "Subtask cancelled.".to_string(),
+            created_at_ms: at_ms,
+            metadata: json!({
+                "attempt": job.attempt_number(),
+                "turnId": running_turn.as_ref().map(|(_, _, turn_id)| turn_id),
+            }),
+        };
+        Self::append_subtask_event(&mut job, event);
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::UpsertJob(job.clone()),
+        );
+        self.push_activity(
+            format!("job_cancelled:{job_id}:{at_ms}"),
+            "job_cancelled",
+            format!("Subtask `{job_id}` cancelled."),
+            Some(job.workspace_id.clone()),
+            job.thread_id.clone(),
+            false,
+            at_ms,
+            json!({ "subtaskId": job_id }),
+        );
+        if was_queued {
+            self.refresh_queue_positions(at_ms);
+        }
+        Ok(running_turn)
+    }
+
+    /// Stores a new schedule and computes its first run.
+    pub(crate) fn add_schedule(
+        &mut self,
+        mut schedule: SupervisorSchedule,
+    ) -> Result<SupervisorSchedule, String> {
+        if self.state.schedules.contains_key(&schedule.id) {
+            return Err(format!("schedule `{}` already exists", schedule.id));
+        }
+        let cron = SupervisorCronSchedule::parse(&schedule.cron)?;
+        schedule.next_run_at_ms = cron.next_after(schedule.created_at_ms);
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::UpsertSchedule(schedule.clone()),
+        );
+        self.push_activity(
+            format!("schedule_added:{}", schedule.id),
+            "schedule_added",
+            format!("Schedule `{}` added (`{}`).", schedule.id, schedule.cron),
+            None,
+            None,
+            false,
+            schedule.created_at_ms,
+            json!({ "scheduleId": schedule.id, "cron": schedule.cron }),
+        );
+        Ok(schedule)
+    }
+
+    pub(crate) fn remove_schedule(&mut self, schedule_id: &str, at_ms: i64) -> Result<(), String> {
+        if !self.state.schedules.contains_key(schedule_id) {
+            return Err(format!("schedule `{schedule_id}` not found"));
+        }
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::RemoveSchedule {
+                schedule_id: schedule_id.to_string(),
+            },
+        );
+        self.push_activity(
+            format!("schedule_removed:{schedule_id}:{at_ms}"),
+            "schedule_removed",
+            format!("Schedule `{schedule_id}` removed."),
+            None,
+            None,
+            false,
+            at_ms,
+            json!({ "scheduleId": schedule_id }),
+        );
+        Ok(())
+    }
+
+    /// Pauses or resumes a schedule. Resuming starts from the next occurrence after `at_ms`, so
+    /// runs that fell inside the pause are not caught up.
+    pub(crate) fn set_schedule_paused(
+        &mut self,
+        schedule_id: &str,
+        paused: bool,
+        at_ms: i64,
+    ) -> Result<SupervisorSchedule, String> {
+        let Some(mut schedule) = self.state.schedules.get(schedule_id).cloned() else {
+            return Err(format!("schedule `{schedule_id}` not found"));
+        };
+        schedule.paused = paused;
+        if !paused {
+            schedule.next_run_at_ms =
+                SupervisorCronSchedule::parse(&schedule.cron)?.next_after(at_ms);
+        }
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::UpsertSchedule(schedule.clone()),
+        );
+        let kind = if paused {
+            "schedule_paused"
+        } else {
+            "schedule_resumed"
+        };
+        self.push_activity(
+            format!("{kind}:{schedule_id}:{at_ms}"),
+            kind,
+            format!(
+                "Schedule `{schedule_id}` {}.",
+                if paused { "paused" } else { "resumed" }
+            ),
+            None,
+            None,
+            false,
+            at_ms,
+            json!({ "scheduleId": schedule_id }),
+        );
+        Ok(schedule)
+    }
+
+    pub(crate) fn schedules(&self) -> Vec<SupervisorSchedule> {
+        self.state.schedules.values().cloned().collect()
+    }
+
+    /// Returns the schedules due at `now_ms` and advances each to its next occurrence after
+    /// `now_ms` before anything is dispatched, so a crash mid-run never repeats a run.
+    /// Occurrences older than the grace period were missed while the supervisor was down; they
+    /// are folded into one run or skipped according to the schedule's `catch_up`.
+    pub(crate) fn take_due_schedules(&mut self, now_ms: i64) -> Vec<SupervisorScheduleRun> {
+        let due = self
+            .state
+            .schedules
+            .values()
+            .filter(|schedule| {
+                !schedule.paused
+                    && schedule
+                        .next_run_at_ms
+                        .is_some_and(|next_run_at_ms| next_run_at_ms <= now_ms)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut runs = Vec::new();
+        for mut schedule in due {
+            let Some(scheduled_for_ms) = schedule.next_run_at_ms else {
+                continue;
+            };
+            let cron = match SupervisorCronSchedule::parse(&schedule.cron) {
+                Ok(cron) => cron,
+                Err(error) => {
+                    schedule.next_run_at_ms = None;
+                    schedule.last_error = Some(error);
+                    apply_update(
+                        &mut self.state,
+                        SupervisorStateUpdate::UpsertSchedule(schedule),
+                    );
+                    continue;
+                }
+            };
+
+            let mut occurrences = 1u32;
+            let mut latest_ms = scheduled_for_ms;
+            while occurrences < SCHEDULE_MISSED_RUN_COUNT_LIMIT {
+                match cron.next_after(latest_ms) {
+                    Some(next_ms) if next_ms <= now_ms => {
+                        occurrences += 1;
+                        latest_ms = next_ms;
+                    }
+                    _ => break,
+                }
+            }
+            schedule.next_run_at_ms = cron.next_after(now_ms);
+
+            let latest_on_time = now_ms - latest_ms <= SCHEDULE_MISSED_RUN_GRACE_MS;
+            let skipped = match (schedule.catch_up, latest_on_time) {
+                (SupervisorScheduleCatchUp::Once, _) => 0,
+                (SupervisorScheduleCatchUp::Skip, true) => occurrences - 1,
+                (SupervisorScheduleCatchUp::Skip, false) => occurrences,
+            };
+            if skipped > 0 {
+                self.push_activity(
+                    format!("schedule_missed:{}:{scheduled_for_ms}", schedule.id),
+                    "schedule_missed",
+                    format!(
+                        "Schedule `{}` skipped {skipped} run(s) missed while the supervisor was not running.",
+                        schedule.id
+                    ),
+                    None,
+                    None,
+                    false,
+                    now_ms,
+                    json!({
+                        "scheduleId": schedule.id,
+                        "missedRuns": skipped,
+                        "firstMissedAtMs": scheduled_for_ms,
+                    }),
+                );
+            }
+
+            apply_update(
+                &mut self.state,
+                SupervisorStateUpdate::UpsertSchedule(schedule.clone()),
+            );
+            if skipped < occurrences {
+                runs.push(SupervisorScheduleRun {
+                    schedule,
+                    scheduled_for_ms: latest_ms,
+                    missed_runs: occurrences - 1 - skipped,
+                });
+            }
+        }
+        runs
+    }
+
+    /// Records the outcome of a schedule run. `error` carries dispatch failures; jobs that did
+    /// start are still linked from the schedule.
+    pub(crate) fn record_schedule_run(
+        &mut self,
+        run: &SupervisorScheduleRun,
+        job_ids: Vec<String>,
+        error: Option<String>,
+        at_ms: i64,
+    ) {
+        let schedule_id = run.schedule.id.as_str();
+        let Some(mut schedule) = self.state.schedules.get(schedule_id).cloned() else {
+            return;
+        };
+        schedule.last_run_at_ms = Some(run.scheduled_for_ms);
+        schedule.run_count += 1;
+        schedule.last_job_ids = job_ids.clone();
+        schedule.last_error = error.clone();
+        apply_update(
+            &mut self.state,
+            SupervisorStateUpdate::UpsertSchedule(schedule),
+        );
+
+        let catch_up_note = if run.missed_runs > 0 {
+            format!(" (catching up {} missed run(s))", run.missed_runs)
+        } else {
+            String::new()
+        };
+        let metadata = json!({
+            "scheduleId": schedule_id,
+            "scheduledForMs": run.scheduled_for_ms,
+            "missedRuns": run.missed_runs,
+            "subtaskIds": job_ids,
+        });
+        match error {
+            None => self.push_activity(
+                format!("schedule_run:{schedule_id}:{}", run.scheduled_for_ms),
+                "schedule_run",
+                format!(
+                    "Schedule `{schedule_id}` started {} subtask(s){catch_up_note}.",
+                    job_ids.len()
+                ),
+                None,
+                None,
+                false,
+                at_ms,
+                metadata,
+            ),
+            Some(error) => self.push_signal(
+                format!("schedule:{schedule_id}:failed"),
+                SupervisorSignalKind::Failed,
+                None,
+                None,
+                None,
+                format!("Schedule `{schedule_id}` run failed{catch_up_note}: {error}"),
+                at_ms,
+                metadata,
+            ),
+        }
+    }
+
+    /// `(job_id, workspace_id)` of finished jobs whose digest still lacks the files changed.
+    pub(crate) fn pending_job_digests(&self) -> Vec<(String, String)> {
+        self.state
+            .jobs
+            .values()
+            .filter(|job| {
+                job.status.is_terminal()
+                    && !job.can_retry()
+                    && job
+                        .digest
+                        .as_ref()
+                        .is_some_and(|digest| digest.captured_at_ms.is_none())
+            })
+            .map(|job| (job.id.clone(), job.workspace_id.clone()))
+            .collect()
+    }
+
+    /// Stores the workspace status taken before the job's current attempt started.
+    pub(crate) fn record_job_baseline(
+        &mut self,
+        job_id: &str,
+        files: Vec<SupervisorJobFileChange>,
+    ) {
+        let Some(mut job) = self.state.jobs.get(job_id).cloned() else {
+            return;
+        };
+        let Some(digest) = job.digest.as_mut() else {
+            return;
+        };
+        digest.baseline_files = Some(files);
+        apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
+    }
+
+    pub(crate) fn record_job_digest(
+        &mut self,
+        job_id: &str,
+        files: Result<Vec<SupervisorJobFileChange>, String>,
+        at_ms: i64,
+    ) {
+        let Some(mut job) = self.state.jobs.get(job_id).cloned() else {
+            return;
+        };
+        let last_message = job.result.clone();
+        let Some(digest) = job.digest.as_mut() else {
+            return;
+        };
+        match files {
+            Ok(files) => {
+                let baseline = digest.baseline_files.as_deref().unwrap_or_default();
+                digest.files_changed = Some(
+                    files
+                        .into_iter()
+                        .filter(|file| !baseline.contains(file))
+                        .collect(),
+                );
+            }
+            Err(error) => digest.files_error = Some(error),
+        }
+        digest.last_message = last_message;
+        digest.captured_at_ms = Some(at_ms);
+        apply_update(&mut self.state, SupervisorStateUpdate::UpsertJob(job));
+    }
+
+    fn push_job_failed_signal(&mut self, job: &SupervisorJobState, at_ms: i64) {
+        let attempts = job.attempt_number();
+        self.push_signal(
+            format!("job:{}:failed", job.id),
+            SupervisorSignalKind::Failed,
+            Some(job.workspace_id.clone()),
+            job.thread_id.clone(),
+            Some(job.id.clone()),
+            format!(
+                "Subtask failed after {attempts} attempt(s): {}",
+                job.error.as_deref().unwrap_or("unknown error")
+            ),
+            at_ms,
+            json!({ "attempts": attempts }),
+        );
+    }
+
+    fn compute_health(
+        &self,
+        snapshot: &SupervisorWorkspaceHealthInput,
+        now_ms: i64,
+    ) -> SupervisorHealth {
+        if !snapshot.connected {
+            return SupervisorHealth::Disconnected;
+        }
+
+        let Some(last_activity) = self
+            .workspace_last_event_at_ms
+            .get(&snapshot.workspace_id)
+            .copied()
+        else {
+            return SupervisorHealth::Stale;
+        };
+
+        let policy = snapshot.health.as_ref().unwrap_or(&self.config.health);
+        let age = now_ms.saturating_sub(last_activity);
+        if age >= policy.disconnected_after_ms {
+            SupervisorHealth::Disconnected
+        } else if age >= policy.stale_after_ms {
+            SupervisorHealth::Stale
+        } else {
+            SupervisorHealth::Healthy
+        }
+    }
+
+    fn workspace_has_active_workload(&self, workspace_id: &str) -> bool {
         let has_active_job = self.state.jobs.values().any(|job| {
             job.workspace_id == workspace_id
                 && matches!(
@@ -1044,6 +2476,11 @@ impl SupervisorLoop {
             .jobs
             .values()
             .filter(|job| job.workspace_id == workspace_id)
+            .filter(|job| {
+                job.status != SupervisorJobStatus::Cancelled
+                    && !job.waits_on_dependencies()
+                    && !job.waits_for_capacity()
+            })
             .filter(|job| {
                 if let Some(thread_id) = thread_id {
                     return job.thread_id.as_deref() == Some(thread_id);
@@ -1090,6 +2527,52 @@ impl SupervisorLoop {
         });
     }
 
+    /// Thread keys with an unresolved approval or question, or whose job waits for the user.
+    fn threads_waiting_for_user(&self) -> HashSet<String> {
+        let approvals = self
+            .state
+            .pending_approvals
+            .values()
+            .filter(|approval| approval.resolved_at_ms.is_none())
+            .filter_map(|approval| {
+                let thread_id = approval.thread_id.as_deref()?;
+                Some(super::thread_map_key(&approval.workspace_id, thread_id))
+            });
+        let questions = self
+            .state
+            .open_questions
+            .values()
+            .filter(|question| question.resolved_at_ms.is_none())
+            .map(|question| super::thread_map_key(&question.workspace_id, &question.thread_id));
+        let jobs = self
+            .state
+            .jobs
+            .values()
+            .filter(|job| job.status == SupervisorJobStatus::WaitingForUser)
+            .filter_map(|job| {
+                let thread_id = job.thread_id.as_deref()?;
+                Some(super::thread_map_key(&job.workspace_id, thread_id))
+            });
+        approvals.chain(questions).chain(jobs).collect()
+    }
+
+    /// Every event for a tracked thread counts as activity, including the agent message,
+    /// reasoning and command output deltas that are not otherwise normalized, so a turn
+    /// that is still streaming output is not reported as stalled.
+    fn touch_thread_activity(&mut self, workspace_id: &str, message: &Value, received_at_ms: i64) {
+        let Some(thread_id) = message
+            .get("params")
+            .and_then(|params| params.get("threadId").or_else(|| params.get("thread_id")))
+            .and_then(Value::as_str)
+        else {
+            return;
+        };
+        let key = super::thread_map_key(workspace_id, thread_id);
+        if let Some(thread) = self.state.threads.get_mut(&key) {
+            thread.last_activity_at_ms = thread.last_activity_at_ms.max(Some(received_at_ms));
+        }
+    }
+
     fn apply_thread_activity(
         &mut self,
         workspace_id: &str,
@@ -1181,7 +2664,34 @@ impl SupervisorLoop {
     }
 }
 
-fn summarize_text(value: &str, max_chars: usize) -> String {
+/// Replaces `{{<job_id>.result}}` placeholders with the prerequisites' final agent messages.
+fn render_dependent_prompt(template: &str, results: &[(String, String)]) -> String {
+    results
+        .iter()
+        .fold(template.to_string(), |prompt, (job_id, result)| {
+            prompt.replace(&format!("{{{{{job_id}.result}}}}"), result.trim())
+        })
+}
+
+/// Heuristic for commands that run a test suite, e.g. `cargo test`, `npm run test:unit` or
+/// `pytest -k retry`.
+fn is_test_command(command: &str) -> bool {
+    const TEST_RUNNERS: &[&str] = &["pytest", "jest", "vitest", "nextest", "mocha", "rspec"];
+    command
+        .split(|ch: char| ch.is_whitespace() || matches!(ch, '\'' | '"' | ';' | '&' | '|' | '('))
+        .any(|token| {
+            token == "test"
+                || token.starts_with("test:")
+                || TEST_RUNNERS.iter().any(|runner| {
+                    token == *runner
+                        || token
+                            .strip_suffix(runner)
+                            .is_some_and(|prefix| prefix.ends_with('/'))
+                })
+        })
+}
+
+pub(crate) fn summarize_text(value: &str, max_chars: usize) -> String {
     let trimmed = value.trim();
     if trimmed.chars().count() <= max_chars {
         return trimmed.to_string();
@@ -1203,7 +2713,20 @@ fn format_guided_system_message(
     )
 }
 
-fn request_value_key(workspace_id: &str, request_id: &Value) -> String {
+fn format_idle_duration(idle_ms: i64) -> String {
+    let seconds = idle_ms / 1_000;
+    if seconds >= 60 {
+        format!("{} min", seconds / 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+fn session_disconnected_signal_id(workspace_id: &str) -> String {
+    format!("session:{workspace_id}:disconnected")
+}
+
+pub(crate) fn request_value_key(workspace_id: &str, request_id: &Value) -> String {
     let request_id_value = request_id
         .as_str()
         .map(str::trim)
@@ -1217,6 +2740,7 @@ fn request_value_key(workspace_id: &str, request_id: &Value) -> String {
 
 #[cfg(test)]
 mod tests {
+    use super::super::SupervisorJobPolicy;
     use super::*;
     use serde_json::json;
 
@@ -1270,8 +2794,11 @@ mod tests {
     #[test]
     fn pull_health_check_emits_stale_and_disconnected_signals_for_active_workspace() {
         let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig {
-            stale_after_ms: 10,
-            disconnected_after_ms: 20,
+            health: SupervisorHealthPolicy {
+                stale_after_ms: 10,
+                disconnected_after_ms: 20,
+                ..SupervisorHealthPolicy::default()
+            },
             activity_feed_limit: 100,
         });
 
@@ -1291,6 +2818,7 @@ mod tests {
             workspace_id: "ws-health".to_string(),
             workspace_name: Some("Health Workspace".to_string()),
             connected: true,
+            health: None,
         }];
 
         loop_state.run_health_check(&input, 105);
@@ -1322,8 +2850,11 @@ mod tests {
     #[test]
     fn pull_health_check_suppresses_noise_for_idle_workspace() {
         let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig {
-            stale_after_ms: 10,
-            disconnected_after_ms: 20,
+            health: SupervisorHealthPolicy {
+                stale_after_ms: 10,
+                disconnected_after_ms: 20,
+                ..SupervisorHealthPolicy::default()
+            },
             activity_feed_limit: 100,
         });
 
@@ -1339,6 +2870,7 @@ mod tests {
             workspace_id: "ws-idle".to_string(),
             workspace_name: Some("Idle Workspace".to_string()),
             connected: true,
+            health: None,
         }];
 
         loop_state.run_health_check(&input, 112);
@@ -1352,45 +2884,289 @@ mod tests {
                 .health,
             SupervisorHealth::Stale
         );
-
-        loop_state.run_health_check(&input, 125);
-        let disconnected_snapshot = loop_state.snapshot();
-        assert_eq!(disconnected_snapshot.signals.len(), 0);
-        assert_eq!(
-            disconnected_snapshot
-                .workspaces
-                .get("ws-idle")
-                .expect("workspace should exist")
-                .health,
-            SupervisorHealth::Disconnected
+
+        loop_state.run_health_check(&input, 125);
+        let disconnected_snapshot = loop_state.snapshot();
+        assert_eq!(disconnected_snapshot.signals.len(), 0);
+        assert_eq!(
+            disconnected_snapshot
+                .workspaces
+                .get("ws-idle")
+                .expect("workspace should exist")
+                .health,
+            SupervisorHealth::Disconnected
+        );
+    }
+
+    #[test]
+    fn pull_health_check_emits_disconnected_signal_when_active_workspace_loses_connection() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.apply_app_server_event(
+            "ws-active",
+            &json!({
+                "method": "turn/started",
+                "params": {
+                    "threadId": "thread-active",
+                    "turnId": "turn-active"
+                }
+            }),
+            100,
+        );
+
+        let input = vec![SupervisorWorkspaceHealthInput {
+            workspace_id: "ws-active".to_string(),
+            workspace_name: Some("Active Workspace".to_string()),
+            connected: false,
+            health: None,
+        }];
+
+        loop_state.run_health_check(&input, 101);
+        let snapshot = loop_state.snapshot();
+        assert_eq!(snapshot.signals.len(), 1);
+        assert_eq!(snapshot.signals[0].kind, SupervisorSignalKind::Disconnected);
+    }
+
+    #[test]
+    fn pull_health_check_flags_stalled_turn_with_workspace_override() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.apply_app_server_event(
+            "ws-stall",
+            &json!({
+                "method": "turn/started",
+                "params": {
+                    "threadId": "thread-stall",
+                    "turnId": "turn-stall"
+                }
+            }),
+            1_000,
+        );
+
+        let input = vec![SupervisorWorkspaceHealthInput {
+            workspace_id: "ws-stall".to_string(),
+            workspace_name: Some("Stall Workspace".to_string()),
+            connected: true,
+            health: Some(SupervisorHealthPolicy {
+                stale_after_ms: 600_000,
+                disconnected_after_ms: 900_000,
+                thread_stall_after_ms: Some(120_000),
+                stall_action: SupervisorStallAction::Interrupt,
+                nudge_prompt: None,
+            }),
+        }];
+
+        assert!(loop_state.run_health_check(&input, 60_000).is_empty());
+
+        let stalled = loop_state.run_health_check(&input, 121_000);
+        assert_eq!(
+            stalled,
+            vec![SupervisorStalledTurn {
+                workspace_id: "ws-stall".to_string(),
+                thread_id: "thread-stall".to_string(),
+                turn_id: "turn-stall".to_string(),
+                action: SupervisorStallAction::Interrupt,
+                nudge_prompt: None,
+            }]
+        );
+        let snapshot = loop_state.snapshot();
+        let thread = snapshot
+            .threads
+            .values()
+            .find(|thread| thread.id == "thread-stall")
+            .expect("thread should exist");
+        assert_eq!(thread.status, SupervisorThreadStatus::Stalled);
+        assert_eq!(snapshot.signals.len(), 1);
+        assert_eq!(
+            snapshot.signals[0].id,
+            "stall:ws-stall:thread-stall:turn-stall"
+        );
+        assert_eq!(snapshot.signals[0].kind, SupervisorSignalKind::Stalled);
+        assert_eq!(
+            snapshot.signals[0].message,
+            "Thread thread-stall has had no activity for 2 min during turn turn-stall."
+        );
+        assert_eq!(snapshot.signals[0].context["autoAction"], "interrupt");
+
+        assert!(loop_state.run_health_check(&input, 180_000).is_empty());
+
+        loop_state.record_stall_action(&stalled[0], Err("session closed".to_string()), 181_000);
+        let activity = &loop_state.snapshot().activity_feed[0];
+        assert_eq!(activity.kind, "stall_action");
+        assert!(activity.needs_input);
+    }
+
+    #[test]
+    fn streaming_deltas_keep_a_long_turn_from_stalling() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.apply_app_server_event(
+            "ws-build",
+            &json!({
+                "method": "turn/started",
+                "params": { "threadId": "thread-build", "turnId": "turn-build" }
+            }),
+            1_000,
+        );
+        for (method, at_ms) in [
+            ("item/reasoning/textDelta", 60_000),
+            ("item/commandExecution/outputDelta", 110_000),
+            ("item/agentMessage/delta", 200_000),
+        ] {
+            loop_state.apply_app_server_event(
+                "ws-build",
+                &json!({
+                    "method": method,
+                    "params": {
+                        "threadId": "thread-build",
+                        "turnId": "turn-build",
+                        "itemId": "item-1",
+                        "delta": "compiling..."
+                    }
+                }),
+                at_ms,
+            );
+        }
+
+        let input = vec![SupervisorWorkspaceHealthInput {
+            workspace_id: "ws-build".to_string(),
+            workspace_name: None,
+            connected: true,
+            health: Some(SupervisorHealthPolicy {
+                stale_after_ms: 600_000,
+                disconnected_after_ms: 900_000,
+                thread_stall_after_ms: Some(120_000),
+                stall_action: SupervisorStallAction::Interrupt,
+                nudge_prompt: None,
+            }),
+        }];
+
+        assert!(loop_state.run_health_check(&input, 300_000).is_empty());
+        assert_eq!(
+            loop_state.run_health_check(&input, 320_000).len(),
+            1,
+            "the turn stalls once the deltas stop"
+        );
+    }
+
+    #[test]
+    fn turn_waiting_on_an_approval_is_not_stalled() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.apply_app_server_event(
+            "ws-1",
+            &json!({
+                "method": "turn/started",
+                "params": { "threadId": "thread-1", "turnId": "turn-1" }
+            }),
+            1_000,
+        );
+        loop_state.apply_app_server_event(
+            "ws-1",
+            &json!({
+                "id": 42,
+                "method": "workspace/requestApproval",
+                "params": { "threadId": "thread-1", "turnId": "turn-1", "itemId": "item-1" }
+            }),
+            2_000,
+        );
+        let input = vec![SupervisorWorkspaceHealthInput {
+            workspace_id: "ws-1".to_string(),
+            workspace_name: None,
+            connected: true,
+            health: Some(SupervisorHealthPolicy {
+                stale_after_ms: 600_000,
+                disconnected_after_ms: 900_000,
+                thread_stall_after_ms: Some(120_000),
+                stall_action: SupervisorStallAction::Interrupt,
+                nudge_prompt: None,
+            }),
+        }];
+
+        assert!(loop_state.run_health_check(&input, 500_000).is_empty());
+
+        loop_state.resolve_pending_approval("ws-1:42", 510_000);
+        assert!(
+            loop_state.run_health_check(&input, 600_000).is_empty(),
+            "the idle clock restarts when the approval is answered"
+        );
+        assert_eq!(loop_state.run_health_check(&input, 700_000).len(), 1);
+    }
+
+    #[test]
+    fn session_crash_and_restart_events_raise_and_ack_disconnected_signal() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.apply_app_server_event(
+            "ws-crash",
+            &json!({
+                "method": "codex/disconnected",
+                "params": {
+                    "workspaceId": "ws-crash",
+                    "reason": "Codex app-server exited with status 1",
+                    "exitCode": 1,
+                    "willRestart": true
+                }
+            }),
+            100,
+        );
+
+        let snapshot = loop_state.snapshot();
+        let workspace = snapshot.workspaces.get("ws-crash").expect("workspace");
+        assert!(!workspace.connected);
+        assert_eq!(workspace.health, SupervisorHealth::Disconnected);
+        assert_eq!(snapshot.signals.len(), 1);
+        assert_eq!(snapshot.signals[0].id, "session:ws-crash:disconnected");
+        assert_eq!(snapshot.signals[0].kind, SupervisorSignalKind::Disconnected);
+        assert_eq!(snapshot.activity_feed[0].kind, "session_disconnected");
+        assert!(!snapshot.activity_feed[0].needs_input);
+
+        loop_state.apply_app_server_event(
+            "ws-crash",
+            &json!({
+                "method": "codex/reconnecting",
+                "params": { "workspaceId": "ws-crash", "attempt": 1, "delayMs": 1000 }
+            }),
+            110,
+        );
+        loop_state.apply_app_server_event(
+            "ws-crash",
+            &json!({
+                "method": "codex/reconnected",
+                "params": {
+                    "workspaceId": "ws-crash",
+                    "attempt": 1,
+                    "resumedThreadIds": ["thread-1"],
+                    "failedThreadIds": []
+                }
+            }),
+            1_200,
         );
+
+        let snapshot = loop_state.snapshot();
+        let workspace = snapshot.workspaces.get("ws-crash").expect("workspace");
+        assert!(workspace.connected);
+        assert_eq!(workspace.health, SupervisorHealth::Healthy);
+        assert_eq!(snapshot.signals[0].acknowledged_at_ms, Some(1_200));
+        let kinds = snapshot
+            .activity_feed
+            .iter()
+            .map(|entry| entry.kind.as_str())
+            .collect::<Vec<_>>();
+        assert!(kinds.contains(&"session_reconnecting"));
+        assert!(kinds.contains(&"session_recovered"));
     }
 
     #[test]
-    fn pull_health_check_emits_disconnected_signal_when_active_workspace_loses_connection() {
+    fn session_disconnect_without_restart_needs_input() {
         let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
         loop_state.apply_app_server_event(
-            "ws-active",
+            "ws-gone",
             &json!({
-                "method": "turn/started",
-                "params": {
-                    "threadId": "thread-active",
-                    "turnId": "turn-active"
-                }
+                "method": "codex/disconnected",
+                "params": { "workspaceId": "ws-gone", "reason": "restart limit reached", "willRestart": false }
             }),
             100,
         );
 
-        let input = vec![SupervisorWorkspaceHealthInput {
-            workspace_id: "ws-active".to_string(),
-            workspace_name: Some("Active Workspace".to_string()),
-            connected: false,
-        }];
-
-        loop_state.run_health_check(&input, 101);
         let snapshot = loop_state.snapshot();
-        assert_eq!(snapshot.signals.len(), 1);
-        assert_eq!(snapshot.signals[0].kind, SupervisorSignalKind::Disconnected);
+        assert!(snapshot.activity_feed[0].needs_input);
+        assert_eq!(snapshot.signals[0].acknowledged_at_ms, None);
     }
 
     #[test]
@@ -1579,6 +3355,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn job_digest_collects_commands_and_captures_files_after_completion() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.upsert_job(tracked_running_job("job-1", "ws-1", "thread-1"));
+        let command_event = |item_id: &str, command: &str, exit_code: i64| {
+            json!({
+                "method": "item/completed",
+                "params": {
+                    "threadId": "thread-1",
+                    "item": {
+                        "id": item_id,
+                        "type": "commandExecution",
+                        "command": command,
+                        "exitCode": exit_code
+                    }
+                }
+            })
+        };
+
+        loop_state.apply_app_server_event("ws-1", &command_event("item-1", "rg retry", 0), 10);
+        for at_ms in [11, 12] {
+            loop_state.apply_app_server_event(
+                "ws-1",
+                &command_event("item-2", "bash -lc 'cargo test -p app'", 101),
+                at_ms,
+            );
+        }
+        loop_state.apply_app_server_event(
+            "ws-1",
+            &json!({
+                "method": "item/completed",
+                "params": {
+                    "threadId": "thread-1",
+                    "item": { "id": "item-3", "type": "agentMessage", "text": "Fixed the retry loop" }
+                }
+            }),
+            13,
+        );
+        assert!(
+            loop_state.pending_job_digests().is_empty(),
+            "files are captured only once the job finishes"
+        );
+        loop_state.apply_app_server_event(
+            "ws-1",
+            &json!({
+                "method": "turn/completed",
+                "params": { "threadId": "thread-1", "turnId": "turn-1" }
+            }),
+            14,
+        );
+
+        assert_eq!(
+            loop_state.pending_job_digests(),
+            vec![("job-1".to_string(), "ws-1".to_string())]
+        );
+        loop_state.record_job_digest(
+            "job-1",
+            Ok(vec![SupervisorJobFileChange {
+                path: "src/retry.rs".to_string(),
+                status: "M".to_string(),
+                additions: 4,
+                deletions: 0,
+            }]),
+            20,
+        );
+
+        let digest = loop_state.snapshot().jobs["job-1"]
+            .digest
+            .clone()
+            .expect("digest");
+        assert_eq!(
+            digest
+                .commands
+                .iter()
+                .map(|command| (command.command.as_str(), command.exit_code, command.is_test))
+                .collect::<Vec<_>>(),
+            vec![
+                ("rg retry", Some(0), false),
+                ("bash -lc 'cargo test -p app'", Some(101), true),
+            ]
+        );
+        assert_eq!(digest.files_changed.as_ref().map(Vec::len), Some(1));
+        assert_eq!(digest.last_message.as_deref(), Some("Fixed the retry loop"));
+        assert_eq!(digest.captured_at_ms, Some(20));
+        assert!(loop_state.pending_job_digests().is_empty());
+
+        assert!(is_test_command("npm run test:unit"));
+        assert!(is_test_command("./node_modules/.bin/vitest run"));
+        assert!(!is_test_command("ls tests/ src/testing"));
+    }
+
+    #[test]
+    fn job_digest_leaves_out_files_dirty_before_the_attempt() {
+        let file = |path: &str, status: &str, additions: i64| SupervisorJobFileChange {
+            path: path.to_string(),
+            status: status.to_string(),
+            additions,
+            deletions: 0,
+        };
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        let mut job = tracked_running_job("job-1", "ws-1", "thread-1");
+        job.digest = Some(Box::default());
+        loop_state.upsert_job(job);
+        loop_state.record_job_baseline(
+            "job-1",
+            vec![file("notes.md", "M", 2), file("src/lib.rs", "M", 1)],
+        );
+        loop_state.apply_app_server_event(
+            "ws-1",
+            &json!({
+                "method": "turn/completed",
+                "params": { "threadId": "thread-1", "turnId": "turn-1" }
+            }),
+            10,
+        );
+
+        loop_state.record_job_digest(
+            "job-1",
+            Ok(vec![
+                file("notes.md", "M", 2),
+                file("src/lib.rs", "M", 6),
+                file("src/new.rs", "A", 12),
+            ]),
+            20,
+        );
+
+        let digest = loop_state.snapshot().jobs["job-1"]
+            .digest
+            .clone()
+            .expect("digest");
+        assert_eq!(
+            digest.files_changed,
+            Some(vec![
+                file("src/lib.rs", "M", 6),
+                file("src/new.rs", "A", 12)
+            ])
+        );
+    }
+
     #[test]
     fn child_question_is_bridged_and_marks_job_waiting_for_user() {
         let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
@@ -1714,4 +3629,482 @@ mod tests {
             "expected user-facing chat messages without technical prefixes"
         );
     }
+
+    #[test]
+    fn job_timeout_interrupts_turn_and_schedules_retry() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        let mut job = tracked_running_job("job-4", "ws-4", "thread-4");
+        job.attempt = 1;
+        job.policy = SupervisorJobPolicy {
+            max_attempts: 2,
+            retry_backoff_seconds: 5,
+            timeout_seconds: Some(60),
+            ..SupervisorJobPolicy::default()
+        };
+        loop_state.upsert_job(job);
+        loop_state.apply_app_server_event(
+            "ws-4",
+            &json!({
+                "method": "turn/started",
+                "params": { "threadId": "thread-4", "turnId": "turn-4" }
+            }),
+            10,
+        );
+
+        assert!(loop_state.apply_job_policies(30_000).is_empty());
+
+        let tasks = loop_state.apply_job_policies(60_002);
+        assert_eq!(
+            tasks,
+            vec![SupervisorJobPolicyTask::Interrupt {
+                job_id: "job-4".to_string(),
+                workspace_id: "ws-4".to_string(),
+                thread_id: "thread-4".to_string(),
+                turn_id: "turn-4".to_string(),
+            }]
+        );
+        let job = loop_state.snapshot().jobs["job-4"].clone();
+        assert_eq!(job.status, SupervisorJobStatus::Queued);
+        assert_eq!(job.next_retry_at_ms, Some(65_002));
+        assert_eq!(job.error.as_deref(), Some("Attempt 1 timed out after 60s."));
+        let kinds = job
+            .recent_events
+            .iter()
+            .map(|event| event.kind.as_str())
+            .collect::<Vec<_>>();
+        assert!(kinds.ends_with(&["timed_out", "retry_scheduled"]));
+
+        loop_state.record_job_interrupt("job-4", "turn-4", Ok(()), 60_010);
+        loop_state.apply_app_server_event(
+            "ws-4",
+            &json!({
+                "method": "turn/completed",
+                "params": { "threadId": "thread-4", "turnId": "turn-4" }
+            }),
+            60_020,
+        );
+        assert_eq!(
+            loop_state.snapshot().jobs["job-4"].status,
+            SupervisorJobStatus::Queued,
+            "late completion of the interrupted turn must not finish the job"
+        );
+
+        let tasks = loop_state.apply_job_policies(65_002);
+        let [SupervisorJobPolicyTask::Dispatch { job: retry, .. }] = tasks.as_slice() else {
+            panic!("expected a retry task, got {tasks:?}");
+        };
+        assert_eq!(retry.thread_id.as_deref(), Some("thread-4"));
+
+        let cancelled = loop_state.record_attempt_outcome(
+            "job-4",
+            &SupervisorDispatchActionResult {
+                action_id: "job-4:attempt-2".to_string(),
+                action_type: Default::default(),
+                workspace_id: "ws-4".to_string(),
+                dedupe_key: String::new(),
+                status: SupervisorDispatchStatus::Dispatched,
+                thread_id: Some("thread-4".to_string()),
+                turn_id: Some("turn-5".to_string()),
+                error: None,
+                idempotent_replay: false,
+            },
+            65_100,
+        );
+        assert!(!cancelled);
+        let snapshot = loop_state.snapshot();
+        let job = &snapshot.jobs["job-4"];
+        assert_eq!(job.status, SupervisorJobStatus::Running);
+        assert_eq!(job.attempt, 2);
+        assert!(!job.can_retry());
+        assert_eq!(
+            snapshot.threads["ws-4:thread-4"].active_turn_id.as_deref(),
+            Some("turn-5")
+        );
+    }
+
+    #[test]
+    fn exhausted_retries_raise_failed_signal_and_cancel_rejects_finished_jobs() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        let mut job = tracked_running_job("job-5", "ws-5", "thread-5");
+        job.attempt = 1;
+        job.policy.timeout_seconds = Some(1);
+        loop_state.upsert_job(job);
+
+        let tasks = loop_state.apply_job_policies(5_000);
+        assert!(tasks.is_empty(), "no active turn to interrupt");
+        let snapshot = loop_state.snapshot();
+        assert_eq!(snapshot.jobs["job-5"].status, SupervisorJobStatus::Failed);
+        assert!(snapshot
+            .signals
+            .iter()
+            .any(|signal| signal.id == "job:job-5:failed"
+                && signal.kind == SupervisorSignalKind::Failed));
+
+        assert_eq!(
+            loop_state.cancel_job("job-5", 6_000),
+            Err("subtask `job-5` has already finished".to_string())
+        );
+        assert!(loop_state.cancel_job("missing", 6_000).is_err());
+    }
+
+    #[test]
+    fn cancel_job_returns_running_turn_and_blocks_further_events() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.upsert_job(tracked_running_job("job-6", "ws-6", "thread-6"));
+        loop_state.apply_app_server_event(
+            "ws-6",
+            &json!({
+                "method": "turn/started",
+                "params": { "threadId": "thread-6", "turnId": "turn-6" }
+            }),
+            10,
+        );
+
+        assert_eq!(
+            loop_state.cancel_job("job-6", 20),
+            Ok(Some((
+                "ws-6".to_string(),
+                "thread-6".to_string(),
+                "turn-6".to_string()
+            )))
+        );
+        loop_state.apply_app_server_event(
+            "ws-6",
+            &json!({
+                "method": "turn/completed",
+                "params": { "threadId": "thread-6", "turnId": "turn-6" }
+            }),
+            30,
+        );
+
+        let snapshot = loop_state.snapshot();
+        let job = &snapshot.jobs["job-6"];
+        assert_eq!(job.status, SupervisorJobStatus::Cancelled);
+        assert_eq!(job.completed_at_ms, Some(20));
+        assert!(job
+            .recent_events
+            .iter()
+            .any(|event| event.kind == "cancelled"));
+        assert!(snapshot
+            .activity_feed
+            .iter()
+            .any(|entry| entry.kind == "job_cancelled"));
+        assert!(loop_state.apply_job_policies(1_000_000).is_empty());
+    }
+
+    #[test]
+    fn dependents_start_after_prerequisites_complete_with_templated_results() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        let mut implement = tracked_running_job("implement", "ws-1", "thread-1");
+        implement.attempt = 1;
+        loop_state.upsert_job(implement);
+        let dependent = |id: &str, depends_on: &[&str], description: &str| SupervisorJobState {
+            id: id.to_string(),
+            workspace_id: "ws-2".to_string(),
+            description: description.to_string(),
+            status: SupervisorJobStatus::Queued,
+            depends_on: depends_on.iter().map(ToString::to_string).collect(),
+            ..Default::default()
+        };
+        loop_state.upsert_job(dependent(
+            "review",
+            &["implement"],
+            "Review this change: {{implement.result}}",
+        ));
+        loop_state.upsert_job(dependent("open-pr", &["review"], "Open a PR"));
+
+        assert!(loop_state.apply_job_policies(10).is_empty());
+
+        for (method, params) in [
+            (
+                "item/completed",
+                json!({
+                    "threadId": "thread-1",
+                    "item": { "id": "msg-1", "type": "agentMessage", "text": "Added retries to the client." }
+                }),
+            ),
+            (
+                "turn/completed",
+                json!({ "threadId": "thread-1", "turnId": "turn-1" }),
+            ),
+        ] {
+            loop_state.apply_app_server_event(
+                "ws-1",
+                &json!({ "method": method, "params": params }),
+                20,
+            );
+        }
+
+        let tasks = loop_state.apply_job_policies(30);
+        let [SupervisorJobPolicyTask::Dispatch { job, prompt }] = tasks.as_slice() else {
+            panic!("expected the review job to be released, got {tasks:?}");
+        };
+        assert_eq!(job.id, "review");
+        assert_eq!(prompt, "Review this change: Added retries to the client.");
+
+        let restored: SupervisorState =
+            serde_json::from_value(serde_json::to_value(loop_state.snapshot()).expect("encode"))
+                .expect("decode");
+        assert_eq!(restored.jobs["open-pr"].depends_on, vec!["review"]);
+        assert_eq!(
+            restored.jobs["implement"].result.as_deref(),
+            Some("Added retries to the client.")
+        );
+    }
+
+    #[test]
+    fn prerequisite_failure_cascades_through_dependents() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.upsert_job(SupervisorJobState {
+            id: "implement".to_string(),
+            workspace_id: "ws-1".to_string(),
+            status: SupervisorJobStatus::Failed,
+            error: Some("tests failed".to_string()),
+            attempt: 1,
+            ..Default::default()
+        });
+        for (id, dependency) in [("review", "implement"), ("open-pr", "review")] {
+            loop_state.upsert_job(SupervisorJobState {
+                id: id.to_string(),
+                workspace_id: "ws-2".to_string(),
+                status: SupervisorJobStatus::Queued,
+                depends_on: vec![dependency.to_string()],
+                policy: SupervisorJobPolicy {
+                    max_attempts: 3,
+                    ..SupervisorJobPolicy::default()
+                },
+                ..Default::default()
+            });
+        }
+
+        assert!(loop_state.apply_job_policies(100).is_empty());
+
+        let snapshot = loop_state.snapshot();
+        assert_eq!(snapshot.jobs["review"].status, SupervisorJobStatus::Failed);
+        assert_eq!(
+            snapshot.jobs["review"].error.as_deref(),
+            Some("prerequisite `implement` failed: tests failed")
+        );
+        assert_eq!(snapshot.jobs["open-pr"].status, SupervisorJobStatus::Failed);
+        assert!(!snapshot.jobs["open-pr"].can_retry());
+        assert!(snapshot
+            .signals
+            .iter()
+            .any(|signal| signal.id == "job:open-pr:failed"));
+        assert!(loop_state.apply_job_policies(1_000_000).is_empty());
+    }
+
+    #[test]
+    fn schedules_run_when_due_and_handle_missed_runs() {
+        // Quarter-hour boundaries line up in every time zone, so local time does not matter.
+        const QUARTER_MS: i64 = 15 * 60_000;
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        for (id, catch_up) in [
+            ("sched-once", SupervisorScheduleCatchUp::Once),
+            ("sched-skip", SupervisorScheduleCatchUp::Skip),
+        ] {
+            let schedule = loop_state
+                .add_schedule(SupervisorSchedule {
+                    id: id.to_string(),
+                    cron: "*/15 * * * *".to_string(),
+                    catch_up,
+                    ..Default::default()
+                })
+                .expect("add schedule");
+            assert_eq!(schedule.next_run_at_ms, Some(QUARTER_MS));
+        }
+        assert!(loop_state
+            .add_schedule(SupervisorSchedule {
+                id: "sched-once".to_string(),
+                cron: "@daily".to_string(),
+                ..Default::default()
+            })
+            .is_err());
+
+        assert!(loop_state.take_due_schedules(QUARTER_MS - 1).is_empty());
+        let runs = loop_state.take_due_schedules(QUARTER_MS + 5_000);
+        assert_eq!(runs.len(), 2);
+        assert!(runs
+            .iter()
+            .all(|run| run.scheduled_for_ms == QUARTER_MS && run.missed_runs == 0));
+        assert!(
+            loop_state.take_due_schedules(QUARTER_MS + 6_000).is_empty(),
+            "a run is handed out only once"
+        );
+        loop_state.record_schedule_run(
+            &runs[0],
+            vec!["schedule-sched-once-900000-1".to_string()],
+            None,
+            QUARTER_MS + 6_000,
+        );
+        let schedule = &loop_state.snapshot().schedules["sched-once"];
+        assert_eq!(schedule.run_count, 1);
+        assert_eq!(schedule.last_run_at_ms, Some(QUARTER_MS));
+        assert_eq!(schedule.next_run_at_ms, Some(2 * QUARTER_MS));
+
+        // Down from just before the second occurrence until well after the fifth.
+        let now_ms = 5 * QUARTER_MS + 120_000;
+        let runs = loop_state.take_due_schedules(now_ms);
+        let [run] = runs.as_slice() else {
+            panic!("expected only the catch-up run, got {runs:?}");
+        };
+        assert_eq!(run.schedule.id, "sched-once");
+        assert_eq!(run.scheduled_for_ms, 5 * QUARTER_MS);
+        assert_eq!(run.missed_runs, 3);
+        let snapshot = loop_state.snapshot();
+        assert!(snapshot
+            .schedules
+            .values()
+            .all(|schedule| schedule.next_run_at_ms == Some(6 * QUARTER_MS)));
+        let skipped = snapshot
+            .activity_feed
+            .iter()
+            .find(|entry| entry.kind == "schedule_missed")
+            .expect("skip schedule records its missed runs");
+        assert_eq!(skipped.metadata["scheduleId"], "sched-skip");
+        assert_eq!(skipped.metadata["missedRuns"], 4);
+
+        loop_state.record_schedule_run(run, Vec::new(), Some("ws-1: offline".to_string()), now_ms);
+        assert!(loop_state
+            .snapshot()
+            .signals
+            .iter()
+            .any(|signal| signal.id == "schedule:sched-once:failed"));
+
+        loop_state
+            .set_schedule_paused("sched-once", true, now_ms)
+            .expect("pause");
+        let runs = loop_state.take_due_schedules(10 * QUARTER_MS);
+        assert!(runs.iter().all(|run| run.schedule.id != "sched-once"));
+        let resumed = loop_state
+            .set_schedule_paused("sched-once", false, 20 * QUARTER_MS)
+            .expect("resume");
+        assert_eq!(resumed.next_run_at_ms, Some(21 * QUARTER_MS));
+        loop_state
+            .remove_schedule("sched-skip", 20 * QUARTER_MS)
+            .expect("remove");
+        assert!(loop_state
+            .remove_schedule("sched-skip", 20 * QUARTER_MS)
+            .is_err());
+    }
+
+    #[test]
+    fn queued_jobs_start_in_fifo_order_as_turn_slots_free_up() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        loop_state.upsert_job(tracked_running_job("job-run", "ws-1", "thread-1"));
+        for (id, queued_at_ms) in [("job-b", 20), ("job-a", 10), ("job-c", 30)] {
+            loop_state.upsert_job(SupervisorJobState {
+                id: id.to_string(),
+                workspace_id: "ws-1".to_string(),
+                description: format!("Prompt for {id}"),
+                status: SupervisorJobStatus::Queued,
+                queued_at_ms: Some(queued_at_ms),
+                ..Default::default()
+            });
+        }
+
+        assert!(loop_state.apply_job_policies(100).is_empty());
+        let positions = |loop_state: &SupervisorLoop| {
+            ["job-a", "job-b", "job-c"].map(|id| loop_state.snapshot().jobs[id].queue_position)
+        };
+        assert_eq!(positions(&loop_state), [Some(1), Some(2), Some(3)]);
+        assert!(loop_state.snapshot().jobs["job-a"]
+            .recent_events
+            .iter()
+            .any(|event| event.metadata["queuePosition"] == 1));
+
+        let mut actions = vec![SupervisorExecutableAction::DispatchTurn(
+            serde_json::from_value(json!({
+                "action_id": "job-new",
+                "workspace_id": "ws-1",
+                "prompt": "New work"
+            }))
+            .expect("action"),
+        )];
+        loop_state.admit_dispatch_actions(&mut actions);
+        let SupervisorExecutableAction::DispatchTurn(action) = &actions[0] else {
+            panic!("expected dispatch action");
+        };
+        assert!(
+            action.wait_for_capacity,
+            "new work lines up behind the queue"
+        );
+
+        let mut finished = loop_state.snapshot().jobs["job-run"].clone();
+        finished.status = SupervisorJobStatus::Completed;
+        loop_state.upsert_job(finished);
+        let tasks = loop_state.apply_job_policies(200);
+        let [SupervisorJobPolicyTask::Dispatch { job, prompt }] = tasks.as_slice() else {
+            panic!("expected the queue head to start, got {tasks:?}");
+        };
+        assert_eq!(job.id, "job-a");
+        assert_eq!(prompt, "Prompt for job-a");
+        assert_eq!(positions(&loop_state), [None, Some(1), Some(2)]);
+
+        let dispatch_action = |action_id: &str| {
+            SupervisorExecutableAction::DispatchTurn(
+                serde_json::from_value(json!({
+                    "action_id": action_id,
+                    "workspace_id": "ws-1",
+                    "prompt": "More work"
+                }))
+                .expect("action"),
+            )
+        };
+        let mut actions = vec![dispatch_action("job-late"), dispatch_action("job-run")];
+        loop_state.admit_dispatch_actions(&mut actions);
+        let waits = actions
+            .iter()
+            .map(|action| match action {
+                SupervisorExecutableAction::DispatchTurn(action) => action.wait_for_capacity,
+                _ => false,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            waits,
+            vec![true, false],
+            "the starting queue head holds its slot; tracked ids are left to the executor"
+        );
+
+        loop_state
+            .cancel_job("job-b", 300)
+            .expect("cancel queued job");
+        assert_eq!(positions(&loop_state), [None, None, Some(1)]);
+    }
+
+    #[test]
+    fn interleaved_dispatches_do_not_share_the_last_turn_slot() {
+        let mut loop_state = SupervisorLoop::new(SupervisorLoopConfig::default());
+        let dispatch = |action_id: &str| {
+            vec![SupervisorExecutableAction::DispatchTurn(
+                serde_json::from_value(json!({
+                    "action_id": action_id,
+                    "workspace_id": "ws-1",
+                    "prompt": "Work"
+                }))
+                .expect("action"),
+            )]
+        };
+        let waits = |actions: &[SupervisorExecutableAction]| match &actions[0] {
+            SupervisorExecutableAction::DispatchTurn(action) => action.wait_for_capacity,
+            _ => panic!("expected dispatch action"),
+        };
+
+        let mut first = dispatch("job-first");
+        let first_admitted = loop_state.admit_dispatch_actions(&mut first);
+        assert!(!waits(&first));
+        assert_eq!(first_admitted, vec!["job-first".to_string()]);
+
+        let mut second = dispatch("job-second");
+        assert!(loop_state.admit_dispatch_actions(&mut second).is_empty());
+        assert!(
+            waits(&second),
+            "the first dispatch still holds the slot while it starts"
+        );
+
+        loop_state.release_dispatch_admissions(&first_admitted);
+        let mut third = dispatch("job-third");
+        loop_state.admit_dispatch_actions(&mut third);
+        assert!(!waits(&third), "a failed dispatch gives its slot back");
+    }
 }
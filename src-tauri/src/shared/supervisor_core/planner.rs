@@ -0,0 +1,509 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::shared::codex_aux_core::extract_json_value;
+use crate::types::AppSettings;
+
+use super::contract::{
+    validate_supervisor_action_contract, SupervisorActionContract, SupervisorPlannerAction,
+    SUPERVISOR_ACTION_CONTRACT_VERSION,
+};
+use super::dispatch::{SupervisorDispatchBatchResult, SupervisorDispatchStatus};
+use super::routing::{
+    SupervisorRouteDecision, SupervisorRouteKind, SupervisorRouteWorkspaceMetadata,
+    SupervisorRouterKind,
+};
+use super::SupervisorHealth;
+
+pub(crate) const SUPERVISOR_PLANNER_MAX_ACTIONS: usize = 4;
+pub(crate) const SUPERVISOR_PLANNER_ROUTE_KIND: &str = "planner";
+
+/// Validated outcome of a planner run.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SupervisorPlannerDecision {
+    Dispatch {
+        reason: String,
+        contract: SupervisorActionContract,
+    },
+    Clarify {
+        reason: String,
+        question: String,
+        options: Vec<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+enum SupervisorPlannerResponse {
+    Dispatch {
+        #[serde(default)]
+        reason: String,
+        contract: SupervisorActionContract,
+    },
+    Clarify {
+        #[serde(default)]
+        reason: String,
+        question: String,
+        #[serde(default)]
+        options: Vec<String>,
+    },
+}
+
+fn is_dispatchable(workspace: &SupervisorRouteWorkspaceMetadata) -> bool {
+    workspace.connected
+        && workspace.available
+        && !matches!(workspace.health, SupervisorHealth::Disconnected)
+}
+
+/// Picks the workspace whose Codex session runs the planner thread: the dedicated
+/// Supervisor workspace when it is configured and available, else the first available one.
+pub(crate) fn select_planner_workspace(
+    workspaces: &[SupervisorRouteWorkspaceMetadata],
+    settings: &AppSettings,
+) -> Option<String> {
+    let mut available = workspaces
+        .iter()
+        .filter(|workspace| is_dispatchable(workspace));
+    if settings.supervisor_dedicated_workspace_enabled {
+        if let Some(dedicated_workspace_id) = settings
+            .supervisor_dedicated_workspace_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            if let Some(workspace) = available
+                .clone()
+                .find(|workspace| workspace.workspace_id == dedicated_workspace_id)
+            {
+                return Some(workspace.workspace_id.clone());
+            }
+        }
+    }
+    available
+        .next()
+        .map(|workspace| workspace.workspace_id.clone())
+}
+
+pub(crate) fn build_supervisor_planner_prompt(
+    prompt: &str,
+    workspaces: &[SupervisorRouteWorkspaceMetadata],
+) -> String {
+    let workspace_lines = workspaces
+        .iter()
+        .map(|workspace| {
+            json!({
+                "workspace_id": workspace.workspace_id,
+                "name": workspace.name,
+                "path": workspace.path,
+                "branch": workspace.branch,
+                "available": is_dispatchable(workspace),
+            })
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "You are the routing planner for a Supervisor that delegates tasks to Codex workspaces.\n\
+Decide which workspaces should handle the user request and write the task each one should run.\n\
+\n\
+Workspaces (one JSON object per line):\n\
+{workspace_lines}\n\
+\n\
+Reply with a single JSON object and nothing else, in one of these shapes:\n\
+{{\"decision\":\"dispatch\",\"reason\":\"<why these targets>\",\"contract\":{{\"version\":\"{SUPERVISOR_ACTION_CONTRACT_VERSION}\",\"actions\":[{{\"type\":\"dispatch_turn\",\"action_id\":\"1\",\"workspace_id\":\"<id>\",\"prompt\":\"<task for that workspace>\",\"model\":null,\"effort\":null}}]}}}}\n\
+{{\"decision\":\"clarify\",\"reason\":\"<what is unclear>\",\"question\":\"<question for the user>\",\"options\":[\"<workspace_id>\"]}}\n\
+\n\
+Rules:\n\
+- Only target workspaces with \"available\": true, at most one action per workspace.\n\
+- Use at most {SUPERVISOR_PLANNER_MAX_ACTIONS} actions.\n\
+- Ask for clarification instead of guessing when the target is unclear.\n\
+- Leave model and effort null unless the request asks for them.\n\
+\n\
+User request:\n\
+{}",
+        prompt.trim()
+    )
+}
+
+/// Parses the planner reply and checks it against the known workspaces. Dispatch
+/// contracts are rewritten so action ids, dedupe keys and route metadata come from the
+/// Supervisor rather than the model.
+pub(crate) fn parse_supervisor_planner_response(
+    raw: &str,
+    workspaces: &[SupervisorRouteWorkspaceMetadata],
+    action_id_prefix: &str,
+) -> Result<SupervisorPlannerDecision, String> {
+    let value = extract_json_value(raw).ok_or_else(|| "planner did not return JSON".to_string())?;
+    let response: SupervisorPlannerResponse = serde_json::from_value(value)
+        .map_err(|error| format!("invalid planner response: {error}"))?;
+
+    match response {
+        SupervisorPlannerResponse::Dispatch { reason, contract } => {
+            let reason = normalize_reason(reason, "Planner selected the target workspaces.");
+            let contract =
+                normalize_planner_contract(contract, workspaces, action_id_prefix, &reason)?;
+            Ok(SupervisorPlannerDecision::Dispatch { reason, contract })
+        }
+        SupervisorPlannerResponse::Clarify {
+            reason,
+            question,
+            options,
+        } => {
+            let question = question.trim();
+            if question.is_empty() {
+                return Err("planner clarification is missing a question".to_string());
+            }
+            let known = workspaces
+                .iter()
+                .map(|workspace| workspace.workspace_id.as_str())
+                .collect::<HashSet<_>>();
+            let mut seen = HashSet::new();
+            let options = options
+                .into_iter()
+                .map(|option| option.trim().to_string())
+                .filter(|option| known.contains(option.as_str()) && seen.insert(option.clone()))
+                .collect();
+            Ok(SupervisorPlannerDecision::Clarify {
+                reason: normalize_reason(reason, "Planner needs more detail before dispatching."),
+                question: question.to_string(),
+                options,
+            })
+        }
+    }
+}
+
+fn normalize_reason(reason: String, fallback: &str) -> String {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        fallback.to_string()
+    } else {
+        reason.to_string()
+    }
+}
+
+fn normalize_planner_contract(
+    contract: SupervisorActionContract,
+    workspaces: &[SupervisorRouteWorkspaceMetadata],
+    action_id_prefix: &str,
+    reason: &str,
+) -> Result<SupervisorActionContract, String> {
+    if contract.actions.is_empty() {
+        return Err("planner contract has no actions".to_string());
+    }
+    if contract.actions.len() > SUPERVISOR_PLANNER_MAX_ACTIONS {
+        return Err(format!(
+            "planner proposed {} actions (limit {SUPERVISOR_PLANNER_MAX_ACTIONS})",
+            contract.actions.len()
+        ));
+    }
+
+    let available = workspaces
+        .iter()
+        .filter(|workspace| is_dispatchable(workspace))
+        .map(|workspace| workspace.workspace_id.as_str())
+        .collect::<HashSet<_>>();
+    let mut targeted = HashSet::new();
+    let mut actions = Vec::with_capacity(contract.actions.len());
+
+    for (index, action) in contract.actions.into_iter().enumerate() {
+        let SupervisorPlannerAction::DispatchTurn(mut action) = action else {
+            return Err("planner may only propose `dispatch_turn` actions".to_string());
+        };
+        let workspace_id = action.workspace_id.trim().to_string();
+        if !available.contains(workspace_id.as_str()) {
+            return Err(format!(
+                "planner targeted unavailable workspace `{workspace_id}`"
+            ));
+        }
+        if !targeted.insert(workspace_id.clone()) {
+            return Err(format!(
+                "planner targeted workspace `{workspace_id}` more than once"
+            ));
+        }
+
+        let action_id = format!("{action_id_prefix}-{}", index + 1);
+        action.dedupe_key = Some(action_id.clone());
+        action.action_id = action_id;
+        action.workspace_id = workspace_id;
+        action.thread_id = None;
+        action.access_mode = None;
+        action.route_kind = Some(SUPERVISOR_PLANNER_ROUTE_KIND.to_string());
+        action.route_reason = Some(reason.to_string());
+        action.route_fallback = None;
+        action.depends_on = Vec::new();
+        actions.push(SupervisorPlannerAction::DispatchTurn(action));
+    }
+
+    let contract = SupervisorActionContract {
+        version: SUPERVISOR_ACTION_CONTRACT_VERSION.to_string(),
+        actions,
+    };
+    validate_supervisor_action_contract(contract.clone())?;
+    Ok(contract)
+}
+
+/// Route metadata recorded for a planner decision.
+pub(crate) fn planner_route_decision(
+    decision: &SupervisorPlannerDecision,
+    candidates: Vec<SupervisorRouteWorkspaceMetadata>,
+) -> SupervisorRouteDecision {
+    match decision {
+        SupervisorPlannerDecision::Dispatch { reason, contract } => {
+            let targets = contract
+                .actions
+                .iter()
+                .filter_map(|action| match action {
+                    SupervisorPlannerAction::DispatchTurn(action) => Some(action),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            SupervisorRouteDecision {
+                kind: SupervisorRouteKind::WorkspaceDelegate,
+                reason: reason.clone(),
+                router: SupervisorRouterKind::Planner,
+                workspace_id: (targets.len() == 1).then(|| targets[0].workspace_id.clone()),
+                local_tool: None,
+                model: targets.first().and_then(|action| action.model.clone()),
+                used_dedicated_workspace: false,
+                requires_workspace_connection: false,
+                fallback_message: None,
+                clarification: None,
+                options: targets
+                    .iter()
+                    .map(|action| action.workspace_id.clone())
+                    .collect(),
+                candidates,
+            }
+        }
+        SupervisorPlannerDecision::Clarify {
+            reason,
+            question,
+            options,
+        } => SupervisorRouteDecision {
+            kind: SupervisorRouteKind::Clarification,
+            reason: reason.clone(),
+            router: SupervisorRouterKind::Planner,
+            workspace_id: None,
+            local_tool: None,
+            model: None,
+            used_dedicated_workspace: false,
+            requires_workspace_connection: false,
+            fallback_message: None,
+            clarification: Some(question.clone()),
+            options: options.clone(),
+            candidates,
+        },
+    }
+}
+
+pub(crate) fn format_planner_dispatch_message(
+    reason: &str,
+    contract: &SupervisorActionContract,
+    dispatch: &SupervisorDispatchBatchResult,
+) -> String {
+    let dispatched = dispatch
+        .results
+        .iter()
+        .filter(|item| item.status != SupervisorDispatchStatus::Failed)
+        .count();
+    let failed = dispatch.results.len().saturating_sub(dispatched);
+    let mut lines = vec![
+        format!(
+            "Planner sent {} task(s): {} succeeded, {} failed.",
+            contract.actions.len(),
+            dispatched,
+            failed
+        ),
+        format!("Reason: {reason}"),
+    ];
+
+    for item in &dispatch.results {
+        let prompt = contract.actions.iter().find_map(|action| match action {
+            SupervisorPlannerAction::DispatchTurn(action) if action.action_id == item.action_id => {
+                Some(action.prompt.trim().chars().take(140).collect::<String>())
+            }
+            _ => None,
+        });
+        let task = prompt
+            .map(|prompt| format!(" — {prompt}"))
+            .unwrap_or_default();
+        match item.status {
+            SupervisorDispatchStatus::Dispatched | SupervisorDispatchStatus::Applied => {
+                lines.push(format!("- {}: started{task}", item.workspace_id))
+            }
+            SupervisorDispatchStatus::Queued => lines.push(format!(
+                "- {}: queued until a turn slot frees up{task}",
+                item.workspace_id
+            )),
+            SupervisorDispatchStatus::Failed => lines.push(format!(
+                "- {}: failed to start ({}){task}",
+                item.workspace_id,
+                item.error.as_deref().unwrap_or("unknown error")
+            )),
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::supervisor_core::contract::SupervisorDispatchTurnAction;
+
+    fn workspace(id: &str, available: bool) -> SupervisorRouteWorkspaceMetadata {
+        SupervisorRouteWorkspaceMetadata {
+            workspace_id: id.to_string(),
+            name: format!("{id}-name"),
+            path: format!("/tmp/{id}"),
+            branch: None,
+            connected: available,
+            available,
+            health: if available {
+                SupervisorHealth::Healthy
+            } else {
+                SupervisorHealth::Disconnected
+            },
+            capabilities: Vec::new(),
+        }
+    }
+
+    fn dispatch_turn(
+        contract: &SupervisorActionContract,
+        index: usize,
+    ) -> &SupervisorDispatchTurnAction {
+        match &contract.actions[index] {
+            SupervisorPlannerAction::DispatchTurn(action) => action,
+            other => panic!("unexpected action {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_fenced_dispatch_contract_and_rewrites_metadata() {
+        let workspaces = [workspace("ws-1", true), workspace("ws-2", true)];
+        let raw = r#"Here is the plan:
+```json
+{"decision":"dispatch","reason":"Backend and docs both change","contract":{"version":"supervisor.actions.v2","actions":[
+  {"type":"dispatch_turn","action_id":"a","workspace_id":"ws-1","prompt":"Fix the API","model":"gpt-5","effort":"high","access_mode":"full-access","thread_id":"t-1"},
+  {"type":"dispatch_turn","action_id":"a","workspace_id":" ws-2 ","prompt":"Update docs"}
+]}}
+```"#;
+
+        let decision =
+            parse_supervisor_planner_response(raw, &workspaces, "planner-1").expect("decision");
+        let SupervisorPlannerDecision::Dispatch { reason, contract } = decision else {
+            panic!("expected dispatch");
+        };
+        assert_eq!(reason, "Backend and docs both change");
+        assert_eq!(contract.version, SUPERVISOR_ACTION_CONTRACT_VERSION);
+
+        let first = dispatch_turn(&contract, 0);
+        assert_eq!(first.action_id, "planner-1-1");
+        assert_eq!(first.dedupe_key.as_deref(), Some("planner-1-1"));
+        assert_eq!(first.model.as_deref(), Some("gpt-5"));
+        assert_eq!(first.effort.as_deref(), Some("high"));
+        assert!(first.access_mode.is_none());
+        assert!(first.thread_id.is_none());
+        assert_eq!(first.route_kind.as_deref(), Some("planner"));
+        assert_eq!(dispatch_turn(&contract, 1).workspace_id, "ws-2");
+
+        let route = planner_route_decision(
+            &SupervisorPlannerDecision::Dispatch { reason, contract },
+            workspaces.to_vec(),
+        );
+        assert_eq!(route.router, SupervisorRouterKind::Planner);
+        assert_eq!(route.options, vec!["ws-1".to_string(), "ws-2".to_string()]);
+        assert!(route.workspace_id.is_none());
+    }
+
+    #[test]
+    fn rejects_contracts_outside_planner_limits() {
+        let workspaces = [workspace("ws-1", true), workspace("ws-off", false)];
+        let cases = [
+            (
+                r#"{"decision":"dispatch","contract":{"version":"supervisor.dispatch.v1","actions":[{"type":"dispatch_turn","action_id":"1","workspace_id":"ws-off","prompt":"x"}]}}"#,
+                "unavailable workspace `ws-off`",
+            ),
+            (
+                r#"{"decision":"dispatch","contract":{"version":"supervisor.dispatch.v1","actions":[{"type":"dispatch_turn","action_id":"1","workspace_id":"ws-1","prompt":"x"},{"type":"dispatch_turn","action_id":"2","workspace_id":"ws-1","prompt":"y"}]}}"#,
+                "more than once",
+            ),
+            (
+                r#"{"decision":"dispatch","contract":{"version":"supervisor.actions.v2","actions":[{"type":"ack_signal","action_id":"1","signal_id":"s"}]}}"#,
+                "only propose `dispatch_turn`",
+            ),
+            (
+                r#"{"decision":"dispatch","contract":{"version":"supervisor.dispatch.v1","actions":[{"type":"dispatch_turn","action_id":"1","workspace_id":"ws-1","prompt":"  "}]}}"#,
+                "prompt",
+            ),
+            (
+                r#"{"decision":"dispatch","contract":{"version":"supervisor.dispatch.v1","actions":[{"type":"dispatch_turn","action_id":"1","workspace_id":"ws-1","prompt":"x","priority":1}]}}"#,
+                "invalid planner response",
+            ),
+            ("I would send this to ws-1.", "did not return JSON"),
+        ];
+
+        for (raw, expected) in cases {
+            let error = parse_supervisor_planner_response(raw, &workspaces, "p")
+                .expect_err("planner response should be rejected");
+            assert!(
+                error.contains(expected),
+                "{error} should contain {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn clarification_keeps_only_known_workspace_options() {
+        let workspaces = [workspace("ws-1", true), workspace("ws-2", false)];
+        let decision = parse_supervisor_planner_response(
+            r#"{"decision":"clarify","question":"Which repo?","options":["ws-1","ws-2","ws-x","ws-1"]}"#,
+            &workspaces,
+            "p",
+        )
+        .expect("decision");
+        assert_eq!(
+            decision,
+            SupervisorPlannerDecision::Clarify {
+                reason: "Planner needs more detail before dispatching.".to_string(),
+                question: "Which repo?".to_string(),
+                options: vec!["ws-1".to_string(), "ws-2".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn planner_runs_in_dedicated_workspace_when_available() {
+        let workspaces = [
+            workspace("ws-0", false),
+            workspace("ws-1", true),
+            workspace("ws-2", true),
+        ];
+        let mut settings = AppSettings::default();
+        assert_eq!(
+            select_planner_workspace(&workspaces, &settings).as_deref(),
+            Some("ws-1")
+        );
+
+        settings.supervisor_dedicated_workspace_enabled = true;
+        settings.supervisor_dedicated_workspace_id = Some("ws-2".to_string());
+        assert_eq!(
+            select_planner_workspace(&workspaces, &settings).as_deref(),
+            Some("ws-2")
+        );
+
+        settings.supervisor_dedicated_workspace_id = Some("ws-0".to_string());
+        assert_eq!(
+            select_planner_workspace(&workspaces, &settings).as_deref(),
+            Some("ws-1")
+        );
+
+        let prompt = build_supervisor_planner_prompt("  Ship it  ", &workspaces);
+        assert!(prompt.ends_with("User request:\nShip it"));
+        assert!(prompt.contains(r#""workspace_id":"ws-0""#));
+        assert!(prompt.contains(r#""available":false"#));
+    }
+}
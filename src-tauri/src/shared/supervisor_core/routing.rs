@@ -36,11 +36,22 @@ pub(crate) enum SupervisorLocalTool {
     Help,
 }
 
+/// Which router produced a route decision.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SupervisorRouterKind {
+    #[default]
+    Deterministic,
+    Planner,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub(crate) struct SupervisorRouteDecision {
     pub(crate) kind: SupervisorRouteKind,
     pub(crate) reason: String,
     #[serde(default)]
+    pub(crate) router: SupervisorRouterKind,
+    #[serde(default)]
     pub(crate) workspace_id: Option<String>,
     #[serde(default)]
     pub(crate) local_tool: Option<SupervisorLocalTool>,
@@ -79,6 +90,7 @@ pub(crate) fn select_supervisor_route(
         return SupervisorRouteDecision {
             kind: SupervisorRouteKind::LocalTool,
             reason: "Prompt matched Supervisor local-tool intent.".to_string(),
+            router: SupervisorRouterKind::Deterministic,
             workspace_id: None,
             local_tool: Some(local_tool),
             model: None,
@@ -311,6 +323,7 @@ fn workspace_decision(
     SupervisorRouteDecision {
         kind: SupervisorRouteKind::WorkspaceDelegate,
         reason,
+        router: SupervisorRouterKind::Deterministic,
         workspace_id: Some(workspace_id),
         local_tool: None,
         model,
@@ -333,6 +346,7 @@ fn clarification_decision(
     SupervisorRouteDecision {
         kind: SupervisorRouteKind::Clarification,
         reason,
+        router: SupervisorRouterKind::Deterministic,
         workspace_id: None,
         local_tool: None,
         model: None,
@@ -0,0 +1,254 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+/// How far ahead `next_after` looks before deciding an expression never fires.
+const CRON_SEARCH_LIMIT_DAYS: i64 = 366 * 5;
+
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A five-field cron expression (`minute hour day-of-month month day-of-week`) evaluated in
+/// the local time zone. Supports `*`, lists, ranges, `/step`, month and weekday names, and
+/// the `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` aliases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SupervisorCronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl SupervisorCronSchedule {
+    pub(crate) fn parse(expression: &str) -> Result<Self, String> {
+        let trimmed = expression.trim();
+        let expanded = match trimmed.to_ascii_lowercase().as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            _ => trimmed,
+        };
+        let fields = expanded.split_whitespace().collect::<Vec<_>>();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(format!(
+                "invalid cron expression `{trimmed}`: expected 5 fields (minute hour day month weekday)"
+            ));
+        };
+
+        let (minutes, _) = parse_field("minute", minute, 0, 59, &[])?;
+        let (hours, _) = parse_field("hour", hour, 0, 23, &[])?;
+        let (days_of_month, day_of_month_restricted) =
+            parse_field("day of month", day_of_month, 1, 31, &[])?;
+        let (months, _) = parse_field("month", month, 1, 12, MONTH_NAMES)?;
+        let (mut days_of_week, day_of_week_restricted) =
+            parse_field("day of week", day_of_week, 0, 7, WEEKDAY_NAMES)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes,
+            hours,
+            days_of_month,
+            months,
+            days_of_week,
+            day_of_month_restricted,
+            day_of_week_restricted,
+        })
+    }
+
+    /// First matching minute strictly after `after_ms`, in the local time zone.
+    pub(crate) fn next_after(&self, after_ms: i64) -> Option<i64> {
+        self.next_after_in(&Local, after_ms)
+    }
+
+    pub(crate) fn next_after_in<Tz: TimeZone>(&self, tz: &Tz, after_ms: i64) -> Option<i64> {
+        let after = tz.timestamp_millis_opt(after_ms).single()?.naive_local();
+        let mut candidate = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = candidate + Duration::days(CRON_SEARCH_LIMIT_DAYS);
+
+        while candidate < limit {
+            let date = candidate.date();
+            if !has_bit(self.months, date.month()) {
+                candidate = first_day_of_next_month(date)?;
+                continue;
+            }
+            if !self.matches_day(date) {
+                candidate = start_of_day(date + Duration::days(1))?;
+                continue;
+            }
+            if !has_bit(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if has_bit(self.minutes, candidate.minute()) {
+                // Minutes skipped by a DST gap have no local time and are passed over.
+                if let Some(local) = tz.from_local_datetime(&candidate).earliest() {
+                    let timestamp_ms = local.timestamp_millis();
+                    if timestamp_ms > after_ms {
+                        return Some(timestamp_ms);
+                    }
+                }
+            }
+            candidate += Duration::minutes(1);
+        }
+        None
+    }
+
+    /// Classic cron rule: when both day fields are restricted, either one matching is enough.
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = has_bit(self.days_of_month, date.day());
+        let day_of_week = has_bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+fn parse_field(
+    name: &str,
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+) -> Result<(u64, bool), String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid {name} step in `{part}`"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(name, start, min, max, names)?,
+                parse_value(name, end, min, max, names)?,
+            )
+        } else {
+            let value = parse_value(name, range, min, max, names)?;
+            (value, if part.contains('/') { max } else { value })
+        };
+        if start > end {
+            return Err(format!("invalid {name} range `{range}`"));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok((mask, field != "*"))
+}
+
+fn parse_value(name: &str, raw: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let lowered = raw.to_ascii_lowercase();
+    let value = match names.iter().position(|candidate| *candidate == lowered) {
+        Some(index) => index as u32 + min,
+        None => raw
+            .parse::<u32>()
+            .map_err(|_| format!("invalid {name} value `{raw}`"))?,
+    };
+    if value < min || value > max {
+        return Err(format!(
+            "{name} value `{raw}` is out of range ({min}-{max})"
+        ));
+    }
+    Ok(value)
+}
+
+fn has_bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn start_of_day(date: NaiveDate) -> Option<NaiveDateTime> {
+    date.and_hms_opt(0, 0, 0)
+}
+
+fn first_day_of_next_month(date: NaiveDate) -> Option<NaiveDateTime> {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    start_of_day(NaiveDate::from_ymd_opt(year, month, 1)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn utc_ms(value: &str) -> i64 {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M")
+            .expect("timestamp")
+            .and_utc()
+            .timestamp_millis()
+    }
+
+    fn next(expression: &str, after: &str) -> Option<i64> {
+        SupervisorCronSchedule::parse(expression)
+            .expect("valid cron")
+            .next_after_in(&Utc, utc_ms(after))
+    }
+
+    #[test]
+    fn computes_next_run_for_common_expressions() {
+        // 2026-10-16 is a Friday.
+        assert_eq!(
+            next("0 9 * * 1-5", "2026-10-16 09:00"),
+            Some(utc_ms("2026-10-19 09:00"))
+        );
+        assert_eq!(
+            next("0 9 * * mon-fri", "2026-10-16 08:59"),
+            Some(utc_ms("2026-10-16 09:00"))
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2026-10-16 10:07"),
+            Some(utc_ms("2026-10-16 10:15"))
+        );
+        assert_eq!(
+            next("@daily", "2026-12-31 23:30"),
+            Some(utc_ms("2027-01-01 00:00"))
+        );
+        assert_eq!(
+            next("30 2 1 feb,jun *", "2026-03-01 00:00"),
+            Some(utc_ms("2026-06-01 02:30"))
+        );
+        // Day of month OR Sunday (7 is an alias for 0) once both are restricted.
+        assert_eq!(
+            next("0 0 13 * 7", "2026-10-16 00:00"),
+            Some(utc_ms("2026-10-18 00:00"))
+        );
+        assert_eq!(next("0 0 31 2 *", "2026-01-01 00:00"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "0 9 * * fun",
+        ] {
+            assert!(
+                SupervisorCronSchedule::parse(expression).is_err(),
+                "expected `{expression}` to be rejected"
+            );
+        }
+    }
+}
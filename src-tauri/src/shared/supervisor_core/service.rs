@@ -8,27 +8,40 @@ use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::shared::codex_aux_core::run_background_prompt_core;
 use crate::types::{AppSettings, WorkspaceEntry};
 
 use super::chat::{
-    build_dispatch_contract, format_ack_message, format_dispatch_message, format_feed_message,
-    format_help_message, format_status_message, parse_supervisor_chat_command,
+    build_dispatch_contract, format_ack_message, format_cancel_message, format_dispatch_message,
+    format_feed_message, format_help_message, format_job_message, format_schedule_list_message,
+    format_schedule_saved_message, format_status_message, parse_supervisor_chat_command,
     SupervisorChatCommand, SupervisorChatDispatchRequest, SupervisorChatHistoryResponse,
-    SupervisorChatSendResponse, SUPERVISOR_CHAT_FEED_LIMIT,
+    SupervisorChatSendResponse, SupervisorScheduleCommand, SUPERVISOR_CHAT_FEED_LIMIT,
 };
-use super::contract::parse_supervisor_action_contract_value;
+use super::contract::{parse_supervisor_action_contract_value, SupervisorActionContract};
 use super::dispatch::{
-    SupervisorDispatchAction, SupervisorDispatchBatchResult, SupervisorDispatchExecutor,
-    SupervisorDispatchStatus, WorkspaceSessionDispatchBackend,
+    ensure_response_ok, redispatch_action, SupervisorActionType, SupervisorControlAction,
+    SupervisorDispatchAction, SupervisorDispatchBackend, SupervisorDispatchBatchResult,
+    SupervisorDispatchExecutor, SupervisorDispatchStatus, SupervisorExecutableAction,
+    WorkspaceSessionDispatchBackend,
+};
+use super::planner::{
+    build_supervisor_planner_prompt, format_planner_dispatch_message,
+    parse_supervisor_planner_response, planner_route_decision, select_planner_workspace,
+    SupervisorPlannerDecision,
 };
 use super::routing::{
     select_supervisor_route, SupervisorLocalTool, SupervisorRouteDecision, SupervisorRouteKind,
-    SupervisorRouteWorkspaceMetadata,
+    SupervisorRouteWorkspaceMetadata, SupervisorRouterKind,
+};
+use super::supervisor_loop::{
+    collect_job_baselines, now_timestamp_ms, request_value_key, SupervisorJobPolicyTask,
+    SupervisorLoop,
 };
-use super::supervisor_loop::{now_timestamp_ms, SupervisorLoop};
 use super::{
-    SupervisorActivityEntry, SupervisorChatMessage, SupervisorChatMessageRole, SupervisorJobState,
-    SupervisorJobStatus, SupervisorState,
+    SupervisorActivityEntry, SupervisorChatMessage, SupervisorChatMessageRole, SupervisorJobPolicy,
+    SupervisorJobRetryThread, SupervisorJobState, SupervisorJobStatus, SupervisorPendingApproval,
+    SupervisorSchedule, SupervisorState,
 };
 
 const SUPERVISOR_FEED_DEFAULT_LIMIT: usize = 100;
@@ -111,12 +124,19 @@ pub(crate) async fn supervisor_chat_autoconnect_target_core(
     route.workspace_id
 }
 
+/// Shared state a supervisor chat message is routed and dispatched against.
+pub(crate) struct SupervisorChatContext<'a> {
+    pub(crate) supervisor_loop: &'a Arc<Mutex<SupervisorLoop>>,
+    pub(crate) dispatch_executor: &'a Arc<Mutex<SupervisorDispatchExecutor>>,
+    pub(crate) sessions: &'a Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    pub(crate) workspaces: &'a Mutex<HashMap<String, WorkspaceEntry>>,
+    pub(crate) app_settings: &'a Mutex<AppSettings>,
+    /// Hides the planner's background thread, like other background prompts.
+    pub(crate) on_planner_thread: &'a (dyn Fn(&str, &str) + Sync),
+}
+
 pub(crate) async fn supervisor_chat_send_core(
-    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
-    dispatch_executor: &Arc<Mutex<SupervisorDispatchExecutor>>,
-    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    context: &SupervisorChatContext<'_>,
     command: &str,
     received_at_ms: i64,
 ) -> Result<SupervisorChatSendResponse, String> {
@@ -132,16 +152,7 @@ pub(crate) async fn supervisor_chat_send_core(
         created_at_ms: received_at_ms,
     };
 
-    let response_text = execute_supervisor_chat_command(
-        supervisor_loop,
-        dispatch_executor,
-        sessions,
-        workspaces,
-        app_settings,
-        command,
-        received_at_ms,
-    )
-    .await;
+    let response_text = execute_supervisor_chat_command(context, command, received_at_ms).await;
 
     let system_message = SupervisorChatMessage {
         id: format!("chat-system-{}-{}", received_at_ms, Uuid::new_v4().simple()),
@@ -150,7 +161,7 @@ pub(crate) async fn supervisor_chat_send_core(
         created_at_ms: now_timestamp_ms(),
     };
 
-    let mut supervisor_loop = supervisor_loop.lock().await;
+    let mut supervisor_loop = context.supervisor_loop.lock().await;
     supervisor_loop.append_chat_message(user_message);
     supervisor_loop.append_chat_message(system_message);
     Ok(SupervisorChatSendResponse {
@@ -159,11 +170,7 @@ pub(crate) async fn supervisor_chat_send_core(
 }
 
 async fn execute_supervisor_chat_command(
-    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
-    dispatch_executor: &Arc<Mutex<SupervisorDispatchExecutor>>,
-    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    context: &SupervisorChatContext<'_>,
     command: &str,
     received_at_ms: i64,
 ) -> String {
@@ -171,10 +178,10 @@ async fn execute_supervisor_chat_command(
         let execution = async {
             let command = parse_supervisor_chat_command(command)?;
             execute_parsed_supervisor_chat_command(
-                supervisor_loop,
-                dispatch_executor,
-                sessions,
-                workspaces,
+                context.supervisor_loop,
+                context.dispatch_executor,
+                context.sessions,
+                context.workspaces,
                 command,
                 received_at_ms,
             )
@@ -188,16 +195,7 @@ async fn execute_supervisor_chat_command(
         };
     }
 
-    let execution = execute_freeform_supervisor_chat(
-        supervisor_loop,
-        dispatch_executor,
-        sessions,
-        workspaces,
-        app_settings,
-        command,
-        received_at_ms,
-    )
-    .await;
+    let execution = execute_freeform_supervisor_chat(context, command, received_at_ms).await;
 
     match execution {
         Ok(response) => response,
@@ -208,47 +206,57 @@ async fn execute_supervisor_chat_command(
 #[derive(Debug)]
 enum FreeformDispatchOutcome {
     Dispatch(SupervisorChatDispatchRequest),
+    Planned {
+        reason: String,
+        contract: SupervisorActionContract,
+    },
     LocalTool(SupervisorRouteDecision),
     Clarification(SupervisorRouteDecision),
 }
 
 async fn execute_freeform_supervisor_chat(
-    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
-    dispatch_executor: &Arc<Mutex<SupervisorDispatchExecutor>>,
-    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    context: &SupervisorChatContext<'_>,
     command: &str,
     received_at_ms: i64,
 ) -> Result<String, String> {
-    if let Some(reply_result) =
-        try_route_reply_to_waiting_subtask(supervisor_loop, sessions, command, received_at_ms)
-            .await?
-    {
-        return Ok(reply_result);
-    }
-
-    match build_freeform_dispatch_request(
+    let supervisor_loop = context.supervisor_loop;
+    if let Some(reply_result) = try_route_reply_to_waiting_subtask(
         supervisor_loop,
-        sessions,
-        workspaces,
-        app_settings,
+        context.sessions,
         command,
         received_at_ms,
     )
     .await?
     {
+        return Ok(reply_result);
+    }
+
+    match build_freeform_dispatch_request(context, command, received_at_ms).await? {
         FreeformDispatchOutcome::Dispatch(request) => {
             execute_parsed_supervisor_chat_command(
                 supervisor_loop,
-                dispatch_executor,
-                sessions,
-                workspaces,
-                SupervisorChatCommand::Dispatch(request),
+                context.dispatch_executor,
+                context.sessions,
+                context.workspaces,
+                SupervisorChatCommand::Dispatch(Box::new(request)),
                 received_at_ms,
             )
             .await
         }
+        FreeformDispatchOutcome::Planned { reason, contract } => {
+            let contract_value =
+                serde_json::to_value(&contract).map_err(|error| error.to_string())?;
+            let dispatch = supervisor_dispatch_core(
+                supervisor_loop,
+                context.dispatch_executor,
+                context.sessions,
+                &contract_value,
+            )
+            .await?;
+            Ok(format_planner_dispatch_message(
+                &reason, &contract, &dispatch,
+            ))
+        }
         FreeformDispatchOutcome::LocalTool(route) => {
             execute_local_tool_route(
                 supervisor_loop,
@@ -263,33 +271,77 @@ async fn execute_freeform_supervisor_chat(
 }
 
 async fn build_freeform_dispatch_request(
-    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
-    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
+    context: &SupervisorChatContext<'_>,
     prompt: &str,
     received_at_ms: i64,
 ) -> Result<FreeformDispatchOutcome, String> {
-    let workspace_metadata =
-        collect_route_workspace_metadata(supervisor_loop, sessions, workspaces).await;
-    let settings = app_settings.lock().await.clone();
-    let route = select_supervisor_route(prompt, &workspace_metadata, &settings);
+    let workspace_metadata = collect_route_workspace_metadata(
+        context.supervisor_loop,
+        context.sessions,
+        context.workspaces,
+    )
+    .await;
+    let settings = context.app_settings.lock().await.clone();
+    let mut route = select_supervisor_route(prompt, &workspace_metadata, &settings);
 
-    {
-        let mut supervisor_loop = supervisor_loop.lock().await;
-        let decision_metadata = serde_json::to_value(&route).unwrap_or(Value::Null);
-        supervisor_loop.record_route_decision(
-            &format!("{}-{}", received_at_ms, Uuid::new_v4().simple()),
-            format!(
-                "Free-form route decision: {} ({})",
-                route_kind_label(&route.kind),
-                route.reason
-            ),
+    if settings.supervisor_planner_enabled && route.kind != SupervisorRouteKind::LocalTool {
+        let action_id_prefix = format!(
+            "planner-dispatch-{}-{}",
             received_at_ms,
-            decision_metadata,
+            Uuid::new_v4().simple()
         );
+        match run_supervisor_planner(
+            context,
+            prompt,
+            &workspace_metadata,
+            &settings,
+            &action_id_prefix,
+        )
+        .await
+        {
+            Ok(decision) => {
+                let planned_route = planner_route_decision(&decision, route.candidates.clone());
+                let mut decision_metadata =
+                    serde_json::to_value(&planned_route).unwrap_or(Value::Null);
+                if let SupervisorPlannerDecision::Dispatch { contract, .. } = &decision {
+                    decision_metadata["contract"] =
+                        serde_json::to_value(contract).unwrap_or(Value::Null);
+                }
+                record_freeform_route_decision(
+                    context.supervisor_loop,
+                    &planned_route,
+                    decision_metadata,
+                    received_at_ms,
+                )
+                .await;
+                return Ok(match decision {
+                    SupervisorPlannerDecision::Dispatch { reason, contract } => {
+                        FreeformDispatchOutcome::Planned { reason, contract }
+                    }
+                    SupervisorPlannerDecision::Clarify { .. } => {
+                        FreeformDispatchOutcome::Clarification(planned_route)
+                    }
+                });
+            }
+            Err(error) => {
+                let planner_note = format!("Planner unavailable ({error}); used keyword routing.");
+                route.fallback_message = Some(match route.fallback_message.take() {
+                    Some(existing) => format!("{planner_note} {existing}"),
+                    None => planner_note,
+                });
+            }
+        }
     }
 
+    let decision_metadata = serde_json::to_value(&route).unwrap_or(Value::Null);
+    record_freeform_route_decision(
+        context.supervisor_loop,
+        &route,
+        decision_metadata,
+        received_at_ms,
+    )
+    .await;
+
     match route.kind {
         SupervisorRouteKind::LocalTool => Ok(FreeformDispatchOutcome::LocalTool(route)),
         SupervisorRouteKind::Clarification => Ok(FreeformDispatchOutcome::Clarification(route)),
@@ -316,12 +368,54 @@ async fn build_freeform_dispatch_request(
                     route_kind: Some("workspace_delegate".to_string()),
                     route_reason: Some(route.reason.clone()),
                     route_fallback: route.fallback_message.clone(),
+                    policy: SupervisorJobPolicy::default(),
                 },
             ))
         }
     }
 }
 
+async fn run_supervisor_planner(
+    context: &SupervisorChatContext<'_>,
+    prompt: &str,
+    workspace_metadata: &[SupervisorRouteWorkspaceMetadata],
+    settings: &AppSettings,
+    action_id_prefix: &str,
+) -> Result<SupervisorPlannerDecision, String> {
+    let workspace_id = select_planner_workspace(workspace_metadata, settings)
+        .ok_or_else(|| "no connected workspace can run the planner".to_string())?;
+    let response = run_background_prompt_core(
+        context.sessions,
+        workspace_id,
+        build_supervisor_planner_prompt(prompt, workspace_metadata),
+        context.on_planner_thread,
+        "Timeout waiting for supervisor planner",
+        "Unknown error during supervisor planning",
+    )
+    .await?;
+    parse_supervisor_planner_response(&response, workspace_metadata, action_id_prefix)
+}
+
+async fn record_freeform_route_decision(
+    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
+    route: &SupervisorRouteDecision,
+    decision_metadata: Value,
+    received_at_ms: i64,
+) {
+    let mut supervisor_loop = supervisor_loop.lock().await;
+    supervisor_loop.record_route_decision(
+        &format!("{}-{}", received_at_ms, Uuid::new_v4().simple()),
+        format!(
+            "Free-form route decision ({} router): {} ({})",
+            router_kind_label(&route.router),
+            route_kind_label(&route.kind),
+            route.reason
+        ),
+        received_at_ms,
+        decision_metadata,
+    );
+}
+
 async fn execute_local_tool_route(
     supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
     tool: SupervisorLocalTool,
@@ -361,6 +455,13 @@ fn format_route_clarification(route: &SupervisorRouteDecision) -> String {
     lines.join("\n")
 }
 
+fn router_kind_label(router: &SupervisorRouterKind) -> &'static str {
+    match router {
+        SupervisorRouterKind::Deterministic => "deterministic",
+        SupervisorRouterKind::Planner => "planner",
+    }
+}
+
 fn route_kind_label(kind: &SupervisorRouteKind) -> &'static str {
     match kind {
         SupervisorRouteKind::WorkspaceDelegate => "workspace_delegate",
@@ -701,6 +802,224 @@ async fn execute_parsed_supervisor_chat_command(
                     .await?;
             Ok(format_dispatch_message(&request, &dispatch))
         }
+        SupervisorChatCommand::Cancel { job_id } => {
+            supervisor_cancel_job_core(supervisor_loop, sessions, &job_id).await
+        }
+        SupervisorChatCommand::Job { job_id } => {
+            let snapshot = supervisor_snapshot_core(supervisor_loop).await;
+            format_job_message(&snapshot, &job_id)
+        }
+        SupervisorChatCommand::Schedule(command) => {
+            execute_schedule_command(supervisor_loop, command, received_at_ms).await
+        }
+    }
+}
+
+async fn execute_schedule_command(
+    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
+    command: SupervisorScheduleCommand,
+    received_at_ms: i64,
+) -> Result<String, String> {
+    let mut supervisor_loop = supervisor_loop.lock().await;
+    match command {
+        SupervisorScheduleCommand::Add {
+            cron,
+            catch_up,
+            dispatch,
+        } => {
+            let schedule = supervisor_loop.add_schedule(SupervisorSchedule {
+                id: format!("sched-{}", &Uuid::new_v4().simple().to_string()[..8]),
+                cron,
+                dispatch: *dispatch,
+                catch_up,
+                created_at_ms: received_at_ms,
+                ..Default::default()
+            })?;
+            Ok(format_schedule_saved_message(&schedule, "added"))
+        }
+        SupervisorScheduleCommand::List => {
+            Ok(format_schedule_list_message(&supervisor_loop.schedules()))
+        }
+        SupervisorScheduleCommand::Remove { schedule_id } => {
+            supervisor_loop.remove_schedule(&schedule_id, received_at_ms)?;
+            Ok(format!("Schedule `{schedule_id}` removed."))
+        }
+        SupervisorScheduleCommand::Pause { schedule_id } => {
+            let schedule =
+                supervisor_loop.set_schedule_paused(&schedule_id, true, received_at_ms)?;
+            Ok(format_schedule_saved_message(&schedule, "paused"))
+        }
+        SupervisorScheduleCommand::Resume { schedule_id } => {
+            let schedule =
+                supervisor_loop.set_schedule_paused(&schedule_id, false, received_at_ms)?;
+            Ok(format_schedule_saved_message(&schedule, "resumed"))
+        }
+    }
+}
+
+pub(crate) async fn supervisor_cancel_job_core(
+    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    job_id: &str,
+) -> Result<String, String> {
+    let running_turn = {
+        let mut supervisor_loop = supervisor_loop.lock().await;
+        supervisor_loop.cancel_job(job_id, now_timestamp_ms())?
+    };
+    let Some((workspace_id, thread_id, turn_id)) = running_turn else {
+        return Ok(format_cancel_message(job_id, None));
+    };
+
+    let backend = WorkspaceSessionDispatchBackend::new(sessions);
+    let interrupted = backend
+        .interrupt_turn(&workspace_id, &thread_id, &turn_id)
+        .await
+        .and_then(ensure_response_ok);
+    supervisor_loop.lock().await.record_job_interrupt(
+        job_id,
+        &turn_id,
+        interrupted.clone(),
+        now_timestamp_ms(),
+    );
+    Ok(format_cancel_message(job_id, Some((&turn_id, interrupted))))
+}
+
+/// Enforces job timeouts, starts retries whose backoff elapsed and dispatches dependents whose
+/// prerequisites completed. Runs on the health tick.
+pub(crate) async fn run_job_policy_tick(
+    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    now_ms: i64,
+) {
+    let tasks = supervisor_loop.lock().await.apply_job_policies(now_ms);
+    if tasks.is_empty() {
+        return;
+    }
+
+    let backend = WorkspaceSessionDispatchBackend::new(sessions);
+    for task in tasks {
+        match task {
+            SupervisorJobPolicyTask::Interrupt {
+                job_id,
+                workspace_id,
+                thread_id,
+                turn_id,
+            } => {
+                let result = backend
+                    .interrupt_turn(&workspace_id, &thread_id, &turn_id)
+                    .await
+                    .and_then(ensure_response_ok);
+                supervisor_loop.lock().await.record_job_interrupt(
+                    &job_id,
+                    &turn_id,
+                    result,
+                    now_timestamp_ms(),
+                );
+            }
+            SupervisorJobPolicyTask::Dispatch { job, prompt } => {
+                let baseline = collect_job_baselines(sessions, [job.workspace_id.clone()])
+                    .await
+                    .remove(&job.workspace_id);
+                let result = redispatch_action(&backend, job_attempt_action(&job, prompt)).await;
+                let cancelled = {
+                    let mut supervisor_loop = supervisor_loop.lock().await;
+                    let cancelled = supervisor_loop.record_attempt_outcome(
+                        &job.id,
+                        &result,
+                        now_timestamp_ms(),
+                    );
+                    if let Some(files) = baseline {
+                        supervisor_loop.record_job_baseline(&job.id, files);
+                    }
+                    cancelled
+                };
+                if let (true, Some(thread_id), Some(turn_id)) = (
+                    cancelled,
+                    result.thread_id.as_ref(),
+                    result.turn_id.as_ref(),
+                ) {
+                    let _ = backend
+                        .interrupt_turn(&job.workspace_id, thread_id, turn_id)
+                        .await;
+                }
+            }
+        }
+    }
+}
+
+/// Dispatches every schedule occurrence that is due. Runs on the daemon's health tick; the
+/// desktop app does not persist Supervisor state, so it leaves schedules to the daemon.
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) async fn run_schedule_tick(
+    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
+    dispatch_executor: &Arc<Mutex<SupervisorDispatchExecutor>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    now_ms: i64,
+) {
+    let runs = supervisor_loop.lock().await.take_due_schedules(now_ms);
+    for run in runs {
+        let contract = build_dispatch_contract(
+            &run.schedule.dispatch,
+            &format!("schedule-{}-{}", run.schedule.id, run.scheduled_for_ms),
+        );
+        let (job_ids, error) =
+            match supervisor_dispatch_core(supervisor_loop, dispatch_executor, sessions, &contract)
+                .await
+            {
+                Ok(dispatch) => {
+                    let job_ids = dispatch
+                        .results
+                        .iter()
+                        .filter(|result| result.status != SupervisorDispatchStatus::Failed)
+                        .map(|result| result.action_id.clone())
+                        .collect::<Vec<_>>();
+                    let errors = dispatch
+                        .results
+                        .iter()
+                        .filter_map(|result| {
+                            result
+                                .error
+                                .as_ref()
+                                .map(|error| format!("{}: {error}", result.workspace_id))
+                        })
+                        .collect::<Vec<_>>();
+                    (job_ids, (!errors.is_empty()).then(|| errors.join("; ")))
+                }
+                Err(error) => (Vec::new(), Some(error)),
+            };
+        supervisor_loop
+            .lock()
+            .await
+            .record_schedule_run(&run, job_ids, error, now_timestamp_ms());
+    }
+}
+
+/// Builds the dispatch for a job's next attempt. The first attempt of a dependent uses the
+/// thread from its contract action; retries follow the job's `retry_thread` policy.
+fn job_attempt_action(job: &SupervisorJobState, prompt: String) -> SupervisorDispatchAction {
+    let next_attempt = job.attempt + 1;
+    let thread_id = match job.policy.retry_thread {
+        SupervisorJobRetryThread::Fresh if job.attempt > 0 => None,
+        _ => job.thread_id.clone(),
+    };
+    SupervisorDispatchAction {
+        action_id: format!("{}:attempt-{next_attempt}", job.id),
+        workspace_id: job.workspace_id.clone(),
+        thread_id,
+        prompt,
+        dedupe_key: job
+            .dedupe_key
+            .as_ref()
+            .map(|dedupe_key| format!("{dedupe_key}:attempt-{next_attempt}")),
+        model: job.model.clone(),
+        effort: job.effort.clone(),
+        access_mode: job.access_mode.clone(),
+        route_kind: job.route_kind.clone(),
+        route_reason: job.route_reason.clone(),
+        route_fallback: job.route_fallback.clone(),
+        policy: job.policy.clone(),
+        depends_on: Vec::new(),
+        wait_for_capacity: false,
     }
 }
 
@@ -771,18 +1090,220 @@ pub(crate) async fn supervisor_dispatch_core(
 ) -> Result<SupervisorDispatchBatchResult, String> {
     let validated_contract = parse_supervisor_action_contract_value(contract)?;
     let dispatch_actions = validated_contract.dispatch_actions;
-    let backend = WorkspaceSessionDispatchBackend::new(sessions);
+    let (actions, admitted) = {
+        let mut supervisor_loop = supervisor_loop.lock().await;
+        let mut actions =
+            hydrate_control_actions(&supervisor_loop.snapshot(), validated_contract.actions);
+        let admitted = supervisor_loop.admit_dispatch_actions(&mut actions);
+        (actions, admitted)
+    };
+    let baselines = collect_job_baselines(
+        sessions,
+        actions.iter().filter_map(|action| match action {
+            SupervisorExecutableAction::DispatchTurn(action)
+                if action.depends_on.is_empty() && !action.wait_for_capacity =>
+            {
+                Some(action.workspace_id.clone())
+            }
+            _ => None,
+        }),
+    )
+    .await;
+    let backend =
+        WorkspaceSessionDispatchBackend::new(sessions).with_supervisor_loop(supervisor_loop);
     let dispatch_result = {
         let mut executor = dispatch_executor.lock().await;
-        executor
-            .dispatch_batch(&backend, dispatch_actions.clone())
-            .await
+        executor.execute_batch(&backend, actions.clone()).await
     };
 
     apply_dispatch_outcome_events(supervisor_loop, &dispatch_result, &dispatch_actions).await;
+    {
+        let mut supervisor_loop = supervisor_loop.lock().await;
+        for result in &dispatch_result.results {
+            if result.status != SupervisorDispatchStatus::Dispatched || result.idempotent_replay {
+                continue;
+            }
+            if let Some(files) = baselines.get(&result.workspace_id) {
+                supervisor_loop.record_job_baseline(&result.action_id, files.clone());
+            }
+        }
+        supervisor_loop.release_dispatch_admissions(&admitted);
+    }
+    apply_control_outcome_events(supervisor_loop, &dispatch_result, &actions).await;
     Ok(dispatch_result)
 }
 
+/// Fills control actions from the tracked approval or open question so the response reuses the
+/// original request id (and question ids) when the planner only knows the request key.
+fn hydrate_control_actions(
+    state: &SupervisorState,
+    actions: Vec<SupervisorExecutableAction>,
+) -> Vec<SupervisorExecutableAction> {
+    actions
+        .into_iter()
+        .map(|action| {
+            if let SupervisorExecutableAction::Control(SupervisorControlAction::RespondApproval {
+                action_id,
+                workspace_id,
+                request_id,
+                decision,
+                dedupe_key,
+            }) = action
+            {
+                let request_id = state
+                    .pending_approvals
+                    .get(&request_value_key(&workspace_id, &request_id))
+                    .filter(|approval| approval.resolved_at_ms.is_none())
+                    .map(SupervisorPendingApproval::original_request_id)
+                    .unwrap_or(request_id);
+                return SupervisorExecutableAction::Control(
+                    SupervisorControlAction::RespondApproval {
+                        action_id,
+                        workspace_id,
+                        request_id,
+                        decision,
+                        dedupe_key,
+                    },
+                );
+            }
+            let SupervisorExecutableAction::Control(SupervisorControlAction::AnswerQuestion {
+                action_id,
+                workspace_id,
+                mut request_id,
+                answer,
+                mut question_ids,
+                dedupe_key,
+            }) = action
+            else {
+                return action;
+            };
+
+            let request_key = request_value_key(&workspace_id, &request_id);
+            if let Some(question) = state
+                .open_questions
+                .get(&request_key)
+                .filter(|question| question.resolved_at_ms.is_none())
+            {
+                if let Some(original) = question
+                    .context
+                    .get("requestId")
+                    .filter(|value| !value.is_null())
+                {
+                    request_id = original.clone();
+                }
+                if question_ids.is_empty() {
+                    question_ids = question
+                        .context
+                        .get("questionIds")
+                        .and_then(Value::as_array)
+                        .map(|ids| {
+                            ids.iter()
+                                .filter_map(Value::as_str)
+                                .map(ToString::to_string)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                }
+            }
+
+            SupervisorExecutableAction::Control(SupervisorControlAction::AnswerQuestion {
+                action_id,
+                workspace_id,
+                request_id,
+                answer,
+                question_ids,
+                dedupe_key,
+            })
+        })
+        .collect()
+}
+
+async fn apply_control_outcome_events(
+    supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
+    dispatch_result: &SupervisorDispatchBatchResult,
+    actions: &[SupervisorExecutableAction],
+) {
+    let controls_by_id = actions
+        .iter()
+        .filter_map(|action| match action {
+            SupervisorExecutableAction::Control(control) => Some((control.action_id(), control)),
+            SupervisorExecutableAction::DispatchTurn(_) => None,
+        })
+        .collect::<HashMap<_, _>>();
+    if controls_by_id.is_empty() {
+        return;
+    }
+
+    let timestamp_ms = now_timestamp_ms();
+    let mut supervisor_loop = supervisor_loop.lock().await;
+    for result in &dispatch_result.results {
+        if result.idempotent_replay {
+            continue;
+        }
+        let Some(control) = controls_by_id.get(result.action_id.as_str()).copied() else {
+            continue;
+        };
+
+        let action_type = result.action_type.as_str();
+        let message = match result.status {
+            SupervisorDispatchStatus::Failed => format!(
+                "Supervisor action `{action_type}` failed: {}",
+                result.error.as_deref().unwrap_or("unknown error")
+            ),
+            _ => format!("Supervisor action `{action_type}` applied."),
+        };
+        supervisor_loop.record_control_action(
+            &result.action_id,
+            message,
+            (!result.workspace_id.is_empty()).then(|| result.workspace_id.clone()),
+            result.thread_id.clone(),
+            timestamp_ms,
+            json!({
+                "actionId": result.action_id,
+                "actionType": action_type,
+                "status": result.status,
+                "turnId": result.turn_id,
+                "error": result.error,
+            }),
+        );
+
+        if result.status != SupervisorDispatchStatus::Applied {
+            continue;
+        }
+        match control {
+            SupervisorControlAction::RespondApproval {
+                workspace_id,
+                request_id,
+                ..
+            } => {
+                let request_key = request_value_key(workspace_id, request_id);
+                supervisor_loop.resolve_pending_approval(&request_key, timestamp_ms);
+            }
+            SupervisorControlAction::AnswerQuestion {
+                workspace_id,
+                request_id,
+                answer,
+                ..
+            } => {
+                let waiting_job = supervisor_loop.waiting_jobs().into_iter().find(|job| {
+                    job.workspace_id == *workspace_id
+                        && job.waiting_request_id.as_ref() == Some(request_id)
+                });
+                let delivered = waiting_job.is_some_and(|job| {
+                    supervisor_loop
+                        .mark_reply_delivered(&job.id, request_id, answer, timestamp_ms)
+                        .is_ok()
+                });
+                if !delivered {
+                    let request_key = request_value_key(workspace_id, request_id);
+                    supervisor_loop.resolve_open_question(&request_key, timestamp_ms);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 async fn apply_dispatch_outcome_events(
     supervisor_loop: &Arc<Mutex<SupervisorLoop>>,
     dispatch_result: &SupervisorDispatchBatchResult,
@@ -800,6 +1321,13 @@ async fn apply_dispatch_outcome_events(
         .collect::<HashMap<_, _>>();
 
     for result in &dispatch_result.results {
+        if result.action_type != SupervisorActionType::DispatchTurn {
+            continue;
+        }
+        // A replayed result describes the original dispatch; it must not reset the job.
+        if result.idempotent_replay && supervisor_loop.has_job(&result.action_id) {
+            continue;
+        }
         let action = actions_by_id.get(result.action_id.as_str()).copied();
         let mut job = SupervisorJobState {
             id: result.action_id.clone(),
@@ -827,12 +1355,24 @@ async fn apply_dispatch_outcome_events(
             waiting_request_id: None,
             waiting_question_ids: Vec::new(),
             recent_events: Vec::new(),
+            policy: action.map(|entry| entry.policy.clone()).unwrap_or_default(),
+            attempt: 1,
+            next_retry_at_ms: None,
+            depends_on: action
+                .map(|entry| entry.depends_on.clone())
+                .unwrap_or_default(),
+            result: None,
+            queued_at_ms: None,
+            queue_position: None,
+            digest: None,
+            starting: false,
         };
 
         match result.status {
             SupervisorDispatchStatus::Dispatched => {
                 job.status = SupervisorJobStatus::Running;
                 job.started_at_ms = Some(timestamp_ms);
+                job.digest = Some(Box::default());
                 job.recent_events.push(super::SupervisorSubtaskEvent {
                     id: format!("queued:{}:{}", job.id, timestamp_ms),
                     kind: "queued".to_string(),
@@ -875,6 +1415,33 @@ async fn apply_dispatch_outcome_events(
                     timestamp_ms,
                 );
             }
+            SupervisorDispatchStatus::Applied => {}
+            SupervisorDispatchStatus::Queued => {
+                // A re-sent contract must not reset a dependent that is already tracked.
+                if supervisor_loop.has_job(&job.id) {
+                    continue;
+                }
+                job.attempt = 0;
+                if job.depends_on.is_empty() {
+                    // Held back by the concurrency limits; the queue event is added with the
+                    // job's position.
+                    job.queued_at_ms = Some(timestamp_ms);
+                    supervisor_loop.upsert_job(job);
+                    supervisor_loop.refresh_queue_positions(timestamp_ms);
+                    continue;
+                }
+                job.recent_events.push(super::SupervisorSubtaskEvent {
+                    id: format!("waiting:{}", job.id),
+                    kind: "queued".to_string(),
+                    message: format!("Waiting for prerequisites: {}", job.depends_on.join(", ")),
+                    created_at_ms: timestamp_ms,
+                    metadata: json!({
+                        "workspaceId": result.workspace_id,
+                        "dependsOn": job.depends_on,
+                    }),
+                });
+                supervisor_loop.upsert_job(job);
+            }
             SupervisorDispatchStatus::Failed => {
                 let message = result
                     .error
@@ -926,10 +1493,8 @@ mod tests {
     use super::*;
     use std::future::Future;
     use std::process::Stdio;
-    use std::sync::atomic::AtomicU64;
 
     use tokio::process::Command;
-    use tokio::sync::mpsc;
 
     use crate::backend::app_server::WorkspaceSession;
     use crate::shared::supervisor_core::dispatch::SupervisorDispatchActionResult;
@@ -996,8 +1561,8 @@ mod tests {
         let mut child = command.spawn().expect("spawn test session process");
         let stdin = child.stdin.take().expect("test session stdin");
 
-        Arc::new(WorkspaceSession {
-            entry: WorkspaceEntry {
+        Arc::new(WorkspaceSession::new(
+            WorkspaceEntry {
                 id: workspace_id.to_string(),
                 name: format!("Workspace {workspace_id}"),
                 path: ".".to_string(),
@@ -1007,14 +1572,9 @@ mod tests {
                 worktree: None,
                 settings: WorkspaceSettings::default(),
             },
-            child: Mutex::new(child),
-            stdin: Mutex::new(stdin),
-            pending: Mutex::new(HashMap::new()),
-            next_id: AtomicU64::new(1),
-            background_thread_callbacks: Mutex::new(
-                HashMap::<String, mpsc::UnboundedSender<Value>>::new(),
-            ),
-        })
+            child,
+            stdin,
+        ))
     }
 
     async fn stop_reply_session(session: &Arc<WorkspaceSession>) {
@@ -1206,11 +1766,14 @@ mod tests {
             let app_settings = Mutex::new(AppSettings::default());
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "/help",
                 100,
             )
@@ -1240,11 +1803,14 @@ mod tests {
             let app_settings = Mutex::new(AppSettings::default());
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "/dispatch --ws ws-1",
                 100,
             )
@@ -1274,11 +1840,14 @@ mod tests {
             let app_settings = Mutex::new(AppSettings::default());
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "status",
                 200,
             )
@@ -1329,11 +1898,14 @@ mod tests {
             let app_settings = Mutex::new(AppSettings::default());
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "/status",
                 300,
             )
@@ -1362,11 +1934,14 @@ mod tests {
             let app_settings = Mutex::new(AppSettings::default());
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "run smoke tests",
                 100,
             )
@@ -1385,6 +1960,55 @@ mod tests {
         });
     }
 
+    #[test]
+    fn supervisor_chat_send_core_falls_back_to_keyword_router_when_planner_cannot_run() {
+        run_async(async {
+            let supervisor_loop = Arc::new(Mutex::new(SupervisorLoop::new(
+                SupervisorLoopConfig::default(),
+            )));
+            let dispatch_executor = Arc::new(Mutex::new(SupervisorDispatchExecutor::new()));
+            let sessions = Mutex::new(HashMap::new());
+            let workspaces = Mutex::new(HashMap::new());
+            let app_settings = Mutex::new(AppSettings {
+                supervisor_planner_enabled: true,
+                ..AppSettings::default()
+            });
+
+            let response = supervisor_chat_send_core(
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| panic!("planner should not start a thread"),
+                },
+                "run smoke tests",
+                100,
+            )
+            .await
+            .expect("chat send");
+
+            let system_message = response.messages.last().expect("system message");
+            assert!(
+                system_message
+                    .text
+                    .contains("Fallback note: Planner unavailable (no connected workspace"),
+                "unexpected response text: {}",
+                system_message.text
+            );
+
+            let snapshot = supervisor_snapshot_core(&supervisor_loop).await;
+            let route_entry = snapshot
+                .activity_feed
+                .iter()
+                .find(|entry| entry.kind == "route_decision")
+                .expect("route decision entry");
+            assert_eq!(route_entry.metadata["router"], "deterministic");
+            assert!(route_entry.message.contains("deterministic router"));
+        });
+    }
+
     #[test]
     fn supervisor_chat_autoconnect_target_core_returns_explicit_single_workspace() {
         run_async(async {
@@ -1462,10 +2086,14 @@ mod tests {
                 route_kind: Some("workspace_delegate".to_string()),
                 route_reason: Some("selected by routing score".to_string()),
                 route_fallback: None,
+                policy: SupervisorJobPolicy::default(),
+                depends_on: Vec::new(),
+                wait_for_capacity: false,
             }];
             let dispatch_result = SupervisorDispatchBatchResult {
                 results: vec![SupervisorDispatchActionResult {
                     action_id: "job-1".to_string(),
+                    action_type: SupervisorActionType::DispatchTurn,
                     workspace_id: "ws-1".to_string(),
                     dedupe_key: "dedupe-1".to_string(),
                     status: SupervisorDispatchStatus::Dispatched,
@@ -1521,6 +2149,11 @@ mod tests {
             assert_eq!(job.model.as_deref(), Some("gpt-5-mini"));
             assert_eq!(job.effort.as_deref(), Some("high"));
             assert_eq!(job.access_mode.as_deref(), Some("full-access"));
+            assert_eq!(
+                supervisor_loop.lock().await.pending_job_digests(),
+                vec![("job-1".to_string(), "ws-1".to_string())],
+                "a job that ran no commands still gets its files captured"
+            );
 
             let chat = supervisor_chat_history_core(&supervisor_loop).await;
             assert!(
@@ -1552,6 +2185,125 @@ mod tests {
         });
     }
 
+    #[test]
+    fn apply_control_outcome_events_resolves_approval_and_question() {
+        run_async(async {
+            let supervisor_loop = Arc::new(Mutex::new(SupervisorLoop::new(
+                SupervisorLoopConfig::default(),
+            )));
+            {
+                let mut supervisor_loop = supervisor_loop.lock().await;
+                supervisor_loop.apply_app_server_event(
+                    "ws-1",
+                    &json!({
+                        "id": 42,
+                        "method": "item/commandExecution/requestApproval",
+                        "params": { "threadId": "thread-1", "turnId": "turn-1" }
+                    }),
+                    100,
+                );
+                supervisor_loop.apply_app_server_event(
+                    "ws-1",
+                    &json!({
+                        "id": 7,
+                        "method": "item/tool/requestUserInput",
+                        "params": {
+                            "threadId": "thread-1",
+                            "questions": [{ "id": "q1", "question": "Which branch?" }]
+                        }
+                    }),
+                    101,
+                );
+            }
+
+            let actions = {
+                let snapshot = supervisor_snapshot_core(&supervisor_loop).await;
+                hydrate_control_actions(
+                    &snapshot,
+                    vec![
+                        SupervisorExecutableAction::Control(
+                            SupervisorControlAction::RespondApproval {
+                                action_id: "action-1".to_string(),
+                                workspace_id: "ws-1".to_string(),
+                                request_id: json!("42"),
+                                decision: "accept".to_string(),
+                                dedupe_key: None,
+                            },
+                        ),
+                        SupervisorExecutableAction::Control(
+                            SupervisorControlAction::AnswerQuestion {
+                                action_id: "action-2".to_string(),
+                                workspace_id: "ws-1".to_string(),
+                                request_id: json!("7"),
+                                answer: "main".to_string(),
+                                question_ids: Vec::new(),
+                                dedupe_key: None,
+                            },
+                        ),
+                    ],
+                )
+            };
+            let SupervisorExecutableAction::Control(SupervisorControlAction::RespondApproval {
+                request_id,
+                ..
+            }) = &actions[0]
+            else {
+                panic!("expected respond_approval action");
+            };
+            assert_eq!(request_id, &json!(42));
+            let SupervisorExecutableAction::Control(SupervisorControlAction::AnswerQuestion {
+                request_id,
+                question_ids,
+                ..
+            }) = &actions[1]
+            else {
+                panic!("expected answer_question action");
+            };
+            assert_eq!(request_id, &json!(7));
+            assert_eq!(question_ids, &vec!["q1".to_string()]);
+
+            let control_result = |action_id: &str, action_type| SupervisorDispatchActionResult {
+                action_id: action_id.to_string(),
+                action_type,
+                workspace_id: "ws-1".to_string(),
+                dedupe_key: action_id.to_string(),
+                status: SupervisorDispatchStatus::Applied,
+                thread_id: None,
+                turn_id: None,
+                error: None,
+                idempotent_replay: false,
+            };
+            let dispatch_result = SupervisorDispatchBatchResult {
+                results: vec![
+                    control_result("action-1", SupervisorActionType::RespondApproval),
+                    control_result("action-2", SupervisorActionType::AnswerQuestion),
+                ],
+            };
+
+            apply_control_outcome_events(&supervisor_loop, &dispatch_result, &actions).await;
+
+            let snapshot = supervisor_snapshot_core(&supervisor_loop).await;
+            assert!(snapshot.pending_approvals["ws-1:42"]
+                .resolved_at_ms
+                .is_some());
+            assert!(snapshot
+                .signals
+                .iter()
+                .find(|signal| signal.id == "approval:ws-1:42")
+                .and_then(|signal| signal.acknowledged_at_ms)
+                .is_some());
+            assert!(snapshot.open_questions["ws-1:7"].resolved_at_ms.is_some());
+            assert_eq!(
+                snapshot
+                    .activity_feed
+                    .iter()
+                    .filter(|entry| entry.kind == "control_action")
+                    .count(),
+                2
+            );
+        });
+    }
+
     #[test]
     fn supervisor_chat_send_core_disambiguates_reply_when_multiple_subtasks_wait() {
         run_async(async {
@@ -1570,11 +2322,14 @@ mod tests {
             }
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "Use staging environment",
                 200,
             )
@@ -1620,11 +2375,14 @@ mod tests {
             }
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "@job-1 Use staging endpoint",
                 300,
             )
@@ -1675,11 +2433,14 @@ mod tests {
             }
 
             let response = supervisor_chat_send_core(
-                &supervisor_loop,
-                &dispatch_executor,
-                &sessions,
-                &workspaces,
-                &app_settings,
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
                 "@job-1 Continue with rollout",
                 400,
             )
@@ -1696,4 +2457,180 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn job_policy_tick_records_failed_retry_and_chat_cancel_stops_job() {
+        run_async(async {
+            let supervisor_loop = Arc::new(Mutex::new(SupervisorLoop::new(
+                SupervisorLoopConfig::default(),
+            )));
+            let dispatch_executor = Arc::new(Mutex::new(SupervisorDispatchExecutor::new()));
+            let sessions = Mutex::new(HashMap::new());
+            let workspaces = Mutex::new(HashMap::new());
+            let app_settings = Mutex::new(AppSettings::default());
+
+            supervisor_loop.lock().await.upsert_job(SupervisorJobState {
+                id: "job-1".to_string(),
+                workspace_id: "ws-1".to_string(),
+                thread_id: Some("thread-1".to_string()),
+                description: "Run the flaky suite".to_string(),
+                status: SupervisorJobStatus::Queued,
+                requested_at_ms: 1,
+                policy: SupervisorJobPolicy {
+                    max_attempts: 3,
+                    ..SupervisorJobPolicy::default()
+                },
+                attempt: 1,
+                next_retry_at_ms: Some(100),
+                ..Default::default()
+            });
+
+            run_job_policy_tick(&supervisor_loop, &sessions, 100).await;
+
+            let job = supervisor_snapshot_core(&supervisor_loop).await.jobs["job-1"].clone();
+            assert_eq!(job.status, SupervisorJobStatus::Failed);
+            assert_eq!(job.attempt, 2);
+            assert_eq!(
+                job.error.as_deref(),
+                Some("workspace `ws-1` is not connected")
+            );
+            assert!(job.can_retry());
+
+            let response = supervisor_chat_send_core(
+                &SupervisorChatContext {
+                    supervisor_loop: &supervisor_loop,
+                    dispatch_executor: &dispatch_executor,
+                    sessions: &sessions,
+                    workspaces: &workspaces,
+                    app_settings: &app_settings,
+                    on_planner_thread: &|_, _| {},
+                },
+                "/cancel job-1",
+                200,
+            )
+            .await
+            .expect("chat send");
+            assert_eq!(
+                response
+                    .messages
+                    .last()
+                    .map(|message| message.text.as_str()),
+                Some("Subtask `job-1` cancelled.")
+            );
+
+            run_job_policy_tick(&supervisor_loop, &sessions, 1_000_000).await;
+            let job = supervisor_snapshot_core(&supervisor_loop).await.jobs["job-1"].clone();
+            assert_eq!(job.status, SupervisorJobStatus::Cancelled);
+            assert_eq!(job.next_retry_at_ms, None);
+        });
+    }
+
+    #[test]
+    fn dependent_dispatch_is_queued_then_failed_when_prerequisite_cannot_start() {
+        run_async(async {
+            let supervisor_loop = Arc::new(Mutex::new(SupervisorLoop::new(
+                SupervisorLoopConfig::default(),
+            )));
+            let dispatch_executor = Arc::new(Mutex::new(SupervisorDispatchExecutor::new()));
+            let sessions = Mutex::new(HashMap::new());
+            let contract = json!({
+                "version": "supervisor.dispatch.v1",
+                "actions": [
+                    {
+                        "type": "dispatch_turn",
+                        "action_id": "implement",
+                        "workspace_id": "ws-1",
+                        "prompt": "Implement the feature"
+                    },
+                    {
+                        "type": "dispatch_turn",
+                        "action_id": "review",
+                        "workspace_id": "ws-2",
+                        "prompt": "Review: {{implement.result}}",
+                        "depends_on": ["implement"]
+                    }
+                ]
+            });
+
+            let dispatch = supervisor_dispatch_core(
+                &supervisor_loop,
+                &dispatch_executor,
+                &sessions,
+                &contract,
+            )
+            .await
+            .expect("dispatch");
+            assert_eq!(dispatch.results[1].status, SupervisorDispatchStatus::Queued);
+
+            let review = supervisor_snapshot_core(&supervisor_loop).await.jobs["review"].clone();
+            assert!(review.waits_on_dependencies());
+            assert_eq!(review.description, "Review: {{implement.result}}");
+
+            run_job_policy_tick(&supervisor_loop, &sessions, now_timestamp_ms()).await;
+
+            let snapshot = supervisor_snapshot_core(&supervisor_loop).await;
+            assert_eq!(
+                snapshot.jobs["implement"].status,
+                SupervisorJobStatus::Failed
+            );
+            assert_eq!(snapshot.jobs["review"].status, SupervisorJobStatus::Failed);
+            assert_eq!(
+                snapshot.jobs["review"].error.as_deref(),
+                Some("prerequisite `implement` failed: workspace `ws-1` is not connected")
+            );
+        });
+    }
+
+    #[test]
+    fn fanout_in_one_workspace_queues_turns_beyond_the_limit() {
+        run_async(async {
+            let supervisor_loop = Arc::new(Mutex::new(SupervisorLoop::new(
+                SupervisorLoopConfig::default(),
+            )));
+            let dispatch_executor = Arc::new(Mutex::new(SupervisorDispatchExecutor::new()));
+            let sessions = Mutex::new(HashMap::new());
+            let contract = json!({
+                "version": "supervisor.dispatch.v1",
+                "actions": [
+                    { "type": "dispatch_turn", "action_id": "fix-a", "workspace_id": "ws-1", "prompt": "Fix A" },
+                    { "type": "dispatch_turn", "action_id": "fix-b", "workspace_id": "ws-1", "prompt": "Fix B" },
+                    { "type": "dispatch_turn", "action_id": "audit", "workspace_id": "ws-1", "prompt": "Audit", "access_mode": "read-only" }
+                ]
+            });
+
+            let dispatch = supervisor_dispatch_core(
+                &supervisor_loop,
+                &dispatch_executor,
+                &sessions,
+                &contract,
+            )
+            .await
+            .expect("dispatch");
+            let statuses = dispatch
+                .results
+                .iter()
+                .map(|result| result.status.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                statuses,
+                vec![
+                    SupervisorDispatchStatus::Failed,
+                    SupervisorDispatchStatus::Queued,
+                    SupervisorDispatchStatus::Failed,
+                ],
+                "the second writing turn waits; the read-only one is exempt"
+            );
+
+            let snapshot = supervisor_snapshot_core(&supervisor_loop).await;
+            let queued = &snapshot.jobs["fix-b"];
+            assert!(queued.waits_for_capacity());
+            assert_eq!(queued.queue_position, Some(1));
+
+            run_job_policy_tick(&supervisor_loop, &sessions, now_timestamp_ms()).await;
+            let job = supervisor_snapshot_core(&supervisor_loop).await.jobs["fix-b"].clone();
+            assert_eq!(job.status, SupervisorJobStatus::Failed);
+            assert_eq!(job.attempt, 1);
+            assert_eq!(job.queue_position, None);
+        });
+    }
 }
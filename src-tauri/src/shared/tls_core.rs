@@ -0,0 +1,27 @@
+use ring::digest::{digest, SHA256};
+
+/// SHA-256 fingerprint of a DER certificate, formatted as colon-separated uppercase hex.
+///
+/// The daemon prints this at startup and remote clients pin the same value.
+pub(crate) fn certificate_fingerprint(der: &[u8]) -> String {
+    digest(&SHA256, der)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::certificate_fingerprint;
+
+    #[test]
+    fn certificate_fingerprint_formats_sha256_hex_pairs() {
+        let fingerprint = certificate_fingerprint(b"abc");
+        assert_eq!(
+            fingerprint,
+            "BA:78:16:BF:8F:01:CF:EA:41:41:40:DE:5D:AE:22:23:B0:03:61:A3:96:17:7A:9C:B4:10:FF:61:F2:00:15:AD"
+        );
+    }
+}
@@ -11,7 +11,7 @@ pub(crate) use crud_persistence::{
     update_workspace_codex_bin_core, update_workspace_settings_core,
 };
 pub(crate) use git_orchestration::{apply_worktree_changes_core, run_git_command_unit};
-pub(crate) use helpers::{is_workspace_path_dir_core, list_workspaces_core};
+pub(crate) use helpers::{is_workspace_path_dir_core, list_workspaces_core, session_is_connected};
 pub(crate) use io::{
     get_open_app_icon_core, list_workspace_files_core, open_workspace_in_core,
     read_workspace_file_core,
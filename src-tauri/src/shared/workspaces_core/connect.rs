@@ -8,7 +8,6 @@ use tokio::sync::Mutex;
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
-use crate::shared::process_core::kill_child_process_tree;
 use crate::types::{AppSettings, WorkspaceEntry};
 
 use super::helpers::resolve_entry_and_parent;
@@ -34,7 +33,10 @@ where
     };
     let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref());
     let session = spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?;
-    sessions.lock().await.insert(entry.id, session);
+    let previous = sessions.lock().await.insert(entry.id, session);
+    if let Some(previous) = previous {
+        previous.terminate().await;
+    }
     Ok(())
 }
 
@@ -43,7 +45,6 @@ pub(super) async fn kill_session_by_id(
     id: &str,
 ) {
     if let Some(session) = sessions.lock().await.remove(id) {
-        let mut child = session.child.lock().await;
-        kill_child_process_tree(&mut child).await;
+        session.terminate().await;
     }
 }
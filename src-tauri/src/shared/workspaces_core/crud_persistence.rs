@@ -9,13 +9,12 @@ use uuid::Uuid;
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
-use crate::shared::process_core::kill_child_process_tree;
 use crate::shared::{git_core, worktree_core};
 use crate::storage::write_workspaces;
 use crate::types::{AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings};
 
 use super::connect::kill_session_by_id;
-use super::helpers::normalize_setup_script;
+use super::helpers::{normalize_setup_script, session_is_connected};
 
 pub(crate) async fn add_workspace_core<F, Fut>(
     path: String,
@@ -70,8 +69,7 @@ where
             let mut workspaces = workspaces.lock().await;
             workspaces.remove(&entry.id);
         }
-        let mut child = session.child.lock().await;
-        kill_child_process_tree(&mut child).await;
+        session.terminate().await;
         return Err(error);
     }
 
@@ -201,8 +199,7 @@ where
             let mut workspaces = workspaces.lock().await;
             workspaces.remove(&entry.id);
         }
-        let mut child = session.child.lock().await;
-        kill_child_process_tree(&mut child).await;
+        session.terminate().await;
         let _ = tokio::fs::remove_dir_all(&destination_path).await;
         return Err(error);
     }
@@ -357,8 +354,7 @@ where
             let mut workspaces = workspaces.lock().await;
             workspaces.remove(&entry.id);
         }
-        let mut child = session.child.lock().await;
-        kill_child_process_tree(&mut child).await;
+        session.terminate().await;
         let _ = tokio::fs::remove_dir_all(&clone_path).await;
         return Err(error);
     }
@@ -554,7 +550,7 @@ where
     let codex_args_changed = previous_codex_args != entry_snapshot.settings.codex_args;
     let worktree_setup_script_changed =
         previous_worktree_setup_script != entry_snapshot.settings.worktree_setup_script;
-    let connected = sessions.lock().await.contains_key(&id);
+    let connected = session_is_connected(sessions, &id).await;
     if connected && (codex_home_changed || codex_args_changed) {
         let rollback_entry = previous_entry.clone();
         let (default_bin, codex_args) = {
@@ -589,15 +585,14 @@ where
             .await
             .insert(entry_snapshot.id.clone(), new_session)
         {
-            let mut child = old_session.child.lock().await;
-            kill_child_process_tree(&mut child).await;
+            old_session.terminate().await;
         }
     }
     if codex_home_changed || codex_args_changed {
         let app_settings_snapshot = app_settings.lock().await.clone();
         let default_bin = app_settings_snapshot.codex_bin.clone();
         for child in &child_entries {
-            let connected = sessions.lock().await.contains_key(&child.id);
+            let connected = session_is_connected(sessions, &child.id).await;
             if !connected {
                 continue;
             }
@@ -634,8 +629,7 @@ where
                 }
             };
             if let Some(old_session) = sessions.lock().await.insert(child.id.clone(), new_session) {
-                let mut child = old_session.child.lock().await;
-                kill_child_process_tree(&mut child).await;
+                old_session.terminate().await;
             }
         }
     }
@@ -693,7 +687,7 @@ pub(crate) async fn update_workspace_codex_bin_core(
     };
     write_workspaces(storage_path, &list)?;
 
-    let connected = sessions.lock().await.contains_key(&id);
+    let connected = session_is_connected(sessions, &id).await;
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
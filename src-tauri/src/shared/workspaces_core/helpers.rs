@@ -79,7 +79,9 @@ pub(crate) async fn list_workspaces_core(
             name: entry.name.clone(),
             path: entry.path.clone(),
             codex_bin: entry.codex_bin.clone(),
-            connected: sessions.contains_key(&entry.id),
+            connected: sessions
+                .get(&entry.id)
+                .is_some_and(|session| session.is_available()),
             kind: entry.kind.clone(),
             parent_id: entry.parent_id.clone(),
             worktree: entry.worktree.clone(),
@@ -90,6 +92,18 @@ pub(crate) async fn list_workspaces_core(
     result
 }
 
+/// A session left behind after the watchdog gave up restarting it does not count.
+pub(crate) async fn session_is_connected(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: &str,
+) -> bool {
+    sessions
+        .lock()
+        .await
+        .get(workspace_id)
+        .is_some_and(|session| session.is_available())
+}
+
 pub(super) async fn resolve_entry_and_parent(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: &str,
@@ -17,8 +17,8 @@ use crate::types::{
 
 use super::connect::kill_session_by_id;
 use super::helpers::{
-    copy_agents_md_from_parent_to_worktree, normalize_setup_script, worktree_setup_marker_path,
-    AGENTS_MD_FILE_NAME,
+    copy_agents_md_from_parent_to_worktree, normalize_setup_script, session_is_connected,
+    worktree_setup_marker_path, AGENTS_MD_FILE_NAME,
 };
 
 pub(crate) async fn worktree_setup_status_core(
@@ -435,7 +435,7 @@ where
     };
     write_workspaces(storage_path, &list)?;
 
-    let was_connected = sessions.lock().await.contains_key(&entry_snapshot.id);
+    let was_connected = session_is_connected(sessions, &entry_snapshot.id).await;
     if was_connected {
         kill_session_by_id(sessions, &entry_snapshot.id).await;
         let (default_bin, codex_args) = {
@@ -462,7 +462,7 @@ where
         }
     }
 
-    let connected = sessions.lock().await.contains_key(&entry_snapshot.id);
+    let connected = session_is_connected(sessions, &entry_snapshot.id).await;
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
@@ -35,8 +35,10 @@ impl Default for TcpDaemonRuntime {
 pub(crate) struct AppState {
     pub(crate) workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
     pub(crate) sessions: Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>,
-    pub(crate) terminal_sessions: Mutex<HashMap<String, Arc<crate::terminal::TerminalSession>>>,
+    pub(crate) terminal_sessions:
+        Arc<Mutex<HashMap<String, Arc<crate::terminal::TerminalSession>>>>,
     pub(crate) remote_backend: Mutex<Option<crate::remote_backend::RemoteBackend>>,
+    pub(crate) remote_event_cursor: Arc<crate::remote_backend::RemoteEventCursor>,
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
@@ -60,8 +62,9 @@ impl AppState {
         Self {
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
-            terminal_sessions: Mutex::new(HashMap::new()),
+            terminal_sessions: Arc::new(Mutex::new(HashMap::new())),
             remote_backend: Mutex::new(None),
+            remote_event_cursor: Arc::default(),
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
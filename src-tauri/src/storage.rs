@@ -64,7 +64,12 @@ fn sanitize_remote_settings_for_tcp_only(value: &mut Value) {
             entry_obj.retain(|key, _| {
                 matches!(
                     key.as_str(),
-                    "id" | "name" | "provider" | "host" | "token" | "lastConnectedAtMs"
+                    "id" | "name"
+                        | "provider"
+                        | "host"
+                        | "token"
+                        | "tlsFingerprint"
+                        | "lastConnectedAtMs"
                 )
             });
         }
@@ -2,11 +2,15 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use serde_json::{json, Value};
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Emitter, State};
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::AppServerEvent;
 use crate::codex::spawn_workspace_session;
 use crate::remote_backend;
+use crate::shared::supervisor_core::chat::{
+    parse_supervisor_chat_command, SupervisorChatCommand, SupervisorScheduleCommand,
+};
 use crate::shared::supervisor_core::service as supervisor_service;
 use crate::shared::supervisor_core::supervisor_loop;
 use crate::shared::workspaces_core;
@@ -145,6 +149,18 @@ pub(crate) async fn supervisor_chat_send(
         )
         .await;
     }
+    // Schedules are only run by the daemon, which persists the Supervisor state between runs.
+    if matches!(
+        parse_supervisor_chat_command(command.trim()),
+        Ok(SupervisorChatCommand::Schedule(
+            SupervisorScheduleCommand::Add { .. }
+        ))
+    ) {
+        return Err(
+            "Schedules run in the daemon; connect the app to a remote backend to add one."
+                .to_string(),
+        );
+    }
 
     if let Some(workspace_id) = supervisor_service::supervisor_chat_autoconnect_target_core(
         &state.supervisor_loop,
@@ -167,12 +183,30 @@ pub(crate) async fn supervisor_chat_send(
         .await;
     }
 
+    let hide_planner_thread = |workspace_id: &str, thread_id: &str| {
+        let _ = app.emit(
+            "app-server-event",
+            AppServerEvent {
+                workspace_id: workspace_id.to_string(),
+                message: json!({
+                    "method": "codex/backgroundThread",
+                    "params": {
+                        "threadId": thread_id,
+                        "action": "hide"
+                    }
+                }),
+            },
+        );
+    };
     let response = supervisor_service::supervisor_chat_send_core(
-        &state.supervisor_loop,
-        &state.supervisor_dispatch_executor,
-        &state.sessions,
-        &state.workspaces,
-        &state.app_settings,
+        &supervisor_service::SupervisorChatContext {
+            supervisor_loop: &state.supervisor_loop,
+            dispatch_executor: &state.supervisor_dispatch_executor,
+            sessions: &state.sessions,
+            workspaces: &state.workspaces,
+            app_settings: &state.app_settings,
+            on_planner_thread: &hide_planner_thread,
+        },
         &command,
         supervisor_loop::now_timestamp_ms(),
     )
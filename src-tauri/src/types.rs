@@ -8,6 +8,29 @@ pub(crate) struct GitFileStatus {
     pub(crate) deletions: i64,
 }
 
+/// A path with unmerged index entries and the stages it has; a missing stage means the file is
+/// absent on that side.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitConflictFile {
+    pub(crate) path: String,
+    pub(crate) has_base: bool,
+    pub(crate) has_ours: bool,
+    pub(crate) has_theirs: bool,
+}
+
+/// Stage contents of one conflicted path. A side is `None` when the file is absent on that
+/// side, or when `is_binary` is set because some side is binary or too large to inline.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitConflictContents {
+    pub(crate) path: String,
+    pub(crate) base: Option<String>,
+    pub(crate) ours: Option<String>,
+    pub(crate) theirs: Option<String>,
+    pub(crate) is_binary: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitFileDiff {
     pub(crate) path: String,
@@ -30,6 +53,36 @@ pub(crate) struct GitFileDiff {
     pub(crate) new_image_mime: Option<String>,
 }
 
+/// Part of a file diff to stage, unstage or revert. Hunks are identified by the ranges in
+/// their `@@ -oldStart,oldLines +newStart,newLines @@` header; single lines by their number on
+/// the side they appear on (removed lines on the old side, added lines on the new side).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitDiffSelection {
+    #[serde(default)]
+    pub(crate) hunks: Vec<GitDiffHunkRange>,
+    #[serde(default)]
+    pub(crate) removed_lines: Vec<GitLineRange>,
+    #[serde(default)]
+    pub(crate) added_lines: Vec<GitLineRange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitDiffHunkRange {
+    pub(crate) old_start: u32,
+    pub(crate) old_lines: u32,
+    pub(crate) new_start: u32,
+    pub(crate) new_lines: u32,
+}
+
+/// 1-based, inclusive line range.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GitLineRange {
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitCommitDiff {
     pub(crate) path: String,
@@ -75,6 +128,57 @@ pub(crate) struct GitLogResponse {
     pub(crate) behind_entries: Vec<GitLogEntry>,
     #[serde(default)]
     pub(crate) upstream: Option<String>,
+    /// Opaque cursor for the next page; `None` on the last page.
+    #[serde(default, rename = "nextCursor")]
+    pub(crate) next_cursor: Option<String>,
+    /// Set when the walk stopped counting `total` at the scan cap.
+    #[serde(default, rename = "totalCapped")]
+    pub(crate) total_capped: bool,
+}
+
+/// Paging and filters for `get_git_log`. Empty fields mean the full HEAD history;
+/// `since`/`until` are inclusive unix seconds on the commit time.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitLogQuery {
+    #[serde(default)]
+    pub(crate) cursor: Option<String>,
+    #[serde(default, rename = "ref")]
+    pub(crate) reference: Option<String>,
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+    #[serde(default)]
+    pub(crate) since: Option<i64>,
+    #[serde(default)]
+    pub(crate) until: Option<i64>,
+    #[serde(default)]
+    pub(crate) first_parent: bool,
+}
+
+/// Lines `start_line..start_line + line_count` (1-based) of a blamed file, last changed by one
+/// commit. `sha` is `None` for uncommitted working-tree lines.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitBlameHunk {
+    pub(crate) start_line: usize,
+    pub(crate) line_count: usize,
+    pub(crate) sha: Option<String>,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    pub(crate) summary: String,
+}
+
+/// One `stash@{index}` entry, newest first.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitStashEntry {
+    pub(crate) index: usize,
+    pub(crate) sha: String,
+    pub(crate) message: String,
+    pub(crate) timestamp: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -333,6 +437,11 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) launch_scripts: Option<Vec<LaunchScriptEntry>>,
     #[serde(default, rename = "worktreeSetupScript")]
     pub(crate) worktree_setup_script: Option<String>,
+    #[serde(default, rename = "supervisorHealth")]
+    pub(crate) supervisor_health: Option<SupervisorHealthOverride>,
+    /// Overrides the app-wide writing-turn limit for this workspace; `0` means unlimited.
+    #[serde(default, rename = "supervisorMaxWritingTurns")]
+    pub(crate) supervisor_max_writing_turns: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -344,6 +453,108 @@ pub(crate) struct LaunchScriptEntry {
     pub(crate) label: Option<String>,
 }
 
+/// What Supervisor does on its own when a running turn stops producing events.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SupervisorStallAction {
+    #[default]
+    None,
+    Interrupt,
+    Nudge,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SupervisorHealthSettings {
+    #[serde(default = "default_supervisor_stale_after_seconds")]
+    pub(crate) stale_after_seconds: u64,
+    #[serde(default = "default_supervisor_disconnected_after_seconds")]
+    pub(crate) disconnected_after_seconds: u64,
+    /// `0` turns per-thread stall detection off.
+    #[serde(default = "default_supervisor_thread_stall_after_seconds")]
+    pub(crate) thread_stall_after_seconds: u64,
+    #[serde(default)]
+    pub(crate) stall_action: SupervisorStallAction,
+    #[serde(default)]
+    pub(crate) nudge_prompt: Option<String>,
+}
+
+impl Default for SupervisorHealthSettings {
+    fn default() -> Self {
+        Self {
+            stale_after_seconds: default_supervisor_stale_after_seconds(),
+            disconnected_after_seconds: default_supervisor_disconnected_after_seconds(),
+            thread_stall_after_seconds: default_supervisor_thread_stall_after_seconds(),
+            stall_action: SupervisorStallAction::None,
+            nudge_prompt: None,
+        }
+    }
+}
+
+impl SupervisorHealthSettings {
+    /// Applies a workspace override on top of the app-wide values.
+    pub(crate) fn with_override(&self, overrides: Option<&SupervisorHealthOverride>) -> Self {
+        let Some(overrides) = overrides else {
+            return self.clone();
+        };
+        Self {
+            stale_after_seconds: overrides
+                .stale_after_seconds
+                .unwrap_or(self.stale_after_seconds),
+            disconnected_after_seconds: overrides
+                .disconnected_after_seconds
+                .unwrap_or(self.disconnected_after_seconds),
+            thread_stall_after_seconds: overrides
+                .thread_stall_after_seconds
+                .unwrap_or(self.thread_stall_after_seconds),
+            stall_action: overrides.stall_action.unwrap_or(self.stall_action),
+            nudge_prompt: overrides
+                .nudge_prompt
+                .clone()
+                .or_else(|| self.nudge_prompt.clone()),
+        }
+    }
+}
+
+/// Per-workspace health settings; unset fields fall back to the app settings.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SupervisorHealthOverride {
+    #[serde(default)]
+    pub(crate) stale_after_seconds: Option<u64>,
+    #[serde(default)]
+    pub(crate) disconnected_after_seconds: Option<u64>,
+    #[serde(default)]
+    pub(crate) thread_stall_after_seconds: Option<u64>,
+    #[serde(default)]
+    pub(crate) stall_action: Option<SupervisorStallAction>,
+    #[serde(default)]
+    pub(crate) nudge_prompt: Option<String>,
+}
+
+/// How many supervisor turns may run at once; `0` means unlimited.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SupervisorConcurrencySettings {
+    #[serde(default = "default_supervisor_max_writing_turns_per_workspace")]
+    pub(crate) max_writing_turns_per_workspace: u32,
+    #[serde(default)]
+    pub(crate) max_running_turns: u32,
+    /// Let `read-only` jobs run next to writing turns in the same workspace.
+    #[serde(default = "default_supervisor_exempt_read_only")]
+    pub(crate) exempt_read_only: bool,
+}
+
+impl Default for SupervisorConcurrencySettings {
+    fn default() -> Self {
+        Self {
+            max_writing_turns_per_workspace: default_supervisor_max_writing_turns_per_workspace(),
+            max_running_turns: 0,
+            exempt_read_only: default_supervisor_exempt_read_only(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorktreeSetupStatus {
     #[serde(rename = "shouldRun")]
@@ -373,6 +584,10 @@ pub(crate) struct RemoteBackendTarget {
     pub(crate) host: String,
     #[serde(default)]
     pub(crate) token: Option<String>,
+    /// SHA-256 fingerprint of the daemon TLS certificate; when set the connection uses TLS
+    /// and only this certificate is accepted.
+    #[serde(default, rename = "tlsFingerprint")]
+    pub(crate) tls_fingerprint: Option<String>,
     #[serde(default, rename = "lastConnectedAtMs")]
     pub(crate) last_connected_at_ms: Option<i64>,
 }
@@ -409,6 +624,12 @@ pub(crate) struct AppSettings {
         rename = "supervisorFastModel"
     )]
     pub(crate) supervisor_fast_model: String,
+    #[serde(default, rename = "supervisorPlannerEnabled")]
+    pub(crate) supervisor_planner_enabled: bool,
+    #[serde(default, rename = "supervisorHealth")]
+    pub(crate) supervisor_health: SupervisorHealthSettings,
+    #[serde(default, rename = "supervisorConcurrency")]
+    pub(crate) supervisor_concurrency: SupervisorConcurrencySettings,
     #[serde(default = "default_access_mode", rename = "defaultAccessMode")]
     pub(crate) default_access_mode: String,
     #[serde(
@@ -682,6 +903,26 @@ fn default_supervisor_fast_model() -> String {
     "gpt-5-mini".to_string()
 }
 
+fn default_supervisor_stale_after_seconds() -> u64 {
+    90
+}
+
+fn default_supervisor_disconnected_after_seconds() -> u64 {
+    300
+}
+
+fn default_supervisor_thread_stall_after_seconds() -> u64 {
+    600
+}
+
+fn default_supervisor_max_writing_turns_per_workspace() -> u32 {
+    1
+}
+
+fn default_supervisor_exempt_read_only() -> bool {
+    true
+}
+
 fn default_review_delivery_mode() -> String {
     "inline".to_string()
 }
@@ -1111,6 +1352,19 @@ fn default_selected_open_app_id() -> String {
     }
 }
 
+impl AppSettings {
+    /// TLS pin of the active remote backend target, if one is configured.
+    pub(crate) fn active_remote_tls_fingerprint(&self) -> Option<&str> {
+        let active_id = self.active_remote_backend_id.as_deref()?;
+        self.remote_backends
+            .iter()
+            .find(|target| target.id == active_id)
+            .and_then(|target| target.tls_fingerprint.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -1127,6 +1381,9 @@ impl Default for AppSettings {
             ),
             supervisor_dedicated_workspace_id: None,
             supervisor_fast_model: default_supervisor_fast_model(),
+            supervisor_planner_enabled: false,
+            supervisor_health: SupervisorHealthSettings::default(),
+            supervisor_concurrency: SupervisorConcurrencySettings::default(),
             default_access_mode: "current".to_string(),
             review_delivery_mode: default_review_delivery_mode(),
             composer_model_shortcut: default_composer_model_shortcut(),
@@ -1196,7 +1453,8 @@ impl Default for AppSettings {
 #[cfg(test)]
 mod tests {
     use super::{
-        AppSettings, BackendMode, RemoteBackendProvider, WorkspaceEntry, WorkspaceGroup,
+        AppSettings, BackendMode, RemoteBackendProvider, SupervisorConcurrencySettings,
+        SupervisorHealthSettings, SupervisorStallAction, WorkspaceEntry, WorkspaceGroup,
         WorkspaceKind, WorkspaceSettings,
     };
 
@@ -1225,6 +1483,22 @@ mod tests {
         assert!(!settings.supervisor_dedicated_workspace_enabled);
         assert!(settings.supervisor_dedicated_workspace_id.is_none());
         assert_eq!(settings.supervisor_fast_model, "gpt-5-mini");
+        assert!(!settings.supervisor_planner_enabled);
+        assert_eq!(settings.supervisor_health.stale_after_seconds, 90);
+        assert_eq!(settings.supervisor_health.disconnected_after_seconds, 300);
+        assert_eq!(settings.supervisor_health.thread_stall_after_seconds, 600);
+        assert_eq!(
+            settings.supervisor_health.stall_action,
+            SupervisorStallAction::None
+        );
+        assert_eq!(
+            settings.supervisor_concurrency,
+            SupervisorConcurrencySettings {
+                max_writing_turns_per_workspace: 1,
+                max_running_turns: 0,
+                exempt_read_only: true,
+            }
+        );
         assert_eq!(settings.default_access_mode, "current");
         assert_eq!(settings.review_delivery_mode, "inline");
         let expected_primary = if cfg!(target_os = "macos") {
@@ -1404,5 +1678,25 @@ mod tests {
         assert!(settings.sort_order.is_none());
         assert!(settings.group_id.is_none());
         assert!(settings.git_root.is_none());
+        assert!(settings.supervisor_health.is_none());
+        assert!(settings.supervisor_max_writing_turns.is_none());
+    }
+
+    #[test]
+    fn supervisor_health_override_falls_back_to_app_settings() {
+        let settings = WorkspaceSettings {
+            supervisor_health: serde_json::from_str(
+                r#"{"threadStallAfterSeconds":120,"stallAction":"interrupt"}"#,
+            )
+            .expect("override deserialize"),
+            ..WorkspaceSettings::default()
+        };
+        let resolved =
+            SupervisorHealthSettings::default().with_override(settings.supervisor_health.as_ref());
+        assert_eq!(resolved.stale_after_seconds, 90);
+        assert_eq!(resolved.disconnected_after_seconds, 300);
+        assert_eq!(resolved.thread_stall_after_seconds, 120);
+        assert_eq!(resolved.stall_action, SupervisorStallAction::Interrupt);
+        assert!(resolved.nudge_prompt.is_none());
     }
 }
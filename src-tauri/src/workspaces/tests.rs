@@ -58,6 +58,8 @@ fn workspace_with_id_and_kind(
             launch_script: None,
             launch_scripts: None,
             worktree_setup_script: None,
+            supervisor_health: None,
+            supervisor_max_writing_turns: None,
         },
     }
 }
@@ -1,6 +1,9 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import { subscribeAppServerEvents } from "@services/events";
+import {
+  subscribeAppServerEvents,
+  subscribeRemoteBackendConnection,
+} from "@services/events";
 import { threadLiveSubscribe, threadLiveUnsubscribe } from "@services/tauri";
 import {
   getAppServerParams,
@@ -305,6 +308,30 @@ export function useRemoteThreadLiveConnection({
     };
   }, [reconnectLive, reconcileDisconnectedState, setState]);
 
+  useEffect(() => {
+    const unlisten = subscribeRemoteBackendConnection((event) => {
+      if (backendModeRef.current !== "remote") {
+        return;
+      }
+      if (event.state === "reconnecting") {
+        setState("disconnected");
+        return;
+      }
+      // The backend re-attaches live threads itself after reconnecting; this only
+      // restores local state for the selected thread.
+      const workspaceId = activeWorkspaceRef.current?.id ?? null;
+      const threadId = activeThreadIdRef.current;
+      if (event.attempt === 0 || !workspaceId || !threadId || !isDocumentVisible()) {
+        return;
+      }
+      void reconnectLive(workspaceId, threadId, { runResume: false });
+    });
+
+    return () => {
+      unlisten();
+    };
+  }, [reconnectLive, setState]);
+
   useEffect(() => {
     let unlistenWindowFocus: (() => void) | null = null;
     let unlistenWindowBlur: (() => void) | null = null;
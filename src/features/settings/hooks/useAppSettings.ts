@@ -73,6 +73,7 @@ function normalizeRemoteBackends(settings: AppSettings): {
       provider: normalizeRemoteProvider(entry.provider),
       host: normalizeRemoteHost(entry.host),
       token: normalizeRemoteToken(entry.token),
+      tlsFingerprint: normalizeRemoteToken(entry.tlsFingerprint),
       lastConnectedAtMs:
         typeof entry.lastConnectedAtMs === "number" && Number.isFinite(entry.lastConnectedAtMs)
           ? entry.lastConnectedAtMs
@@ -200,6 +200,7 @@ export const useSettingsServerSection = ({
     provider: "tcp",
     host: entry.host?.trim() || DEFAULT_REMOTE_HOST,
     token: entry.token?.trim() ? entry.token.trim() : null,
+    tlsFingerprint: entry.tlsFingerprint?.trim() ? entry.tlsFingerprint.trim() : null,
     lastConnectedAtMs:
       typeof entry.lastConnectedAtMs === "number" && Number.isFinite(entry.lastConnectedAtMs)
         ? entry.lastConnectedAtMs
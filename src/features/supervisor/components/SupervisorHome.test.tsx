@@ -237,6 +237,7 @@ describe("SupervisorHome", () => {
         turn_id: "turn-1",
         item_id: "item-1",
         request_id: "42",
+        request_id_value: 42,
         method: "codex/requestApproval/exec_command",
         params: { command: ["npm", "run", "verify"] },
         created_at_ms: Date.now(),
@@ -132,6 +132,7 @@ describe("useSupervisorSignalAlerts", () => {
           turn_id: null,
           item_id: null,
           request_id: "42",
+          request_id_value: 42,
           method: "codex/requestApproval/shell",
           params: {
             command: ["npm", "run", "typecheck"],
@@ -2,11 +2,13 @@ import { beforeEach, describe, expect, it, vi } from "vitest";
 import type { Event, EventCallback, UnlistenFn } from "@tauri-apps/api/event";
 import { listen } from "@tauri-apps/api/event";
 import type { AppServerEvent } from "../types";
+import type { RemoteBackendConnectionEvent } from "./events";
 import {
   subscribeAppServerEvents,
   subscribeMenuCycleCollaborationMode,
   subscribeMenuCycleModel,
   subscribeMenuNewAgent,
+  subscribeRemoteBackendConnection,
   subscribeSupervisorEvents,
   subscribeTerminalOutput,
 } from "./events";
@@ -170,6 +172,31 @@ describe("events subscriptions", () => {
     cleanup();
   });
 
+  it("delivers remote backend connection state changes", async () => {
+    let listener: EventCallback<RemoteBackendConnectionEvent> = () => {};
+    const unlisten = vi.fn();
+
+    vi.mocked(listen).mockImplementation((_event, handler) => {
+      listener = handler as EventCallback<RemoteBackendConnectionEvent>;
+      return Promise.resolve(unlisten);
+    });
+
+    const onEvent = vi.fn();
+    const cleanup = subscribeRemoteBackendConnection(onEvent);
+    expect(listen).toHaveBeenCalledWith("remote-backend-connection", expect.any(Function));
+
+    const payload: RemoteBackendConnectionEvent = {
+      state: "reconnecting",
+      attempt: 2,
+      retryInMs: 750,
+      error: "connection refused",
+    };
+    listener({ event: "remote-backend-connection", id: 1, payload });
+    expect(onEvent).toHaveBeenCalledWith(payload);
+
+    cleanup();
+  });
+
   it("reports listen errors through options", async () => {
     const error = new Error("nope");
     vi.mocked(listen).mockRejectedValueOnce(error);
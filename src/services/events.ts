@@ -14,6 +14,14 @@ export type TerminalExitEvent = {
   terminalId: string;
 };
 
+export type RemoteBackendConnectionEvent = {
+  state: "connected" | "reconnecting";
+  /** Reconnect attempt number; 0 for the initial connection. */
+  attempt: number;
+  retryInMs?: number;
+  error?: string;
+};
+
 type SubscriptionOptions = {
   onError?: (error: unknown) => void;
 };
@@ -28,6 +36,9 @@ const SUPERVISOR_METHODS = new Set<string>([
   "item/tool/requestUserInput",
   "error",
   "codex/connected",
+  "codex/disconnected",
+  "codex/reconnecting",
+  "codex/reconnected",
 ]);
 
 export type SupervisorAppServerEvent = AppServerEvent & {
@@ -111,6 +122,9 @@ const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-dow
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
 const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
 const terminalExitHub = createEventHub<TerminalExitEvent>("terminal-exit");
+const remoteBackendConnectionHub = createEventHub<RemoteBackendConnectionEvent>(
+  "remote-backend-connection",
+);
 const updaterCheckHub = createEventHub<void>("updater-check");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
@@ -184,6 +198,13 @@ export function subscribeTerminalExit(
   return terminalExitHub.subscribe(onEvent, options);
 }
 
+export function subscribeRemoteBackendConnection(
+  onEvent: (event: RemoteBackendConnectionEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return remoteBackendConnectionHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
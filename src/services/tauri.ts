@@ -16,14 +16,22 @@ import type {
   WorkspaceSettings,
 } from "../types";
 import type {
+  GitBlameHunk,
+  GitConflictContents,
+  GitConflictFile,
+  GitDiffSelection,
   GitFileDiff,
   GitFileStatus,
   GitCommitDiff,
+  GitLineRange,
   GitHubIssuesResponse,
   GitHubPullRequestComment,
   GitHubPullRequestDiff,
   GitHubPullRequestsResponse,
+  GitLogQuery,
   GitLogResponse,
+  GitOperation,
+  GitStashEntry,
   ReviewTarget,
 } from "../types";
 
@@ -160,6 +168,7 @@ export type DeleteAgentInput = {
 };
 
 export const SUPERVISOR_ACTION_CONTRACT_VERSION = "supervisor.dispatch.v1";
+export const SUPERVISOR_ACTION_CONTRACT_VERSION_V2 = "supervisor.actions.v2";
 
 export type SupervisorHealth = "healthy" | "stale" | "disconnected";
 export type SupervisorThreadStatus =
@@ -175,14 +184,20 @@ export type SupervisorJobStatus =
   | "running"
   | "waiting_for_user"
   | "completed"
-  | "failed";
+  | "failed"
+  | "cancelled";
+export type SupervisorJobRetryThread = "reuse" | "fresh";
 export type SupervisorSignalKind =
   | "needs_approval"
   | "failed"
   | "completed"
   | "stalled"
   | "disconnected";
-export type SupervisorDispatchStatus = "dispatched" | "failed";
+export type SupervisorDispatchStatus =
+  | "dispatched"
+  | "applied"
+  | "queued"
+  | "failed";
 export type SupervisorChatMessageRole = "user" | "system";
 
 export type SupervisorWorkspaceState = {
@@ -230,6 +245,43 @@ export type SupervisorJobState = {
   waiting_request_id?: unknown | null;
   waiting_question_ids?: string[];
   recent_events?: SupervisorSubtaskEvent[];
+  policy?: SupervisorJobPolicy;
+  attempt?: number;
+  next_retry_at_ms?: number | null;
+  depends_on?: string[];
+  queued_at_ms?: number | null;
+  queue_position?: number | null;
+  result?: string | null;
+  digest?: SupervisorJobDigest | null;
+};
+
+export type SupervisorJobFileChange = {
+  path: string;
+  status: string;
+  additions?: number;
+  deletions?: number;
+};
+
+export type SupervisorJobDigest = {
+  files_changed?: SupervisorJobFileChange[] | null;
+  baseline_files?: SupervisorJobFileChange[] | null;
+  files_error?: string | null;
+  commands?: SupervisorJobCommand[];
+  last_message?: string | null;
+  captured_at_ms?: number | null;
+};
+
+export type SupervisorJobCommand = {
+  command: string;
+  exit_code?: number | null;
+  is_test?: boolean;
+};
+
+export type SupervisorJobPolicy = {
+  max_attempts: number;
+  retry_backoff_seconds: number;
+  timeout_seconds: number | null;
+  retry_thread: SupervisorJobRetryThread;
 };
 
 export type SupervisorSubtaskEvent = {
@@ -280,6 +332,7 @@ export type SupervisorPendingApproval = {
   turn_id: string | null;
   item_id: string | null;
   request_id: string;
+  request_id_value: string | number | null;
   method: string;
   params: unknown;
   created_at_ms: number;
@@ -293,6 +346,36 @@ export type SupervisorChatMessage = {
   created_at_ms: number;
 };
 
+export type SupervisorScheduleCatchUp = "once" | "skip";
+
+export type SupervisorScheduleDispatch = {
+  workspace_ids: string[];
+  prompt: string;
+  thread_id: string | null;
+  dedupe_key: string | null;
+  model: string | null;
+  effort: string | null;
+  access_mode: string | null;
+  route_kind: string | null;
+  route_reason: string | null;
+  route_fallback: string | null;
+  policy: SupervisorJobPolicy;
+};
+
+export type SupervisorSchedule = {
+  id: string;
+  cron: string;
+  dispatch: SupervisorScheduleDispatch;
+  catch_up: SupervisorScheduleCatchUp;
+  paused: boolean;
+  created_at_ms: number;
+  next_run_at_ms: number | null;
+  last_run_at_ms: number | null;
+  last_error: string | null;
+  last_job_ids: string[];
+  run_count: number;
+};
+
 export type SupervisorSnapshot = {
   workspaces: Record<string, SupervisorWorkspaceState>;
   threads: Record<string, SupervisorThreadState>;
@@ -302,6 +385,7 @@ export type SupervisorSnapshot = {
   open_questions: Record<string, SupervisorOpenQuestion>;
   pending_approvals: Record<string, SupervisorPendingApproval>;
   chat_history: SupervisorChatMessage[];
+  schedules?: Record<string, SupervisorSchedule>;
 };
 
 export type SupervisorFeedResponse = {
@@ -322,11 +406,69 @@ export type SupervisorDispatchTurnAction = {
   route_kind?: string | null;
   route_reason?: string | null;
   route_fallback?: string | null;
+  max_attempts?: number | null;
+  retry_backoff_seconds?: number | null;
+  timeout_seconds?: number | null;
+  retry_thread?: SupervisorJobRetryThread | null;
+  depends_on?: string[];
+};
+
+export type SupervisorInterruptTurnAction = {
+  type: "interrupt_turn";
+  action_id: string;
+  workspace_id: string;
+  thread_id: string;
+  turn_id: string;
+  dedupe_key?: string | null;
+};
+
+export type SupervisorRespondApprovalAction = {
+  type: "respond_approval";
+  action_id: string;
+  workspace_id: string;
+  request_id: string | number;
+  decision: "accept" | "decline";
+  dedupe_key?: string | null;
+};
+
+export type SupervisorAnswerQuestionAction = {
+  type: "answer_question";
+  action_id: string;
+  workspace_id: string;
+  request_id: string | number;
+  answer: string;
+  question_ids?: string[];
+  dedupe_key?: string | null;
+};
+
+export type SupervisorThreadControlAction = {
+  type: "archive_thread" | "compact_thread";
+  action_id: string;
+  workspace_id: string;
+  thread_id: string;
+  dedupe_key?: string | null;
+};
+
+export type SupervisorAckSignalAction = {
+  type: "ack_signal";
+  action_id: string;
+  signal_id: string;
+  dedupe_key?: string | null;
 };
 
+export type SupervisorPlannerAction =
+  | SupervisorDispatchTurnAction
+  | SupervisorInterruptTurnAction
+  | SupervisorRespondApprovalAction
+  | SupervisorAnswerQuestionAction
+  | SupervisorThreadControlAction
+  | SupervisorAckSignalAction;
+
+export type SupervisorActionType = SupervisorPlannerAction["type"];
+
 export type SupervisorActionContract = {
   version: string;
-  actions: SupervisorDispatchTurnAction[];
+  actions: SupervisorPlannerAction[];
 };
 
 export function createSupervisorActionContract(
@@ -338,8 +480,18 @@ export function createSupervisorActionContract(
   };
 }
 
+export function createSupervisorActionContractV2(
+  actions: SupervisorPlannerAction[],
+): SupervisorActionContract {
+  return {
+    version: SUPERVISOR_ACTION_CONTRACT_VERSION_V2,
+    actions,
+  };
+}
+
 export type SupervisorDispatchResult = {
   action_id: string;
+  action_type: SupervisorActionType;
   workspace_id: string;
   dedupe_key: string;
   status: SupervisorDispatchStatus;
@@ -725,6 +877,8 @@ export async function getGitStatus(workspace_id: string): Promise<{
   unstagedFiles: GitFileStatus[];
   totalAdditions: number;
   totalDeletions: number;
+  operation?: GitOperation | null;
+  conflictedFiles?: GitConflictFile[];
 }> {
   return invoke("get_git_status", { workspaceId: workspace_id });
 }
@@ -782,8 +936,13 @@ export async function getGitDiffs(
 export async function getGitLog(
   workspace_id: string,
   limit = 40,
+  query?: GitLogQuery,
 ): Promise<GitLogResponse> {
-  return invoke("get_git_log", { workspaceId: workspace_id, limit });
+  return invoke("get_git_log", {
+    workspaceId: workspace_id,
+    limit,
+    ...(query ? { query } : {}),
+  });
 }
 
 export async function getGitCommitDiff(
@@ -793,6 +952,14 @@ export async function getGitCommitDiff(
   return invoke("get_git_commit_diff", { workspaceId: workspace_id, sha });
 }
 
+export async function getGitBlame(
+  workspaceId: string,
+  path: string,
+  options: { range?: GitLineRange | null; includeWorkingTree?: boolean } = {},
+): Promise<GitBlameHunk[]> {
+  return invoke("get_git_blame", { workspaceId, path, ...options });
+}
+
 export async function getGitRemote(workspace_id: string): Promise<string | null> {
   return invoke("get_git_remote", { workspaceId: workspace_id });
 }
@@ -813,10 +980,112 @@ export async function revertGitFile(workspaceId: string, path: string) {
   return invoke("revert_git_file", { workspaceId, path });
 }
 
+export async function stageGitHunks(
+  workspaceId: string,
+  path: string,
+  selection: GitDiffSelection,
+): Promise<void> {
+  return invoke("stage_git_hunks", { workspaceId, path, selection });
+}
+
+export async function unstageGitHunks(
+  workspaceId: string,
+  path: string,
+  selection: GitDiffSelection,
+): Promise<void> {
+  return invoke("unstage_git_hunks", { workspaceId, path, selection });
+}
+
+export async function revertGitHunks(
+  workspaceId: string,
+  path: string,
+  selection: GitDiffSelection,
+): Promise<void> {
+  return invoke("revert_git_hunks", { workspaceId, path, selection });
+}
+
 export async function revertGitAll(workspaceId: string) {
   return invoke("revert_git_all", { workspaceId });
 }
 
+export async function listGitStashes(
+  workspaceId: string,
+): Promise<GitStashEntry[]> {
+  return invoke("list_git_stashes", { workspaceId });
+}
+
+export async function stashPush(
+  workspaceId: string,
+  options: {
+    message?: string | null;
+    includeUntracked?: boolean;
+    paths?: string[];
+  } = {},
+): Promise<void> {
+  return invoke("stash_push", { workspaceId, ...options });
+}
+
+export async function stashApply(
+  workspaceId: string,
+  index: number,
+  sha?: string | null,
+): Promise<void> {
+  return invoke("stash_apply", { workspaceId, index, sha: sha ?? null });
+}
+
+export async function stashPop(
+  workspaceId: string,
+  index: number,
+  sha?: string | null,
+): Promise<void> {
+  return invoke("stash_pop", { workspaceId, index, sha: sha ?? null });
+}
+
+export async function stashDrop(
+  workspaceId: string,
+  index: number,
+  sha?: string | null,
+): Promise<void> {
+  return invoke("stash_drop", { workspaceId, index, sha: sha ?? null });
+}
+
+export async function getGitStashDiff(
+  workspaceId: string,
+  index: number,
+): Promise<GitCommitDiff[]> {
+  return invoke("get_git_stash_diff", { workspaceId, index });
+}
+
+export async function getGitConflictContents(
+  workspaceId: string,
+  path: string,
+): Promise<GitConflictContents> {
+  return invoke("get_git_conflict_contents", { workspaceId, path });
+}
+
+export async function markGitConflictResolved(
+  workspaceId: string,
+  path: string,
+): Promise<void> {
+  return invoke("mark_git_conflict_resolved", { workspaceId, path });
+}
+
+export async function resolveGitConflictWithSide(
+  workspaceId: string,
+  path: string,
+  side: "ours" | "theirs",
+): Promise<void> {
+  return invoke("resolve_git_conflict_with_side", { workspaceId, path, side });
+}
+
+export async function abortGitOperation(workspaceId: string): Promise<void> {
+  return invoke("abort_git_operation", { workspaceId });
+}
+
+export async function continueGitOperation(workspaceId: string): Promise<void> {
+  return invoke("continue_git_operation", { workspaceId });
+}
+
 export async function commitGit(
   workspaceId: string,
   message: string,
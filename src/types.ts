@@ -150,6 +150,7 @@ export type RemoteBackendTarget = {
   provider: RemoteBackendProvider;
   host: string;
   token: string | null;
+  tlsFingerprint?: string | null;
   lastConnectedAtMs?: number | null;
 };
 export type ThemePreference = "system" | "light" | "dark" | "dim";
@@ -370,6 +371,23 @@ export type GitFileStatus = {
   deletions: number;
 };
 
+export type GitOperation = "merge" | "rebase" | "cherry-pick" | "revert";
+
+export type GitConflictFile = {
+  path: string;
+  hasBase: boolean;
+  hasOurs: boolean;
+  hasTheirs: boolean;
+};
+
+export type GitConflictContents = {
+  path: string;
+  base: string | null;
+  ours: string | null;
+  theirs: string | null;
+  isBinary: boolean;
+};
+
 export type GitFileDiff = {
   path: string;
   diff: string;
@@ -383,6 +401,22 @@ export type GitFileDiff = {
   newImageMime?: string | null;
 };
 
+export type GitLineRange = {
+  start: number;
+  end: number;
+};
+
+export type GitDiffSelection = {
+  hunks?: {
+    oldStart: number;
+    oldLines: number;
+    newStart: number;
+    newLines: number;
+  }[];
+  removedLines?: GitLineRange[];
+  addedLines?: GitLineRange[];
+};
+
 export type GitCommitDiff = {
   path: string;
   status: string;
@@ -412,6 +446,35 @@ export type GitLogResponse = {
   aheadEntries: GitLogEntry[];
   behindEntries: GitLogEntry[];
   upstream: string | null;
+  nextCursor?: string | null;
+  totalCapped?: boolean;
+};
+
+export type GitLogQuery = {
+  cursor?: string | null;
+  ref?: string | null;
+  path?: string | null;
+  author?: string | null;
+  message?: string | null;
+  since?: number | null;
+  until?: number | null;
+  firstParent?: boolean;
+};
+
+export type GitBlameHunk = {
+  startLine: number;
+  lineCount: number;
+  sha: string | null;
+  author: string;
+  timestamp: number;
+  summary: string;
+};
+
+export type GitStashEntry = {
+  index: number;
+  sha: string;
+  message: string;
+  timestamp: number;
 };
 
 export type GitHubIssue = {